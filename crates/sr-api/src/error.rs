@@ -9,9 +9,12 @@ use thiserror::Error;
 use tracing::error;
 
 use sr_common::db::{
-    ConversionStorageError, FeedbackHistoryError, FeedbackStorageError,
-    InteractionEventStorageError, MatchFetchError, QueueDashboardError, QueueStorageError,
+    ConversionStorageError, ExperimentReportError, FeedbackHistoryError, FeedbackStorageError,
+    InteractionEventStorageError, MatchDecisionError, MatchFetchError, MatchInputError,
+    MatchRunStorageError, ProjectStorageError, QueueDashboardError, QueueStorageError,
+    TalentStorageError,
 };
+use sr_common::two_tower::TwoTowerError;
 
 tokio::task_local! {
     static REQUEST_ID: String;
@@ -260,12 +263,42 @@ impl From<QueueDashboardError> for ApiError {
     }
 }
 
+impl From<ExperimentReportError> for ApiError {
+    fn from(value: ExperimentReportError) -> Self {
+        ApiError::database_error(value)
+    }
+}
+
 impl From<MatchFetchError> for ApiError {
     fn from(value: MatchFetchError) -> Self {
         ApiError::database_error(value)
     }
 }
 
+impl From<MatchInputError> for ApiError {
+    fn from(value: MatchInputError) -> Self {
+        ApiError::database_error(value)
+    }
+}
+
+impl From<MatchRunStorageError> for ApiError {
+    fn from(value: MatchRunStorageError) -> Self {
+        ApiError::database_error(value)
+    }
+}
+
+impl From<ProjectStorageError> for ApiError {
+    fn from(value: ProjectStorageError) -> Self {
+        ApiError::database_error(value)
+    }
+}
+
+impl From<TalentStorageError> for ApiError {
+    fn from(value: TalentStorageError) -> Self {
+        ApiError::database_error(value)
+    }
+}
+
 impl From<FeedbackHistoryError> for ApiError {
     fn from(value: FeedbackHistoryError) -> Self {
         ApiError::database_error(value)
@@ -297,6 +330,21 @@ impl From<ConversionStorageError> for ApiError {
     }
 }
 
+impl From<MatchDecisionError> for ApiError {
+    fn from(value: MatchDecisionError) -> Self {
+        match value {
+            MatchDecisionError::InteractionNotFound { .. } => ApiError::NotFound(value.to_string()),
+            MatchDecisionError::InvalidTransition { .. } => ApiError::Conflict(value.to_string()),
+            MatchDecisionError::MissingActor => {
+                ApiError::BadRequest("decision actor is required".into())
+            }
+            MatchDecisionError::Feedback(err) => err.into(),
+            MatchDecisionError::Conversion(err) => err.into(),
+            other => ApiError::database_error(other),
+        }
+    }
+}
+
 impl From<InteractionEventStorageError> for ApiError {
     fn from(value: InteractionEventStorageError) -> Self {
         match value {
@@ -308,6 +356,19 @@ impl From<InteractionEventStorageError> for ApiError {
     }
 }
 
+/// 起動時・SIGHUP で読み込んだ embedder と実験設定が食い違う場合（リクエスト側では直せない）
+impl From<TwoTowerError> for ApiError {
+    fn from(value: TwoTowerError) -> Self {
+        ApiError::Internal(value.to_string())
+    }
+}
+
+impl From<tokio::task::JoinError> for ApiError {
+    fn from(value: tokio::task::JoinError) -> Self {
+        ApiError::Internal(format!("blocking task failed: {value}"))
+    }
+}
+
 impl From<QueueStorageError> for ApiError {
     fn from(value: QueueStorageError) -> Self {
         match value {
@@ -0,0 +1,164 @@
+use axum::extract::{Query, State};
+use chrono::{DateTime, Duration, Utc};
+use serde::Deserialize;
+use sr_common::api::experiments::ExperimentReport;
+use sr_common::db::fetch_variant_outcomes;
+use sr_common::matching::experiment::{ExperimentConfig, MAX_ARM_NAME_LEN};
+use tracing::info;
+
+use crate::auth::AuthUser;
+use crate::error::ApiError;
+use crate::{CacheableJson, SharedState, SHORT_CACHE_CONTROL};
+
+const DEFAULT_LOOKBACK_DAYS: i64 = 30;
+const MAX_VARIANTS: usize = 20;
+
+#[derive(Debug, Deserialize, Default)]
+pub struct ExperimentReportParams {
+    pub since: Option<DateTime<Utc>>,
+    pub until: Option<DateTime<Utc>>,
+    /// カンマ区切り。省略時は設定中の実験のアーム
+    pub variants: Option<String>,
+    /// 比較基準。省略時は実験の control（variants 指定時は先頭）
+    pub control: Option<String>,
+}
+
+struct ReportTarget {
+    experiment: Option<String>,
+    variants: Vec<String>,
+    control: String,
+}
+
+fn resolve_target(
+    params: &ExperimentReportParams,
+    experiment: Option<&ExperimentConfig>,
+) -> Result<ReportTarget, ApiError> {
+    let (experiment_name, variants) = match params.variants.as_deref() {
+        Some(raw) => {
+            let mut variants: Vec<String> = Vec::new();
+            for name in raw.split(',').map(str::trim).filter(|s| !s.is_empty()) {
+                if name.len() > MAX_ARM_NAME_LEN {
+                    return Err(ApiError::BadRequest(format!(
+                        "variant names must be <= {MAX_ARM_NAME_LEN} bytes"
+                    )));
+                }
+                if !variants.iter().any(|v| v == name) {
+                    variants.push(name.to_string());
+                }
+            }
+            (None, variants)
+        }
+        None => {
+            let experiment = experiment.ok_or_else(|| {
+                ApiError::BadRequest(
+                    "no experiment is configured; pass variants=<a>,<b> explicitly".into(),
+                )
+            })?;
+            (Some(experiment.name.clone()), experiment.arm_names())
+        }
+    };
+
+    if variants.is_empty() {
+        return Err(ApiError::BadRequest("variants must not be empty".into()));
+    }
+    if variants.len() > MAX_VARIANTS {
+        return Err(ApiError::BadRequest(format!(
+            "at most {MAX_VARIANTS} variants can be compared"
+        )));
+    }
+
+    let control = match (params.control.as_deref(), experiment_name.as_ref()) {
+        (Some(control), _) => control.trim().to_string(),
+        (None, Some(_)) => experiment
+            .map(|e| e.control().name.clone())
+            .unwrap_or_else(|| variants[0].clone()),
+        (None, None) => variants[0].clone(),
+    };
+    if !variants.contains(&control) {
+        return Err(ApiError::BadRequest(format!(
+            "control `{control}` is not one of the reported variants"
+        )));
+    }
+
+    Ok(ReportTarget {
+        experiment: experiment_name,
+        variants,
+        control,
+    })
+}
+
+/// 実験アーム（variant）ごとのフィードバック率・CV 率を 95% 信頼区間つきで比較する
+pub async fn report(
+    State(state): State<SharedState>,
+    auth: AuthUser,
+    Query(params): Query<ExperimentReportParams>,
+) -> Result<CacheableJson<ExperimentReport>, ApiError> {
+    let since = params
+        .since
+        .unwrap_or_else(|| Utc::now() - Duration::days(DEFAULT_LOOKBACK_DAYS));
+    if params.until.is_some_and(|until| until <= since) {
+        return Err(ApiError::BadRequest("until must be after since".into()));
+    }
+
+    let target = {
+        let experiment = state.experiment.read().await;
+        resolve_target(&params, experiment.as_ref())?
+    };
+    info!(
+        user = %auth.subject,
+        experiment = ?target.experiment,
+        variants = ?target.variants,
+        %since,
+        "building experiment report"
+    );
+
+    let counts = fetch_variant_outcomes(&state.pool, &target.variants, since, params.until).await?;
+    let report = ExperimentReport::build(
+        target.experiment,
+        &target.variants,
+        &target.control,
+        since,
+        params.until,
+        &counts,
+    );
+    Ok(CacheableJson::new(report, SHORT_CACHE_CONTROL))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn experiment() -> ExperimentConfig {
+        ExperimentConfig::from_json(
+            r#"{"name": "tt", "salt": "s", "arms": [{"name": "tt_30"}, {"name": "control"}]}"#,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn resolves_variants_from_experiment_or_query() {
+        let params = ExperimentReportParams::default();
+        let target = resolve_target(&params, Some(&experiment())).unwrap();
+        assert_eq!(target.experiment.as_deref(), Some("tt"));
+        assert_eq!(target.variants, vec!["tt_30", "control"]);
+        assert_eq!(target.control, "control");
+
+        assert!(resolve_target(&params, None).is_err());
+
+        let params = ExperimentReportParams {
+            variants: Some("a, b,a,".into()),
+            ..Default::default()
+        };
+        let target = resolve_target(&params, Some(&experiment())).unwrap();
+        assert_eq!(target.experiment, None);
+        assert_eq!(target.variants, vec!["a", "b"]);
+        assert_eq!(target.control, "a");
+
+        let params = ExperimentReportParams {
+            variants: Some("a,b".into()),
+            control: Some("z".into()),
+            ..Default::default()
+        };
+        assert!(resolve_target(&params, None).is_err());
+    }
+}
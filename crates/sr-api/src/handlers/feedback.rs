@@ -11,7 +11,7 @@ use crate::auth::AuthUser;
 use crate::error::ApiError;
 use crate::SharedState;
 
-const MAX_COMMENT_LEN: usize = 1_000;
+pub(crate) const MAX_COMMENT_LEN: usize = 1_000;
 
 fn validate_feedback_payload(payload: &FeedbackRequest) -> Result<(), ApiError> {
     if payload.interaction_id <= 0 {
@@ -53,6 +53,7 @@ fn validate_feedback_payload(payload: &FeedbackRequest) -> Result<(), ApiError>
         | FeedbackType::ReviewOk
         | FeedbackType::ReviewNg
         | FeedbackType::ReviewPending
+        | FeedbackType::Proposed
         | FeedbackType::Accepted
         | FeedbackType::Rejected
         | FeedbackType::InterviewScheduled
@@ -51,6 +51,7 @@ mod tests {
     use std::sync::{atomic::AtomicBool, Arc};
 
     use sr_common::db::create_pool_from_url;
+    use sr_common::matching::pipeline::SharedTwoTower;
 
     use crate::auth::{AuthConfig, AuthMode, JwtAlgorithm};
     use crate::security::SecurityTxtConfig;
@@ -85,6 +86,8 @@ mod tests {
             pool,
             config,
             match_config: Arc::new(RwLock::new(MatchConfig::default())),
+            experiment: Arc::new(RwLock::new(None)),
+            two_tower: Arc::new(RwLock::new(SharedTwoTower::disabled())),
             rate_limits: default_rate_limits(),
             readiness: Arc::new(AtomicBool::new(readiness)),
         })
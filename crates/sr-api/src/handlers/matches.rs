@@ -1,53 +1,127 @@
+use std::collections::HashMap;
+
 use axum::{
     extract::{Path, State},
+    http::{HeaderName, HeaderValue},
+    response::{IntoResponse, Response},
     Json,
 };
+use chrono::Utc;
 use tracing::info;
 
 use sr_common::api::match_request::MatchRequest;
-use sr_common::api::match_response::MatchResponse;
-use sr_common::db::{fetch_candidates_for_project, fetch_match_by_id};
+use sr_common::api::match_response::{MatchExplainResponse, MatchResponse};
+use sr_common::db::{
+    fetch_active_projects, fetch_match_by_id, fetch_matchable_talents, fetch_talent_master,
+};
+use sr_common::matching::pipeline::MatchRunner;
+use sr_common::rules::active_rules;
 
 use crate::auth::AuthUser;
 use crate::error::ApiError;
 use crate::{CacheableJson, SharedState, SHORT_CACHE_CONTROL};
 
 const DEFAULT_MATCH_LIMIT: usize = 50;
+const MAX_MATCH_LIMIT: usize = 200;
+const ENGINE_VERSION: &str = concat!("sr-api-", env!("CARGO_PKG_VERSION"));
+/// 永続化したランの match_run_id を返すレスポンスヘッダ
+pub const MATCH_RUN_ID_HEADER: &str = "x-match-run-id";
 
+fn validate_match_request(request: &MatchRequest) -> Result<usize, ApiError> {
+    if request.persist && request.project.id.is_none() {
+        return Err(ApiError::BadRequest(
+            "project.id is required when persist is true".into(),
+        ));
+    }
+    if request.talent_ids.as_ref().is_some_and(Vec::is_empty) {
+        return Err(ApiError::BadRequest("talent_ids must not be empty".into()));
+    }
+    Ok(request
+        .limit
+        .unwrap_or(DEFAULT_MATCH_LIMIT)
+        .clamp(1, MAX_MATCH_LIMIT))
+}
+
+/// 投稿された案件に対して MatchingEngine をその場で実行する。
+/// `persist=true` の場合のみ新しい match_run_id で保存し、`x-match-run-id` ヘッダで返す。
 pub async fn run_match(
     State(state): State<SharedState>,
     auth: AuthUser,
     Json(request): Json<MatchRequest>,
-) -> Result<Json<Vec<MatchResponse>>, ApiError> {
-    let project_id = request
-        .project
-        .id
-        .ok_or_else(|| ApiError::BadRequest("project.id is required".into()))?;
-
-    let limit = request.limit.unwrap_or(DEFAULT_MATCH_LIMIT).clamp(1, 200) as u32;
+) -> Result<Response, ApiError> {
+    let limit = validate_match_request(&request)?;
+    let match_config = state.match_config.read().await.clone();
 
-    let talent_filter: Option<Vec<i64>> = request.talent_ids;
+    // config_version / rule_version は MatchRunner がルールのハッシュから付ける
+    let two_tower = state.two_tower.read().await.clone();
+    let mut runner = MatchRunner::from_shared(two_tower).with_engine_version(ENGINE_VERSION);
+    if let Some(experiment) = state.experiment.read().await.clone() {
+        runner = runner.with_experiment(experiment)?;
+    }
     info!(
         user = %auth.subject,
-        project_id,
+        project_id = ?request.project.id,
         limit,
         include_softko = request.include_softko,
-        talent_filter_len = talent_filter.as_ref().map(Vec::len).unwrap_or(0),
-        "running match request"
+        persist = request.persist,
+        match_run_id = runner.match_run_id(),
+        variant = ?runner.variant_for(&request.project),
+        talent_filter_len = request.talent_ids.as_ref().map(Vec::len).unwrap_or(0),
+        "running live match request"
     );
-    let match_config = state.match_config.read().await.clone();
-    let responses = fetch_candidates_for_project(
-        &state.pool,
-        project_id,
-        request.include_softko,
-        limit,
-        0,
-        talent_filter.as_deref(),
-        &match_config,
-    )
+
+    let mut talents = fetch_matchable_talents(&state.pool).await?;
+    if let Some(ids) = request.talent_ids.as_deref() {
+        talents.retain(|talent| talent.id.is_some_and(|id| ids.contains(&id)));
+    }
+
+    // 全人材の採点と埋め込みは CPU を使うので、非同期ワーカーを塞がないよう blocking スレッドで回す
+    let project = request.project;
+    let (runner, mut ranked) = tokio::task::spawn_blocking(move || {
+        let ranked = runner.rank_talents(&project, &talents);
+        (runner, ranked)
+    })
     .await?;
+    if !request.include_softko {
+        ranked.retain(|r| !r.ko.needs_manual_review);
+    }
+    ranked.truncate(limit);
+
+    let interaction_ids = if request.persist {
+        runner
+            .persist_ranked(&state.pool, &ranked)
+            .await?
+            .into_iter()
+            .map(|p| (p.talent_id, p.interaction_id))
+            .collect()
+    } else {
+        HashMap::new()
+    };
+
+    let matched_at = Utc::now();
+    let responses: Vec<MatchResponse> = ranked
+        .iter()
+        .map(|r| {
+            let interaction_id = r.talent.id.and_then(|id| interaction_ids.get(&id).copied());
+            MatchResponse::from_ranked_talent(
+                interaction_id,
+                r,
+                ENGINE_VERSION,
+                matched_at,
+                &match_config,
+            )
+        })
+        .collect();
 
-    Ok(Json(responses))
+    let mut response = Json(responses).into_response();
+    if request.persist {
+        if let Ok(value) = HeaderValue::from_str(runner.match_run_id()) {
+            response
+                .headers_mut()
+                .insert(HeaderName::from_static(MATCH_RUN_ID_HEADER), value);
+        }
+    }
+    Ok(response)
 }
 
 pub async fn get_match(
@@ -63,3 +137,90 @@ pub async fn get_match(
 
     Ok(CacheableJson::new(response, SHORT_CACHE_CONTROL))
 }
+
+/// 保存済みマッチを現在のルールで再評価し、KO 判定の根拠・要素ごとの寄与・Two-Tower の重なりを返す
+pub async fn explain_match(
+    State(state): State<SharedState>,
+    Path(match_id): Path<i64>,
+    auth: AuthUser,
+) -> Result<CacheableJson<MatchExplainResponse>, ApiError> {
+    info!(user = %auth.subject, match_id, "explaining match result");
+    let match_config = state.match_config.read().await.clone();
+    let stored = fetch_match_by_id(&state.pool, match_id, &match_config)
+        .await?
+        .ok_or_else(|| ApiError::NotFound(format!("match {match_id} not found")))?;
+
+    let project = fetch_active_projects(
+        &state.pool,
+        Some(stored.project_id),
+        Utc::now(),
+        false,
+        &[],
+        1,
+    )
+    .await?
+    .into_iter()
+    .next()
+    .ok_or_else(|| ApiError::NotFound(format!("project {} not found", stored.project_id)))?;
+    let talent = fetch_talent_master(&state.pool, stored.talent_id)
+        .await?
+        .ok_or_else(|| ApiError::NotFound(format!("talent {} not found", stored.talent_id)))?
+        .to_talent();
+
+    // 案件起点のランキングと同じ embedder・実験アームの設定で評価する
+    let two_tower = state.two_tower.read().await.clone();
+    let mut runner = MatchRunner::from_shared(two_tower).with_engine_version(ENGINE_VERSION);
+    if let Some(experiment) = state.experiment.read().await.clone() {
+        runner = runner.with_experiment(experiment)?;
+    }
+    let explanation =
+        tokio::task::spawn_blocking(move || runner.explain(&project, &talent)).await?;
+
+    let response = MatchExplainResponse {
+        match_id,
+        interaction_id: stored.interaction_id,
+        stored_score: stored.score,
+        stored_rule_version: stored.rule_version,
+        rule_version: active_rules().version.clone(),
+        explanation,
+    };
+    Ok(CacheableJson::new(response, SHORT_CACHE_CONTROL))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sr_common::Project;
+
+    fn request(project_id: Option<i64>, persist: bool) -> MatchRequest {
+        MatchRequest {
+            project: Project {
+                id: project_id,
+                ..Project::default()
+            },
+            talent_ids: None,
+            include_softko: false,
+            limit: None,
+            persist,
+        }
+    }
+
+    #[test]
+    fn persist_requires_project_id() {
+        assert!(matches!(
+            validate_match_request(&request(None, true)),
+            Err(ApiError::BadRequest(_))
+        ));
+        assert_eq!(
+            validate_match_request(&request(None, false)).unwrap(),
+            DEFAULT_MATCH_LIMIT
+        );
+
+        let mut req = request(Some(1), true);
+        req.limit = Some(1000);
+        assert_eq!(validate_match_request(&req).unwrap(), MAX_MATCH_LIMIT);
+
+        req.talent_ids = Some(vec![]);
+        assert!(validate_match_request(&req).is_err());
+    }
+}
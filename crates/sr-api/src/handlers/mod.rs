@@ -1,9 +1,12 @@
 pub mod candidates;
 pub mod conversion;
+pub mod experiments;
 pub mod feedback;
 pub mod health;
 pub mod interactions;
 pub mod matches;
 pub mod pagination;
+pub mod projects;
 pub mod queue;
 pub mod security;
+pub mod talents;
@@ -0,0 +1,256 @@
+use axum::extract::{Path, Query, State};
+use axum::Json;
+use chrono::{NaiveDate, Utc};
+use serde::Deserialize;
+use sr_common::api::projects::{
+    ProjectDetailResponse, ProjectListFilter, ProjectSimulationRequest, ProjectSimulationResponse,
+    ProjectsListResponse,
+};
+use sr_common::db::{
+    fetch_active_projects, fetch_matchable_talents, fetch_project_detail,
+    list_projects as fetch_listed_projects,
+};
+use sr_common::matching::pipeline::MatchRunner;
+use sr_common::matching::simulation::{apply_project_patch, simulate_project_change};
+use tracing::info;
+
+use crate::auth::AuthUser;
+use crate::error::ApiError;
+use crate::handlers::pagination::validate_pagination;
+use crate::{CacheableJson, SharedState, SHORT_CACHE_CONTROL};
+
+/// `GET /api/v1/projects` のクエリ。単価・開始日の範囲チェックは `build_filter` で行い、
+/// 通ったものだけ [`ProjectListFilter`] に詰め替える
+#[derive(Debug, Deserialize, Default)]
+pub struct ProjectListParams {
+    #[serde(default = "default_limit")]
+    pub limit: i64,
+    #[serde(default)]
+    pub offset: i64,
+    pub todofuken: Option<String>,
+    pub remote_onsite: Option<String>,
+    pub tanka_min: Option<i32>,
+    pub tanka_max: Option<i32>,
+    pub start_from: Option<NaiveDate>,
+    pub start_to: Option<NaiveDate>,
+    pub has_candidates: Option<bool>,
+}
+
+const fn default_limit() -> i64 {
+    50
+}
+
+#[derive(Debug, Deserialize, Default)]
+pub struct ProjectDetailParams {
+    /// 詳細に含める上位候補の件数
+    pub match_limit: Option<i64>,
+}
+
+const DEFAULT_MATCH_LIMIT: i64 = 20;
+const MAX_MATCH_LIMIT: i64 = 200;
+
+fn non_blank(value: Option<String>) -> Option<String> {
+    value
+        .map(|v| v.trim().to_string())
+        .filter(|v| !v.is_empty())
+}
+
+fn build_filter(params: ProjectListParams) -> Result<ProjectListFilter, ApiError> {
+    for (name, value) in [
+        ("tanka_min", params.tanka_min),
+        ("tanka_max", params.tanka_max),
+    ] {
+        if value.is_some_and(|v| v < 0) {
+            return Err(ApiError::BadRequest(format!("{name} must not be negative")));
+        }
+    }
+    if let (Some(min), Some(max)) = (params.tanka_min, params.tanka_max) {
+        if min > max {
+            return Err(ApiError::BadRequest(
+                "tanka_min must not exceed tanka_max".into(),
+            ));
+        }
+    }
+    if let (Some(from), Some(to)) = (params.start_from, params.start_to) {
+        if from > to {
+            return Err(ApiError::BadRequest(
+                "start_from must not be after start_to".into(),
+            ));
+        }
+    }
+
+    Ok(ProjectListFilter {
+        todofuken: non_blank(params.todofuken),
+        remote_onsite: non_blank(params.remote_onsite),
+        tanka_min: params.tanka_min,
+        tanka_max: params.tanka_max,
+        start_from: params.start_from,
+        start_to: params.start_to,
+        has_candidates: params.has_candidates,
+    })
+}
+
+pub async fn list_projects(
+    State(state): State<SharedState>,
+    Query(params): Query<ProjectListParams>,
+    auth: AuthUser,
+) -> Result<CacheableJson<ProjectsListResponse>, ApiError> {
+    let (limit, offset) = validate_pagination(params.limit, params.offset)?;
+    let filter = build_filter(params)?;
+    info!(
+        user = %auth.subject,
+        limit,
+        offset,
+        todofuken = ?filter.todofuken,
+        remote_onsite = ?filter.remote_onsite,
+        has_candidates = ?filter.has_candidates,
+        "listing projects"
+    );
+
+    let response = fetch_listed_projects(&state.pool, &filter, limit, offset).await?;
+    Ok(CacheableJson::new(response, SHORT_CACHE_CONTROL))
+}
+
+pub async fn get_project(
+    State(state): State<SharedState>,
+    Path(project_id): Path<i64>,
+    Query(params): Query<ProjectDetailParams>,
+    auth: AuthUser,
+) -> Result<CacheableJson<ProjectDetailResponse>, ApiError> {
+    let match_limit = params.match_limit.unwrap_or(DEFAULT_MATCH_LIMIT);
+    if !(1..=MAX_MATCH_LIMIT).contains(&match_limit) {
+        return Err(ApiError::BadRequest(format!(
+            "match_limit must be between 1 and {MAX_MATCH_LIMIT}"
+        )));
+    }
+    info!(user = %auth.subject, project_id, match_limit, "fetching project detail");
+
+    let detail = fetch_project_detail(&state.pool, project_id, match_limit)
+        .await?
+        .ok_or_else(|| ApiError::NotFound(format!("project {project_id} not found")))?;
+    Ok(CacheableJson::new(detail, SHORT_CACHE_CONTROL))
+}
+
+const DEFAULT_SIMULATION_LIMIT: usize = 50;
+const MAX_SIMULATION_LIMIT: usize = 200;
+
+fn validate_simulation_request(request: &ProjectSimulationRequest) -> Result<usize, ApiError> {
+    if request.patch.is_empty() {
+        return Err(ApiError::BadRequest("patch must not be empty".into()));
+    }
+    let limit = request.limit.unwrap_or(DEFAULT_SIMULATION_LIMIT);
+    if !(1..=MAX_SIMULATION_LIMIT).contains(&limit) {
+        return Err(ApiError::BadRequest(format!(
+            "limit must be between 1 and {MAX_SIMULATION_LIMIT}"
+        )));
+    }
+    Ok(limit)
+}
+
+/// 案件フィールドを書き換えた場合の候補の増減を、人材プール全体の KO + スコアリングで試算する（保存しない）
+pub async fn simulate_project(
+    State(state): State<SharedState>,
+    Path(project_id): Path<i64>,
+    auth: AuthUser,
+    Json(request): Json<ProjectSimulationRequest>,
+) -> Result<Json<ProjectSimulationResponse>, ApiError> {
+    let limit = validate_simulation_request(&request)?;
+    let fields: Vec<&str> = request.patch.keys().map(String::as_str).collect();
+    info!(
+        user = %auth.subject,
+        project_id,
+        ?fields,
+        include_softko = request.include_softko,
+        limit,
+        "simulating project requirement change"
+    );
+
+    let baseline = fetch_active_projects(&state.pool, Some(project_id), Utc::now(), false, &[], 1)
+        .await?
+        .into_iter()
+        .next()
+        .ok_or_else(|| ApiError::NotFound(format!("project {project_id} not found")))?;
+    let simulated = apply_project_patch(&baseline, &request.patch)
+        .map_err(|err| ApiError::BadRequest(err.to_string()))?;
+
+    let talents = fetch_matchable_talents(&state.pool).await?;
+
+    // ランキングと同じく、この案件に割り当たる実験アームの prefilter（スキル閾値）で評価する
+    let two_tower = state.two_tower.read().await.clone();
+    let mut runner = MatchRunner::from_shared(two_tower);
+    if let Some(experiment) = state.experiment.read().await.clone() {
+        runner = runner.with_experiment(experiment)?;
+    }
+    let include_softko = request.include_softko;
+    let (simulated, simulation) = tokio::task::spawn_blocking(move || {
+        let simulation = simulate_project_change(
+            runner.prefilter_for(&baseline),
+            &baseline,
+            &simulated,
+            &talents,
+            include_softko,
+            limit,
+        );
+        (simulated, simulation)
+    })
+    .await?;
+
+    Ok(Json(ProjectSimulationResponse {
+        project_id,
+        simulated_project: simulated,
+        simulation,
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn validates_tanka_and_start_ranges() {
+        let params = ProjectListParams {
+            tanka_min: Some(80),
+            tanka_max: Some(60),
+            ..ProjectListParams::default()
+        };
+        assert!(matches!(build_filter(params), Err(ApiError::BadRequest(_))));
+
+        let params = ProjectListParams {
+            start_from: NaiveDate::from_ymd_opt(2026, 12, 1),
+            start_to: NaiveDate::from_ymd_opt(2026, 11, 1),
+            ..ProjectListParams::default()
+        };
+        assert!(build_filter(params).is_err());
+
+        let params = ProjectListParams {
+            todofuken: Some("  ".into()),
+            remote_onsite: Some("フルリモート".into()),
+            has_candidates: Some(true),
+            ..ProjectListParams::default()
+        };
+        let filter = build_filter(params).unwrap();
+        assert_eq!(filter.todofuken, None);
+        assert_eq!(filter.remote_onsite.as_deref(), Some("フルリモート"));
+        assert_eq!(filter.has_candidates, Some(true));
+    }
+
+    #[test]
+    fn validates_simulation_request() {
+        let request: ProjectSimulationRequest = serde_json::from_str(r#"{"patch":{}}"#).unwrap();
+        assert!(matches!(
+            validate_simulation_request(&request),
+            Err(ApiError::BadRequest(_))
+        ));
+
+        let request: ProjectSimulationRequest =
+            serde_json::from_str(r#"{"patch":{"monthly_tanka_max":85},"limit":500}"#).unwrap();
+        assert!(validate_simulation_request(&request).is_err());
+
+        let request: ProjectSimulationRequest =
+            serde_json::from_str(r#"{"patch":{"remote_onsite":"ハイブリッド"}}"#).unwrap();
+        assert_eq!(
+            validate_simulation_request(&request).unwrap(),
+            DEFAULT_SIMULATION_LIMIT
+        );
+    }
+}
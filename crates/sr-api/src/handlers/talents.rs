@@ -0,0 +1,345 @@
+use axum::extract::{Path, Query, State};
+use axum::Json;
+use chrono::{Duration, Utc};
+use serde::Deserialize;
+use sr_common::api::match_response::MatchResponse;
+use sr_common::api::talents::{
+    TalentDetailResponse, TalentMatchDecisionRequest, TalentMatchDecisionResponse,
+    TalentProjectMatch, TalentProjectsResponse, TalentSearchQuery, TalentSearchResponse,
+};
+use sr_common::db::{
+    fetch_active_projects, fetch_project_names, fetch_talent_detail, fetch_talent_master,
+    record_talent_match_decision, search_talents,
+};
+use sr_common::matching::pipeline::MatchRunner;
+use tracing::info;
+
+use crate::auth::AuthUser;
+use crate::error::ApiError;
+use crate::handlers::feedback::MAX_COMMENT_LEN;
+use crate::handlers::pagination::validate_pagination;
+use crate::{CacheableJson, SharedState, SHORT_CACHE_CONTROL};
+
+/// `serde(flatten)` は query string の数値を文字列として渡してしまうため、フィールドを展開して受ける
+#[derive(Debug, Deserialize, Default)]
+pub struct TalentListParams {
+    #[serde(default = "default_limit")]
+    pub limit: i64,
+    #[serde(default)]
+    pub offset: i64,
+    pub search: Option<String>,
+    pub skill: Option<String>,
+    pub location: Option<String>,
+    pub availability: Option<String>,
+    pub availability_within_days: Option<i32>,
+    pub score_min: Option<f64>,
+    pub score_max: Option<f64>,
+}
+
+const fn default_limit() -> i64 {
+    20
+}
+
+/// 今日から何日先まで稼働開始日を絞り込めるか
+const MAX_AVAILABILITY_WINDOW_DAYS: i32 = 365;
+
+fn build_search_query(params: TalentListParams) -> Result<TalentSearchQuery, ApiError> {
+    for (name, value) in [
+        ("score_min", params.score_min),
+        ("score_max", params.score_max),
+    ] {
+        if let Some(v) = value {
+            if !(0.0..=1.0).contains(&v) {
+                return Err(ApiError::BadRequest(format!(
+                    "{name} must be between 0.0 and 1.0"
+                )));
+            }
+        }
+    }
+    if let (Some(min), Some(max)) = (params.score_min, params.score_max) {
+        if min > max {
+            return Err(ApiError::BadRequest(
+                "score_min must not exceed score_max".into(),
+            ));
+        }
+    }
+    if let Some(days) = params.availability_within_days {
+        if !(0..=MAX_AVAILABILITY_WINDOW_DAYS).contains(&days) {
+            return Err(ApiError::BadRequest(format!(
+                "availability_within_days must be between 0 and {MAX_AVAILABILITY_WINDOW_DAYS}"
+            )));
+        }
+    }
+
+    Ok(TalentSearchQuery {
+        search: params.search,
+        skill: params.skill,
+        location: params.location,
+        availability: params.availability,
+        availability_within_days: params.availability_within_days,
+        score_min: params.score_min,
+        score_max: params.score_max,
+    })
+}
+
+pub async fn list_talents(
+    State(state): State<SharedState>,
+    Query(params): Query<TalentListParams>,
+    auth: AuthUser,
+) -> Result<CacheableJson<TalentSearchResponse>, ApiError> {
+    let (limit, offset) = validate_pagination(params.limit, params.offset)?;
+    let query = build_search_query(params)?;
+    info!(
+        user = %auth.subject,
+        limit,
+        offset,
+        has_search = query.search.is_some(),
+        skill = ?query.skill,
+        location = ?query.location,
+        availability_within_days = ?query.availability_within_days,
+        "searching talents"
+    );
+
+    let response = search_talents(&state.pool, &query, limit, offset).await?;
+    Ok(CacheableJson::new(response, SHORT_CACHE_CONTROL))
+}
+
+pub async fn get_talent(
+    State(state): State<SharedState>,
+    Path(talent_id): Path<i64>,
+    auth: AuthUser,
+) -> Result<CacheableJson<TalentDetailResponse>, ApiError> {
+    info!(user = %auth.subject, talent_id, "fetching talent detail");
+
+    let detail = fetch_talent_detail(&state.pool, talent_id)
+        .await?
+        .ok_or_else(|| ApiError::NotFound(format!("talent {talent_id} not found")))?;
+    Ok(CacheableJson::new(detail, SHORT_CACHE_CONTROL))
+}
+
+#[derive(Debug, Deserialize)]
+pub struct TalentProjectsParams {
+    #[serde(default = "default_projects_limit")]
+    pub limit: usize,
+    #[serde(default)]
+    pub include_softko: bool,
+    /// 直近 N 日に受信した案件をランキング対象にする
+    #[serde(default = "default_lookback_days")]
+    pub lookback_days: i64,
+}
+
+impl Default for TalentProjectsParams {
+    fn default() -> Self {
+        Self {
+            limit: default_projects_limit(),
+            include_softko: false,
+            lookback_days: default_lookback_days(),
+        }
+    }
+}
+
+const fn default_projects_limit() -> usize {
+    20
+}
+
+const fn default_lookback_days() -> i64 {
+    30
+}
+
+const MAX_PROJECTS_LIMIT: usize = 200;
+const MAX_LOOKBACK_DAYS: i64 = 180;
+/// 1 リクエストでランキングする案件数の上限（受信日の新しい順）
+const MAX_RANKED_PROJECTS: i64 = 1000;
+const ENGINE_VERSION: &str = concat!("sr-api-", env!("CARGO_PKG_VERSION"));
+
+fn validate_projects_params(params: &TalentProjectsParams) -> Result<(), ApiError> {
+    if !(1..=MAX_PROJECTS_LIMIT).contains(&params.limit) {
+        return Err(ApiError::BadRequest(format!(
+            "limit must be between 1 and {MAX_PROJECTS_LIMIT}"
+        )));
+    }
+    if !(1..=MAX_LOOKBACK_DAYS).contains(&params.lookback_days) {
+        return Err(ApiError::BadRequest(format!(
+            "lookback_days must be between 1 and {MAX_LOOKBACK_DAYS}"
+        )));
+    }
+    Ok(())
+}
+
+/// 人材起点で直近の案件をその場でランキングする（保存はしない）
+pub async fn list_talent_projects(
+    State(state): State<SharedState>,
+    Path(talent_id): Path<i64>,
+    Query(params): Query<TalentProjectsParams>,
+    auth: AuthUser,
+) -> Result<CacheableJson<TalentProjectsResponse>, ApiError> {
+    validate_projects_params(&params)?;
+    info!(
+        user = %auth.subject,
+        talent_id,
+        limit = params.limit,
+        include_softko = params.include_softko,
+        lookback_days = params.lookback_days,
+        "ranking projects for talent"
+    );
+
+    let talent = fetch_talent_master(&state.pool, talent_id)
+        .await?
+        .ok_or_else(|| ApiError::NotFound(format!("talent {talent_id} not found")))?
+        .to_talent();
+    let received_since = Utc::now() - Duration::days(params.lookback_days);
+    let projects = fetch_active_projects(
+        &state.pool,
+        None,
+        received_since,
+        false,
+        &[],
+        MAX_RANKED_PROJECTS,
+    )
+    .await?;
+
+    let match_config = state.match_config.read().await.clone();
+    // config_version / rule_version は MatchRunner がルールのハッシュから付ける。
+    // 案件起点のランキングと同じ embedder・実験アームで採点する
+    let two_tower = state.two_tower.read().await.clone();
+    let mut runner = MatchRunner::from_shared(two_tower).with_engine_version(ENGINE_VERSION);
+    if let Some(experiment) = state.experiment.read().await.clone() {
+        runner = runner.with_experiment(experiment)?;
+    }
+
+    // 最大 MAX_RANKED_PROJECTS 件の採点と埋め込みは blocking スレッドで回す
+    let projects_considered = projects.len();
+    let mut ranked =
+        tokio::task::spawn_blocking(move || runner.rank_projects(&talent, &projects)).await?;
+    if !params.include_softko {
+        ranked.retain(|r| !r.ko.needs_manual_review);
+    }
+    ranked.truncate(params.limit);
+
+    let project_ids: Vec<i64> = ranked.iter().filter_map(|r| r.project.id).collect();
+    let names = fetch_project_names(&state.pool, &project_ids).await?;
+    let matched_at = Utc::now();
+    let items = ranked
+        .iter()
+        .map(|r| TalentProjectMatch {
+            project_name: r.project.id.and_then(|id| names.get(&id).cloned()),
+            work_todofuken: r.project.work_todofuken.clone(),
+            remote_onsite: r.project.remote_onsite.clone(),
+            monthly_tanka_min: r.project.monthly_tanka_min,
+            monthly_tanka_max: r.project.monthly_tanka_max,
+            // 逆引きは永続化しないので interaction_id は付けない
+            result: MatchResponse::from_ranked_talent(
+                None,
+                r,
+                ENGINE_VERSION,
+                matched_at,
+                &match_config,
+            ),
+        })
+        .collect();
+
+    let response = TalentProjectsResponse {
+        talent_id,
+        projects_considered,
+        items,
+    };
+    Ok(CacheableJson::new(response, SHORT_CACHE_CONTROL))
+}
+
+fn validate_decision_payload(payload: &TalentMatchDecisionRequest) -> Result<(), ApiError> {
+    if let Some(comment) = payload.comment.as_deref().map(str::trim) {
+        if comment.is_empty() {
+            return Err(ApiError::BadRequest("comment cannot be empty".into()));
+        }
+        if comment.len() > MAX_COMMENT_LEN {
+            return Err(ApiError::BadRequest(format!(
+                "comment must be <= {MAX_COMMENT_LEN} characters"
+            )));
+        }
+    }
+    Ok(())
+}
+
+pub async fn decide_match(
+    State(state): State<SharedState>,
+    Path((talent_id, project_id)): Path<(i64, i64)>,
+    auth: AuthUser,
+    Json(payload): Json<TalentMatchDecisionRequest>,
+) -> Result<Json<TalentMatchDecisionResponse>, ApiError> {
+    validate_decision_payload(&payload)?;
+    info!(
+        user = %auth.subject,
+        talent_id,
+        project_id,
+        decision = payload.decision.as_ref(),
+        "recording talent match decision"
+    );
+
+    let status =
+        record_talent_match_decision(&state.pool, &auth.subject, talent_id, project_id, &payload)
+            .await?;
+    Ok(Json(TalentMatchDecisionResponse { status }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rejects_out_of_range_score_filters() {
+        let params = TalentListParams {
+            score_min: Some(0.8),
+            score_max: Some(0.2),
+            ..TalentListParams::default()
+        };
+        assert!(matches!(
+            build_search_query(params),
+            Err(ApiError::BadRequest(_))
+        ));
+
+        let params = TalentListParams {
+            score_max: Some(1.5),
+            ..TalentListParams::default()
+        };
+        assert!(build_search_query(params).is_err());
+
+        let params = TalentListParams {
+            availability_within_days: Some(30),
+            score_min: Some(0.5),
+            ..TalentListParams::default()
+        };
+        let query = build_search_query(params).unwrap();
+        assert_eq!(query.availability_within_days, Some(30));
+    }
+
+    #[test]
+    fn validates_talent_projects_params() {
+        assert!(validate_projects_params(&TalentProjectsParams::default()).is_ok());
+
+        let params = TalentProjectsParams {
+            limit: 0,
+            ..TalentProjectsParams::default()
+        };
+        assert!(matches!(
+            validate_projects_params(&params),
+            Err(ApiError::BadRequest(_))
+        ));
+
+        let params = TalentProjectsParams {
+            lookback_days: MAX_LOOKBACK_DAYS + 1,
+            ..TalentProjectsParams::default()
+        };
+        assert!(validate_projects_params(&params).is_err());
+    }
+
+    #[test]
+    fn rejects_blank_decision_comment() {
+        let payload: TalentMatchDecisionRequest =
+            serde_json::from_str(r#"{"decision":"reject","comment":"  "}"#).unwrap();
+        assert!(validate_decision_payload(&payload).is_err());
+
+        let payload: TalentMatchDecisionRequest =
+            serde_json::from_str(r#"{"decision":"propose"}"#).unwrap();
+        assert!(validate_decision_payload(&payload).is_ok());
+    }
+}
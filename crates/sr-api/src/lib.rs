@@ -35,6 +35,10 @@ use sha2::{Digest, Sha256};
 use sr_common::api::match_response::MatchConfig;
 use sr_common::db::create_pool_from_url_checked;
 use sr_common::db::{run_migrations, PgPool};
+use sr_common::matching::experiment::ExperimentConfig;
+use sr_common::matching::pipeline::SharedTwoTower;
+use sr_common::matching::weights::active_weights_file;
+use sr_common::rules::{init_active_rules, install_rules, ActiveRules};
 use sr_metrics::init_metrics;
 use tokio::sync::RwLock;
 use tokio::time::Duration as TokioDuration;
@@ -53,8 +57,8 @@ pub mod security;
 use auth::{AuthConfig, AuthMode, JwtAlgorithm};
 use error::{ApiError, RateLimitMeta};
 use handlers::{
-    candidates, conversion, feedback, health, interactions, matches, queue,
-    security as security_handler,
+    candidates, conversion, experiments, feedback, health, interactions, matches, projects, queue,
+    security as security_handler, talents,
 };
 use security::SecurityTxtConfig;
 use sr_common::logging::{init_tracing_subscriber, install_tracing_panic_hook};
@@ -371,6 +375,10 @@ pub struct AppState {
     pub pool: PgPool,
     pub config: AppConfig,
     pub match_config: Arc<RwLock<MatchConfig>>,
+    /// MATCH_EXPERIMENT_CONFIG の A/B 実験（未設定なら None）。SIGHUP で再読込
+    pub experiment: Arc<RwLock<Option<ExperimentConfig>>>,
+    /// 起動時に読み込んだ Two-Tower embedder。リクエストごとのランナーで共有し、SIGHUP で読み直す
+    pub two_tower: Arc<RwLock<SharedTwoTower>>,
     pub(crate) rate_limits: RateLimits,
     pub readiness: Arc<std::sync::atomic::AtomicBool>,
 }
@@ -807,10 +815,27 @@ pub fn create_router(state: SharedState) -> Router {
         )
         .route("/match", post(matches::run_match))
         .route("/matches/:match_id", get(matches::get_match))
+        .route("/matches/:match_id/explain", get(matches::explain_match))
+        .route("/projects", get(projects::list_projects))
+        .route("/projects/:project_id", get(projects::get_project))
+        .route(
+            "/projects/:project_id/simulate",
+            post(projects::simulate_project),
+        )
         .route(
             "/projects/:project_id/candidates",
             get(candidates::list_candidates),
         )
+        .route("/talents", get(talents::list_talents))
+        .route("/talents/:talent_id", get(talents::get_talent))
+        .route(
+            "/talents/:talent_id/projects",
+            get(talents::list_talent_projects),
+        )
+        .route(
+            "/talents/:talent_id/matches/:project_id/decision",
+            post(talents::decide_match),
+        )
         .route("/feedback", post(feedback::submit_feedback))
         .route(
             "/feedback/history/:interaction_id",
@@ -820,7 +845,8 @@ pub fn create_router(state: SharedState) -> Router {
             "/interactions/events",
             post(interactions::submit_interaction_event),
         )
-        .route("/conversions", post(conversion::submit_conversion));
+        .route("/conversions", post(conversion::submit_conversion))
+        .route("/experiments/report", get(experiments::report));
     let deprecated_api_routes = api_routes
         .clone()
         .layer(middleware::from_fn(add_deprecation_headers));
@@ -875,6 +901,8 @@ pub fn test_state(api_key: &str) -> SharedState {
         pool,
         config: AppConfig::for_tests(auth),
         match_config: Arc::new(RwLock::new(MatchConfig::default())),
+        experiment: Arc::new(RwLock::new(None)),
+        two_tower: Arc::new(RwLock::new(SharedTwoTower::disabled())),
         rate_limits: default_rate_limits(),
         readiness: Arc::new(std::sync::atomic::AtomicBool::new(true)),
     })
@@ -1283,6 +1311,40 @@ mod tests {
         );
     }
 
+    #[test]
+    fn reload_match_config_reads_rule_file() {
+        let path = std::env::temp_dir().join(format!("sr-api-rules-{}.toml", std::process::id()));
+        std::fs::write(
+            &path,
+            "[review]\nauto_match_threshold = 0.85\nmanual_review_margin = 0.05\n",
+        )
+        .unwrap();
+        with_envs(
+            &[
+                ("MATCH_RULES_PATH", path.to_str()),
+                // ファイル指定時は従来の環境変数を無視する
+                ("AUTO_MATCH_THRESHOLD", Some("0.2")),
+            ],
+            || {
+                tokio::runtime::Builder::new_current_thread()
+                    .enable_all()
+                    .build()
+                    .unwrap()
+                    .block_on(async {
+                        let state = test_state("test-key");
+                        let version = reload_match_config_from_env(&state)
+                            .await
+                            .expect("reload should succeed");
+                        let guard = state.match_config.read().await;
+                        assert!((guard.auto_match_threshold - 0.85).abs() < f64::EPSILON);
+                        assert_eq!(sr_common::rules::active_rules().version, version);
+                        assert_ne!(version, sr_common::rules::MatchRules::default().version());
+                    });
+            },
+        );
+        std::fs::remove_file(&path).ok();
+    }
+
     #[test]
     fn reload_match_config_rejects_invalid_values() {
         with_envs(&[("AUTO_MATCH_THRESHOLD", Some("invalid"))], || {
@@ -1327,12 +1389,19 @@ mod tests {
     }
 }
 
-async fn reload_match_config_from_env(state: &SharedState) -> Result<(), String> {
+/// ルールファイル・実験設定を読み直す。どれかが不正なら何も差し替えない
+async fn reload_match_config_from_env(state: &SharedState) -> Result<String, String> {
     dotenv_override().ok();
-    let updated = MatchConfig::from_env_checked()?;
-    let mut guard = state.match_config.write().await;
-    *guard = updated;
-    Ok(())
+    let rules = ActiveRules::from_env().map_err(|err| err.to_string())?;
+    let updated = MatchConfig::from_rules(&rules);
+    let experiment = ExperimentConfig::from_env().map_err(|err| err.to_string())?;
+    let two_tower =
+        SharedTwoTower::try_from_env(experiment.as_ref()).map_err(|err| err.to_string())?;
+    let version = install_rules(rules).version.clone();
+    *state.match_config.write().await = updated;
+    *state.experiment.write().await = experiment;
+    *state.two_tower.write().await = two_tower;
+    Ok(version)
 }
 
 fn spawn_match_config_reload_listener(state: SharedState) {
@@ -1343,7 +1412,9 @@ fn spawn_match_config_reload_listener(state: SharedState) {
         tokio::spawn(async move {
             while hup.recv().await.is_some() {
                 match reload_match_config_from_env(&state).await {
-                    Ok(_) => info!("reloaded match configuration from environment"),
+                    Ok(rule_version) => {
+                        info!(%rule_version, "reloaded match configuration from environment")
+                    }
                     Err(err) => error!(error = %err, "failed to reload match configuration"),
                 }
             }
@@ -1377,7 +1448,20 @@ pub async fn run() -> Result<(), ApiError> {
 
     let cli = Cli::parse();
     let config = AppConfig::from_cli(cli)?;
-    let match_config = MatchConfig::from_env_checked().map_err(ApiError::BadRequest)?;
+    let rules = init_active_rules().map_err(|err| ApiError::BadRequest(err.to_string()))?;
+    info!(rule_version = %rules.version, path = ?rules.path, "loaded match rules");
+    let match_config = MatchConfig::from_rules(&rules);
+    let experiment =
+        ExperimentConfig::from_env().map_err(|err| ApiError::BadRequest(err.to_string()))?;
+    // モデルの読み込み失敗はハンドラ内の panic にせず起動を止める
+    let two_tower = SharedTwoTower::try_from_env(experiment.as_ref())
+        .map_err(|err| ApiError::BadRequest(err.to_string()))?;
+    // 学習済み重みファイルが壊れていたら定数重みに黙って戻さず起動を止める
+    if let Some(weights) =
+        active_weights_file().map_err(|err| ApiError::BadRequest(err.to_string()))?
+    {
+        info!(version = %weights.version, "using fitted matching weights");
+    }
     let pool = create_pool_from_url_checked(&config.database_url)
         .await
         .map_err(|err| ApiError::database_error(format!("failed to create pool: {err}")))?;
@@ -1391,6 +1475,8 @@ pub async fn run() -> Result<(), ApiError> {
         pool,
         config: config.clone(),
         match_config: Arc::new(RwLock::new(match_config)),
+        experiment: Arc::new(RwLock::new(experiment)),
+        two_tower: Arc::new(RwLock::new(two_tower)),
         rate_limits,
         readiness: Arc::new(std::sync::atomic::AtomicBool::new(true)),
     });
@@ -45,3 +45,114 @@ async fn livez_healthy_and_queue_requires_auth() {
 
     assert_eq!(interaction_events.status(), StatusCode::UNAUTHORIZED);
 }
+
+#[tokio::test]
+async fn talent_routes_are_mounted_behind_auth() {
+    let state = sr_api::test_state("test-key");
+    let app = sr_api::create_router(state);
+
+    for uri in [
+        "/api/talents?limit=20&offset=0",
+        "/api/v1/talents/42",
+        "/api/v1/talents/42/projects?limit=10",
+    ] {
+        let response = app
+            .clone()
+            .oneshot(Request::builder().uri(uri).body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED, "{uri}");
+    }
+
+    let decision = app
+        .oneshot(
+            Request::builder()
+                .uri("/api/talents/42/matches/7/decision")
+                .method("POST")
+                .header("content-type", "application/json")
+                .body(Body::from(r#"{"decision":"propose"}"#))
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(decision.status(), StatusCode::UNAUTHORIZED);
+}
+
+#[tokio::test]
+async fn project_routes_are_mounted_behind_auth() {
+    let state = sr_api::test_state("test-key");
+    let app = sr_api::create_router(state);
+
+    for uri in ["/api/projects?has_candidates=true", "/api/v1/projects/3"] {
+        let response = app
+            .clone()
+            .oneshot(Request::builder().uri(uri).body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED, "{uri}");
+    }
+
+    let simulate = app
+        .oneshot(
+            Request::builder()
+                .uri("/api/v1/projects/3/simulate")
+                .method("POST")
+                .header("content-type", "application/json")
+                .body(Body::from(r#"{"patch":{"monthly_tanka_max":85}}"#))
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(simulate.status(), StatusCode::UNAUTHORIZED);
+}
+
+#[tokio::test]
+async fn experiment_report_requires_auth_and_a_variant_set() {
+    let state = sr_api::test_state("test-key");
+    let app = sr_api::create_router(state);
+
+    let unauthorized = app
+        .clone()
+        .oneshot(
+            Request::builder()
+                .uri("/api/v1/experiments/report")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(unauthorized.status(), StatusCode::UNAUTHORIZED);
+
+    // 実験未設定で variants も無ければ DB に触れず 400
+    let missing_variants = app
+        .oneshot(
+            Request::builder()
+                .uri("/api/v1/experiments/report")
+                .header("x-api-key", "test-key")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(missing_variants.status(), StatusCode::BAD_REQUEST);
+}
+
+#[tokio::test]
+async fn match_routes_are_mounted_behind_auth() {
+    let state = sr_api::test_state("test-key");
+    let app = sr_api::create_router(state);
+
+    for uri in ["/api/matches/5", "/api/v1/matches/5/explain"] {
+        let response = app
+            .clone()
+            .oneshot(Request::builder().uri(uri).body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED, "{uri}");
+    }
+}
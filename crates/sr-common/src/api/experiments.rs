@@ -0,0 +1,177 @@
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+
+use crate::db::VariantOutcomeCounts;
+use crate::evaluation::{ProportionEstimate, RateDifference, Z_95};
+
+/// GET /api/experiments/report のレスポンス
+///
+/// 割り当て単位は案件だが、率はペア（interaction_logs の行）単位で数えるため、
+/// 区間は案件内の相関を無視した近似（やや狭め）になる。
+#[derive(Debug, Clone, Serialize)]
+pub struct ExperimentReport {
+    pub experiment: Option<String>,
+    pub control: String,
+    pub since: DateTime<Utc>,
+    pub until: Option<DateTime<Utc>>,
+    pub confidence_level: f64,
+    pub arms: Vec<ArmReport>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ArmReport {
+    pub variant: String,
+    pub impressions: i64,
+    pub projects: i64,
+    /// フィードバック率（feedback / impressions）
+    pub feedback_rate: ProportionEstimate,
+    /// 好意的フィードバックの割合（positive / feedback）
+    pub positive_feedback_rate: ProportionEstimate,
+    /// 面談化率（interviews / impressions）
+    pub interview_rate: ProportionEstimate,
+    /// 成約率（contracts / impressions）
+    pub contract_rate: ProportionEstimate,
+    /// control との差（control 自身と、どちらかが 0 件の指標は null）
+    pub vs_control: Option<ArmComparison>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ArmComparison {
+    pub feedback_rate: Option<RateDifference>,
+    pub positive_feedback_rate: Option<RateDifference>,
+    pub interview_rate: Option<RateDifference>,
+    pub contract_rate: Option<RateDifference>,
+}
+
+impl ArmReport {
+    pub fn from_counts(counts: &VariantOutcomeCounts) -> Self {
+        let rate = |successes: i64, trials: i64| {
+            ProportionEstimate::wilson(successes.max(0) as u64, trials.max(0) as u64, Z_95)
+        };
+        Self {
+            variant: counts.variant.clone(),
+            impressions: counts.impressions,
+            projects: counts.projects,
+            feedback_rate: rate(counts.feedback, counts.impressions),
+            positive_feedback_rate: rate(counts.positive_feedback, counts.feedback),
+            interview_rate: rate(counts.interviews, counts.impressions),
+            contract_rate: rate(counts.contracts, counts.impressions),
+            vs_control: None,
+        }
+    }
+
+    fn compare(&self, control: &ArmReport) -> ArmComparison {
+        ArmComparison {
+            feedback_rate: RateDifference::newcombe(&self.feedback_rate, &control.feedback_rate),
+            positive_feedback_rate: RateDifference::newcombe(
+                &self.positive_feedback_rate,
+                &control.positive_feedback_rate,
+            ),
+            interview_rate: RateDifference::newcombe(&self.interview_rate, &control.interview_rate),
+            contract_rate: RateDifference::newcombe(&self.contract_rate, &control.contract_rate),
+        }
+    }
+}
+
+impl ExperimentReport {
+    /// `variants` の順に並べる（ログの無い variant も 0 件で出す）
+    pub fn build(
+        experiment: Option<String>,
+        variants: &[String],
+        control: &str,
+        since: DateTime<Utc>,
+        until: Option<DateTime<Utc>>,
+        counts: &[VariantOutcomeCounts],
+    ) -> Self {
+        let mut arms: Vec<ArmReport> = variants
+            .iter()
+            .map(|variant| {
+                counts
+                    .iter()
+                    .find(|c| &c.variant == variant)
+                    .map(ArmReport::from_counts)
+                    .unwrap_or_else(|| {
+                        ArmReport::from_counts(&VariantOutcomeCounts {
+                            variant: variant.clone(),
+                            impressions: 0,
+                            projects: 0,
+                            feedback: 0,
+                            positive_feedback: 0,
+                            interviews: 0,
+                            contracts: 0,
+                        })
+                    })
+            })
+            .collect();
+
+        if let Some(control_arm) = arms.iter().find(|arm| arm.variant == control).cloned() {
+            for arm in arms.iter_mut().filter(|arm| arm.variant != control) {
+                arm.vs_control = Some(arm.compare(&control_arm));
+            }
+        }
+
+        Self {
+            experiment,
+            control: control.to_string(),
+            since,
+            until,
+            confidence_level: 0.95,
+            arms,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn counts(
+        variant: &str,
+        impressions: i64,
+        feedback: i64,
+        interviews: i64,
+    ) -> VariantOutcomeCounts {
+        VariantOutcomeCounts {
+            variant: variant.into(),
+            impressions,
+            projects: impressions / 10,
+            feedback,
+            positive_feedback: feedback / 2,
+            interviews,
+            contracts: 0,
+        }
+    }
+
+    #[test]
+    fn report_compares_each_arm_against_control() {
+        let since = Utc::now();
+        let report = ExperimentReport::build(
+            Some("tt".into()),
+            &["control".into(), "tt_30".into(), "idle".into()],
+            "control",
+            since,
+            None,
+            &[
+                counts("tt_30", 1000, 300, 60),
+                counts("control", 1000, 200, 30),
+            ],
+        );
+
+        assert_eq!(report.arms.len(), 3);
+        assert_eq!(report.arms[0].variant, "control");
+        assert!(report.arms[0].vs_control.is_none());
+
+        let tt = report.arms[1].vs_control.as_ref().unwrap();
+        let feedback = tt.feedback_rate.unwrap();
+        assert!((feedback.diff - 0.1).abs() < 1e-12);
+        assert!(feedback.excludes_zero());
+        assert!(tt.interview_rate.unwrap().excludes_zero());
+        // 成約 0 件同士でも差は 0 として区間を出す
+        assert_eq!(tt.contract_rate.unwrap().diff, 0.0);
+
+        let idle = &report.arms[2];
+        assert_eq!(idle.impressions, 0);
+        assert_eq!(idle.feedback_rate.rate, None);
+        assert!(idle.vs_control.as_ref().unwrap().feedback_rate.is_none());
+    }
+}
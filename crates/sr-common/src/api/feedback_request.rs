@@ -3,12 +3,15 @@ use strum::AsRefStr;
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, AsRefStr)]
 #[serde(rename_all = "snake_case")]
+#[strum(serialize_all = "snake_case")]
 pub enum FeedbackType {
     ThumbsUp,
     ThumbsDown,
     ReviewOk,
     ReviewNg,
     ReviewPending,
+    /// 営業が候補として提案した（先方の承諾ではない。学習ラベルは accepted より弱い）
+    Proposed,
     Accepted,
     Rejected,
     InterviewScheduled,
@@ -17,6 +20,7 @@ pub enum FeedbackType {
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, AsRefStr)]
 #[serde(rename_all = "snake_case")]
+#[strum(serialize_all = "snake_case")]
 pub enum NgReasonCategory {
     Tanka,
     Skill,
@@ -28,6 +32,7 @@ pub enum NgReasonCategory {
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, AsRefStr)]
 #[serde(rename_all = "snake_case")]
+#[strum(serialize_all = "snake_case")]
 pub enum FeedbackSource {
     Gui,
     Crm,
@@ -43,3 +48,23 @@ pub struct FeedbackRequest {
     pub comment: Option<String>,
     pub source: FeedbackSource,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn db_values_match_serde_names() {
+        // feedback_events の CHECK 制約は snake_case の値を前提にしている
+        for feedback_type in [
+            FeedbackType::Proposed,
+            FeedbackType::InterviewScheduled,
+            FeedbackType::ThumbsUp,
+        ] {
+            let serde_name = serde_json::to_value(&feedback_type).unwrap();
+            assert_eq!(serde_name, feedback_type.as_ref());
+        }
+        assert_eq!(NgReasonCategory::Tanka.as_ref(), "tanka");
+        assert_eq!(FeedbackSource::Gui.as_ref(), "gui");
+    }
+}
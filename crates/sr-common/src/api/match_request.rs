@@ -12,4 +12,7 @@ pub struct MatchRequest {
     pub include_softko: bool,
     #[serde(default)]
     pub limit: Option<usize>,
+    /// true の場合、新しい match_run_id で match_results / interaction_logs に保存する（project.id 必須）
+    #[serde(default)]
+    pub persist: bool,
 }
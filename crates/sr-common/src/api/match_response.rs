@@ -3,7 +3,11 @@ use std::collections::HashMap;
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 
+use crate::matching::explain::MatchExplanation;
 use crate::matching::ko_unified::{KoDecision, MatchResult, ScoreBreakdown as CoreScoreBreakdown};
+use crate::matching::pipeline::RankedTalentMatch;
+use crate::matching::scoring::{MatchScore, ScoringResult};
+use crate::rules::ActiveRules;
 
 /// GUI向けマッチング結果レスポンス
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -12,8 +16,9 @@ pub struct MatchResponse {
     pub talent_id: i64,
     /// 案件ID
     pub project_id: i64,
-    /// インタラクションID（feedback紐付け用）
-    pub interaction_id: i64,
+    /// インタラクションID（feedback紐付け用）。永続化していないライブ実行の結果には無い
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub interaction_id: Option<i64>,
 
     // === 判定結果 ===
     /// 自動マッチ推奨（HardKoなし & score > threshold）
@@ -72,7 +77,7 @@ impl MatchResponse {
         let mut response = Self {
             talent_id,
             project_id,
-            interaction_id,
+            interaction_id: Some(interaction_id),
             auto_match_eligible: result.auto_match_eligible,
             manual_review_required: result.manual_review_required,
             score: result.score,
@@ -102,6 +107,51 @@ impl MatchResponse {
     }
 }
 
+impl MatchResponse {
+    /// ライブ実行のランキング結果からレスポンスを構築する（全 KO チェックの判定を含む）。
+    /// 永続化しない実行では `interaction_id` は None（レスポンスから省く）。
+    pub fn from_ranked_talent(
+        interaction_id: Option<i64>,
+        ranked: &RankedTalentMatch,
+        engine_version: &str,
+        matched_at: DateTime<Utc>,
+        config: &MatchConfig,
+    ) -> Self {
+        let mut response = Self {
+            talent_id: ranked.talent.id.unwrap_or_default(),
+            project_id: ranked.project.id.unwrap_or_default(),
+            interaction_id,
+            auto_match_eligible: false,
+            manual_review_required: ranked.ko.needs_manual_review,
+            score: ranked.total_score,
+            score_breakdown: ScoreBreakdown::from(&ranked.detailed_score),
+            two_tower_score: ranked.two_tower_score,
+            ko_decisions: ranked
+                .ko
+                .decisions
+                .iter()
+                .map(|(name, decision)| (name.to_string(), KoDecisionDto::from(decision)))
+                .collect(),
+            ko_reasons: ranked.ko.prioritized_reasons(),
+            details: MatchDetails::from(&ranked.detailed_score),
+            engine_version: engine_version.to_string(),
+            rule_version: config
+                .rule_version
+                .clone()
+                .unwrap_or_else(|| "unknown".to_string()),
+            matched_at,
+        };
+
+        if response.is_near_threshold(config) {
+            response.manual_review_required = true;
+        }
+
+        response.auto_match_eligible =
+            !ranked.ko.is_hard_knockout && response.is_auto_match_eligible(config);
+        response
+    }
+}
+
 /// スコア内訳
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 pub struct ScoreBreakdown {
@@ -132,6 +182,19 @@ impl From<&CoreScoreBreakdown> for ScoreBreakdown {
     }
 }
 
+impl From<&MatchScore> for ScoreBreakdown {
+    fn from(value: &MatchScore) -> Self {
+        Self {
+            tanka: value.tanka.score,
+            location: value.location.score,
+            skills: value.skills.score,
+            experience: value.experience.score,
+            contract: value.contract.score,
+            business_total: value.total,
+        }
+    }
+}
+
 /// KO判定DTO
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct KoDecisionDto {
@@ -165,6 +228,22 @@ impl From<&KoDecision> for KoDecisionDto {
     }
 }
 
+/// 保存済みマッチの説明（`GET /matches/:id/explain`）
+///
+/// 説明は現在のルール・重みで再計算する。保存時のスコア・rule_version と並べて返す。
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MatchExplainResponse {
+    pub match_id: i64,
+    pub interaction_id: Option<i64>,
+    /// 保存時の最終スコア
+    pub stored_score: f64,
+    /// 保存時の rule_version
+    pub stored_rule_version: String,
+    /// 説明の計算に使った rule_version
+    pub rule_version: String,
+    pub explanation: MatchExplanation,
+}
+
 /// 詳細説明
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 pub struct MatchDetails {
@@ -192,7 +271,25 @@ pub struct MatchDetails {
     pub availability: Option<String>,
 }
 
-/// マッチング設定（環境変数から読み込み）
+impl From<&MatchScore> for MatchDetails {
+    fn from(value: &MatchScore) -> Self {
+        fn detail(result: &ScoringResult) -> Option<String> {
+            let trimmed = result.details.trim();
+            (!trimmed.is_empty()).then(|| trimmed.to_string())
+        }
+
+        Self {
+            location: detail(&value.location),
+            skills: detail(&value.skills),
+            tanka: detail(&value.tanka),
+            experience: detail(&value.experience),
+            contract: detail(&value.contract),
+            ..Self::default()
+        }
+    }
+}
+
+/// マッチング設定（ルールの review 節から読み込み）
 #[derive(Debug, Clone)]
 pub struct MatchConfig {
     /// 自動マッチ推奨の閾値（デフォルト: 0.7）
@@ -219,37 +316,19 @@ impl MatchConfig {
         Self::from_env_checked().unwrap_or_default()
     }
 
+    /// ルール（`MATCH_RULES_PATH` または従来の環境変数）の review 節から読み込む
     pub fn from_env_checked() -> Result<Self, String> {
-        fn parse_env_f64(key: &str, default: f64) -> Result<f64, String> {
-            match std::env::var(key) {
-                Ok(raw) => raw.parse::<f64>().map_err(|_| {
-                    format!("{key} must be a number between 0.0 and 1.0 (got `{raw}`)")
-                }),
-                Err(std::env::VarError::NotPresent) => Ok(default),
-                Err(err) => Err(format!("failed to read {key}: {err}")),
-            }
-        }
-
-        let auto_match_threshold = parse_env_f64("AUTO_MATCH_THRESHOLD", 0.7)?;
-        let manual_review_margin = parse_env_f64("MANUAL_REVIEW_MARGIN", 0.1)?;
-        let rule_version = std::env::var("MATCH_RULE_VERSION").ok();
+        let rules = ActiveRules::from_env().map_err(|err| err.to_string())?;
+        Ok(Self::from_rules(&rules))
+    }
 
-        if !(0.0..=1.0).contains(&auto_match_threshold) {
-            return Err(format!(
-                "AUTO_MATCH_THRESHOLD must be between 0.0 and 1.0 (got {auto_match_threshold})"
-            ));
-        }
-        if !(0.0..=1.0).contains(&manual_review_margin) {
-            return Err(format!(
-                "MANUAL_REVIEW_MARGIN must be between 0.0 and 1.0 (got {manual_review_margin})"
-            ));
+    /// 閾値はルールから、期待する rule_version は MATCH_RULE_VERSION から取る
+    pub fn from_rules(rules: &ActiveRules) -> Self {
+        Self {
+            auto_match_threshold: rules.rules.review.auto_match_threshold,
+            manual_review_margin: rules.rules.review.manual_review_margin,
+            rule_version: std::env::var("MATCH_RULE_VERSION").ok(),
         }
-
-        Ok(Self {
-            auto_match_threshold,
-            manual_review_margin,
-            rule_version,
-        })
     }
 }
 
@@ -364,7 +443,7 @@ mod tests {
 
         assert_eq!(response.talent_id, 10);
         assert_eq!(response.project_id, 20);
-        assert_eq!(response.interaction_id, 100);
+        assert_eq!(response.interaction_id, Some(100));
         assert!(response.manual_review_required);
         assert_eq!(response.score, 0.82);
         assert_eq!(response.two_tower_score, None);
@@ -376,7 +455,7 @@ mod tests {
     #[test]
     fn auto_match_helpers_respect_config() {
         let mut response = MatchResponse {
-            interaction_id: 1,
+            interaction_id: Some(1),
             talent_id: 1,
             project_id: 2,
             auto_match_eligible: true,
@@ -419,4 +498,54 @@ mod tests {
         assert!(response.manual_review_required);
         assert!(!response.auto_match_eligible);
     }
+
+    #[test]
+    fn builds_response_from_live_ranking_with_all_ko_decisions() {
+        use crate::matching::pipeline::MatchingEngine;
+        use crate::two_tower::TwoTowerConfig;
+        use crate::{Project, Talent};
+
+        let project = Project {
+            id: Some(20),
+            monthly_tanka_max: Some(120),
+            work_todofuken: Some("東京都".into()),
+            remote_onsite: Some("リモート併用".into()),
+            required_skills_keywords: vec!["Rust".into()],
+            ..Project::default()
+        };
+        let talent = Talent {
+            id: Some(10),
+            desired_price_min: Some(80),
+            residential_todofuken: Some("東京都".into()),
+            possessed_skills_keywords: vec!["rust".into()],
+            ..Talent::default()
+        };
+
+        let ranked = MatchingEngine::default().rank_talents_for_project(
+            &project,
+            &[talent],
+            None,
+            &TwoTowerConfig::default(),
+        );
+        assert_eq!(ranked.len(), 1);
+
+        let config = MatchConfig::default();
+        let response =
+            MatchResponse::from_ranked_talent(None, &ranked[0], "sr-api-test", Utc::now(), &config);
+
+        assert_eq!(response.talent_id, 10);
+        assert_eq!(response.project_id, 20);
+        assert_eq!(response.interaction_id, None);
+        let json = serde_json::to_value(&response).unwrap();
+        assert!(json.get("interaction_id").is_none());
+        assert_eq!(response.score, ranked[0].total_score);
+        assert_eq!(
+            response.score_breakdown.business_total,
+            ranked[0].detailed_score.total
+        );
+        // Pass を含む全 KO チェックの判定を返す
+        assert_eq!(response.ko_decisions.len(), ranked[0].ko.decisions.len());
+        assert!(response.ko_decisions.values().any(|d| d.ko_type == "pass"));
+        assert_eq!(response.engine_version, "sr-api-test");
+    }
 }
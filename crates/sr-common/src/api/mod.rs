@@ -1,9 +1,12 @@
 pub mod conversion;
+pub mod experiments;
 pub mod feedback_request;
 pub mod feedback_response;
 pub mod interaction_event;
 pub mod match_request;
 pub mod match_response;
 pub mod models;
+pub mod projects;
 pub mod queue_dashboard;
 pub mod queue_job;
+pub mod talents;
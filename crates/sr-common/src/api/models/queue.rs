@@ -183,6 +183,8 @@ pub struct QueueJobListResponse {
 pub struct QueueJobDetail {
     pub job: QueueJobListItem,
     pub partial_fields: Option<serde_json::Value>,
+    /// Rust 抽出の根拠（フィールド名 → マッチ位置・ルール・確信度）
+    pub field_evidence: Option<serde_json::Value>,
     pub last_error: Option<String>,
     pub llm_latency_ms: Option<i32>,
     pub processing_started_at: Option<DateTime<Utc>>,
@@ -193,6 +195,8 @@ pub struct QueueJobDetail {
 pub struct QueueJobDetailResponse {
     pub job: QueueJobListItem,
     pub partial_fields: Option<serde_json::Value>,
+    /// Rust 抽出の根拠（フィールド名 → マッチ位置・ルール・確信度）
+    pub field_evidence: Option<serde_json::Value>,
     pub last_error: Option<String>,
     pub llm_latency_ms: Option<i32>,
     pub processing_started_at: Option<DateTime<Utc>>,
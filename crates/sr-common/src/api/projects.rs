@@ -0,0 +1,181 @@
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use strum::AsRefStr;
+
+use crate::matching::simulation::ProjectSimulation;
+use crate::Project;
+
+/// `GET /api/projects` の絞り込み条件
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct ProjectListFilter {
+    /// 勤務地の都道府県（正式名称・完全一致）
+    pub todofuken: Option<String>,
+    /// リモート/常駐区分（完全一致）
+    pub remote_onsite: Option<String>,
+    /// 単価レンジ（万円）。案件の単価レンジと重なるものを返す
+    pub tanka_min: Option<i32>,
+    pub tanka_max: Option<i32>,
+    /// 開始日の範囲（開始日不明の案件は除外される）
+    pub start_from: Option<NaiveDate>,
+    pub start_to: Option<NaiveDate>,
+    /// 有効な（KO されていない）候補の有無
+    pub has_candidates: Option<bool>,
+}
+
+/// 案件一覧の 1 行（GUI `ProjectListItem`）
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ProjectListItem {
+    pub id: i64,
+    /// GUI 互換のため `id` と同じ値を返す
+    pub project_id: i64,
+    pub project_name: String,
+    pub monthly_tanka_min: Option<i32>,
+    pub monthly_tanka_max: Option<i32>,
+    pub work_todofuken: Option<String>,
+    pub remote_onsite: Option<String>,
+    pub start_date: Option<NaiveDate>,
+    pub received_at: DateTime<Utc>,
+    pub requires_manual_review: bool,
+    /// 有効な match_results の候補人数
+    pub matched_count: i64,
+    /// 提案済み（feedback accepted / conversion contacted・entry）の人数
+    pub proposed_count: i64,
+    /// 面談以降（feedback interview_scheduled / conversion interview_scheduled・offer）の人数
+    pub interviewing_count: i64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProjectsListResponse {
+    pub items: Vec<ProjectListItem>,
+    pub limit: i64,
+    pub offset: i64,
+    /// Total number of projects matching the current filters (for pagination)
+    pub total: i64,
+    pub has_more: bool,
+}
+
+/// 案件詳細の候補ごとの状態（GUI `ProjectMatchStatus`）
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, AsRefStr)]
+#[serde(rename_all = "snake_case")]
+#[strum(serialize_all = "snake_case")]
+pub enum ProjectMatchStatus {
+    Pending,
+    Proposed,
+    Rejected,
+    InterviewScheduled,
+    Accepted,
+    NoResponse,
+}
+
+impl ProjectMatchStatus {
+    /// interaction_logs.outcome（優先度順に再計算済みの feedback_type）から状態を導く
+    pub fn from_feedback(feedback_type: Option<&str>) -> Self {
+        match feedback_type {
+            Some("thumbs_up" | "review_ok" | "proposed") => Self::Proposed,
+            Some("accepted") => Self::Accepted,
+            Some("thumbs_down" | "review_ng" | "rejected") => Self::Rejected,
+            Some("interview_scheduled") => Self::InterviewScheduled,
+            Some("no_response") => Self::NoResponse,
+            _ => Self::Pending,
+        }
+    }
+}
+
+/// 案件詳細に並べる候補人材（GUI `ProjectMatch`）
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ProjectMatch {
+    pub interaction_id: i64,
+    pub match_result_id: i64,
+    pub talent_id: i64,
+    pub talent_name: Option<String>,
+    pub score: f64,
+    pub headline: Option<String>,
+    pub key_skills: Vec<String>,
+    /// 希望単価（万円）
+    pub desired_rate_min: Option<u32>,
+    pub desired_rate_max: Option<u32>,
+    pub status: ProjectMatchStatus,
+    pub last_feedback_type: Option<String>,
+    pub last_feedback_at: Option<DateTime<Utc>>,
+}
+
+/// 案件の元メール情報（本文は含めない）
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct SourceEmailMeta {
+    pub message_id: String,
+    pub subject: Option<String>,
+    pub sender_name: Option<String>,
+    pub sender_address: Option<String>,
+    pub received_at: DateTime<Utc>,
+}
+
+/// `GET /api/projects/:id`（GUI `ProjectDetailResponse` + 正規化済み案件と元メール情報）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProjectDetailResponse {
+    pub id: i64,
+    pub name: String,
+    /// 元メールの件名
+    pub summary: Option<String>,
+    pub rate_min: Option<u32>,
+    pub rate_max: Option<u32>,
+    pub work_style: Option<String>,
+    pub skills: Vec<String>,
+    pub matches: Vec<ProjectMatch>,
+    pub project: Project,
+    pub source_email: Option<SourceEmailMeta>,
+    pub final_method: String,
+    pub requires_manual_review: bool,
+    pub manual_review_reason: Option<String>,
+}
+
+/// `POST /api/projects/:id/simulate` のリクエスト
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProjectSimulationRequest {
+    /// 上書きする `Project` フィールド（例: `{"monthly_tanka_max": 85, "remote_onsite": "ハイブリッド"}`）
+    pub patch: Map<String, Value>,
+    /// SoftKo の候補も通過扱いにする
+    #[serde(default)]
+    pub include_softko: bool,
+    /// newly_passing / newly_failing に並べる件数
+    pub limit: Option<usize>,
+}
+
+/// `POST /api/projects/:id/simulate` のレスポンス（保存はしない）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProjectSimulationResponse {
+    pub project_id: i64,
+    /// patch 適用後の案件
+    pub simulated_project: Project,
+    #[serde(flatten)]
+    pub simulation: ProjectSimulation,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn match_status_mirrors_gui_feedback_mapping() {
+        assert_eq!(
+            ProjectMatchStatus::from_feedback(Some("review_ok")),
+            ProjectMatchStatus::Proposed
+        );
+        assert_eq!(
+            ProjectMatchStatus::from_feedback(Some("thumbs_down")),
+            ProjectMatchStatus::Rejected
+        );
+        assert_eq!(
+            ProjectMatchStatus::from_feedback(Some("interview_scheduled")),
+            ProjectMatchStatus::InterviewScheduled
+        );
+        assert_eq!(
+            ProjectMatchStatus::from_feedback(None),
+            ProjectMatchStatus::Pending
+        );
+        assert_eq!(
+            serde_json::to_value(ProjectMatchStatus::NoResponse).unwrap(),
+            "no_response"
+        );
+    }
+}
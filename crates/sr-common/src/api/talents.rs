@@ -0,0 +1,262 @@
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use strum::AsRefStr;
+
+use crate::api::feedback_request::NgReasonCategory;
+use crate::api::match_response::MatchResponse;
+
+/// `GET /api/talents` の検索条件
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct TalentSearchQuery {
+    /// 氏名・スキル・メモの部分一致
+    pub search: Option<String>,
+    /// スキルタグの部分一致（大文字小文字無視）
+    pub skill: Option<String>,
+    /// 居住都道府県・最寄駅の部分一致
+    pub location: Option<String>,
+    /// 営業ステータスの部分一致（例: "待機", "稼働中"）
+    pub availability: Option<String>,
+    /// 今日から N 日以内に稼働開始可能
+    pub availability_within_days: Option<i32>,
+    /// 最良スコアの下限（0.0-1.0）
+    pub score_min: Option<f64>,
+    /// 最良スコアの上限（0.0-1.0）
+    pub score_max: Option<f64>,
+}
+
+/// 人材一覧の 1 行（GUI `TalentListItem`）
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct TalentListItem {
+    pub id: i64,
+    pub name: String,
+    /// ◎〇 の能力区分から導いた主担当ロール
+    pub role: Option<String>,
+    pub location: Option<String>,
+    pub availability_date: Option<NaiveDate>,
+    pub skills: Vec<String>,
+    /// 有効な match_results の最良スコア（KO 除外）
+    pub score: Option<f64>,
+    pub experience_highlights: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TalentSearchResponse {
+    pub items: Vec<TalentListItem>,
+    pub limit: i64,
+    pub offset: i64,
+    /// Total number of talents matching the current filters (for pagination)
+    pub total: i64,
+    pub has_more: bool,
+}
+
+/// 人材詳細のプロフィール部（GUI `TalentProfile`）
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct TalentProfile {
+    pub id: i64,
+    pub name: Option<String>,
+    pub title: Option<String>,
+    pub skills: Vec<String>,
+    /// 営業ステータス（Lark の表記のまま）
+    pub availability: Option<String>,
+    pub available_from: Option<NaiveDate>,
+    pub location: Option<String>,
+    /// 希望単価下限（万円）
+    pub desired_price_min: Option<u32>,
+}
+
+/// 人材×案件ペアの営業ステータス
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, AsRefStr)]
+#[serde(rename_all = "snake_case")]
+#[strum(serialize_all = "snake_case")]
+pub enum TalentMatchStatus {
+    Pending,
+    Proposed,
+    Accepted,
+    InProject,
+    Rejected,
+}
+
+impl TalentMatchStatus {
+    /// 最新の conversion stage と最新の（取り消されていない）feedback_type から状態を導く。
+    /// CV（conversion_events）は営業プロセスの実績なので feedback より優先する。
+    pub fn derive(conversion_stage: Option<&str>, feedback_type: Option<&str>) -> Self {
+        match conversion_stage {
+            Some("contract_signed") => return Self::InProject,
+            Some("interview_scheduled" | "offer") => return Self::Accepted,
+            Some("contacted" | "entry") => return Self::Proposed,
+            Some("lost") => return Self::Rejected,
+            _ => {}
+        }
+
+        match feedback_type {
+            Some("interview_scheduled") => Self::Accepted,
+            Some("proposed" | "accepted") => Self::Proposed,
+            Some("rejected") => Self::Rejected,
+            _ => Self::Pending,
+        }
+    }
+
+    pub fn can_propose(self) -> bool {
+        matches!(self, Self::Pending | Self::Rejected)
+    }
+
+    pub fn can_reject(self) -> bool {
+        matches!(self, Self::Pending | Self::Proposed | Self::Accepted)
+    }
+}
+
+/// 人材詳細に並べるマッチ済み案件（GUI `TalentMatchProject`）
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct TalentMatchProject {
+    pub project_id: i64,
+    pub project_name: String,
+    pub score: Option<f64>,
+    pub business_score: Option<f64>,
+    pub status: TalentMatchStatus,
+    pub can_propose: bool,
+    pub can_reject: bool,
+    pub last_status_change: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TalentDetailResponse {
+    pub talent: TalentProfile,
+    pub matches: Vec<TalentMatchProject>,
+}
+
+/// 人材詳細画面からの対応結果。提案/見送りは feedback_events、選考段階は conversion_events に記録する
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, AsRefStr)]
+#[serde(rename_all = "snake_case")]
+#[strum(serialize_all = "snake_case")]
+pub enum TalentMatchDecision {
+    Propose,
+    Reject,
+    InterviewScheduled,
+    Offer,
+    ContractSigned,
+}
+
+impl TalentMatchDecision {
+    /// 現在の状態からこの対応結果を記録できるか
+    pub fn allowed_from(self, status: TalentMatchStatus) -> bool {
+        match self {
+            Self::Propose => status.can_propose(),
+            Self::Reject => status.can_reject(),
+            Self::InterviewScheduled | Self::Offer => {
+                matches!(
+                    status,
+                    TalentMatchStatus::Proposed | TalentMatchStatus::Accepted
+                )
+            }
+            Self::ContractSigned => matches!(status, TalentMatchStatus::Accepted),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TalentMatchDecisionRequest {
+    pub decision: TalentMatchDecision,
+    /// 見送り理由（reject 時のみ。未指定なら other）
+    #[serde(default)]
+    pub ng_reason_category: Option<NgReasonCategory>,
+    #[serde(default)]
+    pub comment: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct TalentMatchDecisionResponse {
+    pub status: TalentMatchStatus,
+}
+
+/// 人材起点の案件ランキング 1 件（`GET /api/talents/:id/projects`）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TalentProjectMatch {
+    pub project_name: Option<String>,
+    pub work_todofuken: Option<String>,
+    pub remote_onsite: Option<String>,
+    /// 単価（万円）
+    pub monthly_tanka_min: Option<u32>,
+    pub monthly_tanka_max: Option<u32>,
+    /// 案件起点と同じ KO 判定・スコア内訳
+    pub result: MatchResponse,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TalentProjectsResponse {
+    pub talent_id: i64,
+    /// ランキング対象にした（受信期間内の）案件数
+    pub projects_considered: usize,
+    pub items: Vec<TalentProjectMatch>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn conversion_stage_takes_precedence_over_feedback() {
+        assert_eq!(
+            TalentMatchStatus::derive(Some("contract_signed"), Some("rejected")),
+            TalentMatchStatus::InProject
+        );
+        assert_eq!(
+            TalentMatchStatus::derive(Some("lost"), Some("accepted")),
+            TalentMatchStatus::Rejected
+        );
+        assert_eq!(
+            TalentMatchStatus::derive(None, Some("accepted")),
+            TalentMatchStatus::Proposed
+        );
+        assert_eq!(
+            TalentMatchStatus::derive(None, Some("proposed")),
+            TalentMatchStatus::Proposed
+        );
+        assert_eq!(
+            TalentMatchStatus::derive(None, Some("thumbs_up")),
+            TalentMatchStatus::Pending
+        );
+        assert!(!TalentMatchStatus::InProject.can_reject());
+        assert!(TalentMatchStatus::Rejected.can_propose());
+    }
+
+    #[test]
+    fn decision_transitions_follow_pipeline_order() {
+        use TalentMatchDecision::*;
+        use TalentMatchStatus as S;
+
+        assert!(Propose.allowed_from(S::Pending));
+        assert!(!Propose.allowed_from(S::Proposed));
+        assert!(Reject.allowed_from(S::Accepted));
+        assert!(!Reject.allowed_from(S::InProject));
+        assert!(InterviewScheduled.allowed_from(S::Proposed));
+        assert!(!InterviewScheduled.allowed_from(S::Pending));
+        assert!(!ContractSigned.allowed_from(S::Proposed));
+        assert!(ContractSigned.allowed_from(S::Accepted));
+
+        let request: TalentMatchDecisionRequest =
+            serde_json::from_str(r#"{"decision":"propose"}"#).unwrap();
+        assert_eq!(request.decision, Propose);
+        assert!(request.ng_reason_category.is_none());
+    }
+
+    #[test]
+    fn list_item_serializes_snake_case_for_gui_client() {
+        let item = TalentListItem {
+            id: 1,
+            name: "山田".into(),
+            role: Some("PM/PMO".into()),
+            location: None,
+            availability_date: NaiveDate::from_ymd_opt(2026, 11, 1),
+            skills: vec!["Java".into()],
+            score: Some(0.8),
+            experience_highlights: vec![],
+        };
+        let value = serde_json::to_value(&item).unwrap();
+        assert_eq!(value["availability_date"], "2026-11-01");
+        assert!(value.get("experience_highlights").is_some());
+        assert_eq!(
+            serde_json::to_value(TalentMatchStatus::InProject).unwrap(),
+            "in_project"
+        );
+    }
+}
@@ -1,4 +1,6 @@
-/// 単価計算の共通パラメータ
+use crate::rules::{active_rules, TankaRules};
+
+/// 単価計算の共通パラメータ（既定値。実行時はルールファイルの [tanka] が優先）
 #[allow(non_snake_case)]
 pub mod TankaParams {
     /// ベース単価（万円）- 人材・案件共通
@@ -33,19 +35,18 @@ pub enum PremiumCategory {
     ModernDev,
 }
 
-fn experience_addition(years: i32) -> (f64, Vec<String>) {
-    use TankaParams::*;
-    let capped_years = years.max(0).min(EXP_YEARS_CAP);
+fn experience_addition(params: &TankaRules, years: i32) -> (f64, Vec<String>) {
+    let capped_years = years.max(0).min(params.exp_years_cap);
     let mut addition = 0.0;
     let mut details = Vec::new();
 
     for year in 1..=capped_years {
         let delta = if year <= 5 {
-            EXP_RATE_1_TO_5
+            params.exp_rate_1_to_5
         } else if year <= 10 {
-            EXP_RATE_6_TO_10
+            params.exp_rate_6_to_10
         } else {
-            EXP_RATE_11_PLUS
+            params.exp_rate_11_plus
         };
         addition += delta;
     }
@@ -60,34 +61,32 @@ fn experience_addition(years: i32) -> (f64, Vec<String>) {
     (addition, details)
 }
 
-fn premium_multiplier(categories: &[PremiumCategory]) -> (f64, Vec<String>) {
-    use TankaParams::*;
+fn premium_multiplier(params: &TankaRules, categories: &[PremiumCategory]) -> (f64, Vec<String>) {
     let mut multiplier = 1.0;
     let mut details = Vec::new();
 
     for cat in categories {
         let (label, pct) = match cat {
-            PremiumCategory::Cloud => ("cloud", PREMIUM_CLOUD),
-            PremiumCategory::PmPmo => ("pm_pmo", PREMIUM_PM_PMO),
-            PremiumCategory::AiMl => ("ai_ml", PREMIUM_AI_ML),
-            PremiumCategory::ModernDev => ("modern_dev", PREMIUM_MODERN_DEV),
+            PremiumCategory::Cloud => ("cloud", params.premium_cloud),
+            PremiumCategory::PmPmo => ("pm_pmo", params.premium_pm_pmo),
+            PremiumCategory::AiMl => ("ai_ml", params.premium_ai_ml),
+            PremiumCategory::ModernDev => ("modern_dev", params.premium_modern_dev),
         };
         multiplier *= 1.0 + pct;
         details.push(format!("{}:+{:.0}%", label, pct * 100.0));
     }
 
-    if multiplier > PREMIUM_CAP {
-        details.push(format!("cap:{:.1}x", PREMIUM_CAP));
-        multiplier = PREMIUM_CAP;
+    if multiplier > params.premium_cap {
+        details.push(format!("cap:{:.1}x", params.premium_cap));
+        multiplier = params.premium_cap;
     }
 
     (multiplier, details)
 }
 
-fn calculate_range(final_tanka: f64) -> (i32, i32) {
-    use TankaParams::RANGE_WIDTH;
-    let min_tanka = (final_tanka - RANGE_WIDTH).floor() as i32;
-    let max_tanka = (final_tanka + RANGE_WIDTH).ceil() as i32;
+fn calculate_range(params: &TankaRules, final_tanka: f64) -> (i32, i32) {
+    let min_tanka = (final_tanka - params.range_width).floor() as i32;
+    let max_tanka = (final_tanka + params.range_width).ceil() as i32;
     (min_tanka, max_tanka)
 }
 
@@ -96,18 +95,30 @@ pub fn calculate_talent_tanka(
     experience_years: i32,
     premium_categories: &[PremiumCategory],
 ) -> (i32, i32, String) {
-    use TankaParams::*;
+    calculate_talent_tanka_with(
+        &active_rules().rules.tanka,
+        experience_years,
+        premium_categories,
+    )
+}
+
+/// 人材単価計算（パラメータ指定）
+pub fn calculate_talent_tanka_with(
+    params: &TankaRules,
+    experience_years: i32,
+    premium_categories: &[PremiumCategory],
+) -> (i32, i32, String) {
     let mut logic = Vec::new();
 
-    let (exp_addition, exp_logic) = experience_addition(experience_years);
+    let (exp_addition, exp_logic) = experience_addition(params, experience_years);
     logic.extend(exp_logic);
-    let base = BASE_TANKA + exp_addition;
+    let base = params.base_tanka + exp_addition;
 
-    let (multiplier, premium_logic) = premium_multiplier(premium_categories);
+    let (multiplier, premium_logic) = premium_multiplier(params, premium_categories);
     logic.extend(premium_logic);
 
     let final_tanka = base * multiplier;
-    let (min_tanka, max_tanka) = calculate_range(final_tanka);
+    let (min_tanka, max_tanka) = calculate_range(params, final_tanka);
 
     (min_tanka, max_tanka, logic.join("; "))
 }
@@ -117,13 +128,25 @@ pub fn calculate_project_tanka(
     experience_years: i32,
     premium_categories: &[PremiumCategory],
 ) -> (i32, i32, String) {
-    use TankaParams::*;
+    calculate_project_tanka_with(
+        &active_rules().rules.tanka,
+        experience_years,
+        premium_categories,
+    )
+}
+
+/// 案件単価計算（パラメータ指定）
+pub fn calculate_project_tanka_with(
+    params: &TankaRules,
+    experience_years: i32,
+    premium_categories: &[PremiumCategory],
+) -> (i32, i32, String) {
     let (min_tanka, max_tanka, logic) =
-        calculate_talent_tanka(experience_years, premium_categories);
+        calculate_talent_tanka_with(params, experience_years, premium_categories);
 
     (
-        min_tanka.max(PROJECT_MIN_TANKA),
-        max_tanka.max(PROJECT_MIN_TANKA_MAX),
+        min_tanka.max(params.project_min_tanka),
+        max_tanka.max(params.project_min_tanka_max),
         logic,
     )
 }
@@ -164,4 +187,18 @@ mod tests {
         assert_eq!(min_tanka, 137); // floor((95*1.5)-5) = 137
         assert_eq!(max_tanka, 148); // ceil((95*1.5)+5) = 148
     }
+
+    #[test]
+    fn rule_parameters_override_constants() {
+        let params = TankaRules {
+            base_tanka: 40.0,
+            project_min_tanka: 60,
+            project_min_tanka_max: 65,
+            ..TankaRules::default()
+        };
+        let (min_tanka, max_tanka, _) = calculate_talent_tanka_with(&params, 0, &[]);
+        assert_eq!((min_tanka, max_tanka), (35, 45));
+        let (min_tanka, max_tanka, _) = calculate_project_tanka_with(&params, 0, &[]);
+        assert_eq!((min_tanka, max_tanka), (60, 65));
+    }
 }
@@ -29,11 +29,29 @@ pub async fn fetch_email_body(
     Ok(row.and_then(|r| r.get::<_, Option<String>>("body_text")))
 }
 
+/// 元メールの送信者（sender_name, sender_address）。案件のクライアントプロファイル照合に使う
+pub async fn fetch_email_sender(
+    pool: &PgPool,
+    message_id: &str,
+) -> Result<Option<(Option<String>, Option<String>)>, PendingEmailError> {
+    let client = pool.get().await?;
+    let stmt = client
+        .prepare_cached(
+            "SELECT sender_name, sender_address FROM ses.anken_emails WHERE message_id = $1",
+        )
+        .await?;
+
+    let row = client
+        .timed_query_opt(&stmt, &[&message_id], "fetch_email_sender")
+        .await?;
+    Ok(row.map(|r| (r.get("sender_name"), r.get("sender_address"))))
+}
+
 /// Fetch pending emails that have not been enqueued into the extraction queue yet.
 ///
 /// This mirrors the reference query in MVP_PLAN.md: select up to `limit` rows from
 /// `ses.anken_emails` that are missing from `ses.extraction_queue`, ordered by
-/// newest first.
+/// newest first. Emails already split into section sub-jobs count as enqueued.
 pub async fn fetch_pending_emails(
     pool: &PgPool,
     limit: i64,
@@ -44,8 +62,10 @@ pub async fn fetch_pending_emails(
         .prepare_cached(
             "SELECT ae.message_id, ae.subject, ae.body_text, ae.created_at
              FROM ses.anken_emails ae
-             LEFT JOIN ses.extraction_queue eq ON ae.message_id = eq.message_id
-             WHERE eq.id IS NULL
+             WHERE NOT EXISTS (
+                 SELECT 1 FROM ses.extraction_queue eq
+                 WHERE eq.message_id = ae.message_id OR eq.source_message_id = ae.message_id
+             )
              ORDER BY ae.created_at DESC
              LIMIT $1",
         )
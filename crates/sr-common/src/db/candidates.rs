@@ -93,7 +93,7 @@ fn map_match_response(row: Row, config: &MatchConfig) -> Result<MatchResponse, M
     };
 
     let mut response = MatchResponse {
-        interaction_id: row.get("interaction_id"),
+        interaction_id: Some(row.get("interaction_id")),
         talent_id: row.get::<_, i64>("talent_id"),
         project_id: row.get::<_, i64>("project_id"),
         auto_match_eligible: false,
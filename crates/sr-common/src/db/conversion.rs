@@ -14,6 +14,16 @@ pub async fn insert_conversion_event(
     pool: &PgPool,
     actor: &str,
     request: &ConversionRequest,
+) -> Result<ConversionResponse, ConversionStorageError> {
+    let client = pool.get().await?;
+    insert_conversion_event_tx(&client, actor, request).await
+}
+
+/// Transaction-friendly variant of [`insert_conversion_event`].
+pub async fn insert_conversion_event_tx(
+    client: &impl TimedClientExt,
+    actor: &str,
+    request: &ConversionRequest,
 ) -> Result<ConversionResponse, ConversionStorageError> {
     let actor = validated_actor(actor).ok_or(ConversionStorageError::MissingActor)?;
 
@@ -23,8 +33,6 @@ pub async fn insert_conversion_event(
         .map(AsRef::as_ref)
         .unwrap_or(ConversionSource::Gui.as_ref());
 
-    let client = pool.get().await?;
-
     let stmt = client
         .prepare_cached(
             "INSERT INTO ses.conversion_events (\
@@ -0,0 +1,79 @@
+use chrono::{DateTime, Utc};
+use tracing::instrument;
+
+use crate::db::util::TimedClientExt;
+use crate::db::PgPool;
+
+db_error!(ExperimentReportError {});
+
+/// variant（実験アーム）ごとの表示・フィードバック・CV 件数
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VariantOutcomeCounts {
+    pub variant: String,
+    /// interaction_logs の行数（表示したペア数）
+    pub impressions: i64,
+    pub projects: i64,
+    /// no_response 以外の outcome が付いたペア
+    pub feedback: i64,
+    /// accepted / thumbs_up / review_ok / interview_scheduled
+    pub positive_feedback: i64,
+    /// 面談設定以降（interview_scheduled / offer / contract_signed）に進んだペア
+    pub interviews: i64,
+    pub contracts: i64,
+}
+
+/// `variants` に含まれる interaction_logs を `[since, until)` の created_at で集計する
+#[instrument(skip(pool))]
+pub async fn fetch_variant_outcomes(
+    pool: &PgPool,
+    variants: &[String],
+    since: DateTime<Utc>,
+    until: Option<DateTime<Utc>>,
+) -> Result<Vec<VariantOutcomeCounts>, ExperimentReportError> {
+    let client = pool.get().await?;
+    let rows = client
+        .timed_query_cached(
+            "WITH cv AS (
+                SELECT interaction_id,
+                       bool_or(stage IN ('interview_scheduled', 'offer', 'contract_signed')) AS interviewed,
+                       bool_or(stage = 'contract_signed') AS contracted
+                FROM ses.conversion_events
+                WHERE interaction_id IS NOT NULL
+                GROUP BY interaction_id
+            )
+            SELECT il.variant,
+                   COUNT(*) AS impressions,
+                   COUNT(DISTINCT il.project_id) AS projects,
+                   COUNT(*) FILTER (
+                       WHERE il.outcome IS NOT NULL AND il.outcome <> 'no_response'
+                   ) AS feedback,
+                   COUNT(*) FILTER (
+                       WHERE il.outcome IN ('accepted', 'thumbs_up', 'review_ok', 'interview_scheduled')
+                   ) AS positive_feedback,
+                   COUNT(*) FILTER (WHERE cv.interviewed) AS interviews,
+                   COUNT(*) FILTER (WHERE cv.contracted) AS contracts
+            FROM ses.interaction_logs il
+            LEFT JOIN cv ON cv.interaction_id = il.id
+            WHERE il.variant = ANY($1)
+              AND il.created_at >= $2
+              AND ($3::timestamptz IS NULL OR il.created_at < $3)
+            GROUP BY il.variant
+            ORDER BY il.variant",
+            &[&variants, &since, &until],
+            "fetch_variant_outcomes",
+        )
+        .await?;
+
+    Ok(rows
+        .iter()
+        .map(|row| VariantOutcomeCounts {
+            variant: row.get("variant"),
+            impressions: row.get("impressions"),
+            projects: row.get("projects"),
+            feedback: row.get("feedback"),
+            positive_feedback: row.get("positive_feedback"),
+            interviews: row.get("interviews"),
+            contracts: row.get("contracts"),
+        })
+        .collect())
+}
@@ -14,7 +14,7 @@ use crate::api::models::queue::{
 };
 use crate::db::util::TimedClientExt;
 use crate::db::{normalize_json, PgPool};
-use crate::queue::{ExtractionJob, QueueStatus};
+use crate::queue::{EntityType, ExtractionJob, QueueStatus};
 use crate::timezone::RUN_DATE_TIMEZONE;
 use once_cell::sync::Lazy;
 
@@ -155,14 +155,51 @@ fn params_from_values(values: &[Box<dyn ToSql + Sync + Send>]) -> Vec<&(dyn ToSq
 }
 
 /// Insert or update a queue row based on `message_id`.
+///
+/// Section sub-jobs (`<message_id>#<n>`) and the whole-email job for the same source message
+/// never coexist: if the other granularity is already queued, nothing is written and 0 is
+/// returned. The check and the upsert run in one transaction that first takes an advisory
+/// lock on the source message id, so concurrent enqueues of the same email are serialized.
 #[instrument(skip(pool, job))]
 pub async fn upsert_extraction_job(
     pool: &PgPool,
     job: &ExtractionJob,
 ) -> Result<u64, QueueStorageError> {
-    let client = pool.get().await?;
+    let mut client = pool.get().await?;
+    let tx = client.transaction().await?;
 
-    let stmt = client
+    tx.timed_execute_cached(
+        "SELECT pg_advisory_xact_lock(hashtext($1))",
+        &[&job.source_message_id()],
+        "upsert_extraction_job_lock",
+    )
+    .await?;
+
+    let conflict_stmt = tx
+        .prepare_cached(
+            "SELECT 1 FROM ses.extraction_queue
+             WHERE (message_id = $1 OR source_message_id = $1)
+               AND message_id <> $2
+               AND (section_index IS NULL) <> $3
+             LIMIT 1",
+        )
+        .await?;
+    let conflicting = tx
+        .timed_query_opt(
+            &conflict_stmt,
+            &[
+                &job.source_message_id(),
+                &job.message_id,
+                &job.section_index.is_none(),
+            ],
+            "upsert_extraction_job_section_conflict",
+        )
+        .await?;
+    if conflicting.is_some() {
+        return Ok(0);
+    }
+
+    let stmt = tx
         .prepare_cached(
             "INSERT INTO ses.extraction_queue (
                 message_id,
@@ -189,11 +226,16 @@ pub async fn upsert_extraction_job(
                 requires_manual_review,
                 manual_review_reason,
                 reprocess_after,
-                canary_target
+                canary_target,
+                entity_type,
+                source_message_id,
+                section_index,
+                field_evidence,
+                section_text
             ) VALUES (
                 $1, $2, $3, $4, $5, $6, $7, $8, $9, $10,
                 $11, $12, $13, $14, $15, $16, $17, $18, $19, $20,
-                $21, $22, $23, $24, $25
+                $21, $22, $23, $24, $25, $26, $27, $28, $29, $30
             )
             ON CONFLICT (message_id) DO UPDATE SET
                 email_subject = EXCLUDED.email_subject,
@@ -218,14 +260,23 @@ pub async fn upsert_extraction_job(
                 requires_manual_review = EXCLUDED.requires_manual_review,
                 manual_review_reason = EXCLUDED.manual_review_reason,
                 reprocess_after = EXCLUDED.reprocess_after,
-                canary_target = EXCLUDED.canary_target;",
+                canary_target = EXCLUDED.canary_target,
+                entity_type = EXCLUDED.entity_type,
+                source_message_id = EXCLUDED.source_message_id,
+                section_index = EXCLUDED.section_index,
+                field_evidence = EXCLUDED.field_evidence,
+                section_text = EXCLUDED.section_text;",
         )
         .await?;
 
     let recommended = job.recommended_method.as_ref().map(|r| r.as_str());
     let final_method = job.final_method.as_ref().map(|f| f.as_str());
+    let section_index = job
+        .section_index
+        .map(|v| i32::try_from(v).map_err(|e| QueueStorageError::Mapping(e.to_string())))
+        .transpose()?;
 
-    let rows = client
+    let rows = tx
         .timed_execute(
             &stmt,
             &[
@@ -254,11 +305,17 @@ pub async fn upsert_extraction_job(
                 &job.manual_review_reason,
                 &job.reprocess_after,
                 &job.canary_target,
+                &job.entity_type.as_str(),
+                &job.source_message_id,
+                &section_index,
+                &normalize_json(&job.field_evidence),
+                &job.section_text,
             ],
             "upsert_extraction_job",
         )
         .await?;
 
+    tx.commit().await?;
     Ok(rows)
 }
 
@@ -308,6 +365,16 @@ pub fn pending_copy(job: &ExtractionJob, received_at: DateTime<Utc>) -> Extracti
     pending
 }
 
+fn parse_entity_type(value: &str) -> Result<EntityType, QueueStorageError> {
+    match value {
+        "project" => Ok(EntityType::Project),
+        "talent" => Ok(EntityType::Talent),
+        other => Err(QueueStorageError::Mapping(format!(
+            "unknown entity_type: {other}"
+        ))),
+    }
+}
+
 fn parse_status(value: &str) -> Result<QueueStatus, QueueStorageError> {
     match value {
         "pending" => Ok(QueueStatus::Pending),
@@ -344,7 +411,7 @@ fn parse_final(value: &str) -> Result<crate::queue::FinalMethod, QueueStorageErr
     }
 }
 
-fn row_to_job(row: &Row) -> Result<ExtractionJob, QueueStorageError> {
+pub(crate) fn row_to_job(row: &Row) -> Result<ExtractionJob, QueueStorageError> {
     Ok(ExtractionJob {
         id: row
             .try_get::<_, i64>("id")
@@ -353,6 +420,7 @@ fn row_to_job(row: &Row) -> Result<ExtractionJob, QueueStorageError> {
                 u64::try_from(id).map_err(|e| QueueStorageError::Mapping(e.to_string()))
             })?,
         message_id: row.try_get("message_id")?,
+        entity_type: parse_entity_type(row.try_get::<_, String>("entity_type")?.as_str())?,
         email_subject: row.try_get("email_subject")?,
         email_received_at: row.try_get("email_received_at")?,
         subject_hash: row.try_get("subject_hash")?,
@@ -368,6 +436,7 @@ fn row_to_job(row: &Row) -> Result<ExtractionJob, QueueStorageError> {
         next_retry_at: row.try_get("next_retry_at")?,
         last_error: row.try_get("last_error")?,
         partial_fields: row.try_get("partial_fields")?,
+        field_evidence: row.try_get("field_evidence")?,
         decision_reason: row.try_get("decision_reason")?,
         recommended_method: row
             .try_get::<_, Option<String>>("recommended_method")?
@@ -388,6 +457,12 @@ fn row_to_job(row: &Row) -> Result<ExtractionJob, QueueStorageError> {
         manual_review_reason: row.try_get("manual_review_reason")?,
         reprocess_after: row.try_get("reprocess_after")?,
         canary_target: row.try_get("canary_target")?,
+        source_message_id: row.try_get("source_message_id")?,
+        section_index: row
+            .try_get::<_, Option<i32>>("section_index")?
+            .map(|v| u32::try_from(v).map_err(|e| QueueStorageError::Mapping(e.to_string())))
+            .transpose()?,
+        section_text: row.try_get("section_text")?,
     })
 }
 
@@ -412,6 +487,7 @@ fn row_to_job_detail(row: &Row) -> Result<QueueJobDetail, QueueStorageError> {
     Ok(QueueJobDetail {
         job: row_to_list_item(row)?,
         partial_fields: row.try_get("partial_fields")?,
+        field_evidence: row.try_get("field_evidence")?,
         last_error: row.try_get("last_error")?,
         llm_latency_ms: row.try_get("llm_latency_ms")?,
         processing_started_at: row.try_get("processing_started_at")?,
@@ -424,6 +500,7 @@ fn row_to_job_detail_response(row: &Row) -> Result<QueueJobDetailResponse, Queue
     Ok(QueueJobDetailResponse {
         job: base.job,
         partial_fields: base.partial_fields,
+        field_evidence: base.field_evidence,
         last_error: base.last_error,
         llm_latency_ms: base.llm_latency_ms,
         processing_started_at: base.processing_started_at,
@@ -578,6 +655,7 @@ pub async fn get_job_by_id(
         opt.map(|detail| QueueJobDetail {
             job: detail.job,
             partial_fields: detail.partial_fields,
+            field_evidence: detail.field_evidence,
             last_error: detail.last_error,
             llm_latency_ms: detail.llm_latency_ms,
             processing_started_at: detail.processing_started_at,
@@ -625,7 +703,20 @@ async fn fetch_project_snapshot(
 ) -> Result<Option<ProjectSnapshot>, QueueStorageError> {
     let stmt = client
         .prepare_cached(
-            "SELECT project_code, message_id, project_name, monthly_tanka_min, monthly_tanka_max, start_date, source_text, requires_manual_review, manual_review_reason FROM ses.projects_enum WHERE message_id = $1 LIMIT 1",
+            // ses.projects（抽出ジョブからの射影）を優先し、無ければ旧 projects_enum を参照する
+            "SELECT project_code, message_id, project_name, monthly_tanka_min, monthly_tanka_max, start_date, source_text, requires_manual_review, manual_review_reason
+             FROM (
+                 SELECT p.id AS project_code, p.message_id, p.project_name, p.monthly_tanka_min, p.monthly_tanka_max, p.start_date::text AS start_date, ae.body_text AS source_text, p.requires_manual_review, p.manual_review_reason, 0 AS source_rank
+                 FROM ses.projects p
+                 LEFT JOIN ses.anken_emails ae ON ae.message_id = split_part(p.message_id, '#', 1)
+                 WHERE p.message_id = $1
+                 UNION ALL
+                 SELECT project_code, message_id, project_name, monthly_tanka_min, monthly_tanka_max, start_date::text, source_text, COALESCE(requires_manual_review, false), manual_review_reason, 1
+                 FROM ses.projects_enum
+                 WHERE message_id = $1
+             ) snapshot
+             ORDER BY source_rank
+             LIMIT 1",
         )
         .await?;
 
@@ -986,7 +1077,7 @@ async fn get_job_detail_with_client<C: GenericClient + TimedClientExt>(
 ) -> Result<Option<QueueJobDetailResponse>, QueueStorageError> {
     let row = client
         .timed_query_opt_cached(
-            "SELECT id, message_id, status, priority, retry_count, next_retry_at, final_method, requires_manual_review, manual_review_reason, decision_reason, created_at, updated_at, partial_fields, last_error, llm_latency_ms, processing_started_at, completed_at FROM ses.extraction_queue WHERE id = $1",
+            "SELECT id, message_id, status, priority, retry_count, next_retry_at, final_method, requires_manual_review, manual_review_reason, decision_reason, created_at, updated_at, partial_fields, field_evidence, last_error, llm_latency_ms, processing_started_at, completed_at FROM ses.extraction_queue WHERE id = $1",
             &[&id],
             "get_job_detail_with_client",
         )
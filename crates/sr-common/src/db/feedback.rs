@@ -32,7 +32,7 @@ fn map_ng_reason(value: &Option<NgReasonCategory>) -> Option<&str> {
 /// Recompute the canonical outcome / feedback_at on interaction_logs based on
 /// non-revoked feedback_events using the priority rules from
 /// TwoTower_SalesFBアルゴ概観.md.
-async fn recompute_interaction_outcome(
+pub(crate) async fn recompute_interaction_outcome(
     client: &(impl GenericClient + TimedClientExt),
     interaction_id: i64,
 ) -> Result<(), PgError> {
@@ -46,11 +46,12 @@ async fn recompute_interaction_outcome(
                  WHEN 'accepted' THEN 1
                  WHEN 'rejected' THEN 2
                  WHEN 'interview_scheduled' THEN 3
-                 WHEN 'review_ok' THEN 4
-                 WHEN 'review_ng' THEN 4
-                 WHEN 'thumbs_up' THEN 5
-                 WHEN 'thumbs_down' THEN 5
-                 WHEN 'no_response' THEN 6
+                 WHEN 'proposed' THEN 4
+                 WHEN 'review_ok' THEN 5
+                 WHEN 'review_ng' THEN 5
+                 WHEN 'thumbs_up' THEN 6
+                 WHEN 'thumbs_down' THEN 6
+                 WHEN 'no_response' THEN 7
                  ELSE 100
                END ASC,
                created_at DESC
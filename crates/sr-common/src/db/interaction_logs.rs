@@ -34,7 +34,14 @@ pub async fn insert_interaction_log(
     log: &InteractionLogInsert,
 ) -> Result<u64, InteractionLogStorageError> {
     let client = pool.get().await?;
+    insert_interaction_log_tx(&client, log).await
+}
 
+/// Transaction-friendly variant of [`insert_interaction_log`].
+pub async fn insert_interaction_log_tx(
+    client: &impl TimedClientExt,
+    log: &InteractionLogInsert,
+) -> Result<u64, InteractionLogStorageError> {
     let stmt = client
         .prepare_cached(
             "INSERT INTO ses.interaction_logs (
@@ -0,0 +1,60 @@
+use chrono::{DateTime, Utc};
+
+use crate::db::anken_emails::{PendingEmail, PendingEmailError};
+use crate::db::util::TimedClientExt;
+use crate::db::PgPool;
+
+/// Fetch the raw body text for a specific message_id from `ses.jinzai_emails`.
+pub async fn fetch_talent_email_body(
+    pool: &PgPool,
+    message_id: &str,
+) -> Result<Option<String>, PendingEmailError> {
+    let client = pool.get().await?;
+    let stmt = client
+        .prepare_cached("SELECT body_text FROM ses.jinzai_emails WHERE message_id = $1")
+        .await?;
+
+    let row = client
+        .timed_query_opt(&stmt, &[&message_id], "fetch_talent_email_body")
+        .await?;
+    Ok(row.and_then(|r| r.get::<_, Option<String>>("body_text")))
+}
+
+/// Fetch talent emails that have not been enqueued into the extraction queue yet.
+///
+/// Same shape as [`crate::db::fetch_pending_emails`], but reads `ses.jinzai_emails`
+/// and uses `received_at` so the availability date is normalized against the mail date.
+pub async fn fetch_pending_talent_emails(
+    pool: &PgPool,
+    limit: i64,
+) -> Result<Vec<PendingEmail>, PendingEmailError> {
+    let client = pool.get().await?;
+
+    let stmt = client
+        .prepare_cached(
+            "SELECT je.message_id, je.subject, je.body_text, je.received_at
+             FROM ses.jinzai_emails je
+             LEFT JOIN ses.extraction_queue eq ON je.message_id = eq.message_id
+             WHERE eq.id IS NULL
+             ORDER BY je.received_at DESC
+             LIMIT $1",
+        )
+        .await?;
+
+    let rows = client
+        .timed_query(&stmt, &[&limit], "fetch_pending_talent_emails")
+        .await?;
+
+    Ok(rows
+        .into_iter()
+        .filter_map(|row| {
+            let body_text: Option<String> = row.get("body_text");
+            body_text.map(|body| PendingEmail {
+                message_id: row.get("message_id"),
+                subject: row.get("subject"),
+                body_text: body,
+                created_at: row.get::<_, DateTime<Utc>>("received_at"),
+            })
+        })
+        .collect())
+}
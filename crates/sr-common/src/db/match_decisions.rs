@@ -0,0 +1,280 @@
+use chrono::{DateTime, Utc};
+use tracing::{info, instrument};
+
+use crate::api::conversion::{ConversionRequest, ConversionSource, ConversionStage};
+use crate::api::feedback_request::{
+    FeedbackRequest, FeedbackSource, FeedbackType, NgReasonCategory,
+};
+use crate::api::feedback_response::FeedbackStatus;
+use crate::api::talents::{TalentMatchDecision, TalentMatchDecisionRequest, TalentMatchStatus};
+use crate::db::conversion::{insert_conversion_event_tx, ConversionStorageError};
+use crate::db::feedback::{
+    insert_feedback_event_tx, recompute_interaction_outcome, FeedbackStorageError,
+};
+use crate::db::talents::STATUS_FEEDBACK_TYPES;
+use crate::db::util::TimedClientExt;
+use crate::db::{validated_actor, PgPool};
+
+db_error!(MatchDecisionError {
+    #[error("no interaction found for talent {talent_id} / project {project_id}")]
+    InteractionNotFound { talent_id: i64, project_id: i64 },
+    #[error("decision {decision} is not allowed while status is {status}")]
+    InvalidTransition { decision: String, status: String },
+    #[error("decision actor is missing")]
+    MissingActor,
+    #[error("failed to record feedback: {0}")]
+    Feedback(#[from] FeedbackStorageError),
+    #[error("failed to record conversion: {0}")]
+    Conversion(#[from] ConversionStorageError),
+});
+
+/// 人材×案件ペアの現在の営業ステータス
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PairStatus {
+    pub status: TalentMatchStatus,
+    /// conversion_events が 1 件以上あるか（選考に進んだことがあるか）
+    pub has_conversion: bool,
+    pub last_status_change: Option<DateTime<Utc>>,
+}
+
+/// ペアの最新 conversion stage と最新の（取り消されていない）状態系 feedback から状態を導く
+pub async fn fetch_pair_status(
+    client: &impl TimedClientExt,
+    talent_id: i64,
+    project_id: i64,
+) -> Result<PairStatus, MatchDecisionError> {
+    let row = client
+        .timed_query_one_cached(
+            "SELECT
+                 (SELECT stage FROM ses.conversion_events
+                  WHERE talent_id = $1 AND project_id = $2
+                  ORDER BY created_at DESC, id DESC LIMIT 1) AS conversion_stage,
+                 (SELECT MAX(created_at) FROM ses.conversion_events
+                  WHERE talent_id = $1 AND project_id = $2) AS conversion_at,
+                 (SELECT feedback_type FROM ses.feedback_events
+                  WHERE talent_id = $1 AND project_id = $2
+                    AND NOT is_revoked AND feedback_type = ANY($3)
+                  ORDER BY created_at DESC, id DESC LIMIT 1) AS feedback_type,
+                 (SELECT MAX(created_at) FROM ses.feedback_events
+                  WHERE talent_id = $1 AND project_id = $2
+                    AND NOT is_revoked AND feedback_type = ANY($3)) AS feedback_at",
+            &[&talent_id, &project_id, &STATUS_FEEDBACK_TYPES],
+            "fetch_pair_status",
+        )
+        .await?;
+
+    let stage: Option<String> = row.get("conversion_stage");
+    let feedback_type: Option<String> = row.get("feedback_type");
+    let conversion_at: Option<DateTime<Utc>> = row.get("conversion_at");
+    let feedback_at: Option<DateTime<Utc>> = row.get("feedback_at");
+    Ok(PairStatus {
+        status: TalentMatchStatus::derive(stage.as_deref(), feedback_type.as_deref()),
+        has_conversion: stage.is_some(),
+        last_status_change: conversion_at.max(feedback_at),
+    })
+}
+
+/// ペアの最新 interaction_logs.id（feedback / conversion の紐付け先）
+async fn latest_interaction_id(
+    client: &impl TimedClientExt,
+    talent_id: i64,
+    project_id: i64,
+) -> Result<i64, MatchDecisionError> {
+    client
+        .timed_query_opt_cached(
+            "SELECT id FROM ses.interaction_logs
+             WHERE talent_id = $1 AND project_id = $2
+             ORDER BY created_at DESC, id DESC
+             LIMIT 1",
+            &[&talent_id, &project_id],
+            "latest_interaction_for_pair",
+        )
+        .await?
+        .map(|row| row.get("id"))
+        .ok_or(MatchDecisionError::InteractionNotFound {
+            talent_id,
+            project_id,
+        })
+}
+
+/// 反対方向の判断（提案 ⇔ 見送り）を取り消し、interaction_logs.outcome を再計算する
+async fn revoke_pair_feedback(
+    client: &impl TimedClientExt,
+    talent_id: i64,
+    project_id: i64,
+    feedback_types: &[&str],
+    actor: &str,
+) -> Result<(), MatchDecisionError> {
+    let rows = client
+        .timed_query_cached(
+            "UPDATE ses.feedback_events
+             SET is_revoked = true, revoked_at = clock_timestamp(), revoked_by = $4
+             WHERE talent_id = $1 AND project_id = $2
+               AND feedback_type = ANY($3) AND NOT is_revoked
+             RETURNING interaction_id",
+            &[&talent_id, &project_id, &feedback_types, &actor],
+            "revoke_pair_feedback",
+        )
+        .await?;
+
+    let mut interaction_ids: Vec<i64> = rows
+        .iter()
+        .filter_map(|row| row.get::<_, Option<i64>>("interaction_id"))
+        .collect();
+    interaction_ids.sort_unstable();
+    interaction_ids.dedup();
+    for interaction_id in interaction_ids {
+        recompute_interaction_outcome(client, interaction_id).await?;
+    }
+    Ok(())
+}
+
+/// 同日に取り消した同一 feedback を再度記録する場合は、冪等キーに当たるので元行を復活させる
+async fn reinstate_feedback(
+    client: &impl TimedClientExt,
+    interaction_id: i64,
+    feedback_type: &str,
+    actor: &str,
+) -> Result<(), MatchDecisionError> {
+    client
+        .timed_execute_cached(
+            "UPDATE ses.feedback_events
+             SET is_revoked = false, revoked_at = NULL, revoked_by = NULL
+             WHERE id = (
+                 SELECT id FROM ses.feedback_events
+                 WHERE interaction_id = $1 AND feedback_type = $2 AND actor = $3
+                 ORDER BY created_at DESC, id DESC
+                 LIMIT 1
+             ) AND is_revoked",
+            &[&interaction_id, &feedback_type, &actor],
+            "reinstate_feedback",
+        )
+        .await?;
+    recompute_interaction_outcome(client, interaction_id).await?;
+    Ok(())
+}
+
+fn conversion_request(
+    interaction_id: i64,
+    talent_id: i64,
+    project_id: i64,
+    stage: ConversionStage,
+) -> ConversionRequest {
+    ConversionRequest {
+        interaction_id: Some(interaction_id),
+        talent_id,
+        project_id,
+        stage,
+        source: Some(ConversionSource::Gui),
+        meta: None,
+    }
+}
+
+/// 人材詳細画面の対応結果を記録し、記録後のペアの状態を返す
+///
+/// - 提案/見送りは最新 interaction に対する feedback（proposed / rejected）として記録し、
+///   逆方向の判断は取り消す。提案は先方の承諾（accepted）ではないので、学習ラベルでも弱く扱われる。選考に進んでいる（conversion がある）ペアは contacted / lost も残す。
+/// - 面談設定・オファー・成約は conversion_events に記録する。
+pub async fn record_talent_match_decision_tx(
+    client: &impl TimedClientExt,
+    actor: &str,
+    talent_id: i64,
+    project_id: i64,
+    request: &TalentMatchDecisionRequest,
+) -> Result<TalentMatchStatus, MatchDecisionError> {
+    let actor = validated_actor(actor).ok_or(MatchDecisionError::MissingActor)?;
+    let interaction_id = latest_interaction_id(client, talent_id, project_id).await?;
+    let current = fetch_pair_status(client, talent_id, project_id).await?;
+
+    if !request.decision.allowed_from(current.status) {
+        return Err(MatchDecisionError::InvalidTransition {
+            decision: request.decision.as_ref().to_string(),
+            status: current.status.as_ref().to_string(),
+        });
+    }
+
+    let conversion_stage = match request.decision {
+        TalentMatchDecision::Propose | TalentMatchDecision::Reject => {
+            let (feedback_type, opposite): (FeedbackType, &[&str]) =
+                if request.decision == TalentMatchDecision::Propose {
+                    (FeedbackType::Proposed, &["rejected"])
+                } else {
+                    (
+                        FeedbackType::Rejected,
+                        &["proposed", "accepted", "interview_scheduled"],
+                    )
+                };
+            revoke_pair_feedback(client, talent_id, project_id, opposite, actor).await?;
+
+            let feedback = FeedbackRequest {
+                interaction_id,
+                ng_reason_category: (feedback_type == FeedbackType::Rejected).then(|| {
+                    request
+                        .ng_reason_category
+                        .clone()
+                        .unwrap_or(NgReasonCategory::Other)
+                }),
+                feedback_type,
+                comment: request.comment.clone(),
+                source: FeedbackSource::Gui,
+            };
+            let response = insert_feedback_event_tx(client, actor, &feedback).await?;
+            if response.status == FeedbackStatus::AlreadyExists {
+                reinstate_feedback(
+                    client,
+                    interaction_id,
+                    feedback.feedback_type.as_ref(),
+                    actor,
+                )
+                .await?;
+            }
+
+            // conversion は feedback より優先されるため、選考に入っているペアは stage でも残す
+            match (current.has_conversion, request.decision) {
+                (true, TalentMatchDecision::Propose) => Some(ConversionStage::Contacted),
+                (true, TalentMatchDecision::Reject) => Some(ConversionStage::Lost),
+                _ => None,
+            }
+        }
+        TalentMatchDecision::InterviewScheduled => Some(ConversionStage::InterviewScheduled),
+        TalentMatchDecision::Offer => Some(ConversionStage::Offer),
+        TalentMatchDecision::ContractSigned => Some(ConversionStage::ContractSigned),
+    };
+
+    if let Some(stage) = conversion_stage {
+        insert_conversion_event_tx(
+            client,
+            actor,
+            &conversion_request(interaction_id, talent_id, project_id, stage),
+        )
+        .await?;
+    }
+
+    let updated = fetch_pair_status(client, talent_id, project_id).await?;
+    info!(
+        talent_id,
+        project_id,
+        interaction_id,
+        decision = request.decision.as_ref(),
+        from = current.status.as_ref(),
+        to = updated.status.as_ref(),
+        "recorded talent match decision"
+    );
+    Ok(updated.status)
+}
+
+#[instrument(skip(pool, actor, request))]
+pub async fn record_talent_match_decision(
+    pool: &PgPool,
+    actor: &str,
+    talent_id: i64,
+    project_id: i64,
+    request: &TalentMatchDecisionRequest,
+) -> Result<TalentMatchStatus, MatchDecisionError> {
+    let mut client = pool.get().await?;
+    let tx = client.transaction().await?;
+    let status =
+        record_talent_match_decision_tx(&tx, actor, talent_id, project_id, request).await?;
+    tx.commit().await?;
+    Ok(status)
+}
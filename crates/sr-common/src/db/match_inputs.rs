@@ -0,0 +1,111 @@
+use chrono::{DateTime, Utc};
+use serde_json::Value;
+use tracing::{instrument, warn};
+
+use crate::db::util::TimedClientExt;
+use crate::db::PgPool;
+use crate::timezone::RUN_DATE_TIMEZONE;
+use crate::Project;
+
+db_error!(MatchInputError {});
+
+/// `ses.projects` の 1 行（JSONB の `project` 列）を matching 用の [`Project`] に変換する
+pub fn project_from_row(id: i64, payload: Value) -> Result<Project, serde_json::Error> {
+    let mut project: Project = serde_json::from_value(payload)?;
+    project.id = Some(id);
+    Ok(project)
+}
+
+/// マッチング対象の案件を取得する。
+///
+/// `ses.projects`（抽出ジョブからの射影）を参照する。
+///
+/// - `project_id` 指定時はその案件のみ（受信日・実行済み判定を無視）
+/// - それ以外は `received_since` 以降に受信した案件のうち、
+///   `unmatched_only=true` なら当日（RUN_DATE_TIMEZONE 基準）の match_results が無いものに限定
+/// - `exclude_project_ids` は同一プロセス内で処理済み（候補 0 件を含む）の案件を除外するために使う
+#[instrument(skip(pool, exclude_project_ids))]
+pub async fn fetch_active_projects(
+    pool: &PgPool,
+    project_id: Option<i64>,
+    received_since: DateTime<Utc>,
+    unmatched_only: bool,
+    exclude_project_ids: &[i64],
+    limit: i64,
+) -> Result<Vec<Project>, MatchInputError> {
+    let client = pool.get().await?;
+
+    let query = format!(
+        "SELECT p.id, p.project
+         FROM ses.projects p
+         WHERE ($1::bigint IS NOT NULL AND p.id = $1)
+            OR ($1::bigint IS NULL
+                AND p.received_at >= $2
+                AND NOT (p.id = ANY($5))
+                AND (NOT $3 OR NOT EXISTS (
+                    SELECT 1 FROM ses.match_results mr
+                    WHERE mr.project_id = p.id
+                      AND mr.deleted_at IS NULL
+                      AND mr.run_date = (now() AT TIME ZONE '{RUN_DATE_TIMEZONE}')::date
+                )))
+         ORDER BY p.received_at DESC, p.id
+         LIMIT $4"
+    );
+
+    let rows = client
+        .timed_query_cached(
+            query.as_str(),
+            &[
+                &project_id,
+                &received_since,
+                &unmatched_only,
+                &limit,
+                &exclude_project_ids,
+            ],
+            "fetch_active_projects",
+        )
+        .await?;
+
+    Ok(rows
+        .into_iter()
+        .filter_map(|row| {
+            let id: i64 = row.get("id");
+            project_from_row(id, row.get("project"))
+                .map_err(|err| warn!(project_id = id, error = %err, "skipping malformed project payload"))
+                .ok()
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::date::{NormalizedStartDate, StartDatePrecision};
+    use chrono::NaiveDate;
+
+    #[test]
+    fn project_row_restores_payload_and_id() {
+        let date = NaiveDate::from_ymd_opt(2025, 2, 1).unwrap();
+        let stored = Project {
+            id: None,
+            monthly_tanka_min: Some(60),
+            monthly_tanka_max: Some(80),
+            work_todofuken: Some("東京都".into()),
+            start_date: Some(NormalizedStartDate {
+                date: Some(date),
+                precision: StartDatePrecision::ExactDay,
+                interpretation_note: None,
+            }),
+            ..Project::default()
+        };
+
+        let project = project_from_row(42, serde_json::to_value(&stored).unwrap()).unwrap();
+
+        assert_eq!(project.id, Some(42));
+        assert_eq!(project.monthly_tanka_min, Some(60));
+        assert_eq!(project.work_todofuken.as_deref(), Some("東京都"));
+        let start = project.start_date.expect("start_date should be mapped");
+        assert_eq!(start.date, Some(date));
+        assert_eq!(start.precision, StartDatePrecision::ExactDay);
+    }
+}
@@ -24,6 +24,41 @@ pub struct MatchResultInsert {
     pub created_at: Option<DateTime<Utc>>,
 }
 
+// run_date is a GENERATED ALWAYS column computed from created_at in JST timezone.
+// We don't pass it explicitly - PostgreSQL computes it automatically.
+// ON CONFLICT updates the existing record with latest values (same JST day = same snapshot).
+// Note: EXCLUDED.created_at is within the same JST day, so run_date won't change.
+const UPSERT_MATCH_RESULT_SQL: &str = "INSERT INTO ses.match_results (
+        talent_id,
+        project_id,
+        is_knockout,
+        ko_reasons,
+        needs_manual_review,
+        score_total,
+        score_breakdown,
+        engine_version,
+        rule_version,
+        last_match_run_id,
+        created_at,
+        updated_at
+    ) VALUES (
+        $1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $11
+    )
+    ON CONFLICT ON CONSTRAINT uniq_match_results_active DO UPDATE SET
+        is_knockout = EXCLUDED.is_knockout,
+        ko_reasons = EXCLUDED.ko_reasons,
+        needs_manual_review = EXCLUDED.needs_manual_review,
+        score_total = EXCLUDED.score_total,
+        score_breakdown = EXCLUDED.score_breakdown,
+        engine_version = EXCLUDED.engine_version,
+        rule_version = EXCLUDED.rule_version,
+        last_match_run_id = EXCLUDED.last_match_run_id,
+        updated_at = EXCLUDED.updated_at,
+        is_deleted = false,
+        deleted_at = NULL,
+        deleted_by = NULL
+    RETURNING id";
+
 /// Insert or update a match result snapshot.
 /// Same-day duplicates are updated with the latest values (UPSERT pattern).
 /// The `run_date` column is a generated column computed from `created_at` in JST timezone.
@@ -33,44 +68,19 @@ pub async fn insert_match_result(
     result: &MatchResultInsert,
 ) -> Result<u64, MatchResultStorageError> {
     let client = pool.get().await?;
+    insert_match_result_tx(&client, result).await?;
+    Ok(1)
+}
 
-    // run_date is a GENERATED ALWAYS column computed from created_at in JST timezone.
-    // We don't pass it explicitly - PostgreSQL computes it automatically.
-    // ON CONFLICT updates the existing record with latest values (same JST day = same snapshot).
-    // Note: EXCLUDED.created_at is within the same JST day, so run_date won't change.
-    let stmt = client
-        .prepare_cached(
-            "INSERT INTO ses.match_results (
-                talent_id,
-                project_id,
-                is_knockout,
-                ko_reasons,
-                needs_manual_review,
-                score_total,
-                score_breakdown,
-                engine_version,
-                rule_version,
-                last_match_run_id,
-                created_at,
-                updated_at
-            ) VALUES (
-                $1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $11
-            )
-            ON CONFLICT ON CONSTRAINT uniq_match_results_active DO UPDATE SET
-                is_knockout = EXCLUDED.is_knockout,
-                ko_reasons = EXCLUDED.ko_reasons,
-                needs_manual_review = EXCLUDED.needs_manual_review,
-                score_total = EXCLUDED.score_total,
-                score_breakdown = EXCLUDED.score_breakdown,
-                engine_version = EXCLUDED.engine_version,
-                rule_version = EXCLUDED.rule_version,
-                last_match_run_id = EXCLUDED.last_match_run_id,
-                updated_at = EXCLUDED.updated_at,
-                is_deleted = false,
-                deleted_at = NULL,
-                deleted_by = NULL",
-        )
-        .await?;
+/// Transaction-friendly variant of [`insert_match_result`].
+///
+/// Returns the `ses.match_results.id` of the inserted (or updated) row so callers
+/// can link `interaction_logs.match_result_id` inside the same transaction.
+pub async fn insert_match_result_tx(
+    client: &impl TimedClientExt,
+    result: &MatchResultInsert,
+) -> Result<i64, MatchResultStorageError> {
+    let stmt = client.prepare_cached(UPSERT_MATCH_RESULT_SQL).await?;
 
     let now = Utc::now();
     let created_at = result.created_at.unwrap_or(now);
@@ -78,8 +88,8 @@ pub async fn insert_match_result(
         .match_run_id
         .clone()
         .unwrap_or_else(|| run_id::generate());
-    let rows = client
-        .timed_execute(
+    let row = client
+        .timed_query_one(
             &stmt,
             &[
                 &result.talent_id,
@@ -98,7 +108,7 @@ pub async fn insert_match_result(
         )
         .await?;
 
-    Ok(rows)
+    Ok(row.get("id"))
 }
 
 #[cfg(test)]
@@ -0,0 +1,92 @@
+use tracing::instrument;
+
+use crate::db::interaction_logs::{
+    insert_interaction_log_tx, InteractionLogInsert, InteractionLogStorageError,
+};
+use crate::db::match_results::{
+    insert_match_result_tx, MatchResultInsert, MatchResultStorageError,
+};
+use crate::db::util::TimedClientExt;
+use crate::db::PgPool;
+
+db_error!(MatchRunStorageError {
+    #[error("failed to write match_results: {0}")]
+    MatchResult(#[from] MatchResultStorageError),
+    #[error("failed to write interaction_logs: {0}")]
+    InteractionLog(#[from] InteractionLogStorageError),
+});
+
+/// 1案件×1人材分の永続化レコード（match_results + interaction_logs）
+#[derive(Debug, Clone)]
+pub struct MatchRunRecord {
+    pub match_result: MatchResultInsert,
+    pub interaction_log: InteractionLogInsert,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct MatchRunWriteSummary {
+    pub match_results: u64,
+    pub interaction_logs: u64,
+}
+
+/// 永続化した 1 人材分の採番結果
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PersistedMatch {
+    pub talent_id: i64,
+    pub match_result_id: i64,
+    pub interaction_id: i64,
+}
+
+/// match_results と interaction_logs を 1 トランザクションで書き込む。
+///
+/// match_results の UPSERT で得た id を interaction_logs.match_result_id に紐付けるため、
+/// 途中で失敗した場合はどちらのテーブルにも部分書き込みが残らない。
+#[instrument(skip(pool, records), fields(records = records.len()))]
+pub async fn insert_match_run(
+    pool: &PgPool,
+    records: &[MatchRunRecord],
+) -> Result<MatchRunWriteSummary, MatchRunStorageError> {
+    let persisted = insert_match_run_returning(pool, records).await?;
+    let written = persisted.len() as u64;
+    Ok(MatchRunWriteSummary {
+        match_results: written,
+        interaction_logs: written,
+    })
+}
+
+/// [`insert_match_run`] と同じ書き込みを行い、採番された match_results / interaction_logs の id を返す
+#[instrument(skip(pool, records), fields(records = records.len()))]
+pub async fn insert_match_run_returning(
+    pool: &PgPool,
+    records: &[MatchRunRecord],
+) -> Result<Vec<PersistedMatch>, MatchRunStorageError> {
+    let mut client = pool.get().await?;
+    let tx = client.transaction().await?;
+
+    let mut persisted = Vec::with_capacity(records.len());
+    for record in records {
+        let match_result_id = insert_match_result_tx(&tx, &record.match_result).await?;
+
+        let mut log = record.interaction_log.clone();
+        log.match_result_id = Some(match_result_id);
+        insert_interaction_log_tx(&tx, &log).await?;
+
+        // UPSERT の影響行からは id が取れないため、一意キーで引き直す
+        let row = tx
+            .timed_query_one_cached(
+                "SELECT id FROM ses.interaction_logs
+                 WHERE match_run_id = $1 AND talent_id = $2 AND project_id = $3",
+                &[&log.match_run_id, &log.talent_id, &log.project_id],
+                "interaction_id_for_run",
+            )
+            .await?;
+        persisted.push(PersistedMatch {
+            talent_id: log.talent_id,
+            match_result_id,
+            interaction_id: row.get("id"),
+        });
+    }
+
+    tx.commit().await?;
+    Ok(persisted)
+}
@@ -4,6 +4,7 @@ use tokio_postgres::Error as PgError;
 use tracing::{info, instrument};
 
 use crate::db::{DbPoolError, PgPool};
+use crate::schema::{training_labels_view_sql, TALENT_EMBEDDINGS_DDL};
 
 #[derive(Debug, Error)]
 pub enum MigrationError {
@@ -218,6 +219,177 @@ BEGIN
 
     ALTER TABLE IF EXISTS ses.schema_migrations ALTER COLUMN applied_at SET DEFAULT clock_timestamp();
 END $$;
+"#,
+    },
+    Migration {
+        id: 3,
+        description: "typed ses.projects table materialized from completed extraction jobs",
+        sql: r#"
+CREATE SCHEMA IF NOT EXISTS ses;
+
+CREATE TABLE IF NOT EXISTS ses.projects (
+    id BIGSERIAL PRIMARY KEY,
+    message_id VARCHAR(255) NOT NULL UNIQUE,
+    extraction_job_id BIGINT NOT NULL,
+    final_method VARCHAR(20) NOT NULL,
+    project_name TEXT NOT NULL,
+
+    work_todofuken VARCHAR(10),
+    work_area VARCHAR(10),
+    remote_onsite VARCHAR(20),
+    monthly_tanka_min INTEGER,
+    monthly_tanka_max INTEGER,
+    start_date DATE,
+    start_date_precision VARCHAR(20),
+
+    project JSONB NOT NULL,
+    field_sources JSONB NOT NULL DEFAULT '{}'::jsonb,
+
+    received_at TIMESTAMPTZ NOT NULL,
+    source_updated_at TIMESTAMPTZ NOT NULL,
+    requires_manual_review BOOLEAN NOT NULL DEFAULT false,
+    manual_review_reason TEXT,
+
+    created_at TIMESTAMPTZ NOT NULL DEFAULT clock_timestamp(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT clock_timestamp(),
+
+    CONSTRAINT chk_projects_final_method CHECK (final_method IN ('rust_completed', 'llm_completed'))
+);
+
+CREATE INDEX IF NOT EXISTS idx_projects_received_at ON ses.projects(received_at DESC, id);
+CREATE INDEX IF NOT EXISTS idx_projects_todofuken ON ses.projects(work_todofuken) WHERE work_todofuken IS NOT NULL;
+CREATE INDEX IF NOT EXISTS idx_projects_tanka ON ses.projects(monthly_tanka_min, monthly_tanka_max);
+CREATE INDEX IF NOT EXISTS idx_projects_start_date ON ses.projects(start_date) WHERE start_date IS NOT NULL;
+CREATE INDEX IF NOT EXISTS idx_projects_extraction_job ON ses.projects(extraction_job_id);
+"#,
+    },
+    Migration {
+        id: 4,
+        description: "residential_todofuken on ses.talents for the Lark importer",
+        sql: r#"
+ALTER TABLE IF EXISTS ses.talents ADD COLUMN IF NOT EXISTS residential_todofuken TEXT;
+"#,
+    },
+    Migration {
+        id: 5,
+        description: "talent_embeddings store for two-tower ANN retrieval",
+        sql: TALENT_EMBEDDINGS_DDL,
+    },
+    Migration {
+        id: 6,
+        description: "entity_type on extraction_queue + talents_enum snapshots from jinzai_emails",
+        sql: r#"
+CREATE SCHEMA IF NOT EXISTS ses;
+
+DO $$
+BEGIN
+    IF EXISTS (
+        SELECT 1 FROM information_schema.tables
+        WHERE table_schema = 'ses' AND table_name = 'extraction_queue'
+    ) THEN
+        ALTER TABLE ses.extraction_queue
+            ADD COLUMN IF NOT EXISTS entity_type VARCHAR(10) NOT NULL DEFAULT 'project';
+        IF NOT EXISTS (
+            SELECT 1 FROM pg_constraint WHERE conname = 'chk_entity_type'
+        ) THEN
+            ALTER TABLE ses.extraction_queue
+                ADD CONSTRAINT chk_entity_type CHECK (entity_type IN ('project', 'talent'));
+        END IF;
+        CREATE INDEX IF NOT EXISTS idx_extraction_queue_entity_status
+            ON ses.extraction_queue(entity_type, status);
+    END IF;
+END $$;
+
+CREATE TABLE IF NOT EXISTS ses.talents_enum (
+    id BIGSERIAL PRIMARY KEY,
+    message_id VARCHAR(255) NOT NULL UNIQUE,
+    talent_name TEXT NOT NULL,
+    summary_text TEXT,
+    desired_price_min INTEGER,
+    available_date DATE,
+    received_at TIMESTAMPTZ NOT NULL,
+    source_text TEXT
+);
+
+ALTER TABLE ses.talents_enum
+    ADD COLUMN IF NOT EXISTS extraction_job_id BIGINT,
+    ADD COLUMN IF NOT EXISTS final_method VARCHAR(20),
+    ADD COLUMN IF NOT EXISTS talent JSONB,
+    ADD COLUMN IF NOT EXISTS field_sources JSONB NOT NULL DEFAULT '{}'::jsonb,
+    ADD COLUMN IF NOT EXISTS source_updated_at TIMESTAMPTZ,
+    ADD COLUMN IF NOT EXISTS requires_manual_review BOOLEAN NOT NULL DEFAULT false,
+    ADD COLUMN IF NOT EXISTS manual_review_reason TEXT,
+    ADD COLUMN IF NOT EXISTS updated_at TIMESTAMPTZ NOT NULL DEFAULT clock_timestamp();
+
+CREATE INDEX IF NOT EXISTS idx_talents_enum_message_id ON ses.talents_enum(message_id);
+CREATE INDEX IF NOT EXISTS idx_talents_enum_extraction_job ON ses.talents_enum(extraction_job_id);
+"#,
+    },
+    Migration {
+        id: 7,
+        description: "section sub-jobs for multi-project emails on extraction_queue",
+        sql: r#"
+ALTER TABLE IF EXISTS ses.extraction_queue
+    ADD COLUMN IF NOT EXISTS source_message_id VARCHAR(255),
+    ADD COLUMN IF NOT EXISTS section_index INTEGER;
+
+DO $$
+BEGIN
+    IF EXISTS (
+        SELECT 1 FROM information_schema.tables
+        WHERE table_schema = 'ses' AND table_name = 'extraction_queue'
+    ) THEN
+        CREATE INDEX IF NOT EXISTS idx_extraction_queue_source_message
+            ON ses.extraction_queue(source_message_id) WHERE source_message_id IS NOT NULL;
+    END IF;
+END $$;
+"#,
+    },
+    Migration {
+        id: 8,
+        description: "per-field extraction evidence on extraction_queue",
+        sql: r#"
+ALTER TABLE IF EXISTS ses.extraction_queue
+    ADD COLUMN IF NOT EXISTS field_evidence JSONB;
+"#,
+    },
+    Migration {
+        id: 9,
+        description: "proposed feedback type, weighted below accepted in training_labels",
+        sql: concat!(
+            r#"
+DO $$
+BEGIN
+    IF EXISTS (
+        SELECT 1 FROM information_schema.tables
+        WHERE table_schema = 'ses' AND table_name = 'feedback_events'
+    ) THEN
+        ALTER TABLE ses.feedback_events DROP CONSTRAINT IF EXISTS chk_feedback_type;
+        ALTER TABLE ses.feedback_events ADD CONSTRAINT chk_feedback_type CHECK (feedback_type IN (
+            'thumbs_up', 'thumbs_down', 'review_ok', 'review_ng', 'review_pending',
+            'proposed', 'accepted', 'rejected', 'interview_scheduled', 'no_response'
+        ));
+    END IF;
+
+    IF (
+        SELECT COUNT(*) FROM information_schema.tables
+        WHERE table_schema = 'ses'
+          AND table_name IN ('interaction_logs', 'interaction_events', 'conversion_events')
+    ) = 3 THEN
+        EXECUTE $view$"#,
+            training_labels_view_sql!(),
+            r#"$view$;
+    END IF;
+END $$;
+"#
+        ),
+    },
+    Migration {
+        id: 10,
+        description: "section text stored on extraction_queue section sub-jobs",
+        sql: r#"
+ALTER TABLE IF EXISTS ses.extraction_queue
+    ADD COLUMN IF NOT EXISTS section_text TEXT;
 "#,
     },
 ];
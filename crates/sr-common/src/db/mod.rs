@@ -26,21 +26,34 @@ pub fn validated_actor(actor: &str) -> Option<&str> {
 pub mod anken_emails;
 pub mod candidates;
 pub mod conversion;
+pub mod experiments;
 pub mod extraction_queue;
 pub mod feedback;
 pub mod feedback_history;
 pub mod interaction_events;
 pub mod interaction_logs;
+pub mod jinzai_emails;
+pub mod match_decisions;
+pub mod match_inputs;
 pub mod match_results;
+pub mod match_runs;
 pub mod migrations;
 pub mod pool;
+pub mod projects;
 pub mod queue_dashboard;
+pub mod talent_embeddings;
+pub mod talents;
+pub mod talents_enum;
+pub mod training;
 pub mod util;
 
 // Keep re-exports unique so downstream crates see a single symbol per helper.
-pub use anken_emails::{fetch_email_body, fetch_pending_emails, PendingEmail, PendingEmailError};
+pub use anken_emails::{
+    fetch_email_body, fetch_email_sender, fetch_pending_emails, PendingEmail, PendingEmailError,
+};
 pub use candidates::{fetch_candidates_for_project, fetch_match_by_id, MatchFetchError};
-pub use conversion::{insert_conversion_event, ConversionStorageError};
+pub use conversion::{insert_conversion_event, insert_conversion_event_tx, ConversionStorageError};
+pub use experiments::{fetch_variant_outcomes, ExperimentReportError, VariantOutcomeCounts};
 pub use extraction_queue::{
     get_job_by_id, get_job_detail_with_includes, list_jobs, lock_next_pending_job, pending_copy,
     recover_stuck_jobs, retry_job, upsert_extraction_job, QueueStorageError,
@@ -50,10 +63,47 @@ pub use feedback::{insert_feedback_event, FeedbackStorageError};
 pub use feedback_history::{fetch_feedback_history, FeedbackHistoryError};
 pub use interaction_events::{insert_interaction_event, InteractionEventStorageError};
 pub use interaction_logs::{
-    insert_interaction_log, InteractionLogInsert, InteractionLogStorageError,
+    insert_interaction_log, insert_interaction_log_tx, InteractionLogInsert,
+    InteractionLogStorageError,
+};
+pub use jinzai_emails::{fetch_pending_talent_emails, fetch_talent_email_body};
+pub use match_decisions::{
+    fetch_pair_status, record_talent_match_decision, record_talent_match_decision_tx,
+    MatchDecisionError, PairStatus,
+};
+pub use match_inputs::{fetch_active_projects, MatchInputError};
+pub use match_results::{
+    insert_match_result, insert_match_result_tx, MatchResultInsert, MatchResultStorageError,
+};
+pub use match_runs::{
+    insert_match_run, insert_match_run_returning, MatchRunRecord, MatchRunStorageError,
+    MatchRunWriteSummary, PersistedMatch,
 };
-pub use match_results::{insert_match_result, MatchResultInsert, MatchResultStorageError};
 pub use migrations::{run_migrations, MigrationError};
 pub use pool::{create_pool_from_url, create_pool_from_url_checked, DbPoolError, PgPool};
+pub use projects::{
+    fetch_project_detail, fetch_project_matches, fetch_project_names,
+    fetch_unprojected_extractions, list_projects, materialize_completed_projects,
+    materialize_extraction, upsert_project, upsert_project_tx, CompletedExtraction,
+    ProjectStorageError, ProjectionSummary,
+};
 pub use queue_dashboard::{fetch_dashboard, QueueDashboardError};
+pub use talent_embeddings::{
+    fetch_talent_embeddings, fetch_talents_needing_embedding, prune_talent_embeddings,
+    upsert_talent_embeddings, EmbeddingModelKey, StaleTalent, StoredTalentEmbedding,
+    TalentEmbeddingStorageError, TalentEmbeddingUpsert,
+};
+pub use talents::{
+    fetch_available_talents, fetch_matchable_talents, fetch_talent_detail, fetch_talent_master,
+    fetch_talent_matches, search_talents, upsert_talent_masters, TalentMasterRow,
+    TalentMasterUpsert, TalentStorageError, TalentUpsertSummary,
+};
+pub use talents_enum::{
+    fetch_unprojected_talent_extractions, materialize_completed_talents,
+    materialize_talent_extraction, upsert_talent_projection, TalentEnumStorageError,
+};
+pub use training::{
+    decode_training_examples, encode_training_examples, fetch_score_breakdown_labels,
+    fetch_training_examples, ScoreBreakdownLabel, TrainingDataError, TrainingExample,
+};
 pub use util::normalize_json;
@@ -0,0 +1,541 @@
+use std::collections::HashMap;
+
+use chrono::{DateTime, Utc};
+use tokio_postgres::Row;
+use tracing::{error, instrument, warn};
+
+use crate::api::projects::{
+    ProjectDetailResponse, ProjectListFilter, ProjectListItem, ProjectMatch, ProjectMatchStatus,
+    ProjectsListResponse, SourceEmailMeta,
+};
+use crate::db::extraction_queue::{row_to_job, QueueStorageError};
+use crate::db::match_inputs::project_from_row;
+use crate::db::talents::{yen_to_man_yen, TalentMasterRow};
+use crate::db::util::TimedClientExt;
+use crate::db::PgPool;
+use crate::extraction::projection::{project_extraction_job, ProjectProjection};
+use crate::queue::ExtractionJob;
+
+db_error!(ProjectStorageError {
+    #[error("failed to serialize project payload: {0}")]
+    Serialization(#[from] serde_json::Error),
+    #[error("failed to map extraction job: {0}")]
+    Queue(#[from] QueueStorageError),
+    #[error("value out of range: {0}")]
+    OutOfRange(String),
+    #[error("section sub-job {job_id} has no stored section text")]
+    MissingSectionText { job_id: u64 },
+});
+
+/// 完了済み抽出ジョブと射影に必要な元メール情報
+#[derive(Debug, Clone)]
+pub struct CompletedExtraction {
+    pub job: ExtractionJob,
+    /// 元メール本文（サブジョブでも分割前の全文）
+    pub body_text: String,
+    pub received_at: DateTime<Utc>,
+    pub sender_name: Option<String>,
+    pub sender_address: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ProjectionSummary {
+    pub projected: u64,
+    pub skipped: u64,
+}
+
+fn to_i32(value: Option<u32>, field: &str) -> Result<Option<i32>, ProjectStorageError> {
+    value
+        .map(|v| {
+            i32::try_from(v).map_err(|_| ProjectStorageError::OutOfRange(format!("{field}={v}")))
+        })
+        .transpose()
+}
+
+/// 射影結果を `ses.projects` に UPSERT し、案件 ID を返す（message_id 単位）
+#[instrument(skip(pool, projection), fields(message_id = %projection.message_id))]
+pub async fn upsert_project(
+    pool: &PgPool,
+    projection: &ProjectProjection,
+) -> Result<i64, ProjectStorageError> {
+    let client = pool.get().await?;
+    upsert_project_tx(&client, projection).await
+}
+
+/// Transaction-friendly variant of [`upsert_project`].
+pub async fn upsert_project_tx(
+    client: &impl TimedClientExt,
+    projection: &ProjectProjection,
+) -> Result<i64, ProjectStorageError> {
+    let project = &projection.project;
+    let extraction_job_id = i64::try_from(projection.extraction_job_id).map_err(|_| {
+        ProjectStorageError::OutOfRange(format!(
+            "extraction_job_id={}",
+            projection.extraction_job_id
+        ))
+    })?;
+    let tanka_min = to_i32(project.monthly_tanka_min, "monthly_tanka_min")?;
+    let tanka_max = to_i32(project.monthly_tanka_max, "monthly_tanka_max")?;
+    let start_date = project.start_date.as_ref().and_then(|s| s.date);
+    let start_date_precision = project
+        .start_date
+        .as_ref()
+        .map(|s| serde_json::to_value(&s.precision))
+        .transpose()?
+        .and_then(|v| v.as_str().map(str::to_string));
+    let payload = serde_json::to_value(project)?;
+    let field_sources = serde_json::to_value(&projection.field_sources)?;
+
+    let stmt = client
+        .prepare_cached(
+            "INSERT INTO ses.projects (
+                message_id,
+                extraction_job_id,
+                final_method,
+                project_name,
+                work_todofuken,
+                work_area,
+                remote_onsite,
+                monthly_tanka_min,
+                monthly_tanka_max,
+                start_date,
+                start_date_precision,
+                project,
+                field_sources,
+                received_at,
+                source_updated_at,
+                requires_manual_review,
+                manual_review_reason
+            ) VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15, $16, $17)
+            ON CONFLICT (message_id) DO UPDATE SET
+                extraction_job_id = EXCLUDED.extraction_job_id,
+                final_method = EXCLUDED.final_method,
+                project_name = EXCLUDED.project_name,
+                work_todofuken = EXCLUDED.work_todofuken,
+                work_area = EXCLUDED.work_area,
+                remote_onsite = EXCLUDED.remote_onsite,
+                monthly_tanka_min = EXCLUDED.monthly_tanka_min,
+                monthly_tanka_max = EXCLUDED.monthly_tanka_max,
+                start_date = EXCLUDED.start_date,
+                start_date_precision = EXCLUDED.start_date_precision,
+                project = EXCLUDED.project,
+                field_sources = EXCLUDED.field_sources,
+                received_at = EXCLUDED.received_at,
+                source_updated_at = EXCLUDED.source_updated_at,
+                requires_manual_review = EXCLUDED.requires_manual_review,
+                manual_review_reason = EXCLUDED.manual_review_reason,
+                updated_at = clock_timestamp()
+            RETURNING id",
+        )
+        .await?;
+
+    let row = client
+        .timed_query_one(
+            &stmt,
+            &[
+                &projection.message_id,
+                &extraction_job_id,
+                &projection.final_method.as_str(),
+                &projection.project_name,
+                &project.work_todofuken,
+                &project.work_area,
+                &project.remote_onsite,
+                &tanka_min,
+                &tanka_max,
+                &start_date,
+                &start_date_precision,
+                &payload,
+                &field_sources,
+                &projection.received_at,
+                &projection.source_updated_at,
+                &projection.requires_manual_review,
+                &projection.manual_review_reason,
+            ],
+            "upsert_project",
+        )
+        .await?;
+
+    Ok(row.get("id"))
+}
+
+/// まだ射影されていない（または射影後に再処理された）完了済みジョブを取得する
+#[instrument(skip(pool))]
+pub async fn fetch_unprojected_extractions(
+    pool: &PgPool,
+    limit: i64,
+) -> Result<Vec<CompletedExtraction>, ProjectStorageError> {
+    let client = pool.get().await?;
+    let stmt = client
+        .prepare_cached(
+            "SELECT eq.*, ae.body_text, ae.received_at AS anken_received_at,
+                    ae.sender_name, ae.sender_address
+             FROM ses.extraction_queue eq
+             JOIN ses.anken_emails ae
+               ON ae.message_id = COALESCE(eq.source_message_id, eq.message_id)
+             LEFT JOIN ses.projects p ON p.message_id = eq.message_id
+             WHERE eq.entity_type = 'project'
+               AND eq.status = 'completed'
+               AND eq.final_method IN ('rust_completed', 'llm_completed')
+               AND (p.id IS NULL
+                    OR p.extraction_job_id <> eq.id
+                    OR p.source_updated_at < eq.updated_at)
+             ORDER BY eq.completed_at NULLS LAST, eq.id
+             LIMIT $1",
+        )
+        .await?;
+
+    let rows = client
+        .timed_query(&stmt, &[&limit], "fetch_unprojected_extractions")
+        .await?;
+
+    rows.iter()
+        .map(|row| {
+            let job = row_to_job(row)?;
+            let received_at = row
+                .get::<_, Option<DateTime<Utc>>>("anken_received_at")
+                .unwrap_or(job.email_received_at);
+            Ok(CompletedExtraction {
+                job,
+                body_text: row
+                    .get::<_, Option<String>>("body_text")
+                    .unwrap_or_default(),
+                received_at,
+                sender_name: row.get("sender_name"),
+                sender_address: row.get("sender_address"),
+            })
+        })
+        .collect()
+}
+
+/// 完了済みジョブ 1 件を射影して UPSERT する。対象外（手動レビュー等）なら `None`。
+/// セクションのサブジョブはエンキュー時に保存したセクション本文だけを射影元にし、
+/// 保存されていなければ元メールを分割し直さずにエラーにする
+pub async fn materialize_extraction(
+    pool: &PgPool,
+    extraction: &CompletedExtraction,
+) -> Result<Option<i64>, ProjectStorageError> {
+    let body_text = extraction.job.source_body(&extraction.body_text).ok_or(
+        ProjectStorageError::MissingSectionText {
+            job_id: extraction.job.id,
+        },
+    )?;
+    let Some(projection) =
+        project_extraction_job(&extraction.job, body_text, extraction.received_at)
+    else {
+        return Ok(None);
+    };
+    let projection = projection.with_sender(
+        extraction.sender_name.as_deref(),
+        extraction.sender_address.as_deref(),
+    );
+    upsert_project(pool, &projection).await.map(Some)
+}
+
+/// 未射影の完了済みジョブを最大 `limit` 件まとめて `ses.projects` に反映する
+#[instrument(skip(pool))]
+pub async fn materialize_completed_projects(
+    pool: &PgPool,
+    limit: i64,
+) -> Result<ProjectionSummary, ProjectStorageError> {
+    let mut summary = ProjectionSummary::default();
+    for extraction in fetch_unprojected_extractions(pool, limit).await? {
+        match materialize_extraction(pool, &extraction).await {
+            Ok(Some(_)) => summary.projected += 1,
+            // 1 件のために他のジョブの射影を止めない
+            Err(err @ ProjectStorageError::MissingSectionText { .. }) => {
+                error!(
+                    job_id = extraction.job.id,
+                    message_id = %extraction.job.message_id,
+                    error = %err,
+                    "section sub-job cannot be projected without its section text"
+                );
+                summary.skipped += 1;
+            }
+            Err(err) => return Err(err),
+            Ok(None) => {
+                warn!(
+                    job_id = extraction.job.id,
+                    message_id = %extraction.job.message_id,
+                    "completed extraction job could not be projected"
+                );
+                summary.skipped += 1;
+            }
+        }
+    }
+    Ok(summary)
+}
+
+/// 案件一覧の各行に付ける候補人数・提案人数・面談人数の集計（ses.projects p に対する LATERAL）
+const PROJECT_PIPELINE_COUNTS: &str = "
+    LEFT JOIN LATERAL (
+        SELECT COUNT(DISTINCT talent_id) AS matched_count
+        FROM ses.match_results
+        WHERE project_id = p.id AND deleted_at IS NULL AND NOT is_knockout
+    ) mc ON true
+    LEFT JOIN LATERAL (
+        SELECT
+            COUNT(DISTINCT talent_id) FILTER (WHERE kind = 'proposed') AS proposed_count,
+            COUNT(DISTINCT talent_id) FILTER (WHERE kind = 'interviewing') AS interviewing_count
+        FROM (
+            SELECT talent_id,
+                   CASE WHEN feedback_type IN ('proposed', 'accepted') THEN 'proposed'
+                        ELSE 'interviewing' END AS kind
+            FROM ses.feedback_events
+            WHERE project_id = p.id AND NOT is_revoked
+              AND feedback_type IN ('proposed', 'accepted', 'interview_scheduled')
+            UNION ALL
+            SELECT talent_id,
+                   CASE WHEN stage IN ('contacted', 'entry') THEN 'proposed' ELSE 'interviewing' END
+            FROM ses.conversion_events
+            WHERE project_id = p.id
+              AND stage IN ('contacted', 'entry', 'interview_scheduled', 'offer')
+        ) pipeline
+    ) pc ON true";
+
+fn row_to_list_item(row: &Row) -> Result<ProjectListItem, ProjectStorageError> {
+    let id: i64 = row.try_get("id")?;
+    Ok(ProjectListItem {
+        id,
+        project_id: id,
+        project_name: row.try_get("project_name")?,
+        monthly_tanka_min: row.try_get("monthly_tanka_min")?,
+        monthly_tanka_max: row.try_get("monthly_tanka_max")?,
+        work_todofuken: row.try_get("work_todofuken")?,
+        remote_onsite: row.try_get("remote_onsite")?,
+        start_date: row.try_get("start_date")?,
+        received_at: row.try_get("received_at")?,
+        requires_manual_review: row.try_get("requires_manual_review")?,
+        matched_count: row.try_get::<_, Option<i64>>("matched_count")?.unwrap_or(0),
+        proposed_count: row
+            .try_get::<_, Option<i64>>("proposed_count")?
+            .unwrap_or(0),
+        interviewing_count: row
+            .try_get::<_, Option<i64>>("interviewing_count")?
+            .unwrap_or(0),
+    })
+}
+
+/// 案件名をまとめて引く（ランキング結果の表示用）
+#[instrument(skip(pool, project_ids), fields(projects = project_ids.len()))]
+pub async fn fetch_project_names(
+    pool: &PgPool,
+    project_ids: &[i64],
+) -> Result<HashMap<i64, String>, ProjectStorageError> {
+    if project_ids.is_empty() {
+        return Ok(HashMap::new());
+    }
+    let client = pool.get().await?;
+    let rows = client
+        .timed_query_cached(
+            "SELECT id, project_name FROM ses.projects WHERE id = ANY($1)",
+            &[&project_ids],
+            "fetch_project_names",
+        )
+        .await?;
+    Ok(rows
+        .iter()
+        .map(|row| (row.get("id"), row.get("project_name")))
+        .collect())
+}
+
+/// `ses.projects` を受信日の新しい順に一覧する
+#[instrument(skip(pool))]
+pub async fn list_projects(
+    pool: &PgPool,
+    filter: &ProjectListFilter,
+    limit: i64,
+    offset: i64,
+) -> Result<ProjectsListResponse, ProjectStorageError> {
+    let client = pool.get().await?;
+    let query = format!(
+        "SELECT p.id, p.project_name, p.monthly_tanka_min, p.monthly_tanka_max,
+                p.work_todofuken, p.remote_onsite, p.start_date, p.received_at,
+                p.requires_manual_review,
+                mc.matched_count, pc.proposed_count, pc.interviewing_count,
+                COUNT(*) OVER() AS total_count
+         FROM ses.projects p
+         {PROJECT_PIPELINE_COUNTS}
+         WHERE ($1::text IS NULL OR p.work_todofuken = $1)
+           AND ($2::text IS NULL OR p.remote_onsite = $2)
+           AND ($3::int IS NULL OR COALESCE(p.monthly_tanka_max, p.monthly_tanka_min) >= $3)
+           AND ($4::int IS NULL OR COALESCE(p.monthly_tanka_min, p.monthly_tanka_max) <= $4)
+           AND ($5::date IS NULL OR p.start_date >= $5)
+           AND ($6::date IS NULL OR p.start_date <= $6)
+           AND ($7::bool IS NULL OR (COALESCE(mc.matched_count, 0) > 0) = $7)
+         ORDER BY p.received_at DESC, p.id DESC
+         LIMIT $8 OFFSET $9"
+    );
+
+    let rows = client
+        .timed_query_cached(
+            query.as_str(),
+            &[
+                &filter.todofuken,
+                &filter.remote_onsite,
+                &filter.tanka_min,
+                &filter.tanka_max,
+                &filter.start_from,
+                &filter.start_to,
+                &filter.has_candidates,
+                &limit,
+                &offset,
+            ],
+            "list_projects",
+        )
+        .await?;
+
+    let items = rows
+        .iter()
+        .map(row_to_list_item)
+        .collect::<Result<Vec<_>, _>>()?;
+    let total = rows
+        .first()
+        .map(|row| row.get::<_, i64>("total_count"))
+        .unwrap_or(0);
+    let has_more = offset + (items.len() as i64) < total;
+
+    Ok(ProjectsListResponse {
+        items,
+        limit,
+        offset,
+        total,
+        has_more,
+    })
+}
+
+fn row_to_project_match(row: &Row) -> Result<ProjectMatch, ProjectStorageError> {
+    let talent = TalentMasterRow {
+        name: row
+            .try_get::<_, Option<String>>("talent_name")?
+            .unwrap_or_default(),
+        nearest_station: row.try_get("nearest_station")?,
+        residential_todofuken: row.try_get("residential_todofuken")?,
+        capability_pm: row.try_get("capability_pm")?,
+        capability_se: row.try_get("capability_se")?,
+        capability_bpo: row.try_get("capability_bpo")?,
+        capability_consul: row.try_get("capability_consul")?,
+        ..TalentMasterRow::default()
+    };
+    let headline = [talent.primary_role(), talent.display_location()]
+        .into_iter()
+        .flatten()
+        .collect::<Vec<_>>();
+    let last_feedback_type: Option<String> = row.try_get("outcome")?;
+
+    Ok(ProjectMatch {
+        interaction_id: row.try_get("interaction_id")?,
+        match_result_id: row.try_get("match_result_id")?,
+        talent_id: row.try_get("talent_id")?,
+        talent_name: row.try_get("talent_name")?,
+        score: row
+            .try_get::<_, Option<f64>>("score_total")?
+            .unwrap_or_default(),
+        headline: (!headline.is_empty()).then(|| headline.join(" / ")),
+        key_skills: row
+            .try_get::<_, Option<Vec<String>>>("skill_tags")?
+            .unwrap_or_default(),
+        desired_rate_min: row
+            .try_get::<_, Option<i32>>("desired_price")?
+            .and_then(yen_to_man_yen),
+        desired_rate_max: None,
+        status: ProjectMatchStatus::from_feedback(last_feedback_type.as_deref()),
+        last_feedback_type,
+        last_feedback_at: row.try_get("feedback_at")?,
+    })
+}
+
+/// 案件の有効な候補（KO 除外）を人材ごとの最新スナップショットでスコア順に取得する
+#[instrument(skip(pool))]
+pub async fn fetch_project_matches(
+    pool: &PgPool,
+    project_id: i64,
+    limit: i64,
+) -> Result<Vec<ProjectMatch>, ProjectStorageError> {
+    let client = pool.get().await?;
+    let rows = client
+        .timed_query_cached(
+            "SELECT m.match_result_id, m.talent_id, m.score_total,
+                    il.id AS interaction_id, il.outcome, il.feedback_at,
+                    t.name AS talent_name, t.skill_tags, t.desired_price,
+                    t.nearest_station, t.residential_todofuken,
+                    t.capability_pm, t.capability_se, t.capability_bpo, t.capability_consul
+             FROM (
+                 SELECT DISTINCT ON (talent_id)
+                        id AS match_result_id, talent_id, score_total
+                 FROM ses.match_results
+                 WHERE project_id = $1 AND deleted_at IS NULL AND NOT is_knockout
+                 ORDER BY talent_id, run_date DESC, updated_at DESC
+             ) m
+             JOIN LATERAL (
+                 SELECT id, outcome, feedback_at
+                 FROM ses.interaction_logs
+                 WHERE match_result_id = m.match_result_id
+                 ORDER BY created_at DESC, id DESC
+                 LIMIT 1
+             ) il ON true
+             LEFT JOIN ses.talents t ON t.id = m.talent_id
+             ORDER BY m.score_total DESC NULLS LAST, m.talent_id
+             LIMIT $2",
+            &[&project_id, &limit],
+            "fetch_project_matches",
+        )
+        .await?;
+
+    rows.iter().map(row_to_project_match).collect()
+}
+
+/// 案件詳細（正規化済み案件・元メール情報・上位候補）。案件が存在しなければ `None`
+#[instrument(skip(pool))]
+pub async fn fetch_project_detail(
+    pool: &PgPool,
+    project_id: i64,
+    match_limit: i64,
+) -> Result<Option<ProjectDetailResponse>, ProjectStorageError> {
+    let client = pool.get().await?;
+    let Some(row) = client
+        .timed_query_opt_cached(
+            "SELECT p.id, p.message_id, p.project_name, p.project, p.final_method,
+                    p.requires_manual_review, p.manual_review_reason, p.received_at,
+                    ae.subject, ae.sender_name, ae.sender_address,
+                    ae.received_at AS email_received_at
+             FROM ses.projects p
+             LEFT JOIN ses.anken_emails ae ON ae.message_id = split_part(p.message_id, '#', 1)
+             WHERE p.id = $1",
+            &[&project_id],
+            "fetch_project_detail",
+        )
+        .await?
+    else {
+        return Ok(None);
+    };
+    drop(client);
+
+    let project = project_from_row(project_id, row.try_get("project")?)?;
+    let message_id: String = row.try_get("message_id")?;
+    let subject: Option<String> = row.try_get("subject")?;
+    let email_received_at: Option<DateTime<Utc>> = row.try_get("email_received_at")?;
+    let source_email = email_received_at.map(|received_at| SourceEmailMeta {
+        message_id: message_id.clone(),
+        subject: subject.clone(),
+        sender_name: row.get("sender_name"),
+        sender_address: row.get("sender_address"),
+        received_at,
+    });
+    let matches = fetch_project_matches(pool, project_id, match_limit).await?;
+
+    Ok(Some(ProjectDetailResponse {
+        id: project_id,
+        name: row.try_get("project_name")?,
+        summary: subject,
+        rate_min: project.monthly_tanka_min,
+        rate_max: project.monthly_tanka_max,
+        work_style: project.remote_onsite.clone(),
+        skills: project.required_skills_keywords.clone(),
+        matches,
+        final_method: row.try_get("final_method")?,
+        requires_manual_review: row.try_get("requires_manual_review")?,
+        manual_review_reason: row.try_get("manual_review_reason")?,
+        source_email,
+        project,
+    }))
+}
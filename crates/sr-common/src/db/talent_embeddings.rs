@@ -0,0 +1,185 @@
+use chrono::{DateTime, Utc};
+use tracing::instrument;
+
+use crate::db::talents::{
+    TalentMasterRow, TalentStorageError, EXCLUDED_SALES_STATUSES, TALENT_MASTER_COLUMNS,
+};
+use crate::db::util::TimedClientExt;
+use crate::db::PgPool;
+use crate::Talent;
+
+db_error!(TalentEmbeddingStorageError {
+    #[error("failed to map talent row: {0}")]
+    Talent(#[from] TalentStorageError),
+});
+
+/// `ses.talent_embeddings` のモデル識別子（embedder 名 + version + 次元）
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EmbeddingModelKey {
+    pub embedder: String,
+    pub version: String,
+    pub dimension: i32,
+}
+
+/// 埋め込みが未作成、または人材マスター更新後に再計算が必要な人材
+#[derive(Debug, Clone)]
+pub struct StaleTalent {
+    pub talent: Talent,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone)]
+pub struct TalentEmbeddingUpsert {
+    pub talent_id: i64,
+    pub talent_updated_at: DateTime<Utc>,
+    pub embedding: Vec<f32>,
+}
+
+#[derive(Debug, Clone)]
+pub struct StoredTalentEmbedding {
+    pub talent_id: i64,
+    pub embedding: Vec<f32>,
+}
+
+/// マッチング候補のうち、`key` の埋め込みが無い／古い人材を id 順に最大 `limit` 件取得する
+#[instrument(skip(pool))]
+pub async fn fetch_talents_needing_embedding(
+    pool: &PgPool,
+    key: &EmbeddingModelKey,
+    limit: i64,
+) -> Result<Vec<StaleTalent>, TalentEmbeddingStorageError> {
+    let client = pool.get().await?;
+    let query = format!(
+        "SELECT {TALENT_MASTER_COLUMNS}
+         FROM ses.talents t
+         WHERE (t.sales_status IS NULL OR NOT (t.sales_status = ANY($1)))
+           AND NOT EXISTS (
+               SELECT 1 FROM ses.talent_embeddings e
+               WHERE e.talent_id = t.id
+                 AND e.embedder = $2 AND e.embedder_version = $3 AND e.dimension = $4
+                 AND e.talent_updated_at >= t.updated_at
+           )
+         ORDER BY t.id
+         LIMIT $5"
+    );
+
+    let rows = client
+        .timed_query_cached(
+            query.as_str(),
+            &[
+                &EXCLUDED_SALES_STATUSES,
+                &key.embedder,
+                &key.version,
+                &key.dimension,
+                &limit,
+            ],
+            "fetch_talents_needing_embedding",
+        )
+        .await?;
+
+    rows.iter()
+        .map(|row| {
+            let master = TalentMasterRow::from_row(row)?;
+            Ok(StaleTalent {
+                talent: master.to_talent(),
+                updated_at: master.updated_at,
+            })
+        })
+        .collect()
+}
+
+/// 埋め込みを UPSERT する（1 トランザクション）
+#[instrument(skip(pool, embeddings), fields(embeddings = embeddings.len()))]
+pub async fn upsert_talent_embeddings(
+    pool: &PgPool,
+    key: &EmbeddingModelKey,
+    embeddings: &[TalentEmbeddingUpsert],
+) -> Result<u64, TalentEmbeddingStorageError> {
+    let mut client = pool.get().await?;
+    let tx = client.transaction().await?;
+    let stmt = tx
+        .prepare_cached(
+            "INSERT INTO ses.talent_embeddings (
+                talent_id, embedder, embedder_version, dimension, embedding, talent_updated_at
+            ) VALUES ($1, $2, $3, $4, $5, $6)
+            ON CONFLICT (talent_id, embedder, embedder_version, dimension) DO UPDATE SET
+                embedding = EXCLUDED.embedding,
+                talent_updated_at = EXCLUDED.talent_updated_at,
+                updated_at = clock_timestamp()",
+        )
+        .await?;
+
+    let mut written = 0;
+    for e in embeddings {
+        written += tx
+            .timed_execute(
+                &stmt,
+                &[
+                    &e.talent_id,
+                    &key.embedder,
+                    &key.version,
+                    &key.dimension,
+                    &e.embedding,
+                    &e.talent_updated_at,
+                ],
+                "upsert_talent_embedding",
+            )
+            .await?;
+    }
+    tx.commit().await?;
+    Ok(written)
+}
+
+/// 最新（人材マスター更新後に再計算済み）かつマッチング候補の人材の埋め込みを取得する
+#[instrument(skip(pool))]
+pub async fn fetch_talent_embeddings(
+    pool: &PgPool,
+    key: &EmbeddingModelKey,
+) -> Result<Vec<StoredTalentEmbedding>, TalentEmbeddingStorageError> {
+    let client = pool.get().await?;
+    let rows = client
+        .timed_query_cached(
+            "SELECT e.talent_id, e.embedding
+             FROM ses.talent_embeddings e
+             JOIN ses.talents t ON t.id = e.talent_id
+             WHERE e.embedder = $1 AND e.embedder_version = $2 AND e.dimension = $3
+               AND e.talent_updated_at >= t.updated_at
+               AND (t.sales_status IS NULL OR NOT (t.sales_status = ANY($4)))
+             ORDER BY e.talent_id",
+            &[
+                &key.embedder,
+                &key.version,
+                &key.dimension,
+                &EXCLUDED_SALES_STATUSES,
+            ],
+            "fetch_talent_embeddings",
+        )
+        .await?;
+
+    Ok(rows
+        .iter()
+        .map(|row| StoredTalentEmbedding {
+            talent_id: row.get("talent_id"),
+            embedding: row.get("embedding"),
+        })
+        .collect())
+}
+
+/// 同じ embedder の旧 version / 別次元の行と、削除済み人材の行を消す
+#[instrument(skip(pool))]
+pub async fn prune_talent_embeddings(
+    pool: &PgPool,
+    key: &EmbeddingModelKey,
+) -> Result<u64, TalentEmbeddingStorageError> {
+    let client = pool.get().await?;
+    let deleted = client
+        .timed_execute_cached(
+            "DELETE FROM ses.talent_embeddings e
+             WHERE (e.embedder = $1 AND (e.embedder_version <> $2 OR e.dimension <> $3))
+                OR NOT EXISTS (SELECT 1 FROM ses.talents t WHERE t.id = e.talent_id)",
+            &[&key.embedder, &key.version, &key.dimension],
+            "prune_talent_embeddings",
+        )
+        .await?;
+    Ok(deleted)
+}
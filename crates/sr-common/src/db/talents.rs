@@ -0,0 +1,765 @@
+use chrono::{DateTime, Datelike, NaiveDate, Utc};
+use serde::Serialize;
+use tokio_postgres::Row;
+use tracing::instrument;
+
+use crate::api::talents::{
+    TalentDetailResponse, TalentListItem, TalentMatchProject, TalentMatchStatus, TalentProfile,
+    TalentSearchQuery, TalentSearchResponse,
+};
+use crate::corrections::english_skill::correct_english_skill;
+use crate::corrections::flow_depth::correct_talent_flow_depth;
+use crate::corrections::station::normalize_station;
+use crate::corrections::todofuken::{correct_todofuken, correct_work_area};
+use crate::date::{NormalizedStartDate, StartDatePrecision};
+use crate::db::util::{escape_like, TimedClientExt};
+use crate::db::PgPool;
+use crate::skill_normalizer::{normalize_skill, normalize_skills_vec};
+use crate::Talent;
+
+db_error!(TalentStorageError {});
+
+/// 営業対象外としてマッチング候補から外す営業ステータス
+pub const EXCLUDED_SALES_STATUSES: &[&str] = &["NG"];
+
+/// ◎〇 以上（>= 2）の能力をスキルトークンとして扱う
+const CAPABILITY_SKILL_THRESHOLD: i32 = 2;
+
+/// 人材×案件の営業ステータス判定に使う feedback_type（GUI 評価系は状態を動かさない）
+pub(crate) const STATUS_FEEDBACK_TYPES: &[&str] =
+    &["proposed", "accepted", "rejected", "interview_scheduled"];
+
+/// 万円換算のしきい値: これ未満の値は既に万円単位で入力されているとみなす
+const MAN_YEN_INPUT_THRESHOLD: i32 = 10_000;
+
+/// `ses.talents`（Lark 由来の人材マスター）の 1 行
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct TalentMasterRow {
+    pub id: i64,
+    pub name: String,
+    pub age: Option<i32>,
+    pub birth_year: Option<i32>,
+    pub nearest_station: Option<String>,
+    pub residential_todofuken: Option<String>,
+    /// 参考原価（円/月）
+    pub desired_price: Option<i32>,
+    pub available_date: Option<NaiveDate>,
+    pub sales_status: Option<String>,
+    pub skill_tags: Vec<String>,
+    /// ◎=3, 〇=2, △=1, ✕=0
+    pub capability_pm: Option<i32>,
+    pub capability_se: Option<i32>,
+    pub capability_bpo: Option<i32>,
+    pub capability_consul: Option<i32>,
+    pub english_level: Option<String>,
+    /// SPONTO から見る商流
+    pub business_relationship: Option<String>,
+    pub lark_record_id: Option<String>,
+    pub updated_at: DateTime<Utc>,
+}
+
+pub(crate) const TALENT_MASTER_COLUMNS: &str =
+    "id, name, age, birth_year, nearest_station, residential_todofuken, desired_price, \
+     available_date, sales_status, skill_tags, capability_pm, capability_se, capability_bpo, \
+     capability_consul, english_level, business_relationship, lark_record_id, updated_at";
+
+impl TalentMasterRow {
+    pub fn from_row(row: &Row) -> Result<Self, TalentStorageError> {
+        Ok(Self {
+            id: row.try_get("id")?,
+            name: row.try_get("name")?,
+            age: row.try_get("age")?,
+            birth_year: row.try_get("birth_year")?,
+            nearest_station: row.try_get("nearest_station")?,
+            residential_todofuken: row.try_get("residential_todofuken")?,
+            desired_price: row.try_get("desired_price")?,
+            available_date: row.try_get("available_date")?,
+            sales_status: row.try_get("sales_status")?,
+            skill_tags: row
+                .try_get::<_, Option<Vec<String>>>("skill_tags")?
+                .unwrap_or_default(),
+            capability_pm: row.try_get("capability_pm")?,
+            capability_se: row.try_get("capability_se")?,
+            capability_bpo: row.try_get("capability_bpo")?,
+            capability_consul: row.try_get("capability_consul")?,
+            english_level: row.try_get("english_level")?,
+            business_relationship: row.try_get("business_relationship")?,
+            lark_record_id: row.try_get("lark_record_id")?,
+            updated_at: row.try_get("updated_at")?,
+        })
+    }
+
+    /// matching 用の [`Talent`] に変換する
+    pub fn to_talent(&self) -> Talent {
+        let mut skills = self.skill_tags.clone();
+        skills.extend(self.capability_tokens());
+
+        let residential_todofuken = self
+            .residential_todofuken
+            .as_deref()
+            .and_then(correct_todofuken);
+
+        Talent {
+            id: Some(self.id),
+            residential_area: residential_todofuken.as_deref().and_then(correct_work_area),
+            residential_todofuken,
+            desired_price_min: self.desired_price.and_then(yen_to_man_yen),
+            possessed_skills_keywords: normalize_skills_vec(&skills),
+            english_skill: self
+                .english_level
+                .as_deref()
+                .and_then(correct_english_skill),
+            flow_depth: self
+                .business_relationship
+                .as_deref()
+                .and_then(flow_depth_from_business_relationship),
+            nearest_station: self.nearest_station.as_deref().and_then(normalize_station),
+            // 生年が無ければ最終同期時点の年齢から逆算する
+            birth_year: self.birth_year.or_else(|| {
+                self.age
+                    .filter(|age| (15..=100).contains(age))
+                    .map(|age| self.updated_at.year() - age)
+            }),
+            availability_date: self.available_date.map(|date| NormalizedStartDate {
+                date: Some(date),
+                precision: StartDatePrecision::ExactDay,
+                interpretation_note: None,
+            }),
+            ..Talent::default()
+        }
+    }
+
+    /// 一覧・詳細で表示する所在地（「都道府県 最寄駅」）
+    pub fn display_location(&self) -> Option<String> {
+        let parts: Vec<&str> = [
+            self.residential_todofuken.as_deref(),
+            self.nearest_station.as_deref(),
+        ]
+        .into_iter()
+        .flatten()
+        .map(str::trim)
+        .filter(|s| !s.is_empty())
+        .collect();
+        (!parts.is_empty()).then(|| parts.join(" "))
+    }
+
+    /// ◎〇 の能力区分のうち最も高いものを主担当ロールとする（同点は PM > SE > コンサル > BPO）
+    pub fn primary_role(&self) -> Option<String> {
+        self.capability_levels()
+            .into_iter()
+            .filter(|(_, level)| *level >= CAPABILITY_SKILL_THRESHOLD)
+            .fold(
+                None,
+                |best: Option<(&str, i32)>, (label, level)| match best {
+                    Some((_, best_level)) if best_level >= level => best,
+                    _ => Some((label, level)),
+                },
+            )
+            .map(|(label, _)| label.to_string())
+    }
+
+    /// 一覧カード用の経歴ハイライト（能力区分の記号表記 + 英語力）
+    pub fn experience_highlights(&self) -> Vec<String> {
+        let mut highlights: Vec<String> = self
+            .capability_levels()
+            .into_iter()
+            .filter(|(_, level)| *level >= CAPABILITY_SKILL_THRESHOLD)
+            .map(|(label, level)| {
+                let mark = if level >= 3 { "◎" } else { "〇" };
+                format!("{label} {mark}")
+            })
+            .collect();
+        if let Some(english) = self
+            .english_level
+            .as_deref()
+            .map(str::trim)
+            .filter(|s| !s.is_empty())
+        {
+            highlights.push(format!("英語: {english}"));
+        }
+        highlights
+    }
+
+    pub fn to_list_item(&self, score: Option<f64>) -> TalentListItem {
+        TalentListItem {
+            id: self.id,
+            name: self.name.clone(),
+            role: self.primary_role(),
+            location: self.display_location(),
+            availability_date: self.available_date,
+            skills: self.skill_tags.clone(),
+            score,
+            experience_highlights: self.experience_highlights(),
+        }
+    }
+
+    pub fn to_profile(&self) -> TalentProfile {
+        TalentProfile {
+            id: self.id,
+            name: Some(self.name.clone()),
+            title: self.primary_role(),
+            skills: self.skill_tags.clone(),
+            availability: self.sales_status.clone(),
+            available_from: self.available_date,
+            location: self.display_location(),
+            desired_price_min: self.desired_price.and_then(yen_to_man_yen),
+        }
+    }
+
+    fn capability_levels(&self) -> Vec<(&'static str, i32)> {
+        [
+            ("PM/PMO", self.capability_pm),
+            ("SE", self.capability_se),
+            ("コンサル", self.capability_consul),
+            ("BPO", self.capability_bpo),
+        ]
+        .into_iter()
+        .filter_map(|(label, level)| level.map(|l| (label, l)))
+        .collect()
+    }
+
+    /// ◎〇 の能力区分をスキルトークンに展開する
+    fn capability_tokens(&self) -> Vec<String> {
+        [
+            (self.capability_pm, &["PM", "PMO"][..]),
+            (self.capability_se, &["SE"][..]),
+            (self.capability_bpo, &["BPO"][..]),
+            (self.capability_consul, &["コンサル"][..]),
+        ]
+        .into_iter()
+        .filter(|(level, _)| level.is_some_and(|l| l >= CAPABILITY_SKILL_THRESHOLD))
+        .flat_map(|(_, tokens)| tokens.iter().map(|t| t.to_string()))
+        .collect()
+    }
+}
+
+/// 参考原価（円）を万円に換算する（千円単位は四捨五入）。
+/// Lark には万円のまま入力された行も混在するため、10,000 未満はそのまま万円として扱う。
+pub fn yen_to_man_yen(price: i32) -> Option<u32> {
+    if price <= 0 {
+        return None;
+    }
+    // i32::MAX 付近でも丸めの加算があふれないよう i64 で計算する
+    let price = i64::from(price);
+    let man_yen = if price < i64::from(MAN_YEN_INPUT_THRESHOLD) {
+        price
+    } else {
+        (price + 5_000) / 10_000
+    };
+    u32::try_from(man_yen).ok()
+}
+
+/// 「SPONTOから見る商流」を人材商流（flow_depth）に変換する
+///
+/// 直契約（自社/プロパー/個人）は `parse_talent_flow_depth` で depth=0 と判定される
+/// 「SPONTO直」に寄せ、それ以外は人材商流 ENUM（1社先/2社先/3社先以上）に補正する。
+pub fn flow_depth_from_business_relationship(raw: &str) -> Option<String> {
+    let trimmed = raw.trim();
+    if trimmed.is_empty() {
+        return None;
+    }
+
+    if ["直", "自社", "プロパー", "個人"]
+        .iter()
+        .any(|kw| trimmed.contains(kw))
+    {
+        return Some("SPONTO直".to_string());
+    }
+
+    correct_talent_flow_depth(trimmed)
+}
+
+/// 人材マスターからマッチング候補を取得する（営業対象外ステータスは除外）
+#[instrument(skip(pool))]
+pub async fn fetch_matchable_talents(pool: &PgPool) -> Result<Vec<Talent>, TalentStorageError> {
+    let client = pool.get().await?;
+    let query = format!(
+        "SELECT {TALENT_MASTER_COLUMNS}
+         FROM ses.talents
+         WHERE sales_status IS NULL OR NOT (sales_status = ANY($1))
+         ORDER BY id"
+    );
+
+    let rows = client
+        .timed_query_cached(
+            query.as_str(),
+            &[&EXCLUDED_SALES_STATUSES],
+            "fetch_matchable_talents",
+        )
+        .await?;
+
+    rows.iter()
+        .map(|row| TalentMasterRow::from_row(row).map(|r| r.to_talent()))
+        .collect()
+}
+
+/// 今日から `within_days` 日以内に稼働開始可能なマッチング候補を取得する（逆引きバッチ用）
+#[instrument(skip(pool))]
+pub async fn fetch_available_talents(
+    pool: &PgPool,
+    within_days: i32,
+) -> Result<Vec<Talent>, TalentStorageError> {
+    let client = pool.get().await?;
+    let query = format!(
+        "SELECT {TALENT_MASTER_COLUMNS}
+         FROM ses.talents
+         WHERE (sales_status IS NULL OR NOT (sales_status = ANY($1)))
+           AND available_date IS NOT NULL
+           AND available_date <= CURRENT_DATE + $2::int
+         ORDER BY available_date, id"
+    );
+
+    let rows = client
+        .timed_query_cached(
+            query.as_str(),
+            &[&EXCLUDED_SALES_STATUSES, &within_days],
+            "fetch_available_talents",
+        )
+        .await?;
+
+    rows.iter()
+        .map(|row| TalentMasterRow::from_row(row).map(|r| r.to_talent()))
+        .collect()
+}
+
+/// 人材マスターから 1 件取得する
+#[instrument(skip(pool))]
+pub async fn fetch_talent_master(
+    pool: &PgPool,
+    id: i64,
+) -> Result<Option<TalentMasterRow>, TalentStorageError> {
+    let client = pool.get().await?;
+    let query = format!("SELECT {TALENT_MASTER_COLUMNS} FROM ses.talents WHERE id = $1");
+
+    let row = client
+        .timed_query_opt_cached(query.as_str(), &[&id], "fetch_talent_master")
+        .await?;
+    row.as_ref().map(TalentMasterRow::from_row).transpose()
+}
+
+/// 人材マスターを検索する。スコアは有効な（削除・KO されていない）match_results の最良値
+#[instrument(skip(pool))]
+pub async fn search_talents(
+    pool: &PgPool,
+    query: &TalentSearchQuery,
+    limit: i64,
+    offset: i64,
+) -> Result<TalentSearchResponse, TalentStorageError> {
+    let client = pool.get().await?;
+    let sql = format!(
+        "WITH best AS (
+             SELECT talent_id, MAX(score_total) AS best_score
+             FROM ses.match_results
+             WHERE deleted_at IS NULL AND NOT is_knockout
+             GROUP BY talent_id
+         )
+         SELECT {TALENT_MASTER_COLUMNS}, b.best_score, COUNT(*) OVER() AS total_count
+         FROM ses.talents t
+         LEFT JOIN best b ON b.talent_id = t.id
+         WHERE ($1::text IS NULL
+                OR t.name ILIKE '%' || $1 || '%' ESCAPE '\\'
+                OR t.memo ILIKE '%' || $1 || '%' ESCAPE '\\'
+                OR EXISTS (SELECT 1 FROM unnest(t.skill_tags) s
+                           WHERE s ILIKE '%' || $1 || '%' ESCAPE '\\'))
+           AND ($2::text IS NULL OR $2 = ANY(t.skill_tags))
+           AND ($3::text IS NULL
+                OR t.residential_todofuken ILIKE '%' || $3 || '%' ESCAPE '\\'
+                OR t.nearest_station ILIKE '%' || $3 || '%' ESCAPE '\\')
+           AND ($4::text IS NULL OR t.sales_status ILIKE '%' || $4 || '%' ESCAPE '\\')
+           AND ($5::int IS NULL OR t.available_date <= CURRENT_DATE + $5::int)
+           AND ($6::float8 IS NULL OR b.best_score >= $6)
+           AND ($7::float8 IS NULL OR b.best_score <= $7)
+         ORDER BY b.best_score DESC NULLS LAST, t.available_date NULLS LAST, t.id
+         LIMIT $8 OFFSET $9"
+    );
+
+    // skill_tags は取り込み時に正規化済みなので、スキルは正規形の完全一致で絞る（「C」で javascript を拾わない）
+    let search = non_blank(query.search.as_deref()).map(escape_like);
+    let skill = non_blank(query.skill.as_deref()).map(normalize_skill);
+    let location = non_blank(query.location.as_deref()).map(escape_like);
+    let availability = non_blank(query.availability.as_deref()).map(escape_like);
+    let rows = client
+        .timed_query_cached(
+            sql.as_str(),
+            &[
+                &search,
+                &skill,
+                &location,
+                &availability,
+                &query.availability_within_days,
+                &query.score_min,
+                &query.score_max,
+                &limit,
+                &offset,
+            ],
+            "search_talents",
+        )
+        .await?;
+
+    let items = rows
+        .iter()
+        .map(|row| {
+            let score = row.try_get::<_, Option<f64>>("best_score")?;
+            TalentMasterRow::from_row(row).map(|t| t.to_list_item(score))
+        })
+        .collect::<Result<Vec<_>, TalentStorageError>>()?;
+    let total = rows
+        .first()
+        .map(|row| row.get::<_, i64>("total_count"))
+        .unwrap_or(0);
+    let has_more = offset + (items.len() as i64) < total;
+
+    Ok(TalentSearchResponse {
+        items,
+        limit,
+        offset,
+        total,
+        has_more,
+    })
+}
+
+/// 人材ごとのマッチ済み案件を、案件単位の最新スナップショットと営業ステータス付きで取得する
+#[instrument(skip(pool))]
+pub async fn fetch_talent_matches(
+    pool: &PgPool,
+    talent_id: i64,
+) -> Result<Vec<TalentMatchProject>, TalentStorageError> {
+    let client = pool.get().await?;
+    let rows = client
+        .timed_query_cached(
+            "SELECT m.project_id,
+                    COALESCE(p.project_name, '案件 #' || m.project_id) AS project_name,
+                    m.score_total,
+                    (m.score_breakdown->>'business_total')::float8 AS business_score,
+                    cv.stage AS conversion_stage,
+                    cv.created_at AS conversion_at,
+                    fb.feedback_type,
+                    fb.created_at AS feedback_at
+             FROM (
+                 SELECT DISTINCT ON (project_id)
+                        project_id, score_total, score_breakdown
+                 FROM ses.match_results
+                 WHERE talent_id = $1 AND deleted_at IS NULL AND NOT is_knockout
+                 ORDER BY project_id, run_date DESC, updated_at DESC
+             ) m
+             LEFT JOIN ses.projects p ON p.id = m.project_id
+             LEFT JOIN LATERAL (
+                 SELECT stage, created_at
+                 FROM ses.conversion_events
+                 WHERE talent_id = $1 AND project_id = m.project_id
+                 ORDER BY created_at DESC, id DESC
+                 LIMIT 1
+             ) cv ON true
+             LEFT JOIN LATERAL (
+                 SELECT feedback_type, created_at
+                 FROM ses.feedback_events
+                 WHERE talent_id = $1 AND project_id = m.project_id
+                   AND NOT is_revoked
+                   AND feedback_type = ANY($2)
+                 ORDER BY created_at DESC, id DESC
+                 LIMIT 1
+             ) fb ON true
+             ORDER BY m.score_total DESC NULLS LAST, m.project_id",
+            &[&talent_id, &STATUS_FEEDBACK_TYPES],
+            "fetch_talent_matches",
+        )
+        .await?;
+
+    rows.iter()
+        .map(|row| {
+            let stage: Option<String> = row.try_get("conversion_stage")?;
+            let feedback_type: Option<String> = row.try_get("feedback_type")?;
+            let conversion_at: Option<DateTime<Utc>> = row.try_get("conversion_at")?;
+            let feedback_at: Option<DateTime<Utc>> = row.try_get("feedback_at")?;
+            let status = TalentMatchStatus::derive(stage.as_deref(), feedback_type.as_deref());
+            Ok(TalentMatchProject {
+                project_id: row.try_get("project_id")?,
+                project_name: row.try_get("project_name")?,
+                score: row.try_get("score_total")?,
+                business_score: row.try_get("business_score")?,
+                status,
+                can_propose: status.can_propose(),
+                can_reject: status.can_reject(),
+                last_status_change: conversion_at.max(feedback_at),
+            })
+        })
+        .collect()
+}
+
+/// 人材詳細（プロフィール + マッチ済み案件）。人材が存在しなければ `None`
+pub async fn fetch_talent_detail(
+    pool: &PgPool,
+    talent_id: i64,
+) -> Result<Option<TalentDetailResponse>, TalentStorageError> {
+    let Some(master) = fetch_talent_master(pool, talent_id).await? else {
+        return Ok(None);
+    };
+    let matches = fetch_talent_matches(pool, talent_id).await?;
+    Ok(Some(TalentDetailResponse {
+        talent: master.to_profile(),
+        matches,
+    }))
+}
+
+fn non_blank(value: Option<&str>) -> Option<&str> {
+    value.map(str::trim).filter(|s| !s.is_empty())
+}
+
+/// Lark エクスポート 1 行分の UPSERT 値（`sr-import` が正規化済みの値を詰める）
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct TalentMasterUpsert {
+    pub lark_record_id: String,
+    pub name: String,
+    pub age: Option<i32>,
+    pub birth_year: Option<i32>,
+    pub nearest_station: Option<String>,
+    pub residential_todofuken: Option<String>,
+    pub desired_price: Option<i32>,
+    pub current_utilization: Option<f32>,
+    pub available_date: Option<NaiveDate>,
+    pub sales_status: Option<String>,
+    pub sales_rep: Option<String>,
+    pub priority_rank: Option<String>,
+    pub skill_tags: Vec<String>,
+    pub project_preferences: Vec<String>,
+    pub usage_types: Vec<String>,
+    pub capability_pm: Option<i32>,
+    pub capability_se: Option<i32>,
+    pub capability_bpo: Option<i32>,
+    pub capability_consul: Option<i32>,
+    pub english_level: Option<String>,
+    pub business_relationship: Option<String>,
+    pub phone: Option<String>,
+    pub email: Option<String>,
+    pub linkedin_url: Option<String>,
+    pub skill_sheet_url: Option<String>,
+    pub skill_sheet_url_2: Option<String>,
+    pub memo: Option<String>,
+    pub inflow_source: Option<String>,
+    pub inflow_date: Option<NaiveDate>,
+    pub registration_date: Option<NaiveDate>,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+pub struct TalentUpsertSummary {
+    pub inserted: u64,
+    pub updated: u64,
+}
+
+/// 人材マスターを lark_record_id 単位で UPSERT する（全件 1 トランザクション）
+#[instrument(skip(pool, talents), fields(talents = talents.len()))]
+pub async fn upsert_talent_masters(
+    pool: &PgPool,
+    talents: &[TalentMasterUpsert],
+) -> Result<TalentUpsertSummary, TalentStorageError> {
+    let mut client = pool.get().await?;
+    let tx = client.transaction().await?;
+    let stmt = tx
+        .prepare_cached(
+            "INSERT INTO ses.talents (
+                lark_record_id, name, age, birth_year, nearest_station, residential_todofuken,
+                desired_price, current_utilization, available_date,
+                sales_status, sales_rep, priority_rank,
+                skill_tags, project_preferences, usage_types,
+                capability_pm, capability_se, capability_bpo, capability_consul,
+                english_level, business_relationship,
+                phone, email, linkedin_url, skill_sheet_url, skill_sheet_url_2,
+                memo, inflow_source, inflow_date, registration_date
+            ) VALUES (
+                $1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15,
+                $16, $17, $18, $19, $20, $21, $22, $23, $24, $25, $26, $27, $28, $29, $30
+            )
+            ON CONFLICT (lark_record_id) DO UPDATE SET
+                name = EXCLUDED.name,
+                age = EXCLUDED.age,
+                birth_year = EXCLUDED.birth_year,
+                nearest_station = EXCLUDED.nearest_station,
+                residential_todofuken = EXCLUDED.residential_todofuken,
+                desired_price = EXCLUDED.desired_price,
+                current_utilization = EXCLUDED.current_utilization,
+                available_date = EXCLUDED.available_date,
+                sales_status = EXCLUDED.sales_status,
+                sales_rep = EXCLUDED.sales_rep,
+                priority_rank = EXCLUDED.priority_rank,
+                skill_tags = EXCLUDED.skill_tags,
+                project_preferences = EXCLUDED.project_preferences,
+                usage_types = EXCLUDED.usage_types,
+                capability_pm = EXCLUDED.capability_pm,
+                capability_se = EXCLUDED.capability_se,
+                capability_bpo = EXCLUDED.capability_bpo,
+                capability_consul = EXCLUDED.capability_consul,
+                english_level = EXCLUDED.english_level,
+                business_relationship = EXCLUDED.business_relationship,
+                phone = EXCLUDED.phone,
+                email = EXCLUDED.email,
+                linkedin_url = EXCLUDED.linkedin_url,
+                skill_sheet_url = EXCLUDED.skill_sheet_url,
+                skill_sheet_url_2 = EXCLUDED.skill_sheet_url_2,
+                memo = EXCLUDED.memo,
+                inflow_source = EXCLUDED.inflow_source,
+                inflow_date = EXCLUDED.inflow_date,
+                registration_date = EXCLUDED.registration_date,
+                updated_at = clock_timestamp()
+            RETURNING (xmax = 0) AS inserted",
+        )
+        .await?;
+
+    let mut summary = TalentUpsertSummary::default();
+    for t in talents {
+        let row = tx
+            .timed_query_one(
+                &stmt,
+                &[
+                    &t.lark_record_id,
+                    &t.name,
+                    &t.age,
+                    &t.birth_year,
+                    &t.nearest_station,
+                    &t.residential_todofuken,
+                    &t.desired_price,
+                    &t.current_utilization,
+                    &t.available_date,
+                    &t.sales_status,
+                    &t.sales_rep,
+                    &t.priority_rank,
+                    &t.skill_tags,
+                    &t.project_preferences,
+                    &t.usage_types,
+                    &t.capability_pm,
+                    &t.capability_se,
+                    &t.capability_bpo,
+                    &t.capability_consul,
+                    &t.english_level,
+                    &t.business_relationship,
+                    &t.phone,
+                    &t.email,
+                    &t.linkedin_url,
+                    &t.skill_sheet_url,
+                    &t.skill_sheet_url_2,
+                    &t.memo,
+                    &t.inflow_source,
+                    &t.inflow_date,
+                    &t.registration_date,
+                ],
+                "upsert_talent_master",
+            )
+            .await?;
+        if row.get::<_, bool>("inserted") {
+            summary.inserted += 1;
+        } else {
+            summary.updated += 1;
+        }
+    }
+
+    tx.commit().await?;
+    Ok(summary)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn lark_row() -> TalentMasterRow {
+        TalentMasterRow {
+            id: 12,
+            name: "山田 太郎".into(),
+            age: Some(35),
+            nearest_station: Some("新宿".into()),
+            residential_todofuken: Some("神奈川".into()),
+            desired_price: Some(800_000),
+            available_date: NaiveDate::from_ymd_opt(2025, 3, 1),
+            skill_tags: vec!["Java".into(), "Spring Boot".into()],
+            capability_pm: Some(3),
+            capability_se: Some(1),
+            capability_consul: Some(2),
+            english_level: Some("ビジネスレベル".into()),
+            business_relationship: Some("2社先".into()),
+            updated_at: Utc.with_ymd_and_hms(2025, 1, 15, 0, 0, 0).unwrap(),
+            ..TalentMasterRow::default()
+        }
+    }
+
+    #[test]
+    fn lark_row_maps_into_gui_list_item_and_profile() {
+        let row = lark_row();
+        let item = row.to_list_item(Some(0.72));
+
+        assert_eq!(item.role.as_deref(), Some("PM/PMO"));
+        assert_eq!(item.location.as_deref(), Some("神奈川 新宿"));
+        assert_eq!(item.score, Some(0.72));
+        assert_eq!(
+            item.experience_highlights,
+            vec!["PM/PMO ◎", "コンサル 〇", "英語: ビジネスレベル"]
+        );
+
+        let profile = row.to_profile();
+        assert_eq!(profile.desired_price_min, Some(80));
+        assert_eq!(profile.available_from, NaiveDate::from_ymd_opt(2025, 3, 1));
+        assert_eq!(TalentMasterRow::default().primary_role(), None);
+    }
+
+    #[test]
+    fn lark_row_maps_into_matching_talent() {
+        let talent = lark_row().to_talent();
+
+        assert_eq!(talent.id, Some(12));
+        assert_eq!(talent.desired_price_min, Some(80));
+        assert_eq!(talent.english_skill.as_deref(), Some("ビジネス"));
+        assert_eq!(talent.flow_depth.as_deref(), Some("2社先"));
+        assert_eq!(talent.nearest_station.as_deref(), Some("新宿駅"));
+        assert_eq!(talent.residential_todofuken.as_deref(), Some("神奈川県"));
+        assert_eq!(talent.residential_area.as_deref(), Some("関東"));
+        assert_eq!(talent.birth_year, Some(1990));
+        assert_eq!(
+            talent.availability_date.and_then(|d| d.date),
+            NaiveDate::from_ymd_opt(2025, 3, 1)
+        );
+
+        let expected = normalize_skills_vec(&[
+            "Java".to_string(),
+            "Spring Boot".to_string(),
+            "PM".to_string(),
+            "PMO".to_string(),
+            "コンサル".to_string(),
+        ]);
+        assert_eq!(talent.possessed_skills_keywords, expected);
+        assert!(!talent
+            .possessed_skills_keywords
+            .contains(&crate::skill_normalizer::normalize_skill("SE")));
+    }
+
+    #[test]
+    fn price_conversion_handles_yen_and_man_yen_inputs() {
+        assert_eq!(yen_to_man_yen(800_000), Some(80));
+        assert_eq!(yen_to_man_yen(1_050_000), Some(105));
+        assert_eq!(yen_to_man_yen(654_999), Some(65));
+        assert_eq!(yen_to_man_yen(75), Some(75));
+        assert_eq!(yen_to_man_yen(0), None);
+        assert_eq!(yen_to_man_yen(-1), None);
+        assert_eq!(yen_to_man_yen(i32::MAX), Some(214_748));
+    }
+
+    #[test]
+    fn business_relationship_maps_to_flow_depth() {
+        assert_eq!(
+            flow_depth_from_business_relationship("SPONTO直").as_deref(),
+            Some("SPONTO直")
+        );
+        assert_eq!(
+            flow_depth_from_business_relationship("個人事業主").as_deref(),
+            Some("SPONTO直")
+        );
+        assert_eq!(
+            flow_depth_from_business_relationship("1社先").as_deref(),
+            Some("1社先")
+        );
+        assert_eq!(
+            flow_depth_from_business_relationship("3社先以上").as_deref(),
+            Some("3社先以上")
+        );
+        assert_eq!(flow_depth_from_business_relationship(""), None);
+        assert_eq!(flow_depth_from_business_relationship("不明"), None);
+        assert_eq!(flow_depth_from_business_relationship("10社先"), None);
+    }
+}
@@ -0,0 +1,197 @@
+use chrono::{DateTime, Utc};
+use tracing::{instrument, warn};
+
+use crate::db::extraction_queue::{row_to_job, QueueStorageError};
+use crate::db::projects::{CompletedExtraction, ProjectionSummary};
+use crate::db::util::TimedClientExt;
+use crate::db::PgPool;
+use crate::extraction::talent::{project_talent_extraction_job, TalentProjection};
+
+db_error!(TalentEnumStorageError {
+    #[error("failed to serialize talent payload: {0}")]
+    Serialization(#[from] serde_json::Error),
+    #[error("failed to map extraction job: {0}")]
+    Queue(#[from] QueueStorageError),
+    #[error("value out of range: {0}")]
+    OutOfRange(String),
+});
+
+/// 人材射影を `ses.talents_enum` に UPSERT し、行 ID を返す（message_id 単位）
+#[instrument(skip(pool, projection, source_text), fields(message_id = %projection.message_id))]
+pub async fn upsert_talent_projection(
+    pool: &PgPool,
+    projection: &TalentProjection,
+    source_text: &str,
+) -> Result<i64, TalentEnumStorageError> {
+    let client = pool.get().await?;
+    let talent = &projection.talent;
+    let extraction_job_id = i64::try_from(projection.extraction_job_id).map_err(|_| {
+        TalentEnumStorageError::OutOfRange(format!(
+            "extraction_job_id={}",
+            projection.extraction_job_id
+        ))
+    })?;
+    let desired_price_min = talent
+        .desired_price_min
+        .map(|v| {
+            i32::try_from(v)
+                .map_err(|_| TalentEnumStorageError::OutOfRange(format!("desired_price_min={v}")))
+        })
+        .transpose()?;
+    let available_date = talent.availability_date.as_ref().and_then(|s| s.date);
+    let summary_text = (!talent.possessed_skills_keywords.is_empty())
+        .then(|| talent.possessed_skills_keywords.join(", "));
+    let payload = serde_json::to_value(talent)?;
+    let field_sources = serde_json::to_value(&projection.field_sources)?;
+
+    let stmt = client
+        .prepare_cached(
+            "INSERT INTO ses.talents_enum (
+                message_id,
+                talent_name,
+                summary_text,
+                desired_price_min,
+                available_date,
+                received_at,
+                source_text,
+                extraction_job_id,
+                final_method,
+                talent,
+                field_sources,
+                source_updated_at,
+                requires_manual_review,
+                manual_review_reason
+            ) VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14)
+            ON CONFLICT (message_id) DO UPDATE SET
+                talent_name = EXCLUDED.talent_name,
+                summary_text = EXCLUDED.summary_text,
+                desired_price_min = EXCLUDED.desired_price_min,
+                available_date = EXCLUDED.available_date,
+                received_at = EXCLUDED.received_at,
+                source_text = EXCLUDED.source_text,
+                extraction_job_id = EXCLUDED.extraction_job_id,
+                final_method = EXCLUDED.final_method,
+                talent = EXCLUDED.talent,
+                field_sources = EXCLUDED.field_sources,
+                source_updated_at = EXCLUDED.source_updated_at,
+                requires_manual_review = EXCLUDED.requires_manual_review,
+                manual_review_reason = EXCLUDED.manual_review_reason,
+                updated_at = clock_timestamp()
+            RETURNING id",
+        )
+        .await?;
+
+    let row = client
+        .timed_query_one(
+            &stmt,
+            &[
+                &projection.message_id,
+                &projection.talent_name,
+                &summary_text,
+                &desired_price_min,
+                &available_date,
+                &projection.received_at,
+                &source_text,
+                &extraction_job_id,
+                &projection.final_method.as_str(),
+                &payload,
+                &field_sources,
+                &projection.source_updated_at,
+                &projection.requires_manual_review,
+                &projection.manual_review_reason,
+            ],
+            "upsert_talent_projection",
+        )
+        .await?;
+
+    Ok(row.get("id"))
+}
+
+/// まだ射影されていない（または射影後に再処理された）完了済みの人材ジョブを取得する
+#[instrument(skip(pool))]
+pub async fn fetch_unprojected_talent_extractions(
+    pool: &PgPool,
+    limit: i64,
+) -> Result<Vec<CompletedExtraction>, TalentEnumStorageError> {
+    let client = pool.get().await?;
+    let stmt = client
+        .prepare_cached(
+            "SELECT eq.*, je.body_text, je.received_at AS jinzai_received_at,
+                    je.sender_name, je.sender_address
+             FROM ses.extraction_queue eq
+             JOIN ses.jinzai_emails je ON je.message_id = eq.message_id
+             LEFT JOIN ses.talents_enum te ON te.message_id = eq.message_id
+             WHERE eq.entity_type = 'talent'
+               AND eq.status = 'completed'
+               AND eq.final_method IN ('rust_completed', 'llm_completed')
+               AND (te.id IS NULL
+                    OR te.extraction_job_id IS NULL
+                    OR te.extraction_job_id <> eq.id
+                    OR te.source_updated_at < eq.updated_at)
+             ORDER BY eq.completed_at NULLS LAST, eq.id
+             LIMIT $1",
+        )
+        .await?;
+
+    let rows = client
+        .timed_query(&stmt, &[&limit], "fetch_unprojected_talent_extractions")
+        .await?;
+
+    rows.iter()
+        .map(|row| {
+            let job = row_to_job(row)?;
+            let received_at = row
+                .get::<_, Option<DateTime<Utc>>>("jinzai_received_at")
+                .unwrap_or(job.email_received_at);
+            Ok(CompletedExtraction {
+                job,
+                body_text: row
+                    .get::<_, Option<String>>("body_text")
+                    .unwrap_or_default(),
+                received_at,
+                sender_name: row.get("sender_name"),
+                sender_address: row.get("sender_address"),
+            })
+        })
+        .collect()
+}
+
+/// 完了済みの人材ジョブ 1 件を射影して UPSERT する。対象外（手動レビュー等）なら `None`
+pub async fn materialize_talent_extraction(
+    pool: &PgPool,
+    extraction: &CompletedExtraction,
+) -> Result<Option<i64>, TalentEnumStorageError> {
+    let Some(projection) = project_talent_extraction_job(
+        &extraction.job,
+        &extraction.body_text,
+        extraction.received_at,
+    ) else {
+        return Ok(None);
+    };
+    upsert_talent_projection(pool, &projection, &extraction.body_text)
+        .await
+        .map(Some)
+}
+
+/// 未射影の完了済み人材ジョブを最大 `limit` 件まとめて `ses.talents_enum` に反映する
+#[instrument(skip(pool))]
+pub async fn materialize_completed_talents(
+    pool: &PgPool,
+    limit: i64,
+) -> Result<ProjectionSummary, TalentEnumStorageError> {
+    let mut summary = ProjectionSummary::default();
+    for extraction in fetch_unprojected_talent_extractions(pool, limit).await? {
+        match materialize_talent_extraction(pool, &extraction).await? {
+            Some(_) => summary.projected += 1,
+            None => {
+                warn!(
+                    job_id = extraction.job.id,
+                    message_id = %extraction.job.message_id,
+                    "completed talent extraction job could not be projected"
+                );
+                summary.skipped += 1;
+            }
+        }
+    }
+    Ok(summary)
+}
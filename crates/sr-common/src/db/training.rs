@@ -0,0 +1,147 @@
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use tracing::{instrument, warn};
+
+use crate::db::match_inputs::project_from_row;
+use crate::db::talents::{TalentMasterRow, TalentStorageError, TALENT_MASTER_COLUMNS};
+use crate::db::util::TimedClientExt;
+use crate::db::PgPool;
+use crate::{Project, Talent};
+
+db_error!(TrainingDataError {
+    #[error("failed to map talent row: {0}")]
+    Talent(#[from] TalentStorageError),
+});
+
+/// `ses.training_labels` の 1 行に案件・人材の特徴量を付けたもの
+///
+/// 案件・人材の履歴テーブルは無いため特徴量は取得時点のもの。マッチ実行後に更新された行は
+/// `features_updated_after_match` で判別できる（学習側で除外するかを選ぶ）。
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TrainingExample {
+    pub interaction_id: i64,
+    pub talent: Talent,
+    pub project: Project,
+    /// 0.0〜1.0（CV > FB > 行動ログの優先順で統合済み）
+    pub label: f64,
+    pub signal_source: Option<String>,
+    /// マッチ実行日（基準タイムゾーン）。train/validation の分割に使う
+    pub run_date: NaiveDate,
+    /// interaction_logs の記録時刻（= マッチ実行時刻）
+    pub matched_at: DateTime<Utc>,
+    pub features_updated_after_match: bool,
+}
+
+/// 学習ラベルを案件（ses.projects）・人材（ses.talents）と結合して取得する。
+/// 案件・人材が削除済みのラベル、案件 payload が壊れているラベルは除外する。
+#[instrument(skip(pool))]
+pub async fn fetch_training_examples(
+    pool: &PgPool,
+    since: Option<DateTime<Utc>>,
+) -> Result<Vec<TrainingExample>, TrainingDataError> {
+    let client = pool.get().await?;
+    let query = format!(
+        "SELECT tl.interaction_id, tl.label::float8 AS label, tl.signal_source,
+                tl.run_date, tl.created_at AS matched_at,
+                (p.updated_at > tl.created_at OR t.updated_at > tl.created_at)
+                    AS features_updated_after_match,
+                p.id AS project_row_id, p.project, t.*
+         FROM ses.training_labels tl
+         JOIN ses.projects p ON p.id = tl.project_id
+         JOIN LATERAL (
+             SELECT {TALENT_MASTER_COLUMNS} FROM ses.talents WHERE id = tl.talent_id
+         ) t ON true
+         WHERE tl.label IS NOT NULL
+           AND ($1::timestamptz IS NULL OR tl.created_at >= $1)
+         ORDER BY tl.interaction_id"
+    );
+
+    let rows = client
+        .timed_query_cached(query.as_str(), &[&since], "fetch_training_examples")
+        .await?;
+
+    let mut examples = Vec::with_capacity(rows.len());
+    for row in &rows {
+        let interaction_id: i64 = row.get("interaction_id");
+        let project_id: i64 = row.get("project_row_id");
+        let project = match project_from_row(project_id, row.get("project")) {
+            Ok(project) => project,
+            Err(err) => {
+                warn!(interaction_id, project_id, error = %err, "skipping label with malformed project payload");
+                continue;
+            }
+        };
+        examples.push(TrainingExample {
+            interaction_id,
+            talent: TalentMasterRow::from_row(row)?.to_talent(),
+            project,
+            label: row.get("label"),
+            signal_source: row.get("signal_source"),
+            run_date: row.get("run_date"),
+            matched_at: row.get("matched_at"),
+            features_updated_after_match: row.get("features_updated_after_match"),
+        });
+    }
+    Ok(examples)
+}
+
+/// JSONL（1 行 1 ペア）にシリアライズする（sr-train のデータセットスナップショット形式）。
+/// interaction_id 順に並んでいれば、同じラベル集合は同じバイト列になる
+pub fn encode_training_examples(examples: &[TrainingExample]) -> serde_json::Result<Vec<u8>> {
+    let mut bytes = Vec::new();
+    for example in examples {
+        serde_json::to_writer(&mut bytes, example)?;
+        bytes.push(b'\n');
+    }
+    Ok(bytes)
+}
+
+/// `encode_training_examples` で書き出した JSONL を読み込む
+pub fn decode_training_examples(bytes: &[u8]) -> serde_json::Result<Vec<TrainingExample>> {
+    serde_json::Deserializer::from_slice(bytes)
+        .into_iter::<TrainingExample>()
+        .collect()
+}
+
+/// 重み学習用: 学習ラベルとマッチ実行時点の `score_breakdown` の組
+#[derive(Debug, Clone)]
+pub struct ScoreBreakdownLabel {
+    pub interaction_id: i64,
+    pub label: f64,
+    pub run_date: NaiveDate,
+    pub score_breakdown: serde_json::Value,
+}
+
+/// 学習ラベルを interaction_logs 経由で match_results の内訳と結合して取得する。
+/// 内訳は実行時点で保存されたものなので、案件・人材の更新の影響を受けない
+#[instrument(skip(pool))]
+pub async fn fetch_score_breakdown_labels(
+    pool: &PgPool,
+    since: Option<DateTime<Utc>>,
+) -> Result<Vec<ScoreBreakdownLabel>, TrainingDataError> {
+    let client = pool.get().await?;
+    let rows = client
+        .timed_query_cached(
+            "SELECT tl.interaction_id, tl.label::float8 AS label, tl.run_date, mr.score_breakdown
+             FROM ses.training_labels tl
+             JOIN ses.interaction_logs il ON il.id = tl.interaction_id
+             JOIN ses.match_results mr ON mr.id = il.match_result_id
+             WHERE tl.label IS NOT NULL
+               AND mr.score_breakdown IS NOT NULL
+               AND ($1::timestamptz IS NULL OR tl.created_at >= $1)
+             ORDER BY tl.interaction_id",
+            &[&since],
+            "fetch_score_breakdown_labels",
+        )
+        .await?;
+
+    Ok(rows
+        .iter()
+        .map(|row| ScoreBreakdownLabel {
+            interaction_id: row.get("interaction_id"),
+            label: row.get("label"),
+            run_date: row.get("run_date"),
+            score_breakdown: row.get("score_breakdown"),
+        })
+        .collect())
+}
@@ -11,6 +11,18 @@ pub fn normalize_json(value: &Option<Value>) -> Option<Json<&Value>> {
     value.as_ref().map(Json)
 }
 
+/// Escape `%`, `_` and `\\` so user input is matched literally by `LIKE ... ESCAPE '\\'`.
+pub fn escape_like(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for c in value.chars() {
+        if matches!(c, '%' | '_' | '\\') {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    escaped
+}
+
 fn slow_query_threshold_ms() -> Option<u64> {
     static CACHE: OnceLock<Option<u64>> = OnceLock::new();
 
@@ -162,4 +174,10 @@ mod tests {
         let normalized = normalize_json(&some);
         assert!(normalized.is_some());
     }
+
+    #[test]
+    fn escape_like_escapes_wildcards() {
+        assert_eq!(escape_like("100%_a\\b"), "100\\%\\_a\\\\b");
+        assert_eq!(escape_like("山田"), "山田");
+    }
 }
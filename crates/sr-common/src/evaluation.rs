@@ -0,0 +1,323 @@
+//! オフライン評価用のランキング指標（sr-train / sr-eval で共有）と、A/B レポート用の比率の区間推定
+//!
+//! ラベルは `ses.training_labels` と同じ 0.0〜1.0 のスケール。二値指標（AUC・precision・recall・MRR）は
+//! `positive_threshold` 以上を正例とみなし、NDCG はラベル値をそのまま graded relevance として使う。
+
+use serde::Serialize;
+
+/// 1 ペア分のモデルスコアと正解ラベル
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ScoredLabel {
+    pub score: f64,
+    pub label: f64,
+}
+
+/// スコア降順に並べる（同点は入力順を保つ）
+pub fn rank_by_score(items: &[ScoredLabel]) -> Vec<ScoredLabel> {
+    let mut ranked = items.to_vec();
+    ranked.sort_by(|a, b| b.score.total_cmp(&a.score));
+    ranked
+}
+
+/// ROC-AUC（同点は平均順位で扱う）。正例・負例のどちらかが 0 件なら `None`
+pub fn roc_auc(items: &[ScoredLabel], positive_threshold: f64) -> Option<f64> {
+    let mut sorted = items.to_vec();
+    sorted.sort_by(|a, b| a.score.total_cmp(&b.score));
+
+    let positives = sorted
+        .iter()
+        .filter(|item| item.label >= positive_threshold)
+        .count();
+    let negatives = sorted.len() - positives;
+    if positives == 0 || negatives == 0 {
+        return None;
+    }
+
+    let mut positive_rank_sum = 0.0;
+    let mut start = 0;
+    while start < sorted.len() {
+        let mut end = start;
+        while end + 1 < sorted.len() && sorted[end + 1].score == sorted[start].score {
+            end += 1;
+        }
+        // 1 始まりの順位の平均
+        let average_rank = (start + end) as f64 / 2.0 + 1.0;
+        positive_rank_sum += average_rank
+            * sorted[start..=end]
+                .iter()
+                .filter(|item| item.label >= positive_threshold)
+                .count() as f64;
+        start = end + 1;
+    }
+
+    let p = positives as f64;
+    Some((positive_rank_sum - p * (p + 1.0) / 2.0) / (p * negatives as f64))
+}
+
+/// NDCG@k（スコア降順に並べ替えてから計算）。理想 DCG が 0（関連ペアなし）なら `None`
+pub fn ndcg_at_k(items: &[ScoredLabel], k: usize) -> Option<f64> {
+    let dcg = |labels: &mut dyn Iterator<Item = f64>| -> f64 {
+        labels
+            .take(k)
+            .enumerate()
+            .map(|(i, label)| (2f64.powf(label) - 1.0) / ((i + 2) as f64).log2())
+            .sum()
+    };
+
+    let mut ideal: Vec<f64> = items.iter().map(|item| item.label).collect();
+    ideal.sort_by(|a, b| b.total_cmp(a));
+    let ideal_dcg = dcg(&mut ideal.into_iter());
+    if ideal_dcg <= 0.0 {
+        return None;
+    }
+    let actual_dcg = dcg(&mut rank_by_score(items).into_iter().map(|item| item.label));
+    Some(actual_dcg / ideal_dcg)
+}
+
+/// 上位 k 件に入った正例 / 全正例。正例が無ければ `None`
+pub fn recall_at_k(items: &[ScoredLabel], k: usize, positive_threshold: f64) -> Option<f64> {
+    let positives = items
+        .iter()
+        .filter(|item| item.label >= positive_threshold)
+        .count();
+    if positives == 0 {
+        return None;
+    }
+    let hits = rank_by_score(items)
+        .iter()
+        .take(k)
+        .filter(|item| item.label >= positive_threshold)
+        .count();
+    Some(hits as f64 / positives as f64)
+}
+
+/// 上位 k 件に占める正例の割合（候補が k 件未満なら候補数で割る）。候補が無ければ `None`
+pub fn precision_at_k(items: &[ScoredLabel], k: usize, positive_threshold: f64) -> Option<f64> {
+    let considered = items.len().min(k);
+    if considered == 0 {
+        return None;
+    }
+    let hits = rank_by_score(items)
+        .iter()
+        .take(k)
+        .filter(|item| item.label >= positive_threshold)
+        .count();
+    Some(hits as f64 / considered as f64)
+}
+
+/// 最初の正例の順位の逆数。正例が無ければ `None`
+pub fn reciprocal_rank(items: &[ScoredLabel], positive_threshold: f64) -> Option<f64> {
+    rank_by_score(items)
+        .iter()
+        .position(|item| item.label >= positive_threshold)
+        .map(|index| 1.0 / (index + 1) as f64)
+}
+
+fn mean(values: impl Iterator<Item = Option<f64>>) -> Option<f64> {
+    let (sum, count) = values
+        .flatten()
+        .fold((0.0, 0usize), |(sum, count), v| (sum + v, count + 1));
+    (count > 0).then(|| sum / count as f64)
+}
+
+/// クエリ（案件）ごとのランキング指標をマクロ平均したもの。AUC のみ全ペアで計算する
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct RankingSummary {
+    pub pairs: usize,
+    pub positives: usize,
+    pub groups: usize,
+    pub auc: Option<f64>,
+    pub ndcg_at_10: Option<f64>,
+    pub mrr: Option<f64>,
+    pub precision_at_5: Option<f64>,
+    pub precision_at_10: Option<f64>,
+    pub recall_at_50: Option<f64>,
+}
+
+impl RankingSummary {
+    /// `groups` はクエリ単位（例: 案件ごと）のラベル付き候補
+    pub fn compute(groups: &[Vec<ScoredLabel>], positive_threshold: f64) -> Self {
+        let all: Vec<ScoredLabel> = groups.iter().flatten().copied().collect();
+        Self {
+            pairs: all.len(),
+            positives: all
+                .iter()
+                .filter(|item| item.label >= positive_threshold)
+                .count(),
+            groups: groups.len(),
+            auc: roc_auc(&all, positive_threshold),
+            ndcg_at_10: mean(groups.iter().map(|g| ndcg_at_k(g, 10))),
+            mrr: mean(
+                groups
+                    .iter()
+                    .map(|g| reciprocal_rank(g, positive_threshold)),
+            ),
+            precision_at_5: mean(
+                groups
+                    .iter()
+                    .map(|g| precision_at_k(g, 5, positive_threshold)),
+            ),
+            precision_at_10: mean(
+                groups
+                    .iter()
+                    .map(|g| precision_at_k(g, 10, positive_threshold)),
+            ),
+            recall_at_50: mean(
+                groups
+                    .iter()
+                    .map(|g| recall_at_k(g, 50, positive_threshold)),
+            ),
+        }
+    }
+}
+
+/// 両側 95% 信頼区間の z 値
+pub const Z_95: f64 = 1.959_963_984_540_054;
+
+/// 二項比率の点推定と Wilson スコア区間。試行 0 件なら率・区間は `None`
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub struct ProportionEstimate {
+    pub successes: u64,
+    pub trials: u64,
+    pub rate: Option<f64>,
+    pub ci_low: Option<f64>,
+    pub ci_high: Option<f64>,
+}
+
+impl ProportionEstimate {
+    pub fn wilson(successes: u64, trials: u64, z: f64) -> Self {
+        let successes = successes.min(trials);
+        if trials == 0 {
+            return Self {
+                successes,
+                trials,
+                rate: None,
+                ci_low: None,
+                ci_high: None,
+            };
+        }
+
+        let n = trials as f64;
+        let p = successes as f64 / n;
+        let z2 = z * z;
+        let center = (p + z2 / (2.0 * n)) / (1.0 + z2 / n);
+        let half = z * (p * (1.0 - p) / n + z2 / (4.0 * n * n)).sqrt() / (1.0 + z2 / n);
+        Self {
+            successes,
+            trials,
+            rate: Some(p),
+            ci_low: Some((center - half).max(0.0)),
+            ci_high: Some((center + half).min(1.0)),
+        }
+    }
+}
+
+/// 比率の差（treatment − control）と Newcombe のハイブリッド Wilson 区間
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub struct RateDifference {
+    pub diff: f64,
+    pub ci_low: f64,
+    pub ci_high: f64,
+}
+
+impl RateDifference {
+    /// どちらかの試行が 0 件なら `None`。両者は同じ z で作った推定値を渡す
+    pub fn newcombe(treatment: &ProportionEstimate, control: &ProportionEstimate) -> Option<Self> {
+        let (p1, l1, u1) = (treatment.rate?, treatment.ci_low?, treatment.ci_high?);
+        let (p2, l2, u2) = (control.rate?, control.ci_low?, control.ci_high?);
+        let diff = p1 - p2;
+        Some(Self {
+            diff,
+            ci_low: diff - ((p1 - l1).powi(2) + (u2 - p2).powi(2)).sqrt(),
+            ci_high: diff + ((u1 - p1).powi(2) + (p2 - l2).powi(2)).sqrt(),
+        })
+    }
+
+    /// 区間が 0 を含まない（95% 区間なら有意水準 5% で差がある）
+    pub fn excludes_zero(&self) -> bool {
+        self.ci_low > 0.0 || self.ci_high < 0.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn items(pairs: &[(f64, f64)]) -> Vec<ScoredLabel> {
+        pairs
+            .iter()
+            .map(|&(score, label)| ScoredLabel { score, label })
+            .collect()
+    }
+
+    #[test]
+    fn auc_handles_perfect_inverse_and_tied_scores() {
+        let perfect = items(&[(0.9, 1.0), (0.8, 1.0), (0.2, 0.0), (0.1, 0.0)]);
+        assert_eq!(roc_auc(&perfect, 0.5), Some(1.0));
+
+        let inverse = items(&[(0.1, 1.0), (0.9, 0.0)]);
+        assert_eq!(roc_auc(&inverse, 0.5), Some(0.0));
+
+        let tied = items(&[(0.5, 1.0), (0.5, 0.0)]);
+        assert_eq!(roc_auc(&tied, 0.5), Some(0.5));
+
+        assert_eq!(roc_auc(&items(&[(0.5, 1.0)]), 0.5), None);
+    }
+
+    #[test]
+    fn ranking_metrics_follow_score_order() {
+        // スコア順: 0.9(負) → 0.5(正) → 0.1(負)
+        let group = items(&[(0.1, 0.0), (0.9, 0.0), (0.5, 1.0)]);
+
+        assert_eq!(reciprocal_rank(&group, 0.5), Some(0.5));
+        assert_eq!(precision_at_k(&group, 2, 0.5), Some(0.5));
+        assert_eq!(recall_at_k(&group, 1, 0.5), Some(0.0));
+        assert_eq!(recall_at_k(&group, 2, 0.5), Some(1.0));
+
+        let ndcg = ndcg_at_k(&group, 10).unwrap();
+        assert!((ndcg - 1.0 / 3f64.log2()).abs() < 1e-12);
+        assert_eq!(ndcg_at_k(&items(&[(0.9, 0.0)]), 10), None);
+    }
+
+    #[test]
+    fn summary_macro_averages_groups_with_positives() {
+        let groups = vec![
+            items(&[(0.9, 1.0), (0.1, 0.0)]),
+            items(&[(0.9, 0.0), (0.1, 1.0)]),
+            items(&[(0.5, 0.0)]),
+        ];
+        let summary = RankingSummary::compute(&groups, 0.5);
+
+        assert_eq!(summary.pairs, 5);
+        assert_eq!(summary.positives, 2);
+        assert_eq!(summary.groups, 3);
+        assert_eq!(summary.mrr, Some(0.75));
+        // 正例の無いグループは precision の分母に入る（0 として平均）
+        assert_eq!(summary.precision_at_5, Some(1.0 / 3.0));
+        assert_eq!(summary.recall_at_50, Some(1.0));
+    }
+
+    #[test]
+    fn wilson_interval_and_newcombe_difference() {
+        // Newcombe (1998) の例: 81/263
+        let control = ProportionEstimate::wilson(81, 263, Z_95);
+        assert!((control.ci_low.unwrap() - 0.2553).abs() < 1e-3);
+        assert!((control.ci_high.unwrap() - 0.3662).abs() < 1e-3);
+
+        let empty = ProportionEstimate::wilson(0, 0, Z_95);
+        assert_eq!(empty.rate, None);
+        let zero = ProportionEstimate::wilson(0, 10, Z_95);
+        assert_eq!(zero.ci_low, Some(0.0));
+        assert!(zero.ci_high.unwrap() > 0.0);
+
+        // Newcombe (1998) 表 II の例: 56/70 vs 48/80 → 0.2 (0.0524, 0.3339)
+        let treatment = ProportionEstimate::wilson(56, 70, Z_95);
+        let control = ProportionEstimate::wilson(48, 80, Z_95);
+        let diff = RateDifference::newcombe(&treatment, &control).unwrap();
+        assert!((diff.diff - 0.2).abs() < 1e-12);
+        assert!((diff.ci_low - 0.0524).abs() < 1e-3);
+        assert!((diff.ci_high - 0.3339).abs() < 1e-3);
+        assert!(diff.excludes_zero());
+        assert_eq!(RateDifference::newcombe(&treatment, &empty), None);
+    }
+}
@@ -0,0 +1,93 @@
+//! 抽出根拠（フィールドごとのマッチ位置・ルール名・確信度）
+//!
+//! - `start`/`end` は抽出対象テキスト中のバイトオフセット（`&body[start..end]` がマッチ箇所）。
+//!   複数案件メールのサブジョブではメール全体ではなくセクション本文が基準
+//! - `rule` はどの正規表現/補正で値が決まったか（例: `RANGE_RE` / `SINGLE_RE`）
+//! - `confidence` は 0.0〜1.0。単発値からの推定や、本文内で値が食い違う場合は下げる
+
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+
+/// ラベル付き・レンジなど値がそのまま書かれているマッチ
+pub const CONFIDENCE_EXPLICIT: f32 = 0.95;
+/// 片側だけ書かれていて残りを推定したマッチ（「70万円〜」→ 上限は +20 万）
+pub const CONFIDENCE_INFERRED: f32 = 0.75;
+/// 単発値や本文全体への補正など、取り違えの多いフォールバック
+pub const CONFIDENCE_FALLBACK: f32 = 0.5;
+/// 本文内に食い違う候補がある場合の減点
+pub const CONFLICT_PENALTY: f32 = 0.4;
+/// これ未満は低確信度として推奨メソッド判定に使う
+pub const LOW_CONFIDENCE_THRESHOLD: f32 = 0.6;
+
+/// 1 フィールド分の抽出根拠
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct FieldEvidence {
+    pub start: usize,
+    pub end: usize,
+    pub rule: String,
+    pub confidence: f32,
+    /// 本文内に別の値を示す候補があった
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub conflicting: bool,
+}
+
+/// フィールド名（`PartialFields` のキー）→ 抽出根拠
+pub type FieldEvidenceMap = BTreeMap<String, FieldEvidence>;
+
+impl FieldEvidence {
+    pub fn new(range: std::ops::Range<usize>, rule: &str, confidence: f32) -> Self {
+        Self {
+            start: range.start,
+            end: range.end,
+            rule: rule.to_string(),
+            confidence,
+            conflicting: false,
+        }
+    }
+
+    /// 食い違う候補があれば確信度を下げて印を付ける
+    pub fn with_conflict(mut self, conflicting: bool) -> Self {
+        if conflicting {
+            self.conflicting = true;
+            self.confidence = (self.confidence - CONFLICT_PENALTY).max(0.0);
+        }
+        self
+    }
+
+    pub fn is_low_confidence(&self) -> bool {
+        self.confidence < LOW_CONFIDENCE_THRESHOLD
+    }
+}
+
+/// 低確信度のフィールド名（キー順）
+pub fn low_confidence_fields(evidence: &FieldEvidenceMap) -> Vec<String> {
+    evidence
+        .iter()
+        .filter(|(_, ev)| ev.is_low_confidence())
+        .map(|(field, _)| field.clone())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn conflicts_lower_confidence_below_threshold() {
+        let explicit = FieldEvidence::new(3..10, "RANGE_RE", CONFIDENCE_EXPLICIT);
+        assert!(!explicit.is_low_confidence());
+
+        let conflicted = explicit.clone().with_conflict(true);
+        assert!(conflicted.conflicting);
+        assert!(conflicted.confidence < explicit.confidence);
+
+        let mut map = FieldEvidenceMap::new();
+        map.insert("monthly_tanka_min".into(), explicit);
+        map.insert(
+            "monthly_tanka_max".into(),
+            FieldEvidence::new(3..10, "SINGLE_RE", CONFIDENCE_FALLBACK),
+        );
+        assert_eq!(low_confidence_fields(&map), vec!["monthly_tanka_max"]);
+    }
+}
@@ -5,13 +5,28 @@ use serde::{Deserialize, Serialize};
 use tracing::warn;
 
 use crate::corrections::{
+    english_skill::correct_english_skill,
     flow_depth::correct_flow_dept,
+    japanese_skill::correct_japanese_skill,
     remote_onsite::correct_remote_onsite,
     todofuken::{correct_todofuken, TODOFUKEN_TO_AREA},
 };
 use crate::queue::RecommendedMethod;
 use crate::skill_normalizer::normalize_skill_set;
 
+use self::evidence::{
+    low_confidence_fields, FieldEvidence, FieldEvidenceMap, CONFIDENCE_EXPLICIT,
+    CONFIDENCE_FALLBACK, CONFIDENCE_INFERRED,
+};
+use self::segment::{section_title, split_project_sections};
+use self::skills::extract_skill_sections;
+
+pub mod evidence;
+pub mod projection;
+pub mod segment;
+pub mod skills;
+pub mod talent;
+
 /// sr-extractor がメール本文から拾う項目（MVP 範囲）
 #[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
 #[serde(rename_all = "snake_case")]
@@ -23,6 +38,17 @@ pub struct PartialFields {
     pub remote_onsite: Option<String>,
     pub flow_dept: Option<String>,
     pub required_skills_keywords: Option<Vec<String>>,
+    pub preferred_skills_keywords: Option<Vec<String>>,
+    /// 「経験3年以上」。1.5 年などは要件側なので切り上げる
+    pub min_experience_years: Option<i32>,
+    pub age_limit_lower: Option<i32>,
+    pub age_limit_upper: Option<i32>,
+    pub interviews_count: Option<i32>,
+    pub hiring_headcount: Option<i32>,
+    pub foreigner_allowed: Option<bool>,
+    pub is_kojin_ok: Option<bool>,
+    pub japanese_skill: Option<String>,
+    pub english_skill: Option<String>,
     pub project_name: Option<String>,
     pub outcome_tag: Option<String>,
     pub decline_reason_tag: Option<String>,
@@ -32,6 +58,9 @@ pub struct PartialFields {
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct ExtractorOutput {
     pub partial: PartialFields,
+    /// フィールドごとの抽出根拠（GUI のレビュー表示・推奨メソッド判定に使う）
+    #[serde(default)]
+    pub evidence: FieldEvidenceMap,
     pub quality: ExtractionQuality,
     pub decision: RecommendedDecision,
 }
@@ -43,6 +72,9 @@ pub struct ExtractionQuality {
     pub tier1_total: usize,
     pub tier2_extracted: usize,
     pub tier2_total: usize,
+    /// 確信度が閾値未満のフィールド（単発値フォールバック・食い違う候補あり）
+    #[serde(default)]
+    pub low_confidence_fields: Vec<String>,
     pub llm_recommended: bool,
     pub reason: String,
 }
@@ -66,6 +98,8 @@ lazy_static! {
         Regex::new(r"(?:〜|～|まで)\s*(\d{1,3})\s*万(?:円)?").unwrap();
     // 単発: "80万円" / "80万円程度" / "80万くらい"
     static ref SINGLE_RE: Regex = Regex::new(r"(\d{1,3})\s*万(?:円)?(?:程度|くらい|前後)?").unwrap();
+    // 単価ラベル: 単発値の直前にあれば単価として信頼する
+    static ref TANKA_LABEL_RE: Regex = Regex::new(r"(単価|月額|報酬|金額|予算)").unwrap();
 
     // 開始日: ASAP / 即日系
     static ref ASAP_RE: Regex = Regex::new(r"(?i)(即日|即時|ASAP)").unwrap();
@@ -101,61 +135,209 @@ lazy_static! {
     static ref TWO_HOP_RE: Regex = Regex::new(r"(?i)(2次|二次)").unwrap();
     static ref THREE_HOP_RE: Regex = Regex::new(r"(?i)(3次|三次)").unwrap();
     static ref FOUR_PLUS_RE: Regex = Regex::new(r"(?i)(4次|四次|4次以上|四次以上)").unwrap();
+
+    // 経験年数: "経験3年以上" / "実務経験：2年以上" / "3年以上の開発経験"
+    static ref EXPERIENCE_MIN_RE: Regex = Regex::new(
+        r"経験(?:年数)?\s*[:：]?\s*(\d{1,2}(?:\.\d)?)\s*年\s*以上|(\d{1,2}(?:\.\d)?)\s*年\s*以上の[^\n。]{0,12}経験"
+    )
+    .unwrap();
+    // 年齢: "25〜45歳" / "〜45歳" / "45歳まで" / "年齢：20代〜30代" / "年齢：40代まで"
+    static ref AGE_RANGE_RE: Regex =
+        Regex::new(r"(\d{2})\s*(?:歳|才)?\s*[〜～~-]\s*(\d{2})\s*(?:歳|才)").unwrap();
+    static ref AGE_UPPER_RE: Regex = Regex::new(
+        r"[〜～~]\s*(\d{2})\s*(?:歳|才)|(\d{2})\s*(?:歳|才)\s*(?:まで|迄|以下)"
+    )
+    .unwrap();
+    static ref AGE_DECADE_RANGE_RE: Regex =
+        Regex::new(r"([1-6])0\s*代\s*[〜～~-]\s*([1-6])0\s*代").unwrap();
+    static ref AGE_DECADE_UPPER_RE: Regex =
+        Regex::new(r"([1-6])0\s*代\s*(?:まで|迄)").unwrap();
+    // 面談回数: "面談2回" / "面談回数：1回" / "面接１回"
+    static ref INTERVIEWS_RE: Regex =
+        Regex::new(r"面(?:談|接)(?:回数)?\s*[:：]?\s*([1-5１-５])\s*回").unwrap();
+    // 募集人数: "募集人数：2名" / "3名募集" / "増員1名"
+    static ref HEADCOUNT_RE: Regex = Regex::new(
+        r"(?:募集|人数|増員)\s*[:：]?\s*(\d{1,2})\s*名|(\d{1,2})\s*名\s*(?:募集|増員)"
+    )
+    .unwrap();
+    // 外国籍: "外国籍不可" / "外国籍の方NG" / "外国籍：可"
+    static ref FOREIGNER_RE: Regex = Regex::new(
+        r"(?i)外国籍(?:の方)?\s*[:：]?\s*(不可|NG|ＮＧ|×|可|OK|ＯＫ|○)"
+    )
+    .unwrap();
+    static ref JAPANESE_ONLY_RE: Regex = Regex::new(r"日本(?:国籍|人)(?:の方)?(?:のみ|限定)").unwrap();
+    // 個人事業主: "個人事業主不可" / "フリーランス可" / "個人：NG"
+    static ref KOJIN_RE: Regex = Regex::new(
+        r"(?i)(?:個人事業主|フリーランス|個人)(?:の方)?\s*[:：]?\s*(不可|NG|ＮＧ|×|可|OK|ＯＫ|○)"
+    )
+    .unwrap();
 }
 
 /// 本文から Tier1/2 相当の項目をまとめて抽出
 pub fn extract_partial_fields(body_text: &str) -> PartialFields {
+    extract_partial_fields_with_evidence(body_text).0
+}
+
+/// `extract_partial_fields` と同じ抽出に、フィールドごとの根拠（マッチ位置・ルール・確信度）を添える。
+/// スキル・案件名は正規表現 1 本で決まらないため根拠を持たない
+pub fn extract_partial_fields_with_evidence(body_text: &str) -> (PartialFields, FieldEvidenceMap) {
     let mut partial = PartialFields::default();
+    let mut evidence = FieldEvidenceMap::new();
+    let mut record = |field: &str, ev: FieldEvidence| {
+        evidence.insert(field.to_string(), ev);
+    };
 
-    if let Some((min, max)) = extract_tanka(body_text) {
+    if let Some(((min, max), ev)) = tanka_with_evidence(body_text) {
         partial.monthly_tanka_min = Some(min);
         partial.monthly_tanka_max = Some(max);
+        record("monthly_tanka_min", ev.clone());
+        record("monthly_tanka_max", ev);
     }
 
-    partial.start_date_raw = extract_start_date_raw(body_text);
-    partial.work_todofuken = extract_work_todofuken(body_text);
-    partial.remote_onsite = extract_remote_onsite(body_text);
-    partial.flow_dept = extract_flow_dept(body_text);
+    if let Some((raw, ev)) = start_date_raw_with_evidence(body_text) {
+        partial.start_date_raw = Some(raw);
+        record("start_date_raw", ev);
+    }
+    if let Some((pref, ev)) = work_todofuken_with_evidence(body_text) {
+        partial.work_todofuken = Some(pref);
+        record("work_todofuken", ev);
+    }
+    if let Some((remote, ev)) = remote_onsite_with_evidence(body_text) {
+        partial.remote_onsite = Some(remote);
+        record("remote_onsite", ev);
+    }
+    if let Some((flow, ev)) = flow_dept_with_evidence(body_text) {
+        partial.flow_dept = Some(flow);
+        record("flow_dept", ev);
+    }
+    if let Some((years, ev)) = min_experience_years_with_evidence(body_text) {
+        partial.min_experience_years = Some(years);
+        record("min_experience_years", ev);
+    }
+    if let Some(((lower, upper), ev)) = age_limits_with_evidence(body_text) {
+        partial.age_limit_lower = lower;
+        partial.age_limit_upper = upper;
+        if lower.is_some() {
+            record("age_limit_lower", ev.clone());
+        }
+        if upper.is_some() {
+            record("age_limit_upper", ev);
+        }
+    }
+    if let Some((count, ev)) = interviews_count_with_evidence(body_text) {
+        partial.interviews_count = Some(count);
+        record("interviews_count", ev);
+    }
+    if let Some((count, ev)) = hiring_headcount_with_evidence(body_text) {
+        partial.hiring_headcount = Some(count);
+        record("hiring_headcount", ev);
+    }
+    if let Some((allowed, ev)) = foreigner_allowed_with_evidence(body_text) {
+        partial.foreigner_allowed = Some(allowed);
+        record("foreigner_allowed", ev);
+    }
+    if let Some((ok, ev)) = kojin_ok_with_evidence(body_text) {
+        partial.is_kojin_ok = Some(ok);
+        record("is_kojin_ok", ev);
+    }
+    if let Some((skill, ev)) =
+        language_skill_with_evidence(body_text, "日本語", correct_japanese_skill)
+    {
+        partial.japanese_skill = Some(skill);
+        record("japanese_skill", ev);
+    }
+    if let Some((skill, ev)) =
+        language_skill_with_evidence(body_text, "英語", correct_english_skill)
+    {
+        partial.english_skill = Some(skill);
+        record("english_skill", ev);
+    }
+
+    let skills = extract_skill_sections(body_text);
+    partial.required_skills_keywords = Some(skills.required).filter(|s| !s.is_empty());
+    partial.preferred_skills_keywords = Some(skills.preferred).filter(|s| !s.is_empty());
     partial.outcome_tag = Some("unknown".to_string());
 
-    partial
+    (partial, evidence)
+}
+
+/// 単価として妥当な範囲（万円）
+fn plausible_tanka(value: u32) -> bool {
+    (30..=200).contains(&value)
+}
+
+fn parse_tanka(caps: &regex::Captures, idx: usize) -> Option<u32> {
+    caps.get(idx)?.as_str().parse().ok()
+}
+
+fn valid_tanka_range(caps: &regex::Captures) -> Option<(u32, u32)> {
+    let min = parse_tanka(caps, 1)?;
+    let max = parse_tanka(caps, 2)?;
+    (plausible_tanka(min) && min <= max && max <= 200).then_some((min, max))
 }
 
 /// 月単価抽出（レンジ/下限のみ/上限のみ/単発に対応）
 /// 単発値は min=max として返す（Tier1 を落とさない）
 pub fn extract_tanka(body_text: &str) -> Option<(u32, u32)> {
+    tanka_with_evidence(body_text).map(|(tanka, _)| tanka)
+}
+
+/// 単価 + 根拠。同じ形の別の値が本文にあれば食い違いとして確信度を下げる
+fn tanka_with_evidence(body_text: &str) -> Option<((u32, u32), FieldEvidence)> {
     // 1. レンジ: "70〜90万円"
     if let Some(caps) = RANGE_RE.captures(body_text) {
-        let min: u32 = caps.get(1)?.as_str().parse().ok()?;
-        let max: u32 = caps.get(2)?.as_str().parse().ok()?;
-        if (30..=200).contains(&min) && min <= max && max <= 200 {
-            return Some((min, max));
+        if let Some(range) = valid_tanka_range(&caps) {
+            let conflicting = RANGE_RE
+                .captures_iter(body_text)
+                .filter_map(|other| valid_tanka_range(&other))
+                .any(|other| other != range);
+            let ev = FieldEvidence::new(caps.get(0)?.range(), "RANGE_RE", CONFIDENCE_EXPLICIT)
+                .with_conflict(conflicting);
+            return Some((range, ev));
         }
     }
 
     // 2. 下限のみ: "70万円〜"
     if let Some(caps) = MIN_ONLY_RE.captures(body_text) {
-        let min: u32 = caps.get(1)?.as_str().parse().ok()?;
-        if (30..=200).contains(&min) {
+        let min = parse_tanka(&caps, 1)?;
+        if plausible_tanka(min) {
             let max = (min + 20).min(200);
-            return Some((min, max));
+            let ev = FieldEvidence::new(caps.get(0)?.range(), "MIN_ONLY_RE", CONFIDENCE_INFERRED);
+            return Some(((min, max), ev));
         }
     }
 
     // 3. 上限のみ: "〜90万円"
     if let Some(caps) = MAX_ONLY_RE.captures(body_text) {
-        let max: u32 = caps.get(1)?.as_str().parse().ok()?;
-        if (30..=200).contains(&max) {
+        let max = parse_tanka(&caps, 1)?;
+        if plausible_tanka(max) {
             let min = if max > 20 { max - 20 } else { 30 };
-            return Some((min, max));
+            let ev = FieldEvidence::new(caps.get(0)?.range(), "MAX_ONLY_RE", CONFIDENCE_INFERRED);
+            return Some(((min, max), ev));
         }
     }
 
     // 4. 単発: "80万円程度" → min=max
+    // 同じ行に「単価」「月額」などのラベルが無い単発値は別の金額（交通費・年収など）の恐れがあるのでフォールバック扱い
     if let Some(caps) = SINGLE_RE.captures(body_text) {
-        let tanka: u32 = caps.get(1)?.as_str().parse().ok()?;
-        if (30..=200).contains(&tanka) {
-            return Some((tanka, tanka));
+        let tanka = parse_tanka(&caps, 1)?;
+        if plausible_tanka(tanka) {
+            let mat = caps.get(0)?;
+            let line_head = body_text[..mat.start()]
+                .rsplit('\n')
+                .next()
+                .unwrap_or_default();
+            let (rule, confidence) = if TANKA_LABEL_RE.is_match(line_head) {
+                ("SINGLE_RE+TANKA_LABEL_RE", CONFIDENCE_INFERRED)
+            } else {
+                ("SINGLE_RE", CONFIDENCE_FALLBACK)
+            };
+            let conflicting = SINGLE_RE
+                .captures_iter(body_text)
+                .filter_map(|other| parse_tanka(&other, 1))
+                .any(|other| plausible_tanka(other) && other != tanka);
+            let ev = FieldEvidence::new(mat.range(), rule, confidence).with_conflict(conflicting);
+            return Some(((tanka, tanka), ev));
         }
     }
 
@@ -164,55 +346,326 @@ pub fn extract_tanka(body_text: &str) -> Option<(u32, u32)> {
 
 /// 開始日の生テキストを保守的に抽出
 pub fn extract_start_date_raw(body_text: &str) -> Option<String> {
+    start_date_raw_with_evidence(body_text).map(|(raw, _)| raw)
+}
+
+fn start_date_raw_with_evidence(body_text: &str) -> Option<(String, FieldEvidence)> {
     if body_text.trim().is_empty() {
         return None;
     }
 
-    if let Some(mat) = ASAP_RE.find(body_text) {
-        return Some(mat.as_str().trim().to_string());
+    let found = |re: &Regex, rule: &str, confidence: f32| {
+        re.find(body_text).map(|mat| {
+            (
+                mat.as_str().trim().to_string(),
+                FieldEvidence::new(mat.range(), rule, confidence),
+            )
+        })
+    };
+
+    if let Some(hit) = found(&ASAP_RE, "ASAP_RE", CONFIDENCE_EXPLICIT) {
+        return Some(hit);
     }
 
-    if let Some(mat) = NEXT_MONTH_PART_RE.find(body_text) {
-        return Some(mat.as_str().trim().to_string());
+    if let Some(hit) = found(
+        &NEXT_MONTH_PART_RE,
+        "NEXT_MONTH_PART_RE",
+        CONFIDENCE_EXPLICIT,
+    ) {
+        return Some(hit);
     }
 
-    if let Some(mat) = NEXT_MONTH_RE.find(body_text) {
-        return Some(mat.as_str().trim().to_string());
+    if let Some(hit) = found(&NEXT_MONTH_RE, "NEXT_MONTH_RE", CONFIDENCE_INFERRED) {
+        return Some(hit);
     }
 
-    if let Some(mat) = MONTH_PART_RE.find(body_text) {
-        return Some(mat.as_str().trim().to_string());
+    if let Some(hit) = found(&MONTH_PART_RE, "MONTH_PART_RE", CONFIDENCE_EXPLICIT) {
+        return Some(hit);
     }
 
     if let Some(mat) = EXACT_DATE_RE.find(body_text) {
         let raw = mat.as_str().trim();
         let normalized = raw.replace('/', "-");
         match NaiveDate::parse_from_str(&normalized, "%Y-%m-%d") {
-            Ok(_) => return Some(raw.to_string()),
+            Ok(_) => {
+                return Some((
+                    raw.to_string(),
+                    FieldEvidence::new(mat.range(), "EXACT_DATE_RE", CONFIDENCE_EXPLICIT),
+                ))
+            }
             Err(err) => {
                 warn!(raw_start_date = raw, error = %err, "failed to parse exact start date");
             }
         }
     }
 
-    if let Some(mat) = MONTH_ONLY_RE.find(body_text) {
-        return Some(mat.as_str().trim().to_string());
-    }
-
-    None
+    // 「N月」単独は開始日以外（納期・面談日など）も拾うのでフォールバック扱い
+    found(&MONTH_ONLY_RE, "MONTH_ONLY_RE", CONFIDENCE_FALLBACK)
 }
 
-/// メール本文から都道府県を最初に見つかったものだけ抽出
+/// メール本文から都道府県を 1 つ抽出（勤務地ラベルの行を優先し、無ければ本文で最初に見つかったもの）
 pub fn extract_work_todofuken(body_text: &str) -> Option<String> {
+    work_todofuken_with_evidence(body_text).map(|(pref, _)| pref)
+}
+
+/// 勤務地を表すラベル。食い違いの判定はこれらの行だけで行う
+const LOCATION_LABELS: &[&str] = &["勤務地", "場所", "最寄"];
+
+/// `text` に出てくる都道府県（表記のまま、出現位置順）。
+/// 「東京都」の中の「京都」のような部分一致は除く
+fn mentioned_prefectures(text: &str) -> Vec<(usize, &'static str)> {
+    let mut keys: Vec<&'static str> = TODOFUKEN_TO_AREA.keys().copied().collect();
+    keys.sort_by_key(|pref| std::cmp::Reverse(pref.len()));
+    let mut rest = text.to_string();
+    let mut found: Vec<(usize, &'static str)> = keys
+        .into_iter()
+        .filter_map(|pref| {
+            let pos = rest.find(pref)?;
+            // バイト位置を保つため、消すのではなく同じ長さの空白で塗りつぶす
+            rest = rest.replace(pref, &" ".repeat(pref.len()));
+            Some((pos, pref))
+        })
+        .collect();
+    found.sort_unstable();
+    found
+}
+
+/// 都道府県 + 根拠。勤務地ラベル（勤務地 / 場所 / 最寄）の行にあればそれを優先し、
+/// ラベル行どうしで別の都道府県が出てくる場合だけ食い違いとする（署名や本社の住所は見ない）
+fn work_todofuken_with_evidence(body_text: &str) -> Option<(String, FieldEvidence)> {
     if body_text.trim().is_empty() {
         return None;
     }
 
-    TODOFUKEN_TO_AREA
-        .keys()
-        .filter(|pref| body_text.contains(*pref))
-        .max_by_key(|pref| pref.len())
-        .and_then(|pref| correct_todofuken(pref))
+    let labelled: Vec<(usize, &'static str)> = lines_with_offsets(body_text)
+        .filter(|(_, line)| LOCATION_LABELS.iter().any(|label| line.contains(label)))
+        .flat_map(|(line_start, line)| {
+            mentioned_prefectures(line)
+                .into_iter()
+                .map(move |(pos, pref)| (line_start + pos, pref))
+        })
+        .collect();
+
+    let (start, pref) = match labelled.first() {
+        Some(&hit) => hit,
+        None => mentioned_prefectures(body_text).into_iter().next()?,
+    };
+    let corrected = correct_todofuken(pref)?;
+    let conflicting = labelled
+        .iter()
+        .filter_map(|(_, other)| correct_todofuken(other))
+        .any(|other| other != corrected);
+    let ev = FieldEvidence::new(
+        start..start + pref.len(),
+        "TODOFUKEN_KEYWORD",
+        CONFIDENCE_EXPLICIT,
+    )
+    .with_conflict(conflicting);
+    Some((corrected, ev))
+}
+
+/// 必要経験年数（「経験3年以上」「3年以上の開発経験」）。小数は切り上げる
+pub fn extract_min_experience_years(body_text: &str) -> Option<i32> {
+    min_experience_years_with_evidence(body_text).map(|(years, _)| years)
+}
+
+fn min_experience_years_with_evidence(body_text: &str) -> Option<(i32, FieldEvidence)> {
+    let caps = EXPERIENCE_MIN_RE.captures(body_text)?;
+    let years: f32 = caps.get(1).or_else(|| caps.get(2))?.as_str().parse().ok()?;
+    (0.0..=30.0).contains(&years).then(|| {
+        (
+            years.ceil() as i32,
+            FieldEvidence::new(
+                caps.get(0).map_or(0..0, |m| m.range()),
+                "EXPERIENCE_MIN_RE",
+                CONFIDENCE_EXPLICIT,
+            ),
+        )
+    })
+}
+
+/// 年齢制限 (下限, 上限)。「〜歳」の明示を優先し、N代表記は年齢ラベルの行だけで読む。
+/// 「N代まで」は N9 歳、「20代〜30代」は 20〜39 歳として扱う
+pub fn extract_age_limits(body_text: &str) -> Option<(Option<i32>, Option<i32>)> {
+    age_limits_with_evidence(body_text).map(|(limits, _)| limits)
+}
+
+type AgeLimits = (Option<i32>, Option<i32>);
+
+/// 「年齢」「年齢制限」のラベル
+const AGE_LABEL: &str = "年齢";
+
+/// 本文を行ごとに (行頭のバイト位置, 改行を除いた行) で返す
+fn lines_with_offsets(body_text: &str) -> impl Iterator<Item = (usize, &str)> {
+    let mut offset = 0usize;
+    body_text.split_inclusive('\n').map(move |raw_line| {
+        let line_start = offset;
+        offset += raw_line.len();
+        (line_start, raw_line.trim_end_matches(['\r', '\n']))
+    })
+}
+
+fn age_limits_with_evidence(body_text: &str) -> Option<(AgeLimits, FieldEvidence)> {
+    let plausible = |age: i32| (18..=70).contains(&age).then_some(age);
+    let capture = |caps: &regex::Captures, idx: usize| -> Option<i32> {
+        caps.get(idx)?.as_str().parse().ok()
+    };
+    let evidence = |caps: &regex::Captures, rule: &str, confidence: f32| {
+        FieldEvidence::new(caps.get(0).map_or(0..0, |m| m.range()), rule, confidence)
+    };
+
+    for caps in AGE_RANGE_RE.captures_iter(body_text) {
+        if let (Some(lower), Some(upper)) = (
+            capture(&caps, 1).and_then(plausible),
+            capture(&caps, 2).and_then(plausible),
+        ) {
+            if lower <= upper {
+                return Some((
+                    (Some(lower), Some(upper)),
+                    evidence(&caps, "AGE_RANGE_RE", CONFIDENCE_EXPLICIT),
+                ));
+            }
+        }
+    }
+    if let Some(caps) = AGE_UPPER_RE.captures(body_text) {
+        let upper = capture(&caps, 1)
+            .or_else(|| capture(&caps, 2))
+            .and_then(plausible)?;
+        return Some((
+            (None, Some(upper)),
+            evidence(&caps, "AGE_UPPER_RE", CONFIDENCE_EXPLICIT),
+        ));
+    }
+
+    // 「20代〜30代活躍中」のような年齢層の紹介を拾わないよう、N代表記は年齢ラベルの行に限る
+    for (line_start, line) in lines_with_offsets(body_text) {
+        if !line.contains(AGE_LABEL) {
+            continue;
+        }
+        let shifted = |caps: &regex::Captures, rule: &str| {
+            let range = caps.get(0).map_or(0..0, |m| m.range());
+            FieldEvidence::new(
+                line_start + range.start..line_start + range.end,
+                rule,
+                CONFIDENCE_INFERRED,
+            )
+        };
+        if let Some(caps) = AGE_DECADE_RANGE_RE.captures(line) {
+            let lower = capture(&caps, 1)? * 10;
+            let upper = capture(&caps, 2)? * 10 + 9;
+            if lower <= upper {
+                return Some((
+                    (plausible(lower.max(18)), plausible(upper)),
+                    shifted(&caps, "AGE_DECADE_RANGE_RE"),
+                ));
+            }
+        }
+        if let Some(caps) = AGE_DECADE_UPPER_RE.captures(line) {
+            let upper = plausible(capture(&caps, 1)? * 10 + 9)?;
+            return Some(((None, Some(upper)), shifted(&caps, "AGE_DECADE_UPPER_RE")));
+        }
+    }
+    None
+}
+
+fn parse_ascii_digit(raw: &str) -> Option<i32> {
+    raw.chars()
+        .map(|c| match c {
+            '０'..='９' => char::from_u32(c as u32 - '０' as u32 + '0' as u32).unwrap_or(c),
+            _ => c,
+        })
+        .collect::<String>()
+        .parse()
+        .ok()
+}
+
+/// 正規表現 1 本で決まる項目の根拠（ラベル付きなので確信度は高い）
+fn explicit_match(caps: &regex::Captures, rule: &str) -> FieldEvidence {
+    FieldEvidence::new(
+        caps.get(0).map_or(0..0, |m| m.range()),
+        rule,
+        CONFIDENCE_EXPLICIT,
+    )
+}
+
+/// 面談回数（「面談2回」）
+pub fn extract_interviews_count(body_text: &str) -> Option<i32> {
+    interviews_count_with_evidence(body_text).map(|(count, _)| count)
+}
+
+fn interviews_count_with_evidence(body_text: &str) -> Option<(i32, FieldEvidence)> {
+    let caps = INTERVIEWS_RE.captures(body_text)?;
+    let count = parse_ascii_digit(&caps[1])?;
+    Some((count, explicit_match(&caps, "INTERVIEWS_RE")))
+}
+
+/// 募集人数（「募集人数：2名」「3名募集」）
+pub fn extract_hiring_headcount(body_text: &str) -> Option<i32> {
+    hiring_headcount_with_evidence(body_text).map(|(count, _)| count)
+}
+
+fn hiring_headcount_with_evidence(body_text: &str) -> Option<(i32, FieldEvidence)> {
+    let caps = HEADCOUNT_RE.captures(body_text)?;
+    let count: i32 = caps.get(1).or_else(|| caps.get(2))?.as_str().parse().ok()?;
+    (1..=50)
+        .contains(&count)
+        .then(|| (count, explicit_match(&caps, "HEADCOUNT_RE")))
+}
+
+fn is_allowed_marker(marker: &str) -> bool {
+    !matches!(marker, "不可" | "NG" | "ng" | "Ng" | "ＮＧ" | "×")
+}
+
+/// 外国籍可否（「外国籍不可」「日本国籍のみ」→ false、「外国籍可」→ true）
+pub fn extract_foreigner_allowed(body_text: &str) -> Option<bool> {
+    foreigner_allowed_with_evidence(body_text).map(|(allowed, _)| allowed)
+}
+
+fn foreigner_allowed_with_evidence(body_text: &str) -> Option<(bool, FieldEvidence)> {
+    if let Some(caps) = FOREIGNER_RE.captures(body_text) {
+        return Some((
+            is_allowed_marker(&caps[1]),
+            explicit_match(&caps, "FOREIGNER_RE"),
+        ));
+    }
+    let mat = JAPANESE_ONLY_RE.find(body_text)?;
+    Some((
+        false,
+        FieldEvidence::new(mat.range(), "JAPANESE_ONLY_RE", CONFIDENCE_EXPLICIT),
+    ))
+}
+
+/// 個人事業主可否（「個人事業主不可」→ false、「フリーランス可」→ true）
+pub fn extract_kojin_ok(body_text: &str) -> Option<bool> {
+    kojin_ok_with_evidence(body_text).map(|(ok, _)| ok)
+}
+
+fn kojin_ok_with_evidence(body_text: &str) -> Option<(bool, FieldEvidence)> {
+    let caps = KOJIN_RE.captures(body_text)?;
+    Some((
+        is_allowed_marker(&caps[1]),
+        explicit_match(&caps, "KOJIN_RE"),
+    ))
+}
+
+/// `keyword`（日本語 / 英語）を含む行の続きを ENUM 補正にかける。
+/// 根拠は補正にかけた行末までの範囲
+fn language_skill_with_evidence(
+    body_text: &str,
+    keyword: &str,
+    correct: fn(&str) -> Option<String>,
+) -> Option<(String, FieldEvidence)> {
+    lines_with_offsets(body_text).find_map(|(line_start, line)| {
+        let pos = line.find(keyword)?;
+        let skill =
+            correct(line[pos + keyword.len()..].trim_start_matches([':', '：', ' ', '\u{3000}']))?;
+        let ev = FieldEvidence::new(
+            line_start + pos..line_start + line.len(),
+            "LANGUAGE_LINE",
+            CONFIDENCE_INFERRED,
+        );
+        Some((skill, ev))
+    })
 }
 
 /// メールから Tier1/Tier2 を抽出し、品質判定まで含めた結果を返す
@@ -220,14 +673,40 @@ pub fn extract_all_fields(body_text: &str, subject: Option<&str>) -> ExtractorOu
     extract_all_fields_with_skills(body_text, subject, None)
 }
 
+/// 複数案件メールは案件ごとに分けて抽出する。
+/// 戻り値は ((セクション番号（1 始まり）, セクション本文)（分割しなければ None）, 抽出結果)。
+/// セクションの案件名は見出し行、無ければ件名を使う
+pub fn extract_project_sections(
+    body_text: &str,
+    subject: Option<&str>,
+) -> Vec<(Option<(u32, String)>, ExtractorOutput)> {
+    let sections = split_project_sections(body_text);
+    if sections.is_empty() {
+        return vec![(None, extract_all_fields(body_text, subject))];
+    }
+
+    sections
+        .into_iter()
+        .zip(1u32..)
+        .map(|(section, index)| {
+            let title = section_title(section);
+            let output = extract_all_fields(section, title.as_deref().or(subject));
+            (Some((index, section.to_string())), output)
+        })
+        .collect()
+}
+
 /// メールから Tier1/Tier2 を抽出し、スキルキーワードも正規化した結果を返す
+/// （`required_skills_keywords` 指定時は本文から抽出した必須スキルより優先）
 pub fn extract_all_fields_with_skills(
     body_text: &str,
     subject: Option<&str>,
     required_skills_keywords: Option<Vec<String>>,
 ) -> ExtractorOutput {
-    let mut partial = extract_partial_fields(body_text);
-    partial.required_skills_keywords = required_skills_keywords;
+    let (mut partial, evidence) = extract_partial_fields_with_evidence(body_text);
+    if required_skills_keywords.is_some() {
+        partial.required_skills_keywords = required_skills_keywords;
+    }
 
     if let Some(subj) = subject {
         let trimmed = subj.trim();
@@ -236,12 +715,14 @@ pub fn extract_all_fields_with_skills(
         }
     }
 
-    normalize_required_skills(&mut partial);
+    normalize_skills(&mut partial.required_skills_keywords);
+    normalize_skills(&mut partial.preferred_skills_keywords);
 
-    let (quality, decision) = evaluate_quality(&partial);
+    let (quality, decision) = evaluate_quality_with_evidence(&partial, &evidence);
 
     ExtractorOutput {
         partial,
+        evidence,
         quality,
         decision,
     }
@@ -249,98 +730,164 @@ pub fn extract_all_fields_with_skills(
 
 /// メール本文からリモート/出社形態を抽出して ENUM 補正
 pub fn extract_remote_onsite(body_text: &str) -> Option<String> {
-    let has_onsite = ONSITE_RE.is_match(body_text);
+    remote_onsite_with_evidence(body_text).map(|(remote, _)| remote)
+}
 
-    if FULL_REMOTE_OPTIONAL_RE.is_match(body_text) {
-        return Some("リモート併用".to_string());
+fn remote_onsite_with_evidence(body_text: &str) -> Option<(String, FieldEvidence)> {
+    let onsite = ONSITE_RE.find(body_text);
+    let hit = |mat: regex::Match, value: &str, rule: &str, confidence: f32| {
+        Some((
+            value.to_string(),
+            FieldEvidence::new(mat.range(), rule, confidence),
+        ))
+    };
+
+    if let Some(mat) = FULL_REMOTE_OPTIONAL_RE.find(body_text) {
+        return hit(
+            mat,
+            "リモート併用",
+            "FULL_REMOTE_OPTIONAL_RE",
+            CONFIDENCE_EXPLICIT,
+        );
     }
 
-    if FULL_REMOTE_RE.is_match(body_text) {
-        if has_onsite {
-            return Some("リモート併用".to_string());
+    if let Some(mat) = FULL_REMOTE_RE.find(body_text) {
+        if onsite.is_some() {
+            // フルリモートと出社の両方が書かれている → 併用と解釈
+            return hit(
+                mat,
+                "リモート併用",
+                "FULL_REMOTE_RE+ONSITE_RE",
+                CONFIDENCE_INFERRED,
+            );
         }
-        return Some("フルリモート".to_string());
+        return hit(mat, "フルリモート", "FULL_REMOTE_RE", CONFIDENCE_EXPLICIT);
     }
 
-    if PARTIAL_REMOTE_RE.is_match(body_text) {
-        return Some("リモート併用".to_string());
+    if let Some(mat) = PARTIAL_REMOTE_RE.find(body_text) {
+        return hit(
+            mat,
+            "リモート併用",
+            "PARTIAL_REMOTE_RE",
+            CONFIDENCE_EXPLICIT,
+        );
     }
 
-    if REMOTE_OPTIONAL_RE.is_match(body_text) {
-        return Some("リモート併用".to_string());
+    if let Some(mat) = REMOTE_OPTIONAL_RE.find(body_text) {
+        return hit(
+            mat,
+            "リモート併用",
+            "REMOTE_OPTIONAL_RE",
+            CONFIDENCE_EXPLICIT,
+        );
     }
 
-    if has_onsite {
-        return correct_remote_onsite("フル出社");
+    if let Some(mat) = onsite {
+        let value = correct_remote_onsite("フル出社")?;
+        return hit(mat, &value, "ONSITE_RE", CONFIDENCE_EXPLICIT);
     }
 
-    correct_remote_onsite(body_text)
+    correct_remote_onsite(body_text).map(|value| {
+        (
+            value,
+            FieldEvidence::new(
+                0..body_text.len(),
+                "correct_remote_onsite",
+                CONFIDENCE_FALLBACK,
+            ),
+        )
+    })
 }
 
 /// メール本文から商流を抽出して ENUM 補正
 pub fn extract_flow_dept(body_text: &str) -> Option<String> {
-    if END_DIRECT_RE.is_match(body_text) {
-        return Some("エンド直".to_string());
-    }
-    if ONE_HOP_RE.is_match(body_text) {
-        return Some("1次請け".to_string());
-    }
-    if TWO_HOP_RE.is_match(body_text) {
-        return Some("2次請け".to_string());
-    }
-    if THREE_HOP_RE.is_match(body_text) {
-        return Some("3次請け".to_string());
-    }
-    if FOUR_PLUS_RE.is_match(body_text) {
-        return Some("4次請け以上".to_string());
+    flow_dept_with_evidence(body_text).map(|(flow, _)| flow)
+}
+
+fn flow_dept_with_evidence(body_text: &str) -> Option<(String, FieldEvidence)> {
+    let rules: [(&Regex, &str, &str); 5] = [
+        (&END_DIRECT_RE, "エンド直", "END_DIRECT_RE"),
+        (&ONE_HOP_RE, "1次請け", "ONE_HOP_RE"),
+        (&TWO_HOP_RE, "2次請け", "TWO_HOP_RE"),
+        (&THREE_HOP_RE, "3次請け", "THREE_HOP_RE"),
+        (&FOUR_PLUS_RE, "4次請け以上", "FOUR_PLUS_RE"),
+    ];
+    for (re, value, rule) in rules {
+        if let Some(mat) = re.find(body_text) {
+            return Some((
+                value.to_string(),
+                FieldEvidence::new(mat.range(), rule, CONFIDENCE_EXPLICIT),
+            ));
+        }
     }
 
     let corrected = correct_flow_dept(body_text);
     if corrected == "不明" {
         None
     } else {
-        Some(corrected)
+        Some((
+            corrected,
+            FieldEvidence::new(0..body_text.len(), "correct_flow_dept", CONFIDENCE_FALLBACK),
+        ))
     }
 }
 
-fn normalize_required_skills(partial: &mut PartialFields) {
-    if let Some(raw) = partial.required_skills_keywords.take() {
+pub(crate) fn normalize_skills(skills: &mut Option<Vec<String>>) {
+    if let Some(raw) = skills.take() {
         let mut normalized: Vec<_> = normalize_skill_set(&raw).into_iter().collect();
         if !normalized.is_empty() {
             normalized.sort();
-            partial.required_skills_keywords = Some(normalized);
+            *skills = Some(normalized);
         }
     }
 }
 
-/// Tier 充足度をカウント
+fn has_skills(skills: &Option<Vec<String>>) -> bool {
+    skills.as_ref().is_some_and(|s| !s.is_empty())
+}
+
+/// Tier 充足度をカウント（必須スキルは skill KO / スコアに直結するため Tier1、歓迎スキルは Tier2）
+/// 経験年数・年齢・外国籍・個人可否・日本語は KO 判定の補助なので Tier2 に数える
 pub fn calculate_quality(partial: &PartialFields) -> ExtractionQuality {
-    let tier1_extracted = [
+    let tier1 = [
         partial.monthly_tanka_min.is_some(),
         partial.monthly_tanka_max.is_some(),
         partial.start_date_raw.is_some(),
         partial.work_todofuken.is_some(),
-    ]
-    .iter()
-    .filter(|&&x| x)
-    .count();
-
-    let tier2_extracted = [partial.remote_onsite.is_some(), partial.flow_dept.is_some()]
-        .iter()
-        .filter(|&&x| x)
-        .count();
+        has_skills(&partial.required_skills_keywords),
+    ];
+    let tier2 = [
+        partial.remote_onsite.is_some(),
+        partial.flow_dept.is_some(),
+        has_skills(&partial.preferred_skills_keywords),
+        partial.min_experience_years.is_some(),
+        partial.age_limit_lower.is_some() || partial.age_limit_upper.is_some(),
+        partial.foreigner_allowed.is_some(),
+        partial.is_kojin_ok.is_some(),
+        partial.japanese_skill.is_some(),
+    ];
 
     ExtractionQuality {
-        tier1_extracted,
-        tier1_total: 4,
-        tier2_extracted,
-        tier2_total: 2,
+        tier1_extracted: tier1.iter().filter(|&&x| x).count(),
+        tier1_total: tier1.len(),
+        tier2_extracted: tier2.iter().filter(|&&x| x).count(),
+        tier2_total: tier2.len(),
+        low_confidence_fields: Vec::new(),
         llm_recommended: false,
         reason: String::new(),
     }
 }
 
-/// Tier 不足ベースで推奨メソッドを決定
+/// 確信度が低いと LLM に回す Tier1 フィールド
+const TIER1_EVIDENCE_FIELDS: &[&str] = &[
+    "monthly_tanka_min",
+    "monthly_tanka_max",
+    "start_date_raw",
+    "work_todofuken",
+];
+
+/// Tier 不足と Tier1 の確信度で推奨メソッドを決定
+/// （Tier1 が揃っていても単発値フォールバックや食い違いがあれば LLM に回す）
 pub fn decide_recommended_method(quality: &ExtractionQuality) -> RecommendedDecision {
     if quality.tier1_extracted < quality.tier1_total {
         return RecommendedDecision {
@@ -352,6 +899,22 @@ pub fn decide_recommended_method(quality: &ExtractionQuality) -> RecommendedDeci
         };
     }
 
+    let low_tier1: Vec<&str> = quality
+        .low_confidence_fields
+        .iter()
+        .map(String::as_str)
+        .filter(|field| TIER1_EVIDENCE_FIELDS.contains(field))
+        .collect();
+    if !low_tier1.is_empty() {
+        return RecommendedDecision {
+            recommended_method: RecommendedMethod::LlmRecommended,
+            reason: format!(
+                "LLM recommended: low confidence Tier1 {}",
+                low_tier1.join(",")
+            ),
+        };
+    }
+
     if quality.tier2_extracted < 1 {
         return RecommendedDecision {
             recommended_method: RecommendedMethod::LlmRecommended,
@@ -394,7 +957,16 @@ pub fn calculate_priority(quality: &ExtractionQuality) -> i32 {
 
 /// quality と decision をまとめて生成
 pub fn evaluate_quality(partial: &PartialFields) -> (ExtractionQuality, RecommendedDecision) {
+    evaluate_quality_with_evidence(partial, &FieldEvidenceMap::new())
+}
+
+/// 抽出根拠の確信度も加味して quality と decision を生成
+pub fn evaluate_quality_with_evidence(
+    partial: &PartialFields,
+    evidence: &FieldEvidenceMap,
+) -> (ExtractionQuality, RecommendedDecision) {
     let mut quality = calculate_quality(partial);
+    quality.low_confidence_fields = low_confidence_fields(evidence);
     let decision = decide_recommended_method(&quality);
     quality.llm_recommended = decision.recommended_method == RecommendedMethod::LlmRecommended;
     quality.reason = decision.reason.clone();
@@ -503,9 +1075,15 @@ mod tests {
         partial.start_date_raw = Some("即日".to_string());
         partial.work_todofuken = Some("東京都".to_string());
 
-        // Tier1=4/4, Tier2=0 -> LLM 推奨
+        // 必須スキルが無ければ Tier1 不足
         let (quality, decision) = evaluate_quality(&partial);
-        assert_eq!(quality.tier1_extracted, 4);
+        assert_eq!((quality.tier1_extracted, quality.tier1_total), (4, 5));
+        assert!(decision.reason.contains("Tier1 incomplete 4/5"));
+
+        // Tier1=5/5, Tier2=0 -> LLM 推奨
+        partial.required_skills_keywords = Some(vec!["rust".to_string()]);
+        let (quality, decision) = evaluate_quality(&partial);
+        assert_eq!(quality.tier1_extracted, 5);
         assert_eq!(quality.tier2_extracted, 0);
         assert_eq!(
             decision.recommended_method,
@@ -567,7 +1145,7 @@ mod tests {
 
     #[test]
     fn extract_all_fields_sets_tiers_and_project_name() {
-        let body = "月額80万円、勤務地: 大阪府。週2リモート可、一次請け案件。即日参画。";
+        let body = "月額80万円、勤務地: 大阪府。週2リモート可、一次請け案件。即日参画。\n【必須スキル】\n・Python\n【尚可】AWS";
         let output = extract_all_fields(body, Some("【案件】データエンジニア"));
 
         assert_eq!(output.partial.monthly_tanka_min, Some(80));
@@ -583,14 +1161,168 @@ mod tests {
             Some("【案件】データエンジニア".to_string())
         );
 
-        assert_eq!(output.quality.tier1_extracted, 4);
-        assert_eq!(output.quality.tier2_extracted, 2);
+        assert_eq!(
+            output.partial.required_skills_keywords,
+            Some(vec!["python".to_string()])
+        );
+        assert_eq!(
+            output.partial.preferred_skills_keywords,
+            Some(vec!["aws".to_string()])
+        );
+
+        assert_eq!(output.quality.tier1_extracted, 5);
+        assert_eq!(output.quality.tier2_extracted, 3);
         assert_eq!(
             output.decision.recommended_method,
             RecommendedMethod::RustRecommended
         );
     }
 
+    #[test]
+    fn evidence_records_span_rule_and_confidence() {
+        let body = "【単価】70〜90万円\n勤務地：東京都\n即日";
+        let (partial, evidence) = extract_partial_fields_with_evidence(body);
+
+        let tanka = &evidence["monthly_tanka_max"];
+        assert_eq!(partial.monthly_tanka_max, Some(90));
+        assert_eq!(tanka.rule, "RANGE_RE");
+        assert_eq!(&body[tanka.start..tanka.end], "70〜90万円");
+        assert!(!tanka.is_low_confidence());
+        assert_eq!(evidence["start_date_raw"].rule, "ASAP_RE");
+        assert_eq!(
+            &body[evidence["work_todofuken"].start..evidence["work_todofuken"].end],
+            "東京都"
+        );
+
+        let (_, evidence) = extract_partial_fields_with_evidence("80万円（スキル次第で85万円）");
+        assert_eq!(evidence["monthly_tanka_min"].rule, "SINGLE_RE");
+        assert!(evidence["monthly_tanka_min"].conflicting);
+
+        let (_, evidence) = extract_partial_fields_with_evidence("勤務地：東京都\n場所：大阪府");
+        assert!(evidence["work_todofuken"].conflicting);
+    }
+
+    #[test]
+    fn signature_address_does_not_conflict_with_work_location() {
+        let body = "単価：80万円\n場所：東京都港区\n即日\n\n--\n株式会社サンプル\n〒530-0001 大阪府大阪市北区梅田1-1-1\n本社：大阪府";
+        let output = extract_all_fields(body, None);
+
+        assert_eq!(output.partial.work_todofuken.as_deref(), Some("東京都"));
+        assert!(!output.evidence["work_todofuken"].conflicting);
+        assert!(!output
+            .quality
+            .low_confidence_fields
+            .contains(&"work_todofuken".to_string()));
+    }
+
+    #[test]
+    fn low_confidence_tier1_recommends_llm() {
+        let skills = "\n【必須スキル】\n・Java\n外国籍不可";
+        let confident = extract_all_fields(&format!("単価：80万円 東京都 即日{skills}"), None);
+        assert_eq!(
+            confident.decision.recommended_method,
+            RecommendedMethod::RustRecommended
+        );
+
+        // ラベル無しの単発値
+        let unlabeled = extract_all_fields(&format!("80万円 東京都 即日{skills}"), None);
+        assert_eq!(unlabeled.quality.tier1_extracted, 5);
+        assert!(unlabeled
+            .quality
+            .low_confidence_fields
+            .contains(&"monthly_tanka_min".to_string()));
+        assert_eq!(
+            unlabeled.decision.recommended_method,
+            RecommendedMethod::LlmRecommended
+        );
+
+        // 食い違う単価レンジ
+        let conflicting = extract_all_fields(
+            &format!("単価：60〜70万円（スキル見合いで80〜90万円） 東京都 即日{skills}"),
+            None,
+        );
+        assert!(conflicting.evidence["monthly_tanka_max"].conflicting);
+        assert!(conflicting.decision.reason.contains("low confidence Tier1"));
+    }
+
+    #[test]
+    fn extracts_ko_related_conditions() {
+        let body = "【条件】\n・Java経験3年以上\n・年齢：25〜45歳\n・面談2回\n・募集人数：2名\n・外国籍不可\n・個人事業主可\n・日本語ネイティブレベル";
+        let partial = extract_partial_fields(body);
+
+        assert_eq!(partial.min_experience_years, Some(3));
+        assert_eq!(partial.age_limit_lower, Some(25));
+        assert_eq!(partial.age_limit_upper, Some(45));
+        assert_eq!(partial.interviews_count, Some(2));
+        assert_eq!(partial.hiring_headcount, Some(2));
+        assert_eq!(partial.foreigner_allowed, Some(false));
+        assert_eq!(partial.is_kojin_ok, Some(true));
+        assert_eq!(partial.japanese_skill.as_deref(), Some("ネイティブ"));
+    }
+
+    #[test]
+    fn condition_extractors_cover_wording_variants() {
+        assert_eq!(extract_min_experience_years("1.5年以上の開発経験"), Some(2));
+        assert_eq!(extract_age_limits("年齢：40代まで"), Some((None, Some(49))));
+        assert_eq!(
+            extract_age_limits("年齢制限：20代〜30代の方"),
+            Some((Some(20), Some(39)))
+        );
+        assert_eq!(extract_age_limits("20代〜30代活躍中"), None);
+        assert_eq!(extract_age_limits("〜45歳くらい"), Some((None, Some(45))));
+        assert_eq!(extract_age_limits("月額70〜90万円"), None);
+        assert_eq!(extract_interviews_count("面接１回（WEB）"), Some(1));
+        assert_eq!(extract_hiring_headcount("3名募集"), Some(3));
+        assert_eq!(extract_hiring_headcount("5名程度のチーム"), None);
+        assert_eq!(extract_foreigner_allowed("外国籍の方OK"), Some(true));
+        assert_eq!(extract_foreigner_allowed("日本国籍のみ"), Some(false));
+        assert_eq!(extract_kojin_ok("フリーランスNG"), Some(false));
+        assert_eq!(
+            language_skill_with_evidence(
+                "日本語：ビジネスレベル",
+                "日本語",
+                correct_japanese_skill
+            )
+            .map(|(skill, _)| skill),
+            Some("N1".to_string())
+        );
+        assert_eq!(extract_foreigner_allowed("特になし"), None);
+    }
+
+    #[test]
+    fn explicit_age_wins_over_unlabelled_decades() {
+        let output = extract_all_fields("経験3年以上\n年齢：〜45歳\n20代〜30代活躍中", None);
+
+        assert_eq!(output.partial.age_limit_lower, None);
+        assert_eq!(output.partial.age_limit_upper, Some(45));
+    }
+
+    #[test]
+    fn multi_project_emails_are_extracted_per_section() {
+        let body = "【案件1】Java開発\n単価：60〜70万円\n勤務地：東京都\n\n【案件2】AWS移行\n単価：90万円\n勤務地：大阪府";
+        let outputs = extract_project_sections(body, Some("案件2件のご紹介"));
+
+        assert_eq!(outputs.len(), 2);
+        let (first_section, first) = &outputs[0];
+        let (second_section, second) = &outputs[1];
+        assert_eq!(first_section.as_ref().map(|(index, _)| *index), Some(1));
+        assert_eq!(
+            second_section
+                .as_ref()
+                .map(|(index, text)| (*index, text.as_str())),
+            Some((2, "【案件2】AWS移行\n単価：90万円\n勤務地：大阪府"))
+        );
+        assert_eq!(first.partial.monthly_tanka_max, Some(70));
+        assert_eq!(first.partial.work_todofuken.as_deref(), Some("東京都"));
+        assert_eq!(first.partial.project_name.as_deref(), Some("Java開発"));
+        assert_eq!(second.partial.monthly_tanka_min, Some(90));
+        assert_eq!(second.partial.work_todofuken.as_deref(), Some("大阪府"));
+
+        let single = extract_project_sections("月額80万円、勤務地: 大阪府", Some("件名"));
+        assert_eq!(single.len(), 1);
+        assert_eq!(single[0].0, None);
+    }
+
     #[test]
     fn normalizes_required_skills_after_extraction() {
         let output = extract_all_fields_with_skills(
@@ -0,0 +1,556 @@
+//! 完了済み抽出ジョブ（Rust `PartialFields` + LLM `extracted`）を typed [`Project`] に射影する
+//!
+//! - LLM が値を返した項目は LLM を優先し、欠けている項目だけ Rust 抽出で補完する
+//! - 全項目に `corrections::*` の補正を通し、開始日は受信日時基準で正規化する
+//! - どのジョブ・どの経路で値が決まったかを項目ごとに記録する
+
+use std::collections::BTreeMap;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+use crate::corrections::{
+    contract_type::correct_contract_type,
+    english_skill::correct_english_skill,
+    flow_depth::{correct_flow_dept, correct_jinzai_flow_limit},
+    japanese_skill::correct_japanese_skill,
+    remote_onsite::correct_remote_onsite,
+    station::normalize_station,
+    tech_kubun::infer_tech_kubun,
+    todofuken::{correct_todofuken, correct_work_area},
+};
+use crate::date::normalize_start_date;
+use crate::extraction::{extract_all_fields, PartialFields};
+use crate::queue::{EntityType, ExtractionJob, FinalMethod, QueueStatus};
+use crate::skill_normalizer::normalize_skills_vec;
+use crate::Project;
+
+/// 項目値の出どころ
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FieldOrigin {
+    /// sr-extractor の正規表現抽出
+    Rust,
+    /// sr-llm-worker の `extracted` ペイロード
+    Llm,
+    /// 他の項目からの推論（都道府県→エリア、スキル→技術区分）
+    Derived,
+}
+
+/// 1 項目分の由来（ses.projects.field_sources に JSONB で保存）
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FieldSource {
+    pub origin: FieldOrigin,
+    pub job_id: u64,
+    pub final_method: String,
+}
+
+/// key は [`Project`] のフィールド名
+pub type FieldSources = BTreeMap<String, FieldSource>;
+
+/// 抽出ジョブ 1 件分の射影結果
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ProjectProjection {
+    pub message_id: String,
+    pub extraction_job_id: u64,
+    pub final_method: FinalMethod,
+    pub project_name: String,
+    pub project: Project,
+    pub field_sources: FieldSources,
+    pub received_at: DateTime<Utc>,
+    /// 射影元ジョブの updated_at（再処理されたジョブの再射影判定に使う）
+    pub source_updated_at: DateTime<Utc>,
+    pub requires_manual_review: bool,
+    pub manual_review_reason: Option<String>,
+}
+
+/// 完了済みジョブを [`Project`] に射影する。
+///
+/// status=completed かつ final_method が rust_completed / llm_completed の案件ジョブのみ対象。
+/// 手動レビュー行きや未完了のジョブ、人材メールのジョブは `None` を返す。
+pub fn project_extraction_job(
+    job: &ExtractionJob,
+    body_text: &str,
+    received_at: DateTime<Utc>,
+) -> Option<ProjectProjection> {
+    if job.status != QueueStatus::Completed || job.entity_type != EntityType::Project {
+        return None;
+    }
+    let final_method = job.final_method.clone()?;
+
+    // llm_completed では partial_fields が LLM の extracted で上書きされているため、
+    // Rust 側の値は本文から再抽出する
+    let (rust, llm) = match final_method {
+        FinalMethod::RustCompleted => {
+            let rust = job
+                .partial_fields
+                .clone()
+                .and_then(|value| serde_json::from_value::<PartialFields>(value).ok())
+                .unwrap_or_else(|| reextract(job, body_text));
+            (rust, None)
+        }
+        FinalMethod::LlmCompleted => (
+            reextract(job, body_text),
+            job.partial_fields.as_ref().and_then(Value::as_object),
+        ),
+        FinalMethod::ManualReview => return None,
+    };
+
+    let mut projector = Projector {
+        job_id: job.id,
+        final_method: final_method.as_str(),
+        sources: FieldSources::new(),
+    };
+    let project = projector.build(&rust, llm, received_at);
+
+    let project_name = projector
+        .pick(
+            "project_name",
+            llm_str(llm, "project_name"),
+            rust.project_name.clone(),
+        )
+        .unwrap_or_else(|| job.email_subject.clone());
+
+    Some(ProjectProjection {
+        message_id: job.message_id.clone(),
+        extraction_job_id: job.id,
+        final_method,
+        project_name,
+        project,
+        field_sources: projector.sources,
+        received_at,
+        source_updated_at: job.updated_at,
+        requires_manual_review: job.requires_manual_review,
+        manual_review_reason: job.manual_review_reason.clone(),
+    })
+}
+
+fn reextract(job: &ExtractionJob, body_text: &str) -> PartialFields {
+    extract_all_fields(body_text, Some(&job.email_subject)).partial
+}
+
+pub(super) struct Projector {
+    pub(super) job_id: u64,
+    pub(super) final_method: &'static str,
+    pub(super) sources: FieldSources,
+}
+
+impl Projector {
+    pub(super) fn record(&mut self, field: &str, origin: FieldOrigin) {
+        self.sources.insert(
+            field.to_string(),
+            FieldSource {
+                origin,
+                job_id: self.job_id,
+                final_method: self.final_method.to_string(),
+            },
+        );
+    }
+
+    /// LLM → Rust の順で最初に得られた値を採用し、由来を記録する
+    pub(super) fn pick<T>(&mut self, field: &str, llm: Option<T>, rust: Option<T>) -> Option<T> {
+        if let Some(value) = llm {
+            self.record(field, FieldOrigin::Llm);
+            return Some(value);
+        }
+        if let Some(value) = rust {
+            self.record(field, FieldOrigin::Rust);
+            return Some(value);
+        }
+        None
+    }
+
+    fn build(
+        &mut self,
+        rust: &PartialFields,
+        llm: Option<&Map<String, Value>>,
+        received_at: DateTime<Utc>,
+    ) -> Project {
+        let work_todofuken = self.pick(
+            "work_todofuken",
+            llm_str(llm, "work_todofuken").and_then(|v| correct_todofuken(&v)),
+            rust.work_todofuken.as_deref().and_then(correct_todofuken),
+        );
+
+        let mut work_area = self.pick(
+            "work_area",
+            llm_str(llm, "work_area").and_then(|v| correct_work_area(&v)),
+            None,
+        );
+        if work_area.is_none() {
+            work_area = work_todofuken.as_deref().and_then(correct_work_area);
+            if work_area.is_some() {
+                self.record("work_area", FieldOrigin::Derived);
+            }
+        }
+
+        let required_skills_keywords = self
+            .pick(
+                "required_skills_keywords",
+                llm_skills(llm, "required_skills_keywords"),
+                rust.required_skills_keywords
+                    .as_deref()
+                    .map(normalize_skills_vec)
+                    .filter(|skills| !skills.is_empty()),
+            )
+            .unwrap_or_default();
+
+        let mut tech_kubun = self.pick("tech_kubun", llm_str(llm, "tech_kubun"), None);
+        if tech_kubun.is_none() {
+            tech_kubun = infer_tech_kubun(&required_skills_keywords);
+            if tech_kubun.is_some() {
+                self.record("tech_kubun", FieldOrigin::Derived);
+            }
+        }
+
+        let start_date = self.pick(
+            "start_date",
+            llm_str(llm, "start_date")
+                .or_else(|| llm_str(llm, "start_date_raw"))
+                .and_then(|raw| normalize_start_date(&raw, received_at)),
+            rust.start_date_raw
+                .as_deref()
+                .and_then(|raw| normalize_start_date(raw, received_at)),
+        );
+
+        Project {
+            id: None,
+            work_todofuken,
+            work_area,
+            remote_onsite: self.pick(
+                "remote_onsite",
+                llm_str(llm, "remote_onsite").and_then(|v| correct_remote_onsite(&v)),
+                rust.remote_onsite
+                    .as_deref()
+                    .and_then(correct_remote_onsite),
+            ),
+            work_station: self.pick(
+                "work_station",
+                llm_str(llm, "work_station").and_then(|v| normalize_station(&v)),
+                None,
+            ),
+            onsite_frequency: self.pick(
+                "onsite_frequency",
+                llm_f64(llm, "onsite_frequency").map(|v| v as f32),
+                None,
+            ),
+            settlement_range: self.pick("settlement_range", llm_str(llm, "settlement_range"), None),
+            interviews_count: self.pick(
+                "interviews_count",
+                llm_i32(llm, "interviews_count"),
+                rust.interviews_count,
+            ),
+            hiring_headcount: self.pick(
+                "hiring_headcount",
+                llm_i32(llm, "hiring_headcount"),
+                rust.hiring_headcount,
+            ),
+            monthly_tanka_min: self.pick(
+                "monthly_tanka_min",
+                llm_u32(llm, "monthly_tanka_min"),
+                rust.monthly_tanka_min,
+            ),
+            monthly_tanka_max: self.pick(
+                "monthly_tanka_max",
+                llm_u32(llm, "monthly_tanka_max"),
+                rust.monthly_tanka_max,
+            ),
+            required_skills_keywords,
+            preferred_skills_keywords: self
+                .pick(
+                    "preferred_skills_keywords",
+                    llm_skills(llm, "preferred_skills_keywords"),
+                    rust.preferred_skills_keywords
+                        .as_deref()
+                        .map(normalize_skills_vec)
+                        .filter(|skills| !skills.is_empty()),
+                )
+                .unwrap_or_default(),
+            // 「3年以上」「1.5年」など。要件側なので切り上げる
+            min_experience_years: self.pick(
+                "min_experience_years",
+                llm_f64(llm, "min_experience_years").map(|v| v.ceil() as i32),
+                rust.min_experience_years,
+            ),
+            japanese_skill: self.pick(
+                "japanese_skill",
+                llm_str(llm, "japanese_skill").and_then(|v| correct_japanese_skill(&v)),
+                rust.japanese_skill.clone(),
+            ),
+            english_skill: self.pick(
+                "english_skill",
+                llm_str(llm, "english_skill").and_then(|v| correct_english_skill(&v)),
+                rust.english_skill.clone(),
+            ),
+            contract_type: self.pick(
+                "contract_type",
+                llm_str(llm, "contract_type").map(|v| correct_contract_type(&v)),
+                None,
+            ),
+            flow_dept: self.pick(
+                "flow_dept",
+                llm_str(llm, "flow_dept").map(|v| correct_flow_dept(&v)),
+                rust.flow_dept.as_deref().map(correct_flow_dept),
+            ),
+            project_type: self.pick("project_type", llm_str_vec(llm, "project_type"), None),
+            jinzai_flow_limit: self.pick(
+                "jinzai_flow_limit",
+                llm_str(llm, "jinzai_flow_limit").and_then(|v| correct_jinzai_flow_limit(&v)),
+                None,
+            ),
+            is_kojin_ok: self.pick(
+                "is_kojin_ok",
+                llm_bool(llm, "is_kojin_ok"),
+                rust.is_kojin_ok,
+            ),
+            tech_kubun,
+            project_keywords: self.pick(
+                "project_keywords",
+                llm_str_vec(llm, "project_keywords"),
+                None,
+            ),
+            age_limit_lower: self.pick(
+                "age_limit_lower",
+                llm_i32(llm, "age_limit_lower"),
+                rust.age_limit_lower,
+            ),
+            age_limit_upper: self.pick(
+                "age_limit_upper",
+                llm_i32(llm, "age_limit_upper"),
+                rust.age_limit_upper,
+            ),
+            foreigner_allowed: self.pick(
+                "foreigner_allowed",
+                llm_bool(llm, "foreigner_allowed"),
+                rust.foreigner_allowed,
+            ),
+            start_date,
+            sender_domain: None,
+            client_name: None,
+        }
+    }
+}
+
+impl ProjectProjection {
+    /// 元メールの送信者をクライアントプロファイルの照合キーとして案件に付ける
+    pub fn with_sender(mut self, sender_name: Option<&str>, sender_address: Option<&str>) -> Self {
+        self.project.sender_domain = sender_address.and_then(sender_domain);
+        self.project.client_name = sender_name
+            .map(str::trim)
+            .filter(|name| !name.is_empty())
+            .map(str::to_string);
+        self
+    }
+}
+
+/// `"営業 太郎 <taro@sales.example.co.jp>"` / `"taro@example.co.jp"` からドメイン（小文字）を取り出す
+pub fn sender_domain(address: &str) -> Option<String> {
+    let address = match (address.rfind('<'), address.rfind('>')) {
+        (Some(start), Some(end)) if start < end => &address[start + 1..end],
+        _ => address,
+    };
+    let (_, domain) = address.trim().rsplit_once('@')?;
+    let domain = domain.trim().trim_end_matches('.').to_lowercase();
+    (!domain.is_empty() && !domain.contains(char::is_whitespace)).then_some(domain)
+}
+
+fn llm_value<'a>(llm: Option<&'a Map<String, Value>>, key: &str) -> Option<&'a Value> {
+    llm.and_then(|obj| obj.get(key)).filter(|v| !v.is_null())
+}
+
+pub(super) fn llm_str(llm: Option<&Map<String, Value>>, key: &str) -> Option<String> {
+    match llm_value(llm, key)? {
+        Value::String(s) => Some(s.trim().to_string()).filter(|s| !s.is_empty()),
+        Value::Number(n) => Some(n.to_string()),
+        _ => None,
+    }
+}
+
+pub(super) fn llm_f64(llm: Option<&Map<String, Value>>, key: &str) -> Option<f64> {
+    let value = match llm_value(llm, key)? {
+        Value::Number(n) => n.as_f64(),
+        Value::String(s) => s.trim().parse::<f64>().ok(),
+        _ => None,
+    }?;
+    value.is_finite().then_some(value)
+}
+
+pub(super) fn llm_i32(llm: Option<&Map<String, Value>>, key: &str) -> Option<i32> {
+    llm_f64(llm, key)
+        .map(f64::round)
+        .filter(|v| *v >= 0.0 && *v <= i32::MAX as f64)
+        .map(|v| v as i32)
+}
+
+pub(super) fn llm_u32(llm: Option<&Map<String, Value>>, key: &str) -> Option<u32> {
+    llm_i32(llm, key).and_then(|v| u32::try_from(v).ok())
+}
+
+pub(super) fn llm_bool(llm: Option<&Map<String, Value>>, key: &str) -> Option<bool> {
+    match llm_value(llm, key)? {
+        Value::Bool(b) => Some(*b),
+        Value::String(s) => match s.trim().to_lowercase().as_str() {
+            "true" | "yes" | "可" | "ok" | "○" | "〇" => Some(true),
+            "false" | "no" | "不可" | "ng" | "×" | "✕" => Some(false),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+fn llm_str_vec(llm: Option<&Map<String, Value>>, key: &str) -> Option<Vec<String>> {
+    let values: Vec<String> = match llm_value(llm, key)? {
+        Value::Array(items) => items
+            .iter()
+            .filter_map(Value::as_str)
+            .map(|s| s.trim().to_string())
+            .filter(|s| !s.is_empty())
+            .collect(),
+        Value::String(s) => s
+            .split([',', '、', '/'])
+            .map(|s| s.trim().to_string())
+            .filter(|s| !s.is_empty())
+            .collect(),
+        _ => Vec::new(),
+    };
+    (!values.is_empty()).then_some(values)
+}
+
+pub(super) fn llm_skills(llm: Option<&Map<String, Value>>, key: &str) -> Option<Vec<String>> {
+    llm_str_vec(llm, key)
+        .map(|skills| normalize_skills_vec(&skills))
+        .filter(|skills| !skills.is_empty())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::date::StartDatePrecision;
+    use chrono::{NaiveDate, TimeZone};
+    use serde_json::json;
+
+    const BODY: &str =
+        "単価：60〜70万円\n期間：即日〜長期\n勤務地：東京都渋谷区（週2出社）\n商流：プライム案件";
+
+    fn completed_job(final_method: FinalMethod, partial_fields: Option<Value>) -> ExtractionJob {
+        let received_at = Utc.with_ymd_and_hms(2025, 1, 10, 0, 0, 0).unwrap();
+        let mut job = ExtractionJob::new("msg-1", "Java案件", received_at, "hash");
+        job.id = 7;
+        job.status = QueueStatus::Completed;
+        job.final_method = Some(final_method);
+        job.partial_fields = partial_fields;
+        job
+    }
+
+    #[test]
+    fn llm_values_win_and_rust_fills_gaps() {
+        let job = completed_job(
+            FinalMethod::LlmCompleted,
+            Some(json!({
+                "project_name": "ECサイトリニューアル",
+                "monthly_tanka_min": 65,
+                "work_todofuken": "東京",
+                "required_skills_keywords": ["Java", "Spring Boot"],
+                "min_experience_years": 2.5,
+                "foreigner_allowed": false,
+                "start_date": null
+            })),
+        );
+        let received_at = job.email_received_at;
+
+        let projection = project_extraction_job(&job, BODY, received_at).unwrap();
+        let project = &projection.project;
+
+        assert_eq!(projection.project_name, "ECサイトリニューアル");
+        assert_eq!(project.monthly_tanka_min, Some(65));
+        assert_eq!(project.monthly_tanka_max, Some(70));
+        assert_eq!(project.work_todofuken.as_deref(), Some("東京都"));
+        assert_eq!(project.work_area.as_deref(), Some("関東"));
+        assert_eq!(project.min_experience_years, Some(3));
+        assert_eq!(project.foreigner_allowed, Some(false));
+        assert!(!project.required_skills_keywords.is_empty());
+
+        let start = project.start_date.as_ref().unwrap();
+        assert_eq!(start.precision, StartDatePrecision::Asap);
+
+        let sources = &projection.field_sources;
+        assert_eq!(sources["monthly_tanka_min"].origin, FieldOrigin::Llm);
+        assert_eq!(sources["monthly_tanka_max"].origin, FieldOrigin::Rust);
+        assert_eq!(sources["work_area"].origin, FieldOrigin::Derived);
+        assert_eq!(sources["start_date"].origin, FieldOrigin::Rust);
+        assert_eq!(sources["start_date"].job_id, 7);
+        assert_eq!(sources["start_date"].final_method, "llm_completed");
+    }
+
+    #[test]
+    fn rust_completed_uses_stored_partial_fields() {
+        let partial = PartialFields {
+            monthly_tanka_min: Some(50),
+            monthly_tanka_max: Some(55),
+            start_date_raw: Some("2025-02-01".into()),
+            work_todofuken: Some("大阪".into()),
+            ..PartialFields::default()
+        };
+        let job = completed_job(
+            FinalMethod::RustCompleted,
+            Some(serde_json::to_value(&partial).unwrap()),
+        );
+
+        let projection = project_extraction_job(&job, "", job.email_received_at).unwrap();
+        let project = &projection.project;
+
+        assert_eq!(projection.project_name, "Java案件");
+        assert_eq!(project.monthly_tanka_min, Some(50));
+        assert_eq!(project.work_todofuken.as_deref(), Some("大阪府"));
+        assert_eq!(
+            project.start_date.as_ref().and_then(|s| s.date),
+            NaiveDate::from_ymd_opt(2025, 2, 1)
+        );
+        assert!(projection
+            .field_sources
+            .values()
+            .all(|s| s.origin != FieldOrigin::Llm));
+    }
+
+    #[test]
+    fn manual_review_and_pending_jobs_are_not_projected() {
+        let job = completed_job(FinalMethod::ManualReview, None);
+        assert!(project_extraction_job(&job, BODY, job.email_received_at).is_none());
+
+        let mut pending = completed_job(FinalMethod::LlmCompleted, None);
+        pending.status = QueueStatus::Pending;
+        assert!(project_extraction_job(&pending, BODY, pending.email_received_at).is_none());
+    }
+
+    #[test]
+    fn sender_becomes_client_profile_keys() {
+        assert_eq!(
+            sender_domain("営業 太郎 <Taro@Sales.Example.co.jp>").as_deref(),
+            Some("sales.example.co.jp")
+        );
+        assert_eq!(
+            sender_domain("taro@example.co.jp").as_deref(),
+            Some("example.co.jp")
+        );
+        assert_eq!(sender_domain("no-address"), None);
+
+        let job = completed_job(FinalMethod::ManualReview, None);
+        let mut llm_job = job.clone();
+        llm_job.final_method = Some(FinalMethod::LlmCompleted);
+        let projection = project_extraction_job(&llm_job, BODY, job.email_received_at)
+            .unwrap()
+            .with_sender(
+                Some(" 株式会社サンプル 営業部 "),
+                Some("eigyo@sample.co.jp"),
+            );
+        assert_eq!(
+            projection.project.client_name.as_deref(),
+            Some("株式会社サンプル 営業部")
+        );
+        assert_eq!(
+            projection.project.sender_domain.as_deref(),
+            Some("sample.co.jp")
+        );
+    }
+}
@@ -0,0 +1,183 @@
+//! 1 通に複数の案件が載った案件メールの分割（ルールベース）
+//!
+//! - 【案件1】【案件2】/ ■案件① のような番号付き見出しが 2 つ以上あれば、見出しごとに切る
+//! - 見出しが無ければ ━━━ / ===== などの区切り線で切り、案件らしいブロックが 2 つ以上ある場合だけ採用する
+//! - 挨拶・署名など案件らしくないブロックは捨てる。分割は本文だけから決まるので同じメールなら常に同じ結果になる
+
+use lazy_static::lazy_static;
+use regex::Regex;
+
+use crate::extraction::extract_tanka;
+use crate::extraction::skills::extract_skill_sections;
+
+lazy_static! {
+    // 行頭の番号付き案件見出し: "【案件1】" / "■案件②" / "[案件 3]" / "案件No.4"
+    static ref SECTION_HEADING_RE: Regex = Regex::new(
+        r"^[【\[［■□◆◇●▼★☆<＜]?\s*案件\s*(?:No\.?|NO\.?|№)?\s*(?:[0-9０-９]{1,2}|[①-⑳]|[一二三四五六七八九十]{1,2})\s*(?:[】\]］>＞:：]|$|\s)"
+    )
+    .unwrap();
+}
+
+/// 区切り線とみなす文字
+const SEPARATOR_CHARS: &[char] = &[
+    '━', '─', '═', '=', '＝', '-', '－', '–', '—', '*', '＊', '■', '□', '◆', '◇', '☆', '★', '~',
+    '～',
+];
+/// 区切り線とみなす最小文字数
+const MIN_SEPARATOR_LEN: usize = 5;
+
+fn is_separator_line(line: &str) -> bool {
+    let trimmed = line.trim();
+    trimmed.chars().count() >= MIN_SEPARATOR_LEN
+        && trimmed.chars().all(|c| SEPARATOR_CHARS.contains(&c))
+}
+
+fn is_section_heading(line: &str) -> bool {
+    SECTION_HEADING_RE.is_match(line.trim())
+}
+
+/// 単価か必須/歓迎スキルが取れるブロックを案件とみなす
+fn looks_like_project(section: &str) -> bool {
+    if extract_tanka(section).is_some() {
+        return true;
+    }
+    let skills = extract_skill_sections(section);
+    !skills.required.is_empty() || !skills.preferred.is_empty()
+}
+
+/// 行頭オフセット付きで行を列挙する
+fn line_offsets(body_text: &str) -> impl Iterator<Item = (usize, &str)> {
+    body_text
+        .split_inclusive('\n')
+        .scan(0usize, |offset, line| {
+            let start = *offset;
+            *offset += line.len();
+            Some((start, line))
+        })
+}
+
+fn split_by_headings(body_text: &str) -> Vec<&str> {
+    let starts: Vec<usize> = line_offsets(body_text)
+        .filter(|(_, line)| is_section_heading(line))
+        .map(|(start, _)| start)
+        .collect();
+    if starts.len() < 2 {
+        return Vec::new();
+    }
+
+    starts
+        .iter()
+        .enumerate()
+        .map(|(i, &start)| {
+            let end = starts.get(i + 1).copied().unwrap_or(body_text.len());
+            body_text[start..end].trim()
+        })
+        .filter(|section| !section.is_empty())
+        .collect()
+}
+
+fn split_by_separators(body_text: &str) -> Vec<&str> {
+    let mut sections = Vec::new();
+    let mut start = 0usize;
+    for (offset, line) in line_offsets(body_text) {
+        if is_separator_line(line) {
+            sections.push(body_text[start..offset].trim());
+            start = offset + line.len();
+        }
+    }
+    sections.push(body_text[start..].trim());
+
+    sections
+        .into_iter()
+        .filter(|section| !section.is_empty() && looks_like_project(section))
+        .collect()
+}
+
+/// 複数案件メールなら案件ごとのセクションを返す（2 件以上）。単一案件なら空
+pub fn split_project_sections(body_text: &str) -> Vec<&str> {
+    let by_headings = split_by_headings(body_text);
+    if by_headings.len() >= 2 {
+        return by_headings;
+    }
+
+    let by_separators = split_by_separators(body_text);
+    if by_separators.len() >= 2 {
+        return by_separators;
+    }
+
+    Vec::new()
+}
+
+/// セクション先頭の見出し行（案件名の代わりに使う）。装飾と「案件N」は落とす
+pub fn section_title(section: &str) -> Option<String> {
+    let first = section
+        .lines()
+        .map(str::trim)
+        .find(|line| !line.is_empty())?;
+    let title = SECTION_HEADING_RE.replace(first, "");
+    let title = title
+        .trim_matches(|c: char| c.is_whitespace() || SEPARATOR_CHARS.contains(&c))
+        .trim_start_matches(['】', ']', '］', ':', '：'])
+        .trim();
+    (!title.is_empty() && title.chars().count() <= 80).then(|| title.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const HEADED: &str = "\
+お世話になっております。下記案件のご紹介です。
+
+【案件1】Java基幹システム刷新
+単価：60〜70万円
+勤務地：東京都
+
+【案件2】AWS移行支援
+単価：80万円
+【必須スキル】
+・AWS
+
+以上、よろしくお願いいたします。";
+
+    const SEPARATED: &str = "\
+各位
+
+━━━━━━━━━━
+Python データ基盤
+月額70〜80万円
+━━━━━━━━━━
+Go API 開発
+月額75万円
+━━━━━━━━━━
+株式会社サンプル 営業部";
+
+    #[test]
+    fn splits_on_numbered_headings() {
+        let sections = split_project_sections(HEADED);
+        assert_eq!(sections.len(), 2);
+        assert!(sections[0].starts_with("【案件1】"));
+        assert!(sections[0].contains("60〜70万円"));
+        assert!(!sections[0].contains("AWS移行"));
+        assert!(sections[1].contains("AWS"));
+        assert_eq!(
+            section_title(sections[0]).as_deref(),
+            Some("Java基幹システム刷新")
+        );
+    }
+
+    #[test]
+    fn splits_on_separator_lines_and_drops_non_project_blocks() {
+        let sections = split_project_sections(SEPARATED);
+        assert_eq!(sections.len(), 2);
+        assert!(sections[0].starts_with("Python"));
+        assert!(sections[1].starts_with("Go API"));
+    }
+
+    #[test]
+    fn single_project_emails_are_not_split() {
+        let single = "━━━━━━━━━━\n月額70〜90万円\n【必須スキル】\n・Rust\n━━━━━━━━━━\n署名";
+        assert!(split_project_sections(single).is_empty());
+        assert!(split_project_sections("【案件1】のみ\n単価：70万円").is_empty());
+    }
+}
@@ -0,0 +1,199 @@
+//! 案件メールの必須 / 歓迎スキル抽出（ルールベース）
+//!
+//! 【必須スキル】・■必須・尚可 などの見出しで区切られたセクションを探し、箇条書きの各行から
+//! `skill_normalizer` の辞書（エイリアス + 表記揺れ）に載っているスキルだけを拾う。
+//! 必須セクション内でも「あれば尚可」等が付いた行は歓迎側に回す。
+
+use crate::skill_normalizer::find_known_skills;
+
+/// 見出しとして扱う行頭の装飾
+const HEADING_MARKERS: &[char] = &[
+    '【', '■', '□', '◆', '◇', '▼', '▽', '★', '☆', '[', '［', '<', '＜', '《', '〈',
+];
+/// 見出しラベルの終端
+const LABEL_TERMINATORS: &[char] = &['】', ']', '］', '>', '＞', '》', '〉', ':', '：'];
+/// 箇条書きの行頭
+const BULLETS: &[char] = &[
+    '・', '-', '‐', '−', '*', '•', '●', '○', '◦', '▪', 'ー', '〇', '✓', '※',
+];
+/// 見出しラベルとみなす最大文字数（これより長い行は本文）
+const MAX_LABEL_CHARS: usize = 16;
+/// 1 セクションで読む最大行数
+const MAX_SECTION_LINES: usize = 30;
+
+const REQUIRED_WORDS: &[&str] = &["必須", "必要スキル", "応募資格", "must"];
+const PREFERRED_WORDS: &[&str] = &[
+    "歓迎",
+    "尚可",
+    "なお可",
+    "あれば",
+    "望ましい",
+    "優遇",
+    "want",
+];
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SkillSections {
+    /// 必須スキル（正規形、出現順）
+    pub required: Vec<String>,
+    /// 歓迎スキル（正規形、出現順。必須と重複するものは除く）
+    pub preferred: Vec<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum SectionKind {
+    Required,
+    Preferred,
+}
+
+fn classify(label: &str) -> Option<SectionKind> {
+    let label = label.to_lowercase();
+    // 「必須ではないが歓迎」のような見出しは歓迎側
+    if PREFERRED_WORDS.iter().any(|w| label.contains(w)) {
+        Some(SectionKind::Preferred)
+    } else if REQUIRED_WORDS.iter().any(|w| label.contains(w)) {
+        Some(SectionKind::Required)
+    } else {
+        None
+    }
+}
+
+/// 見出し行なら (ラベル, 同じ行に続く本文) を返す
+fn split_heading(line: &str) -> Option<(&str, &str)> {
+    let marked = line.starts_with(HEADING_MARKERS);
+    let body = line.trim_start_matches(HEADING_MARKERS).trim_start();
+    let (label, rest) = match body.find(LABEL_TERMINATORS) {
+        Some(pos) => {
+            let terminator_len = body[pos..].chars().next().map_or(0, char::len_utf8);
+            (&body[..pos], &body[pos + terminator_len..])
+        }
+        // 装飾付きで終端の無い短い行（"■必須スキル"）も見出し
+        None if marked => (body, ""),
+        None => return None,
+    };
+    let label = label.trim_end_matches(HEADING_MARKERS).trim();
+    if label.is_empty() || label.chars().count() > MAX_LABEL_CHARS {
+        return None;
+    }
+    Some((label, rest.trim_start_matches(LABEL_TERMINATORS).trim()))
+}
+
+fn push_unique(list: &mut Vec<String>, skills: Vec<String>) {
+    for skill in skills {
+        if !list.contains(&skill) {
+            list.push(skill);
+        }
+    }
+}
+
+/// 本文から必須 / 歓迎スキルを抽出する（該当セクションが無ければ空）
+pub fn extract_skill_sections(body_text: &str) -> SkillSections {
+    let mut sections = SkillSections::default();
+    let mut current: Option<SectionKind> = None;
+    let mut section_lines = 0;
+
+    for raw_line in body_text.lines() {
+        let line = raw_line.trim();
+        if line.is_empty() {
+            // 見出し直後の空行は読み飛ばし、本文の後の空行でセクションを閉じる
+            if section_lines > 0 {
+                current = None;
+            }
+            continue;
+        }
+
+        if !line.starts_with(BULLETS) {
+            if let Some((label, rest)) = split_heading(line) {
+                current = classify(label);
+                section_lines = 0;
+                if let (Some(kind), false) = (current, rest.is_empty()) {
+                    collect_line(&mut sections, kind, rest);
+                    section_lines += 1;
+                }
+                continue;
+            }
+            if line.starts_with(HEADING_MARKERS) {
+                current = None;
+                continue;
+            }
+        }
+
+        let Some(kind) = current else {
+            continue;
+        };
+        collect_line(&mut sections, kind, line);
+        section_lines += 1;
+        if section_lines >= MAX_SECTION_LINES {
+            current = None;
+        }
+    }
+
+    sections
+        .preferred
+        .retain(|skill| !sections.required.contains(skill));
+    sections
+}
+
+fn collect_line(sections: &mut SkillSections, kind: SectionKind, line: &str) {
+    let skills = find_known_skills(line.trim_start_matches(BULLETS));
+    let kind = match kind {
+        SectionKind::Required if classify(line) == Some(SectionKind::Preferred) => {
+            SectionKind::Preferred
+        }
+        kind => kind,
+    };
+    match kind {
+        SectionKind::Required => push_unique(&mut sections.required, skills),
+        SectionKind::Preferred => push_unique(&mut sections.preferred, skills),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn splits_required_and_preferred_sections() {
+        let body = "\
+【案件名】ECサイトリニューアル
+【必須スキル】
+・Java（Spring Boot）での開発経験3年以上
+・ＳＱＬ（PostgreSQL）
+・AWSの利用経験（あれば尚可）
+
+【尚可スキル】
+・Docker / k8s
+・Javaでのテストコード作成
+【単価】80万円
+・Python";
+        let sections = extract_skill_sections(body);
+        assert_eq!(sections.required, vec!["java", "spring", "postgresql"]);
+        assert_eq!(sections.preferred, vec!["aws", "docker", "kubernetes"]);
+    }
+
+    #[test]
+    fn reads_inline_headings_and_ignores_prose() {
+        let body = "\
+Reactのフロント案件です。
+■必須：TypeScript、React
+◆歓迎: Next.js
+応募資格：なし";
+        let sections = extract_skill_sections(body);
+        assert_eq!(sections.required, vec!["typescript", "react"]);
+        assert_eq!(sections.preferred, vec!["nextjs"]);
+
+        assert_eq!(
+            extract_skill_sections("JavaとAWSの案件です"),
+            SkillSections::default()
+        );
+    }
+
+    #[test]
+    fn short_aliases_in_prose_are_not_required_skills() {
+        let sections = extract_skill_sections(
+            "【必須スキル】\n・PG経験3年以上\n・Java\n【尚可】\n・ES経験\n・Go",
+        );
+        assert_eq!(sections.required, vec!["java"]);
+        assert_eq!(sections.preferred, vec!["golang"]);
+    }
+}
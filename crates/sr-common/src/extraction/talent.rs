@@ -0,0 +1,650 @@
+//! 人材メール（ses.jinzai_emails）の抽出と typed [`Talent`] への射影
+//!
+//! - 案件側と同じく Tier 充足度で品質を数え、足りなければ LLM を推奨する
+//! - 「最寄駅：」「稼働：」などのラベル行を優先して読み、本文全体への当てずっぽうは避ける
+//! - 射影は LLM 優先・Rust 補完で、項目ごとの由来を [`FieldSources`] に残す
+
+use chrono::{DateTime, Datelike, Utc};
+use lazy_static::lazy_static;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+use crate::corrections::{
+    contract_type::{correct_gender, correct_talent_contract_type},
+    english_skill::correct_english_skill,
+    flow_depth::correct_talent_flow_depth,
+    japanese_skill::correct_japanese_skill,
+    remote_onsite::correct_remote_onsite,
+    station::normalize_station,
+    todofuken::{correct_todofuken, correct_work_area},
+};
+use crate::date::normalize_start_date;
+use crate::extraction::projection::{
+    llm_f64, llm_i32, llm_skills, llm_str, llm_u32, FieldOrigin, FieldSources, Projector,
+};
+use crate::extraction::{
+    decide_recommended_method, extract_remote_onsite, extract_start_date_raw, extract_tanka,
+    extract_work_todofuken, normalize_skills, ExtractionQuality, RecommendedDecision,
+};
+use crate::queue::{EntityType, ExtractionJob, FinalMethod, QueueStatus, RecommendedMethod};
+use crate::skill_normalizer::{find_known_skills, normalize_skills_vec};
+use crate::Talent;
+
+/// sr-extractor が人材メール本文から拾う項目
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub struct TalentPartialFields {
+    pub talent_name: Option<String>,
+    /// 希望単価の下限（万円）
+    pub desired_price_min: Option<u32>,
+    /// 「中央線 三鷹」など。射影時に `normalize_station` を通す
+    pub nearest_station_raw: Option<String>,
+    pub residential_todofuken: Option<String>,
+    /// 稼働開始の生テキスト。射影時に受信日基準で `normalize_start_date` を通す
+    pub availability_raw: Option<String>,
+    pub age: Option<i32>,
+    pub birth_year: Option<i32>,
+    pub nationality: Option<String>,
+    pub gender: Option<String>,
+    pub japanese_skill: Option<String>,
+    pub english_skill: Option<String>,
+    /// 所属（正社員/契約社員/直個人）
+    pub contract_type: Option<String>,
+    /// 商流（SPONTO直/1社先/2社先/3社先以上）
+    pub flow_depth: Option<String>,
+    pub desired_remote_onsite: Option<String>,
+    pub skills_keywords: Option<Vec<String>>,
+    pub experience_years: Option<f32>,
+}
+
+/// 人材メール1通分の抽出結果
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct TalentExtractorOutput {
+    pub partial: TalentPartialFields,
+    pub quality: ExtractionQuality,
+    pub decision: RecommendedDecision,
+}
+
+lazy_static! {
+    static ref NAME_RE: Regex =
+        Regex::new(r"(?:氏名|名前|イニシャル)\s*[:：】\]]\s*([^\s（(【\n]+)").unwrap();
+    static ref STATION_RE: Regex =
+        Regex::new(r"最寄(?:り)?(?:駅)?\s*[:：】\]]?\s*([^\n、,，。（(／/]+)").unwrap();
+    static ref AGE_RE: Regex = Regex::new(r"(\d{2})\s*(?:歳|才)").unwrap();
+    static ref AGE_LABEL_RE: Regex = Regex::new(r"年齢\s*[:：】\]]\s*(\d{2})").unwrap();
+    static ref BIRTH_YEAR_RE: Regex = Regex::new(
+        r"(?:生年(?:月日)?\s*[:：】\]]\s*((?:19|20)\d{2}))|(?:((?:19|20)\d{2})\s*年\s*(?:\d{1,2}\s*月\s*)?(?:\d{1,2}\s*日\s*)?生)"
+    )
+    .unwrap();
+    static ref NATIONALITY_RE: Regex =
+        Regex::new(r"国籍\s*[:：】\]]\s*([^\s、,，。（(\n]+)").unwrap();
+    static ref GENDER_RE: Regex = Regex::new(r"(男性|女性)").unwrap();
+    static ref JLPT_RE: Regex = Regex::new(r"(?i)JLPT\s*(N[1-5])").unwrap();
+    static ref EXPERIENCE_RE: Regex = Regex::new(
+        r"経験(?:年数)?\s*[:：】\]]?\s*(?:約|通算)?\s*(\d{1,2}(?:\.\d)?)\s*年"
+    )
+    .unwrap();
+    static ref SHA_SAKI_RE: Regex = Regex::new(r"[1-3１-３]\s*社先").unwrap();
+    static ref OWN_EMPLOYEE_RE: Regex =
+        Regex::new(r"弊社(?:正社員|社員|プロパー|契約社員)|自社(?:正社員|社員)").unwrap();
+    static ref PARTNER_RE: Regex = Regex::new(
+        r"(?i)(?:弊社|一社先|1社先)?(?:BP|協力会社|パートナー)(?:社員|要員|個人事業主)?|弊社個人事業主|弊社フリーランス"
+    )
+    .unwrap();
+    static ref CONTRACT_RE: Regex =
+        Regex::new(r"(正社員|契約社員|個人事業主|フリーランス)").unwrap();
+}
+
+/// 稼働開始日とみなすラベル
+const AVAILABILITY_LABELS: &[&str] = &["稼働", "参画", "入場", "開始", "着任"];
+/// 希望単価とみなすラベル
+const PRICE_LABELS: &[&str] = &["単価", "希望", "金額", "報酬"];
+/// 居住地とみなすラベル
+const RESIDENCE_LABELS: &[&str] = &["居住", "在住", "住まい", "住所", "最寄"];
+/// 勤務形態の希望とみなすラベル
+const REMOTE_LABELS: &[&str] = &["リモート", "在宅", "出社", "常駐", "勤務形態"];
+
+/// ラベルを含む行を上から順に返す
+fn labeled_lines<'a>(body_text: &'a str, labels: &'a [&str]) -> impl Iterator<Item = &'a str> {
+    body_text
+        .lines()
+        .map(str::trim)
+        .filter(move |line| labels.iter().any(|label| line.contains(label)))
+}
+
+/// 行内の `keyword` より後ろの本文
+fn after_keyword<'a>(line: &'a str, keyword: &str) -> Option<&'a str> {
+    line.find(keyword).map(|pos| {
+        line[pos + keyword.len()..].trim_start_matches([':', '：', '】', ']', ' ', '\u{3000}'])
+    })
+}
+
+/// 本文から人材の Tier1/2 相当の項目をまとめて抽出
+pub fn extract_talent_partial_fields(body_text: &str) -> TalentPartialFields {
+    let mut partial = TalentPartialFields {
+        talent_name: NAME_RE
+            .captures(body_text)
+            .map(|caps| caps[1].trim().to_string()),
+        desired_price_min: extract_desired_price_min(body_text),
+        nearest_station_raw: extract_nearest_station_raw(body_text),
+        residential_todofuken: labeled_lines(body_text, RESIDENCE_LABELS)
+            .find_map(extract_work_todofuken),
+        availability_raw: extract_availability_raw(body_text),
+        age: extract_age(body_text),
+        birth_year: extract_birth_year(body_text),
+        nationality: extract_nationality(body_text),
+        gender: GENDER_RE
+            .captures(body_text)
+            .and_then(|caps| correct_gender(&caps[1])),
+        japanese_skill: extract_japanese_skill(body_text),
+        english_skill: extract_english_skill(body_text),
+        contract_type: CONTRACT_RE
+            .captures(body_text)
+            .and_then(|caps| correct_talent_contract_type(&caps[1], false)),
+        flow_depth: extract_talent_flow_depth(body_text),
+        desired_remote_onsite: labeled_lines(body_text, REMOTE_LABELS)
+            .find_map(extract_remote_onsite),
+        skills_keywords: None,
+        experience_years: EXPERIENCE_RE
+            .captures(body_text)
+            .and_then(|caps| caps[1].parse::<f32>().ok())
+            .filter(|years| (0.0..=50.0).contains(years)),
+    };
+
+    let skills = find_known_skills(body_text);
+    partial.skills_keywords = Some(skills).filter(|s| !s.is_empty());
+
+    partial
+}
+
+/// 希望単価の下限（万円）。ラベル行を優先し、無ければ本文全体から拾う
+pub fn extract_desired_price_min(body_text: &str) -> Option<u32> {
+    labeled_lines(body_text, PRICE_LABELS)
+        .find_map(extract_tanka)
+        .or_else(|| extract_tanka(body_text))
+        .map(|(min, _)| min)
+}
+
+/// 「最寄駅：JR中央線 三鷹駅」→「三鷹駅」（路線名は落とす）
+pub fn extract_nearest_station_raw(body_text: &str) -> Option<String> {
+    let caps = STATION_RE.captures(body_text)?;
+    let tokens: Vec<&str> = caps[1]
+        .split([' ', '\u{3000}'])
+        .filter(|token| !token.is_empty())
+        .collect();
+    // 「三鷹駅 徒歩10分」のように駅名の後ろに続く補足は捨てる
+    let station = tokens
+        .iter()
+        .find_map(|token| token.find('駅').map(|pos| &token[..pos + '駅'.len_utf8()]))
+        .or_else(|| tokens.last().copied())?;
+    normalize_station(station).filter(|s| s != "駅")
+}
+
+/// 稼働開始の生テキスト。ラベル行を優先し、本文全体からは即日系だけ拾う
+pub fn extract_availability_raw(body_text: &str) -> Option<String> {
+    labeled_lines(body_text, AVAILABILITY_LABELS)
+        .find_map(extract_start_date_raw)
+        .or_else(|| extract_start_date_raw(body_text).filter(|raw| raw == "即日"))
+}
+
+fn extract_age(body_text: &str) -> Option<i32> {
+    AGE_LABEL_RE
+        .captures(body_text)
+        .or_else(|| AGE_RE.captures(body_text))
+        .and_then(|caps| caps[1].parse::<i32>().ok())
+        .filter(|age| (15..=80).contains(age))
+}
+
+fn extract_birth_year(body_text: &str) -> Option<i32> {
+    let caps = BIRTH_YEAR_RE.captures(body_text)?;
+    caps.get(1)
+        .or_else(|| caps.get(2))
+        .and_then(|m| m.as_str().parse().ok())
+}
+
+fn extract_nationality(body_text: &str) -> Option<String> {
+    if let Some(caps) = NATIONALITY_RE.captures(body_text) {
+        return Some(caps[1].trim().to_string());
+    }
+    if body_text.contains("外国籍") {
+        return Some("外国籍".to_string());
+    }
+    if body_text.contains("日本国籍") || body_text.contains("日本人") {
+        return Some("日本".to_string());
+    }
+    None
+}
+
+fn extract_japanese_skill(body_text: &str) -> Option<String> {
+    labeled_lines(body_text, &["日本語"])
+        .find_map(|line| after_keyword(line, "日本語").and_then(correct_japanese_skill))
+        .or_else(|| {
+            JLPT_RE
+                .captures(body_text)
+                .and_then(|caps| correct_japanese_skill(&caps[1]))
+        })
+}
+
+fn extract_english_skill(body_text: &str) -> Option<String> {
+    labeled_lines(body_text, &["英語"])
+        .find_map(|line| after_keyword(line, "英語").and_then(correct_english_skill))
+}
+
+/// 所属・商流の記述から人材商流 ENUM を推定する
+pub fn extract_talent_flow_depth(body_text: &str) -> Option<String> {
+    if let Some(mat) = SHA_SAKI_RE.find(body_text) {
+        if let Some(depth) = correct_talent_flow_depth(mat.as_str()) {
+            return Some(depth);
+        }
+        return Some("1社先".to_string());
+    }
+    if OWN_EMPLOYEE_RE.is_match(body_text) {
+        return Some("1社先".to_string());
+    }
+    if PARTNER_RE.is_match(body_text) {
+        return Some("2社先".to_string());
+    }
+    None
+}
+
+/// 人材メールから Tier1/Tier2 を抽出し、品質判定まで含めた結果を返す
+pub fn extract_all_talent_fields(body_text: &str, subject: Option<&str>) -> TalentExtractorOutput {
+    let mut partial = extract_talent_partial_fields(body_text);
+    if partial.talent_name.is_none() {
+        partial.talent_name = subject
+            .map(str::trim)
+            .filter(|s| !s.is_empty())
+            .map(str::to_string);
+    }
+    normalize_skills(&mut partial.skills_keywords);
+
+    let (quality, decision) = evaluate_talent_quality(&partial);
+
+    TalentExtractorOutput {
+        partial,
+        quality,
+        decision,
+    }
+}
+
+/// Tier 充足度をカウント（単価・稼働開始・最寄駅・スキルは KO / スコアに直結するため Tier1）
+pub fn calculate_talent_quality(partial: &TalentPartialFields) -> ExtractionQuality {
+    let tier1 = [
+        partial.desired_price_min.is_some(),
+        partial.availability_raw.is_some(),
+        partial.nearest_station_raw.is_some(),
+        partial
+            .skills_keywords
+            .as_ref()
+            .is_some_and(|s| !s.is_empty()),
+    ];
+    let tier2 = [
+        partial.experience_years.is_some(),
+        partial.age.is_some() || partial.birth_year.is_some(),
+        partial.nationality.is_some(),
+        partial.japanese_skill.is_some(),
+        partial.flow_depth.is_some(),
+    ];
+
+    ExtractionQuality {
+        tier1_extracted: tier1.iter().filter(|&&x| x).count(),
+        tier1_total: tier1.len(),
+        tier2_extracted: tier2.iter().filter(|&&x| x).count(),
+        tier2_total: tier2.len(),
+        low_confidence_fields: Vec::new(),
+        llm_recommended: false,
+        reason: String::new(),
+    }
+}
+
+/// quality と decision をまとめて生成（判定基準は案件と共通）
+pub fn evaluate_talent_quality(
+    partial: &TalentPartialFields,
+) -> (ExtractionQuality, RecommendedDecision) {
+    let mut quality = calculate_talent_quality(partial);
+    let decision = decide_recommended_method(&quality);
+    quality.llm_recommended = decision.recommended_method == RecommendedMethod::LlmRecommended;
+    quality.reason = decision.reason.clone();
+    (quality, decision)
+}
+
+/// 人材抽出ジョブ 1 件分の射影結果（ses.talents_enum の 1 行）
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct TalentProjection {
+    pub message_id: String,
+    pub extraction_job_id: u64,
+    pub final_method: FinalMethod,
+    pub talent_name: String,
+    pub talent: Talent,
+    pub field_sources: FieldSources,
+    pub received_at: DateTime<Utc>,
+    pub source_updated_at: DateTime<Utc>,
+    pub requires_manual_review: bool,
+    pub manual_review_reason: Option<String>,
+}
+
+/// 完了済みの人材ジョブを [`Talent`] に射影する（対象条件は案件の射影と同じ）
+pub fn project_talent_extraction_job(
+    job: &ExtractionJob,
+    body_text: &str,
+    received_at: DateTime<Utc>,
+) -> Option<TalentProjection> {
+    if job.status != QueueStatus::Completed || job.entity_type != EntityType::Talent {
+        return None;
+    }
+    let final_method = job.final_method.clone()?;
+
+    let (rust, llm) = match final_method {
+        FinalMethod::RustCompleted => {
+            let rust = job
+                .partial_fields
+                .clone()
+                .and_then(|value| serde_json::from_value::<TalentPartialFields>(value).ok())
+                .unwrap_or_else(|| reextract(job, body_text));
+            (rust, None)
+        }
+        FinalMethod::LlmCompleted => (
+            reextract(job, body_text),
+            job.partial_fields.as_ref().and_then(Value::as_object),
+        ),
+        FinalMethod::ManualReview => return None,
+    };
+
+    let mut projector = Projector {
+        job_id: job.id,
+        final_method: final_method.as_str(),
+        sources: FieldSources::new(),
+    };
+    let talent = build_talent(&mut projector, &rust, llm, received_at);
+
+    let talent_name = projector
+        .pick(
+            "talent_name",
+            llm_str(llm, "talent_name"),
+            rust.talent_name.clone(),
+        )
+        .unwrap_or_else(|| job.email_subject.clone());
+
+    Some(TalentProjection {
+        message_id: job.message_id.clone(),
+        extraction_job_id: job.id,
+        final_method,
+        talent_name,
+        talent,
+        field_sources: projector.sources,
+        received_at,
+        source_updated_at: job.updated_at,
+        requires_manual_review: job.requires_manual_review,
+        manual_review_reason: job.manual_review_reason.clone(),
+    })
+}
+
+fn reextract(job: &ExtractionJob, body_text: &str) -> TalentPartialFields {
+    extract_all_talent_fields(body_text, Some(&job.email_subject)).partial
+}
+
+fn build_talent(
+    projector: &mut Projector,
+    rust: &TalentPartialFields,
+    llm: Option<&Map<String, Value>>,
+    received_at: DateTime<Utc>,
+) -> Talent {
+    let residential_todofuken = projector.pick(
+        "residential_todofuken",
+        llm_str(llm, "residential_todofuken").and_then(|v| correct_todofuken(&v)),
+        rust.residential_todofuken
+            .as_deref()
+            .and_then(correct_todofuken),
+    );
+    let residential_area = residential_todofuken.as_deref().and_then(correct_work_area);
+    if residential_area.is_some() {
+        projector.record("residential_area", FieldOrigin::Derived);
+    }
+
+    // 生年が無ければ受信時点の年齢から逆算する
+    let mut birth_year = projector.pick("birth_year", llm_i32(llm, "birth_year"), rust.birth_year);
+    if birth_year.is_none() {
+        let age = llm_i32(llm, "age").or(rust.age);
+        birth_year = age
+            .filter(|age| (15..=80).contains(age))
+            .map(|age| received_at.year() - age);
+        if birth_year.is_some() {
+            projector.record("birth_year", FieldOrigin::Derived);
+        }
+    }
+
+    Talent {
+        id: None,
+        residential_todofuken,
+        residential_area,
+        desired_price_min: projector.pick(
+            "desired_price_min",
+            llm_u32(llm, "desired_price_min"),
+            rust.desired_price_min,
+        ),
+        possessed_skills_keywords: projector
+            .pick(
+                "possessed_skills_keywords",
+                llm_skills(llm, "possessed_skills_keywords")
+                    .or_else(|| llm_skills(llm, "skills_keywords")),
+                rust.skills_keywords
+                    .as_deref()
+                    .map(normalize_skills_vec)
+                    .filter(|skills| !skills.is_empty()),
+            )
+            .unwrap_or_default(),
+        // 保有側なので切り捨てる
+        min_experience_years: projector.pick(
+            "min_experience_years",
+            llm_f64(llm, "experience_years").map(|v| v.floor() as i32),
+            rust.experience_years.map(|v| v.floor() as i32),
+        ),
+        japanese_skill: projector.pick(
+            "japanese_skill",
+            llm_str(llm, "japanese_skill").and_then(|v| correct_japanese_skill(&v)),
+            rust.japanese_skill.clone(),
+        ),
+        english_skill: projector.pick(
+            "english_skill",
+            llm_str(llm, "english_skill").and_then(|v| correct_english_skill(&v)),
+            rust.english_skill.clone(),
+        ),
+        gender: projector.pick(
+            "gender",
+            llm_str(llm, "gender").and_then(|v| correct_gender(&v)),
+            rust.gender.clone(),
+        ),
+        primary_contract_type: projector.pick(
+            "primary_contract_type",
+            llm_str(llm, "contract_type").and_then(|v| correct_talent_contract_type(&v, false)),
+            rust.contract_type.clone(),
+        ),
+        secondary_contract_type: None,
+        flow_depth: projector.pick(
+            "flow_depth",
+            llm_str(llm, "flow_depth").and_then(|v| correct_talent_flow_depth(&v)),
+            rust.flow_depth.clone(),
+        ),
+        nearest_station: projector.pick(
+            "nearest_station",
+            llm_str(llm, "nearest_station").and_then(|v| normalize_station(&v)),
+            rust.nearest_station_raw
+                .as_deref()
+                .and_then(normalize_station),
+        ),
+        desired_remote_onsite: projector.pick(
+            "desired_remote_onsite",
+            llm_str(llm, "desired_remote_onsite").and_then(|v| correct_remote_onsite(&v)),
+            rust.desired_remote_onsite
+                .as_deref()
+                .and_then(correct_remote_onsite),
+        ),
+        ng_keywords: None,
+        birth_year,
+        nationality: projector.pick(
+            "nationality",
+            llm_str(llm, "nationality"),
+            rust.nationality.clone(),
+        ),
+        availability_date: projector.pick(
+            "availability_date",
+            llm_str(llm, "availability_date")
+                .or_else(|| llm_str(llm, "availability_raw"))
+                .and_then(|raw| normalize_start_date(&raw, received_at)),
+            rust.availability_raw
+                .as_deref()
+                .and_then(|raw| normalize_start_date(raw, received_at)),
+        ),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::date::StartDatePrecision;
+    use chrono::TimeZone;
+    use serde_json::json;
+
+    const BODY: &str = "\
+【氏名】T.K（32歳 男性）
+【国籍】中国
+【最寄駅】JR中央線 三鷹駅
+【稼働】即日
+【単価】65万円〜
+【所属】弊社正社員
+【日本語】ビジネスレベル
+【経験年数】7年
+【スキル】Java / Spring Boot / AWS
+リモート併用希望";
+
+    #[test]
+    fn extracts_labeled_talent_fields() {
+        let partial = extract_talent_partial_fields(BODY);
+
+        assert_eq!(partial.talent_name.as_deref(), Some("T.K"));
+        assert_eq!(partial.age, Some(32));
+        assert_eq!(partial.gender.as_deref(), Some("男性"));
+        assert_eq!(partial.nationality.as_deref(), Some("中国"));
+        assert_eq!(partial.nearest_station_raw.as_deref(), Some("三鷹駅"));
+        assert_eq!(partial.availability_raw.as_deref(), Some("即日"));
+        assert_eq!(partial.desired_price_min, Some(65));
+        assert_eq!(partial.contract_type.as_deref(), Some("正社員"));
+        assert_eq!(partial.flow_depth.as_deref(), Some("1社先"));
+        assert_eq!(partial.japanese_skill.as_deref(), Some("N1"));
+        assert_eq!(partial.experience_years, Some(7.0));
+        assert_eq!(
+            partial.desired_remote_onsite.as_deref(),
+            Some("リモート併用")
+        );
+        assert!(partial
+            .skills_keywords
+            .as_ref()
+            .is_some_and(|skills| skills.iter().any(|s| s == "java")));
+    }
+
+    #[test]
+    fn programmer_abbreviation_is_not_read_as_postgresql() {
+        let partial =
+            extract_talent_partial_fields("【氏名】K.S\n・PG経験5年\n【スキル】Java / AWS");
+        assert_eq!(
+            partial.skills_keywords,
+            Some(vec!["java".to_string(), "aws".to_string()])
+        );
+    }
+
+    #[test]
+    fn quality_routes_incomplete_talents_to_llm() {
+        let output = extract_all_talent_fields(BODY, Some("人材ご紹介"));
+        assert_eq!(output.quality.tier1_extracted, output.quality.tier1_total);
+        assert_eq!(
+            output.decision.recommended_method,
+            RecommendedMethod::RustRecommended
+        );
+
+        let sparse = extract_all_talent_fields("Javaエンジニアのご紹介です", Some("人材"));
+        assert!(sparse.quality.tier1_extracted < sparse.quality.tier1_total);
+        assert_eq!(
+            sparse.decision.recommended_method,
+            RecommendedMethod::LlmRecommended
+        );
+        assert_eq!(sparse.partial.talent_name.as_deref(), Some("人材"));
+    }
+
+    #[test]
+    fn flow_depth_reads_affiliation_wording() {
+        assert_eq!(
+            extract_talent_flow_depth("所属：2社先個人"),
+            Some("2社先".to_string())
+        );
+        assert_eq!(
+            extract_talent_flow_depth("弊社BP社員になります"),
+            Some("2社先".to_string())
+        );
+        assert_eq!(extract_talent_flow_depth("特記事項なし"), None);
+    }
+
+    fn completed_talent_job(
+        final_method: FinalMethod,
+        partial_fields: Option<Value>,
+    ) -> ExtractionJob {
+        let received_at = Utc.with_ymd_and_hms(2025, 1, 10, 0, 0, 0).unwrap();
+        let mut job = ExtractionJob::new("jinzai-1", "Java人材", received_at, "hash");
+        job.id = 11;
+        job.entity_type = EntityType::Talent;
+        job.status = QueueStatus::Completed;
+        job.final_method = Some(final_method);
+        job.partial_fields = partial_fields;
+        job
+    }
+
+    #[test]
+    fn projects_talent_with_llm_priority_and_derived_birth_year() {
+        let job = completed_talent_job(
+            FinalMethod::LlmCompleted,
+            Some(json!({
+                "desired_price_min": 70,
+                "nearest_station": "吉祥寺",
+                "english_skill": "日常会話",
+            })),
+        );
+
+        let projection = project_talent_extraction_job(&job, BODY, job.email_received_at).unwrap();
+        let talent = &projection.talent;
+
+        assert_eq!(projection.talent_name, "T.K");
+        assert_eq!(talent.desired_price_min, Some(70));
+        assert_eq!(talent.nearest_station.as_deref(), Some("吉祥寺駅"));
+        assert_eq!(talent.english_skill.as_deref(), Some("会話"));
+        assert_eq!(talent.birth_year, Some(1993));
+        assert_eq!(talent.min_experience_years, Some(7));
+        assert_eq!(
+            talent
+                .availability_date
+                .as_ref()
+                .map(|d| d.precision.clone()),
+            Some(StartDatePrecision::Asap)
+        );
+
+        let sources = &projection.field_sources;
+        assert_eq!(sources["desired_price_min"].origin, FieldOrigin::Llm);
+        assert_eq!(sources["nationality"].origin, FieldOrigin::Rust);
+        assert_eq!(sources["birth_year"].origin, FieldOrigin::Derived);
+    }
+
+    #[test]
+    fn entity_type_gates_both_projections() {
+        let job = completed_talent_job(FinalMethod::RustCompleted, None);
+        assert!(crate::extraction::projection::project_extraction_job(
+            &job,
+            BODY,
+            job.email_received_at
+        )
+        .is_none());
+
+        let mut project_job = job.clone();
+        project_job.entity_type = EntityType::Project;
+        assert!(project_talent_extraction_job(&project_job, BODY, job.email_received_at).is_none());
+        assert!(project_talent_extraction_job(&job, BODY, job.email_received_at).is_some());
+    }
+}
@@ -3,11 +3,13 @@ pub mod calculation;
 pub mod corrections;
 pub mod date;
 pub mod db;
+pub mod evaluation;
 pub mod extraction;
 pub mod logging;
 pub mod matching;
 pub mod normalize;
 pub mod queue;
+pub mod rules;
 pub mod run_id;
 pub mod schema;
 pub mod skill_normalizer;
@@ -48,6 +50,10 @@ pub struct Project {
     pub age_limit_upper: Option<i32>,
     pub foreigner_allowed: Option<bool>,
     pub start_date: Option<NormalizedStartDate>,
+    /// 元メール送信元のドメイン（クライアントプロファイルの照合用）
+    pub sender_domain: Option<String>,
+    /// 元メールの送信者名（会社名を含むことが多い。クライアントプロファイルの照合用）
+    pub client_name: Option<String>,
 }
 
 #[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
@@ -0,0 +1,394 @@
+//! クライアント（エンド / 取引先）ごとの KO ルール上書き
+//!
+//! ルールファイルの `[[clients]]` で定義する。案件の送信元ドメインまたはクライアント名で
+//! プロファイルを引き当て、KO 判定の前に案件側の値を上書きし、追加の KO ルールを評価する。
+//! 適用したプロファイル名は KO 理由と `KnockoutResultV2::client_profile` に残る。
+
+use std::borrow::Cow;
+
+use chrono::Datelike;
+use serde::{Deserialize, Serialize};
+
+use crate::corrections::flow_depth::parse_flow_limit;
+use crate::skill_normalizer::normalize_skill_set;
+use crate::{Project, Talent};
+
+use super::ko_unified::KoDecision;
+
+/// 追加ルールの判定をまとめた KO チェック名
+pub const CLIENT_RULE_CHECK: &str = "client_rule";
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ClientProfile {
+    /// KO 理由に表示する名前（一意）
+    pub name: String,
+    /// 送信元ドメイン（サブドメインも一致）
+    #[serde(default)]
+    pub sender_domains: Vec<String>,
+    /// クライアント名（送信者名に部分一致）
+    #[serde(default)]
+    pub client_names: Vec<String>,
+    #[serde(default)]
+    pub overrides: ClientOverrides,
+    #[serde(default)]
+    pub rules: Vec<ClientRule>,
+}
+
+/// 案件側の値の上書き（指定した項目のみ。抽出値より優先）
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ClientOverrides {
+    pub foreigner_allowed: Option<bool>,
+    /// ses.jinzai_flow_limit_enum の値（SPONTO直人材 / SPONTO一社先まで / 商流制限なし）
+    pub jinzai_flow_limit: Option<String>,
+    pub is_kojin_ok: Option<bool>,
+    pub age_limit_lower: Option<i32>,
+    pub age_limit_upper: Option<i32>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ClientRuleSeverity {
+    Hard,
+    Soft,
+}
+
+/// 追加の KO ルール。条件を満たさない人材を `severity` で KO にする（情報不足は SoftKo）
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ClientRule {
+    pub name: String,
+    pub severity: ClientRuleSeverity,
+    pub condition: ClientRuleCondition,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case", deny_unknown_fields)]
+pub enum ClientRuleCondition {
+    /// 年齢の上限
+    MaxAge { years: i32 },
+    /// 経験年数の下限
+    MinExperienceYears { years: i32 },
+    /// 全て保有している必要があるスキル
+    RequiredSkills { skills: Vec<String> },
+    /// 居住都道府県の許可リスト
+    ResidentialTodofuken { values: Vec<String> },
+}
+
+/// 条件 1 つの判定結果
+enum RuleCheck {
+    Satisfied,
+    Violated(String),
+    Unknown(String),
+}
+
+impl ClientRuleCondition {
+    fn check(&self, talent: &Talent) -> RuleCheck {
+        match self {
+            Self::MaxAge { years } => match talent.birth_year {
+                Some(birth_year) => {
+                    let age = chrono::Utc::now().year() - birth_year;
+                    if age > *years {
+                        RuleCheck::Violated(format!("年齢 {age} > {years}"))
+                    } else {
+                        RuleCheck::Satisfied
+                    }
+                }
+                None => RuleCheck::Unknown("生年情報不足".into()),
+            },
+            Self::MinExperienceYears { years } => match talent.min_experience_years {
+                Some(exp) if exp < *years => {
+                    RuleCheck::Violated(format!("経験年数 {exp} < {years}"))
+                }
+                Some(_) => RuleCheck::Satisfied,
+                None => RuleCheck::Unknown("経験年数情報不足".into()),
+            },
+            Self::RequiredSkills { skills } => {
+                let possessed = normalize_skill_set(&talent.possessed_skills_keywords);
+                if possessed.is_empty() {
+                    return RuleCheck::Unknown("人材スキル情報不足".into());
+                }
+                let mut missing: Vec<_> = normalize_skill_set(skills)
+                    .difference(&possessed)
+                    .cloned()
+                    .collect();
+                missing.sort();
+                if missing.is_empty() {
+                    RuleCheck::Satisfied
+                } else {
+                    RuleCheck::Violated(format!("不足スキル: {}", missing.join(", ")))
+                }
+            }
+            Self::ResidentialTodofuken { values } => {
+                match talent.residential_todofuken.as_deref() {
+                    Some(pref) if values.iter().any(|v| v == pref) => RuleCheck::Satisfied,
+                    Some(pref) => RuleCheck::Violated(format!("居住地 {pref} は対象外")),
+                    None => RuleCheck::Unknown("居住地情報不足".into()),
+                }
+            }
+        }
+    }
+}
+
+impl ClientProfile {
+    /// 送信元ドメイン（完全一致またはサブドメイン）か送信者名の部分一致
+    pub fn matches(&self, project: &Project) -> bool {
+        let domain_match = project.sender_domain.as_deref().is_some_and(|domain| {
+            let domain = domain.to_lowercase();
+            self.sender_domains.iter().any(|d| {
+                let d = d.trim().to_lowercase();
+                domain == d || domain.ends_with(&format!(".{d}"))
+            })
+        });
+        let name_match = project.client_name.as_deref().is_some_and(|name| {
+            self.client_names
+                .iter()
+                .any(|n| !n.trim().is_empty() && name.contains(n.trim()))
+        });
+        domain_match || name_match
+    }
+
+    /// 上書きを適用した案件（上書きが無ければ借用のまま）
+    pub fn apply_overrides<'a>(&self, project: &'a Project) -> Cow<'a, Project> {
+        let o = &self.overrides;
+        if o == &ClientOverrides::default() {
+            return Cow::Borrowed(project);
+        }
+        let mut project = project.clone();
+        if let Some(value) = o.foreigner_allowed {
+            project.foreigner_allowed = Some(value);
+        }
+        if let Some(value) = &o.jinzai_flow_limit {
+            project.jinzai_flow_limit = Some(value.clone());
+        }
+        if let Some(value) = o.is_kojin_ok {
+            project.is_kojin_ok = Some(value);
+        }
+        if let Some(value) = o.age_limit_lower {
+            project.age_limit_lower = Some(value);
+        }
+        if let Some(value) = o.age_limit_upper {
+            project.age_limit_upper = Some(value);
+        }
+        Cow::Owned(project)
+    }
+
+    /// KO チェック名ごとの上書き内容（KO 理由への注記用）
+    fn overridden_inputs(&self, check: &str) -> Vec<String> {
+        let o = &self.overrides;
+        let mut inputs = Vec::new();
+        match check {
+            "foreigner" => {
+                if let Some(v) = o.foreigner_allowed {
+                    inputs.push(format!("foreigner_allowed={v}"));
+                }
+            }
+            "flow" => {
+                if let Some(v) = &o.jinzai_flow_limit {
+                    inputs.push(format!("jinzai_flow_limit={v}"));
+                }
+            }
+            "contract" => {
+                if let Some(v) = o.is_kojin_ok {
+                    inputs.push(format!("is_kojin_ok={v}"));
+                }
+            }
+            "age" => {
+                if let Some(v) = o.age_limit_lower {
+                    inputs.push(format!("age_limit_lower={v}"));
+                }
+                if let Some(v) = o.age_limit_upper {
+                    inputs.push(format!("age_limit_upper={v}"));
+                }
+            }
+            _ => {}
+        }
+        inputs
+    }
+
+    /// 上書きした値が効いた KO の理由にプロファイル名を注記する
+    pub fn annotate(&self, check: &str, decision: KoDecision) -> KoDecision {
+        let inputs = self.overridden_inputs(check);
+        if inputs.is_empty() {
+            return decision;
+        }
+        let note = format!(" (client_profile={}: {})", self.name, inputs.join(", "));
+        match decision {
+            KoDecision::HardKo { reason } => KoDecision::HardKo {
+                reason: reason + &note,
+            },
+            KoDecision::SoftKo { reason } => KoDecision::SoftKo {
+                reason: reason + &note,
+            },
+            KoDecision::Pass => KoDecision::Pass,
+        }
+    }
+
+    /// 追加ルールを 1 つの判定にまとめる（HardKo 優先。理由は `; ` 区切り）
+    pub fn evaluate_rules(&self, talent: &Talent) -> KoDecision {
+        let mut hard = Vec::new();
+        let mut soft = Vec::new();
+        for rule in &self.rules {
+            let label = format!("{}/{}", self.name, rule.name);
+            match (rule.condition.check(talent), rule.severity) {
+                (RuleCheck::Satisfied, _) => {}
+                (RuleCheck::Violated(detail), ClientRuleSeverity::Hard) => {
+                    hard.push(format!("client_rule_violated: [{label}] {detail}"))
+                }
+                (RuleCheck::Violated(detail), ClientRuleSeverity::Soft) => {
+                    soft.push(format!("client_rule_violated: [{label}] {detail}"))
+                }
+                (RuleCheck::Unknown(detail), _) => {
+                    soft.push(format!("client_rule_unknown: [{label}] {detail}"))
+                }
+            }
+        }
+
+        if !hard.is_empty() {
+            KoDecision::HardKo {
+                reason: hard.join("; "),
+            }
+        } else if !soft.is_empty() {
+            KoDecision::SoftKo {
+                reason: soft.join("; "),
+            }
+        } else {
+            KoDecision::Pass
+        }
+    }
+
+    pub fn validate(&self) -> Result<(), String> {
+        if self.name.trim().is_empty() {
+            return Err("clients.name must not be empty".into());
+        }
+        let name = &self.name;
+        if self.sender_domains.iter().all(|d| d.trim().is_empty())
+            && self.client_names.iter().all(|n| n.trim().is_empty())
+        {
+            return Err(format!(
+                "clients `{name}` needs at least one sender_domains or client_names entry"
+            ));
+        }
+        if let Some(limit) = &self.overrides.jinzai_flow_limit {
+            if parse_flow_limit(limit).is_none() {
+                return Err(format!(
+                    "clients `{name}`: unknown overrides.jinzai_flow_limit `{limit}`"
+                ));
+            }
+        }
+        if let (Some(lower), Some(upper)) = (
+            self.overrides.age_limit_lower,
+            self.overrides.age_limit_upper,
+        ) {
+            if lower > upper {
+                return Err(format!(
+                    "clients `{name}`: overrides.age_limit_lower must not exceed age_limit_upper"
+                ));
+            }
+        }
+        for rule in &self.rules {
+            if rule.name.trim().is_empty() {
+                return Err(format!("clients `{name}`: rule name must not be empty"));
+            }
+            let empty = match &rule.condition {
+                ClientRuleCondition::RequiredSkills { skills } => skills.is_empty(),
+                ClientRuleCondition::ResidentialTodofuken { values } => values.is_empty(),
+                _ => false,
+            };
+            if empty {
+                return Err(format!(
+                    "clients `{name}`: rule `{}` has an empty list",
+                    rule.name
+                ));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// 案件に当てはまる最初のプロファイル（ファイルの記述順）
+pub fn find_client_profile<'a>(
+    profiles: &'a [ClientProfile],
+    project: &Project,
+) -> Option<&'a ClientProfile> {
+    profiles.iter().find(|profile| profile.matches(project))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn profile() -> ClientProfile {
+        ClientProfile {
+            name: "acme".into(),
+            sender_domains: vec!["acme.co.jp".into()],
+            client_names: vec!["アクメ".into()],
+            overrides: ClientOverrides {
+                foreigner_allowed: Some(false),
+                ..ClientOverrides::default()
+            },
+            rules: vec![ClientRule {
+                name: "aws".into(),
+                severity: ClientRuleSeverity::Hard,
+                condition: ClientRuleCondition::RequiredSkills {
+                    skills: vec!["AWS".into()],
+                },
+            }],
+        }
+    }
+
+    #[test]
+    fn matches_by_domain_or_client_name() {
+        let profile = profile();
+        let by_domain = Project {
+            sender_domain: Some("sales.acme.co.jp".into()),
+            ..Project::default()
+        };
+        let by_name = Project {
+            client_name: Some("株式会社アクメ 営業部".into()),
+            ..Project::default()
+        };
+        let other = Project {
+            sender_domain: Some("notacme.co.jp".into()),
+            ..Project::default()
+        };
+        assert!(profile.matches(&by_domain));
+        assert!(profile.matches(&by_name));
+        assert!(!profile.matches(&other));
+        assert_eq!(
+            profile.apply_overrides(&other).foreigner_allowed,
+            Some(false)
+        );
+    }
+
+    #[test]
+    fn extra_rules_prefer_hard_ko_and_name_the_profile() {
+        let profile = profile();
+        let talent = Talent {
+            possessed_skills_keywords: vec!["Rust".into()],
+            ..Talent::default()
+        };
+        let decision = profile.evaluate_rules(&talent);
+        assert!(decision.is_hard_ko());
+        assert!(decision.reason().unwrap().contains("[acme/aws]"));
+
+        let unknown = profile.evaluate_rules(&Talent::default());
+        assert!(unknown.is_soft_ko());
+
+        let annotated = profile.annotate(
+            "foreigner",
+            KoDecision::HardKo {
+                reason: "foreigner_not_allowed: 米国".into(),
+            },
+        );
+        assert!(annotated
+            .reason()
+            .unwrap()
+            .ends_with("(client_profile=acme: foreigner_allowed=false)"));
+        assert_eq!(
+            profile.annotate("tanka", KoDecision::Pass),
+            KoDecision::Pass
+        );
+    }
+}
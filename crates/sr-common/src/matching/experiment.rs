@@ -0,0 +1,312 @@
+//! オンライン A/B 実験: 案件ごとの決定論的なアーム割り当て
+//!
+//! 割り当ては `sha256("{salt}:{project_id}")` の先頭 8 バイトを allocation の合計で割った余りで決まるため、
+//! 同じ設定なら再実行・別プロセス（sr-matcher / sr-api）でも同じ案件は同じアームに入る。
+//! アーム名はそのまま `interaction_logs.variant` に記録される。
+
+use std::collections::HashSet;
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use thiserror::Error;
+
+use super::{scoring::TotalScoreWeights, weights::Weights};
+use crate::two_tower::TwoTowerConfig;
+
+/// 実験設定 JSON のパス
+pub const EXPERIMENT_CONFIG_ENV: &str = "MATCH_EXPERIMENT_CONFIG";
+/// `interaction_logs.variant` の列長
+pub const MAX_ARM_NAME_LEN: usize = 50;
+/// アーム名に "control" が無い場合は先頭アームを比較基準にする
+pub const CONTROL_ARM: &str = "control";
+
+#[derive(Debug, Error)]
+pub enum ExperimentConfigError {
+    #[error("failed to read experiment config {path}: {source}")]
+    Io {
+        path: String,
+        #[source]
+        source: std::io::Error,
+    },
+    #[error("failed to parse experiment config: {0}")]
+    Parse(#[from] serde_json::Error),
+    #[error("invalid experiment config: {0}")]
+    Invalid(String),
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ExperimentConfig {
+    /// 実験名（レポート表示用）
+    pub name: String,
+    /// 割り当てハッシュのソルト。変えると全案件が振り直される
+    pub salt: String,
+    pub arms: Vec<ExperimentArm>,
+}
+
+/// 実験アーム。省略した項目は本番の既定値（環境変数）を使う
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ExperimentArm {
+    pub name: String,
+    /// 割り当て比率（相対値。全アームの合計に対する割合で案件を振り分ける）
+    #[serde(default = "default_allocation")]
+    pub allocation: u32,
+    #[serde(default)]
+    pub weights: Option<Weights>,
+    #[serde(default)]
+    pub total_score_weights: Option<TotalScoreWeights>,
+    #[serde(default)]
+    pub two_tower: Option<ArmTwoTower>,
+    /// 必須スキル一致率の閾値（KO 判定と skills スコアの両方に効く）
+    #[serde(default)]
+    pub skill_match_threshold: Option<f64>,
+}
+
+/// アーム単位の Two-Tower 上書き。embedder / 次元はプロセス共通
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ArmTwoTower {
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
+    #[serde(default)]
+    pub weight: Option<f32>,
+    #[serde(default)]
+    pub score_min: Option<f32>,
+    #[serde(default)]
+    pub score_max: Option<f32>,
+}
+
+fn default_allocation() -> u32 {
+    1
+}
+
+fn default_enabled() -> bool {
+    true
+}
+
+impl ArmTwoTower {
+    pub fn apply(&self, base: &TwoTowerConfig) -> TwoTowerConfig {
+        TwoTowerConfig {
+            enabled: self.enabled,
+            weight: self.weight.unwrap_or(base.weight),
+            score_min: self.score_min.unwrap_or(base.score_min),
+            score_max: self.score_max.unwrap_or(base.score_max),
+            ..base.clone()
+        }
+    }
+}
+
+impl ExperimentArm {
+    /// この実験アームで使う Two-Tower 設定
+    pub fn two_tower_config(&self, base: &TwoTowerConfig) -> TwoTowerConfig {
+        self.two_tower
+            .map(|tt| tt.apply(base))
+            .unwrap_or_else(|| base.clone())
+    }
+}
+
+impl ExperimentConfig {
+    pub fn from_json(json: &str) -> Result<Self, ExperimentConfigError> {
+        let config: Self = serde_json::from_str(json)?;
+        config.validate()?;
+        Ok(config)
+    }
+
+    pub fn from_path(path: impl AsRef<Path>) -> Result<Self, ExperimentConfigError> {
+        let path = path.as_ref();
+        let json = std::fs::read_to_string(path).map_err(|source| ExperimentConfigError::Io {
+            path: path.display().to_string(),
+            source,
+        })?;
+        Self::from_json(&json)
+    }
+
+    /// `MATCH_EXPERIMENT_CONFIG` が設定されていれば読み込む（未設定なら None）
+    pub fn from_env() -> Result<Option<Self>, ExperimentConfigError> {
+        match std::env::var(EXPERIMENT_CONFIG_ENV) {
+            Ok(path) if !path.trim().is_empty() => Self::from_path(path.trim()).map(Some),
+            _ => Ok(None),
+        }
+    }
+
+    pub fn validate(&self) -> Result<(), ExperimentConfigError> {
+        let invalid = |msg: String| Err(ExperimentConfigError::Invalid(msg));
+
+        if self.salt.is_empty() {
+            return invalid("salt must not be empty".into());
+        }
+        if self.arms.is_empty() {
+            return invalid("at least one arm is required".into());
+        }
+        if self.total_allocation() == 0 {
+            return invalid("arm allocations must not all be zero".into());
+        }
+
+        let mut seen = HashSet::new();
+        for arm in &self.arms {
+            if arm.name.is_empty() || arm.name.len() > MAX_ARM_NAME_LEN {
+                return invalid(format!(
+                    "arm name `{}` must be 1..={MAX_ARM_NAME_LEN} bytes",
+                    arm.name
+                ));
+            }
+            if !seen.insert(arm.name.as_str()) {
+                return invalid(format!("duplicate arm name `{}`", arm.name));
+            }
+            if let Some(weights) = arm.weights {
+                if !weights.is_valid() {
+                    return invalid(format!(
+                        "arm `{}` weights must be non-negative with a positive sum",
+                        arm.name
+                    ));
+                }
+            }
+            if let Some(threshold) = arm.skill_match_threshold {
+                if !(0.0..=1.0).contains(&threshold) {
+                    return invalid(format!(
+                        "arm `{}` skill_match_threshold must be within 0.0..=1.0",
+                        arm.name
+                    ));
+                }
+            }
+            if let Some(tt) = arm.two_tower {
+                if tt.weight.is_some_and(|w| !w.is_finite() || w < 0.0) {
+                    return invalid(format!(
+                        "arm `{}` two_tower.weight must be non-negative",
+                        arm.name
+                    ));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn total_allocation(&self) -> u64 {
+        self.arms.iter().map(|arm| arm.allocation as u64).sum()
+    }
+
+    /// 案件の割り当て位置（0..全アームの allocation 合計）
+    pub fn bucket(&self, project_id: i64) -> u64 {
+        let digest = Sha256::digest(format!("{}:{project_id}", self.salt).as_bytes());
+        let mut head = [0u8; 8];
+        head.copy_from_slice(&digest[..8]);
+        u64::from_be_bytes(head) % self.total_allocation().max(1)
+    }
+
+    /// 案件が属するアームの添字
+    pub fn assign_index(&self, project_id: i64) -> usize {
+        let mut bucket = self.bucket(project_id);
+        for (idx, arm) in self.arms.iter().enumerate() {
+            let allocation = arm.allocation as u64;
+            if bucket < allocation {
+                return idx;
+            }
+            bucket -= allocation;
+        }
+        0
+    }
+
+    pub fn assign(&self, project_id: i64) -> &ExperimentArm {
+        &self.arms[self.assign_index(project_id)]
+    }
+
+    /// 比較基準のアーム（"control"、無ければ先頭）
+    pub fn control(&self) -> &ExperimentArm {
+        self.arms
+            .iter()
+            .find(|arm| arm.name == CONTROL_ARM)
+            .unwrap_or(&self.arms[0])
+    }
+
+    pub fn arm_names(&self) -> Vec<String> {
+        self.arms.iter().map(|arm| arm.name.clone()).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config(allocations: &[(&str, u32)]) -> ExperimentConfig {
+        ExperimentConfig {
+            name: "tt-weight".into(),
+            salt: "2026-10".into(),
+            arms: allocations
+                .iter()
+                .map(|(name, allocation)| ExperimentArm {
+                    name: (*name).into(),
+                    allocation: *allocation,
+                    weights: None,
+                    total_score_weights: None,
+                    two_tower: None,
+                    skill_match_threshold: None,
+                })
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn assignment_is_deterministic_and_follows_allocation() {
+        let experiment = config(&[("control", 3), ("tt_30", 1)]);
+        let again = experiment.clone();
+
+        let mut counts = [0usize; 2];
+        for project_id in 0..4000 {
+            let idx = experiment.assign_index(project_id);
+            assert_eq!(idx, again.assign_index(project_id));
+            counts[idx] += 1;
+        }
+        let treatment_share = counts[1] as f64 / 4000.0;
+        assert!(
+            (treatment_share - 0.25).abs() < 0.03,
+            "share={treatment_share}"
+        );
+
+        let mut resalted = experiment.clone();
+        resalted.salt = "2026-11".into();
+        assert!((0..200).any(|id| resalted.assign_index(id) != experiment.assign_index(id)));
+    }
+
+    #[test]
+    fn zero_allocation_arm_never_receives_projects() {
+        let experiment = config(&[("control", 1), ("paused", 0)]);
+        assert!((0..500).all(|id| experiment.assign(id).name == "control"));
+    }
+
+    #[test]
+    fn parses_and_validates_arm_overrides() {
+        let experiment = ExperimentConfig::from_json(
+            r#"{
+                "name": "tt-weight",
+                "salt": "s1",
+                "arms": [
+                    {"name": "baseline"},
+                    {"name": "tt_30", "allocation": 2,
+                     "two_tower": {"weight": 0.3}, "skill_match_threshold": 0.5}
+                ]
+            }"#,
+        )
+        .unwrap();
+        assert_eq!(experiment.control().name, "baseline");
+        assert_eq!(experiment.arms[0].allocation, 1);
+
+        let base = TwoTowerConfig::default();
+        let tt = experiment.arms[1].two_tower_config(&base);
+        assert!(tt.enabled);
+        assert_eq!(tt.weight, 0.3);
+        assert_eq!(tt.dimension, base.dimension);
+        assert!(!experiment.arms[0].two_tower_config(&base).enabled);
+
+        for invalid in [
+            r#"{"name": "x", "salt": "s", "arms": []}"#,
+            r#"{"name": "x", "salt": "s", "arms": [{"name": "a"}, {"name": "a"}]}"#,
+            r#"{"name": "x", "salt": "s", "arms": [{"name": "a", "allocation": 0}]}"#,
+            r#"{"name": "x", "salt": "s", "arms": [{"name": "a", "skill_match_threshold": 1.5}]}"#,
+            r#"{"name": "x", "salt": "s", "arms": [{"name": "a", "unknown": 1}]}"#,
+        ] {
+            assert!(ExperimentConfig::from_json(invalid).is_err(), "{invalid}");
+        }
+    }
+}
@@ -0,0 +1,515 @@
+//! マッチ結果の説明（営業向け）
+//!
+//! KO 判定ごとに比較した値、各スコア要素の total への寄与、Two-Tower で重なったトークンを
+//! 1 つの構造にまとめる。値は MatchingEngine と同じ計算式から組み立てる。
+
+use std::borrow::Cow;
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+
+use crate::rules::active_rules;
+use crate::skill_normalizer::normalize_skill_set;
+use crate::two_tower::tokenizer::{tokenize_project, tokenize_talent};
+use crate::two_tower::TwoTowerConfig;
+use crate::{Project, Talent};
+
+use super::client_profile::{ClientProfile, CLIENT_RULE_CHECK};
+use super::ko_unified::{KnockoutResultV2, KoDecision};
+use super::scoring::{
+    calculate_total_score_with_two_tower, calculate_weighted_total_score, MatchScore,
+    MatchingConfig, ScoringResult,
+};
+
+/// 説明に載せる Two-Tower 重なりトークンの既定件数
+pub const DEFAULT_TOP_TOKENS: usize = 5;
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct MatchExplanation {
+    pub talent_id: Option<i64>,
+    pub project_id: Option<i64>,
+    /// いずれかが HardKo
+    pub is_hard_knockout: bool,
+    /// SoftKo が 1 つ以上ある
+    pub needs_manual_review: bool,
+    /// 適用したクライアントプロファイル（KO の比較値は上書き後）
+    pub client_profile: Option<String>,
+    /// KO チェック（KO_REASON_PRIORITY 順ではなく実行順）
+    pub rules: Vec<RuleExplanation>,
+    /// スコア要素（tanka, location, skills, experience, contract, other の順）
+    pub factors: Vec<FactorContribution>,
+    /// 要素の重み付き合計（0.0〜1.0）
+    pub business_score: f64,
+    /// business_score 1.0 あたりの total_score への寄与（total_score_weights と Two-Tower 合成後）
+    pub business_share: f64,
+    pub two_tower: Option<TwoTowerExplanation>,
+    pub total_score: f64,
+    pub score_version: String,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum RuleOutcome {
+    Pass,
+    SoftKo,
+    HardKo,
+}
+
+impl From<&KoDecision> for RuleOutcome {
+    fn from(decision: &KoDecision) -> Self {
+        match decision {
+            KoDecision::Pass => Self::Pass,
+            KoDecision::SoftKo { .. } => Self::SoftKo,
+            KoDecision::HardKo { .. } => Self::HardKo,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct RuleExplanation {
+    /// チェック名（"tanka", "required_skills", ...）
+    pub rule: String,
+    pub outcome: RuleOutcome,
+    pub reason: Option<String>,
+    /// 比較した案件側・人材側の値
+    pub evidence: Vec<RuleEvidence>,
+    /// required_skills のみ
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub skills: Option<SkillComparison>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct RuleEvidence {
+    pub field: String,
+    pub project: Value,
+    pub talent: Value,
+}
+
+impl RuleEvidence {
+    fn new(field: &str, project: impl Serialize, talent: impl Serialize) -> Self {
+        Self {
+            field: field.to_string(),
+            project: json!(project),
+            talent: json!(talent),
+        }
+    }
+}
+
+/// 必須スキルの一致状況（正規化後）
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct SkillComparison {
+    pub matched: Vec<String>,
+    pub missing: Vec<String>,
+    pub match_rate: f64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct FactorContribution {
+    pub factor: String,
+    /// 要素スコア（0.0〜max_score）
+    pub score: f64,
+    pub max_score: f64,
+    pub status: String,
+    pub details: String,
+    /// 詳細スコアの重み
+    pub weight: f64,
+    /// score × weight（business_score への寄与）
+    pub weighted_score: f64,
+    /// total_score への寄与（weighted_score × business_share）
+    pub contribution: f64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct TwoTowerExplanation {
+    pub embedder: String,
+    pub version: String,
+    /// 正規化済みスコア（0.0〜1.0）
+    pub score: f64,
+    pub weight: f64,
+    /// total_score への寄与
+    pub contribution: f64,
+    /// 案件・人材の両方に現れたトークン（寄与の大きい順）
+    pub top_tokens: Vec<TokenOverlap>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct TokenOverlap {
+    pub token: String,
+    pub project_weight: f64,
+    pub talent_weight: f64,
+}
+
+impl TokenOverlap {
+    /// 類似度への寄与の目安（重みの積）
+    pub fn strength(&self) -> f64 {
+        self.project_weight * self.talent_weight
+    }
+}
+
+/// Two-Tower の計算結果（embedder 名・バージョンと正規化済みスコア）
+#[derive(Debug, Clone)]
+pub struct TwoTowerInput<'a> {
+    pub embedder: &'a str,
+    pub version: &'a str,
+    pub score: f64,
+    pub config: &'a TwoTowerConfig,
+}
+
+/// KO 判定・詳細スコア・Two-Tower スコアから説明を組み立てる
+pub fn explain_match(
+    project: &Project,
+    talent: &Talent,
+    ko: &KnockoutResultV2,
+    score: &MatchScore,
+    config: &MatchingConfig,
+    two_tower: Option<TwoTowerInput<'_>>,
+) -> MatchExplanation {
+    let active = active_rules();
+    let profile = ko
+        .client_profile
+        .as_deref()
+        .and_then(|name| active.rules.clients.iter().find(|c| c.name == name));
+    let ko_project = profile.map_or(Cow::Borrowed(project), |p| p.apply_overrides(project));
+    let rules = ko
+        .decisions
+        .iter()
+        .map(|(name, decision)| explain_rule(name, decision, &ko_project, talent, profile))
+        .collect();
+
+    // ランキングと同じく detailed total を calculate_total_score_with_two_tower に通す
+    // （semantic/historical はランキングでは 0）。business 側の比率を要素へ按分する
+    let tt_config = two_tower.as_ref().map(|tt| tt.config);
+    let base_score =
+        calculate_weighted_total_score(score.total, 0.0, 0.0, &config.total_score_weights);
+    let (tt_share, two_tower) = match two_tower.filter(|tt| tt.config.enabled) {
+        Some(tt) => {
+            let weight = tt.config.weight as f64;
+            let share = weight / (1.0 + weight);
+            let explanation = TwoTowerExplanation {
+                embedder: tt.embedder.to_string(),
+                version: tt.version.to_string(),
+                score: tt.score,
+                weight,
+                contribution: share * tt.config.normalize_score(tt.score),
+                top_tokens: overlapping_tokens(project, talent, DEFAULT_TOP_TOKENS),
+            };
+            (share, Some(explanation))
+        }
+        None => (0.0, None),
+    };
+    let business_share = if score.business_rules_score > 0.0 {
+        base_score * (1.0 - tt_share) / score.business_rules_score
+    } else {
+        0.0
+    };
+    let total_score = match tt_config {
+        Some(tt_config) => calculate_total_score_with_two_tower(
+            score.total,
+            0.0,
+            0.0,
+            two_tower.as_ref().map(|tt| tt.score),
+            &config.total_score_weights,
+            tt_config,
+        ),
+        None => base_score,
+    };
+
+    let weights = config.weights;
+    let factors = [
+        ("tanka", &score.tanka, weights.tanka),
+        ("location", &score.location, weights.location),
+        ("skills", &score.skills, weights.skills),
+        ("experience", &score.experience, weights.experience),
+        ("contract", &score.contract, weights.contract),
+        ("other", &score.other, weights.other),
+    ]
+    .into_iter()
+    .map(|(name, result, weight)| factor_contribution(name, result, weight, business_share))
+    .collect();
+
+    MatchExplanation {
+        talent_id: talent.id,
+        project_id: project.id,
+        is_hard_knockout: ko.is_hard_knockout,
+        needs_manual_review: ko.needs_manual_review,
+        client_profile: ko.client_profile.clone(),
+        rules,
+        factors,
+        business_score: score.business_rules_score,
+        business_share,
+        two_tower,
+        total_score,
+        score_version: score.score_version.clone(),
+    }
+}
+
+fn factor_contribution(
+    name: &str,
+    result: &ScoringResult,
+    weight: f64,
+    business_share: f64,
+) -> FactorContribution {
+    let weighted_score = result.score * weight;
+    FactorContribution {
+        factor: name.to_string(),
+        score: result.score,
+        max_score: result.max_score,
+        status: result.status.to_string(),
+        details: result.details.clone(),
+        weight,
+        weighted_score,
+        contribution: weighted_score * business_share,
+    }
+}
+
+fn explain_rule(
+    name: &str,
+    decision: &KoDecision,
+    project: &Project,
+    talent: &Talent,
+    profile: Option<&ClientProfile>,
+) -> RuleExplanation {
+    let mut skills = None;
+    let evidence = match name {
+        "tanka" => vec![RuleEvidence::new(
+            "monthly_tanka",
+            json!({ "min": project.monthly_tanka_min, "max": project.monthly_tanka_max }),
+            json!({ "desired_min": talent.desired_price_min }),
+        )],
+        "required_skills" => {
+            skills = Some(compare_skills(
+                &project.required_skills_keywords,
+                &talent.possessed_skills_keywords,
+            ));
+            vec![RuleEvidence::new(
+                "skills",
+                &project.required_skills_keywords,
+                &talent.possessed_skills_keywords,
+            )]
+        }
+        "location" => vec![
+            RuleEvidence::new(
+                "todofuken",
+                &project.work_todofuken,
+                &talent.residential_todofuken,
+            ),
+            RuleEvidence::new("area", &project.work_area, &talent.residential_area),
+            RuleEvidence::new(
+                "remote_onsite",
+                &project.remote_onsite,
+                &talent.desired_remote_onsite,
+            ),
+        ],
+        "language" => vec![
+            RuleEvidence::new(
+                "japanese_skill",
+                &project.japanese_skill,
+                &talent.japanese_skill,
+            ),
+            RuleEvidence::new(
+                "english_skill",
+                &project.english_skill,
+                &talent.english_skill,
+            ),
+        ],
+        "foreigner" => vec![RuleEvidence::new(
+            "nationality",
+            json!({ "foreigner_allowed": project.foreigner_allowed }),
+            &talent.nationality,
+        )],
+        "contract" => vec![RuleEvidence::new(
+            "contract_type",
+            &project.contract_type,
+            [
+                &talent.primary_contract_type,
+                &talent.secondary_contract_type,
+            ],
+        )],
+        "flow" => vec![RuleEvidence::new(
+            "flow",
+            &project.jinzai_flow_limit,
+            &talent.flow_depth,
+        )],
+        "ng_keyword" => vec![RuleEvidence::new(
+            "keywords",
+            &project.project_keywords,
+            &talent.ng_keywords,
+        )],
+        "age" => vec![RuleEvidence::new(
+            "age",
+            json!({ "lower": project.age_limit_lower, "upper": project.age_limit_upper }),
+            json!({ "birth_year": talent.birth_year }),
+        )],
+        "availability" => vec![RuleEvidence::new(
+            "start_date",
+            &project.start_date,
+            &talent.availability_date,
+        )],
+        CLIENT_RULE_CHECK => vec![RuleEvidence::new(
+            "client_rules",
+            profile.map(|p| &p.rules),
+            json!({
+                "birth_year": talent.birth_year,
+                "min_experience_years": talent.min_experience_years,
+                "skills": talent.possessed_skills_keywords,
+                "residential_todofuken": talent.residential_todofuken,
+            }),
+        )],
+        _ => vec![],
+    };
+
+    RuleExplanation {
+        rule: name.to_string(),
+        outcome: RuleOutcome::from(decision),
+        reason: decision.reason().map(str::to_string),
+        evidence,
+        skills,
+    }
+}
+
+fn compare_skills(project_skills: &[String], talent_skills: &[String]) -> SkillComparison {
+    let required = normalize_skill_set(project_skills);
+    let possessed = normalize_skill_set(talent_skills);
+
+    let mut matched: Vec<_> = required.intersection(&possessed).cloned().collect();
+    let mut missing: Vec<_> = required.difference(&possessed).cloned().collect();
+    matched.sort();
+    missing.sort();
+    let match_rate = if required.is_empty() {
+        1.0
+    } else {
+        matched.len() as f64 / required.len() as f64
+    };
+
+    SkillComparison {
+        matched,
+        missing,
+        match_rate,
+    }
+}
+
+/// 案件・人材の両方に現れたトークンを重みの積の大きい順に返す
+///
+/// 同じトークンが複数回出る場合（必須と歓迎の両方に書かれたスキル等）は大きい方の重みを使う。
+pub fn overlapping_tokens(project: &Project, talent: &Talent, limit: usize) -> Vec<TokenOverlap> {
+    fn max_weights(
+        tokens: Vec<crate::two_tower::tokenizer::WeightedToken>,
+    ) -> HashMap<String, f64> {
+        let mut map: HashMap<String, f64> = HashMap::new();
+        for wt in tokens {
+            let weight = map.entry(wt.token).or_default();
+            *weight = weight.max(wt.weight as f64);
+        }
+        map
+    }
+
+    let talent_tokens = max_weights(tokenize_talent(talent));
+    let mut overlaps: Vec<_> = max_weights(tokenize_project(project))
+        .into_iter()
+        .filter_map(|(token, project_weight)| {
+            talent_tokens
+                .get(&token)
+                .map(|&talent_weight| TokenOverlap {
+                    token,
+                    project_weight,
+                    talent_weight,
+                })
+        })
+        .collect();
+
+    overlaps.sort_by(|a, b| {
+        b.strength()
+            .total_cmp(&a.strength())
+            .then_with(|| a.token.cmp(&b.token))
+    });
+    overlaps.truncate(limit);
+    overlaps
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::matching::ko_checks::run_all_ko_checks;
+    use crate::matching::scoring::BusinessRulesEngine;
+
+    fn project() -> Project {
+        Project {
+            id: Some(10),
+            monthly_tanka_min: Some(70),
+            monthly_tanka_max: Some(80),
+            required_skills_keywords: vec!["Rust".into(), "AWS".into()],
+            work_todofuken: Some("東京都".into()),
+            remote_onsite: Some("フルリモート".into()),
+            ..Project::default()
+        }
+    }
+
+    fn talent() -> Talent {
+        Talent {
+            id: Some(20),
+            desired_price_min: Some(75),
+            possessed_skills_keywords: vec!["rust".into(), "Go".into()],
+            residential_todofuken: Some("東京都".into()),
+            desired_remote_onsite: Some("フルリモート".into()),
+            ..Talent::default()
+        }
+    }
+
+    fn explain(two_tower: Option<TwoTowerInput<'_>>) -> MatchExplanation {
+        let (project, talent) = (project(), talent());
+        let config = MatchingConfig::detailed();
+        let ko = run_all_ko_checks(&project, &talent);
+        let score =
+            BusinessRulesEngine::new(config.clone()).calculate_match_score(&project, &talent);
+        explain_match(&project, &talent, &ko, &score, &config, two_tower)
+    }
+
+    #[test]
+    fn rules_carry_compared_values_and_skill_gaps() {
+        let explanation = explain(None);
+
+        let tanka = explanation
+            .rules
+            .iter()
+            .find(|r| r.rule == "tanka")
+            .unwrap();
+        assert_eq!(tanka.outcome, RuleOutcome::Pass);
+        assert_eq!(tanka.evidence[0].project, json!({ "min": 70, "max": 80 }));
+        assert_eq!(tanka.evidence[0].talent, json!({ "desired_min": 75 }));
+
+        let skills = explanation
+            .rules
+            .iter()
+            .find(|r| r.rule == "required_skills")
+            .and_then(|r| r.skills.clone())
+            .unwrap();
+        assert_eq!(skills.matched.len(), 1);
+        assert_eq!(skills.missing.len(), 1);
+        assert!((skills.match_rate - 0.5).abs() < 1e-9);
+    }
+
+    #[test]
+    fn contributions_add_up_to_total() {
+        let config = TwoTowerConfig {
+            enabled: true,
+            weight: 0.5,
+            ..TwoTowerConfig::default()
+        };
+        let explanation = explain(Some(TwoTowerInput {
+            embedder: "hash",
+            version: "hash-v1",
+            score: 0.6,
+            config: &config,
+        }));
+
+        let factor_sum: f64 = explanation.factors.iter().map(|f| f.contribution).sum();
+        let tt = explanation.two_tower.as_ref().unwrap();
+        assert!((factor_sum + tt.contribution - explanation.total_score).abs() < 1e-9);
+        assert!(tt
+            .top_tokens
+            .iter()
+            .any(|t| t.token == "remote:フルリモート"));
+        assert_eq!(tt.top_tokens[0].token, "loc:東京都");
+    }
+}
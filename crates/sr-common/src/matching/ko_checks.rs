@@ -1,7 +1,8 @@
 use super::{
+    client_profile::{find_client_profile, ClientProfile, CLIENT_RULE_CHECK},
     ko_unified::{KnockoutResultV2, KoDecision},
     location::evaluate_location,
-    skills::check_required_skills,
+    skills::check_required_skills_with_threshold,
 };
 use crate::{
     corrections::{
@@ -13,6 +14,7 @@ use crate::{
         nationality::is_japanese_nationality,
         normalize_contract_type_for_matching,
     },
+    rules::active_rules,
     Project, Talent,
 };
 use chrono::Datelike;
@@ -20,13 +22,57 @@ use std::collections::HashSet;
 
 /// 全KO判定をまとめて実行する
 pub fn run_all_ko_checks(project: &Project, talent: &Talent) -> KnockoutResultV2 {
-    let decisions = vec![
+    run_all_ko_checks_with_skill_threshold(project, talent, None)
+}
+
+/// 必須スキルの一致率閾値を差し替えて全KO判定を実行する（None ならルールの skills.match_threshold）。
+/// 案件に当てはまるクライアントプロファイルがあれば、上書き後の案件で判定し追加ルールを `client_rule` に集約する
+pub fn run_all_ko_checks_with_skill_threshold(
+    project: &Project,
+    talent: &Talent,
+    skill_threshold: Option<f64>,
+) -> KnockoutResultV2 {
+    run_ko_checks_with_clients(
+        project,
+        talent,
+        skill_threshold,
+        &active_rules().rules.clients,
+    )
+}
+
+fn run_ko_checks_with_clients(
+    project: &Project,
+    talent: &Talent,
+    skill_threshold: Option<f64>,
+    clients: &[ClientProfile],
+) -> KnockoutResultV2 {
+    let Some(profile) = find_client_profile(clients, project) else {
+        return KnockoutResultV2::new(base_ko_decisions(project, talent, skill_threshold));
+    };
+
+    let overridden = profile.apply_overrides(project);
+    let mut decisions: Vec<_> = base_ko_decisions(&overridden, talent, skill_threshold)
+        .into_iter()
+        .map(|(name, decision)| (name, profile.annotate(name, decision)))
+        .collect();
+    decisions.push((CLIENT_RULE_CHECK, profile.evaluate_rules(talent)));
+
+    KnockoutResultV2::new(decisions).with_client_profile(&profile.name)
+}
+
+fn base_ko_decisions(
+    project: &Project,
+    talent: &Talent,
+    skill_threshold: Option<f64>,
+) -> Vec<(&'static str, KoDecision)> {
+    vec![
         ("tanka", check_tanka_ko(project, talent)),
         (
             "required_skills",
             check_skill_ko(
                 &project.required_skills_keywords,
                 &talent.possessed_skills_keywords,
+                skill_threshold,
             ),
         ),
         ("location", check_location_ko(project, talent)),
@@ -43,9 +89,7 @@ pub fn run_all_ko_checks(project: &Project, talent: &Talent) -> KnockoutResultV2
         ),
         ("age", check_age_ko(project, talent)),
         ("availability", check_availability_ko(project, talent)),
-    ];
-
-    KnockoutResultV2::new(decisions)
+    ]
 }
 
 /// 単価KO判定（利益<5万円でHardKo、情報不足はSoftKo）
@@ -79,8 +123,12 @@ fn check_tanka_ko(project: &Project, talent: &Talent) -> KoDecision {
 }
 
 /// 必須スキルKO判定（既存のスキルマッチ判定を利用）
-fn check_skill_ko(project_skills: &[String], talent_skills: &[String]) -> KoDecision {
-    let result = check_required_skills(project_skills, talent_skills);
+fn check_skill_ko(
+    project_skills: &[String],
+    talent_skills: &[String],
+    threshold: Option<f64>,
+) -> KoDecision {
+    let result = check_required_skills_with_threshold(project_skills, talent_skills, threshold);
 
     if result.requires_manual_review {
         return KoDecision::SoftKo {
@@ -536,6 +584,53 @@ mod tests {
         assert!(matches!(pass, KoDecision::Pass));
     }
 
+    #[test]
+    fn client_profile_overrides_and_extra_rules_are_recorded() {
+        use crate::matching::client_profile::{
+            ClientOverrides, ClientRule, ClientRuleCondition, ClientRuleSeverity,
+        };
+
+        let clients = vec![ClientProfile {
+            name: "acme".into(),
+            sender_domains: vec!["acme.co.jp".into()],
+            client_names: vec![],
+            overrides: ClientOverrides {
+                foreigner_allowed: Some(false),
+                ..ClientOverrides::default()
+            },
+            rules: vec![ClientRule {
+                name: "senior".into(),
+                severity: ClientRuleSeverity::Soft,
+                condition: ClientRuleCondition::MinExperienceYears { years: 5 },
+            }],
+        }];
+        let mut project = base_project();
+        project.foreigner_allowed = Some(true);
+        let mut talent = base_talent();
+        talent.nationality = Some("アメリカ".into());
+        talent.min_experience_years = Some(3);
+
+        let unmatched = run_ko_checks_with_clients(&project, &talent, None, &clients);
+        assert_eq!(unmatched.client_profile, None);
+        assert!(unmatched
+            .decisions
+            .iter()
+            .all(|(n, _)| *n != CLIENT_RULE_CHECK));
+
+        project.sender_domain = Some("acme.co.jp".into());
+        let result = run_ko_checks_with_clients(&project, &talent, None, &clients);
+        assert_eq!(result.client_profile.as_deref(), Some("acme"));
+        assert!(result.is_hard_knockout);
+        let decision = |name| &result.decisions.iter().find(|(n, _)| *n == name).unwrap().1;
+        assert!(decision("foreigner")
+            .reason()
+            .is_some_and(|r| r.contains("client_profile=acme: foreigner_allowed=false")));
+        assert!(decision(CLIENT_RULE_CHECK)
+            .reason()
+            .is_some_and(|r| r.contains("[acme/senior]")));
+        assert!(decision(CLIENT_RULE_CHECK).is_soft_ko());
+    }
+
     #[test]
     fn english_is_checked_even_when_japanese_unknown() {
         let mut project = base_project();
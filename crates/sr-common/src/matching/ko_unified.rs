@@ -53,6 +53,8 @@ pub struct KnockoutResultV2 {
     pub needs_manual_review: bool,
     /// 全ての判定結果（チェック名, 判定）
     pub decisions: Vec<(&'static str, KoDecision)>,
+    /// 適用したクライアントプロファイル名（`[[clients]]`）
+    pub client_profile: Option<String>,
 }
 
 /// KO理由の優先順位（上にあるほど優先）
@@ -67,6 +69,7 @@ const KO_REASON_PRIORITY: &[&str] = &[
     "location",
     "language",
     "foreigner",
+    "client_rule",
 ];
 
 impl KnockoutResultV2 {
@@ -78,9 +81,15 @@ impl KnockoutResultV2 {
             is_hard_knockout,
             needs_manual_review,
             decisions,
+            client_profile: None,
         }
     }
 
+    pub fn with_client_profile(mut self, name: impl Into<String>) -> Self {
+        self.client_profile = Some(name.into());
+        self
+    }
+
     /// manual_review_reason を生成（SoftKo の理由を ; 区切りで連結）
     pub fn manual_review_reasons(&self) -> Option<String> {
         let soft_reasons: Vec<_> = self
@@ -1,8 +1,13 @@
+pub mod client_profile;
+pub mod experiment;
+pub mod explain;
 pub mod ko_checks;
 pub mod ko_unified;
 pub mod location;
 pub mod pipeline;
 pub mod prefilter;
 pub mod scoring;
+pub mod simulation;
 pub mod skills;
+pub mod weight_fit;
 pub mod weights;
@@ -1,17 +1,28 @@
-use std::{cmp::Ordering, collections::HashMap};
+use std::{cmp::Ordering, collections::HashMap, sync::Arc};
 
 use super::{
-    ko_checks::run_all_ko_checks,
+    experiment::ExperimentConfig,
+    explain::{explain_match, MatchExplanation, TwoTowerInput},
+    ko_checks::{run_all_ko_checks, run_all_ko_checks_with_skill_threshold},
     ko_unified::KnockoutResultV2,
     prefilter::{EnhancedPreFilter, PreFilterConfig, PrefilterCandidate},
     scoring::{
-        calculate_detailed_score, calculate_total_score_with_two_tower, MatchScore, MatchingConfig,
+        calculate_total_score_with_two_tower, BusinessRulesEngine, MatchScore, MatchingConfig,
+        TotalScoreWeights,
     },
 };
 use crate::{
-    db::{insert_interaction_log, InteractionLogInsert, InteractionLogStorageError, PgPool},
+    db::{
+        insert_interaction_log, insert_match_run, insert_match_run_returning,
+        match_results::MatchResultInsert, InteractionLogInsert, InteractionLogStorageError,
+        MatchRunRecord, MatchRunStorageError, MatchRunWriteSummary, PersistedMatch, PgPool,
+    },
+    rules::active_rules,
     run_id,
-    two_tower::{create_embedder, load_config_from_env, TwoTowerConfig, TwoTowerEmbedder},
+    two_tower::{
+        load_config_from_env, try_create_embedder, TalentRecall, TwoTowerConfig, TwoTowerEmbedder,
+        TwoTowerError,
+    },
     Project, Talent,
 };
 use serde_json::json;
@@ -40,24 +51,31 @@ pub struct RankedTalentMatch {
 #[derive(Debug, Clone)]
 pub struct MatchingEngineConfig {
     pub prefilter: PreFilterConfig,
+    /// 詳細スコア（ランキング）の重みと total_score の合成比。オフライン評価で差し替える
+    pub detailed: MatchingConfig,
 }
 
 impl Default for MatchingEngineConfig {
     fn default() -> Self {
         Self {
             prefilter: PreFilterConfig::default(),
+            detailed: MatchingConfig::detailed(),
         }
     }
 }
 
 pub struct MatchingEngine {
     prefilter: EnhancedPreFilter,
+    scorer: BusinessRulesEngine,
+    total_weights: TotalScoreWeights,
 }
 
 impl MatchingEngine {
     pub fn new(config: MatchingEngineConfig) -> Self {
         Self {
             prefilter: EnhancedPreFilter::new(config.prefilter),
+            total_weights: config.detailed.total_score_weights,
+            scorer: BusinessRulesEngine::new(config.detailed),
         }
     }
 
@@ -65,6 +83,10 @@ impl MatchingEngine {
         Self::new(MatchingEngineConfig::default())
     }
 
+    pub fn prefilter(&self) -> &EnhancedPreFilter {
+        &self.prefilter
+    }
+
     /// Prefilter で粗選別した上で詳細スコアを再計算し、総合スコア順に並べる
     pub fn rank_projects(&self, talent: &Talent, projects: &[Project]) -> Vec<RankedMatch> {
         let candidates = self.prefilter.filter_candidates(talent, projects);
@@ -90,7 +112,9 @@ impl MatchingEngine {
     }
 
     fn build_ranked_match(&self, talent: &Talent, candidate: PrefilterCandidate) -> RankedMatch {
-        let detailed_score = calculate_detailed_score(&candidate.project, talent);
+        let detailed_score = self
+            .scorer
+            .calculate_match_score(&candidate.project, talent);
 
         RankedMatch {
             project: candidate.project,
@@ -100,11 +124,108 @@ impl MatchingEngine {
         }
     }
 
+    /// 人材起点の逆引き: `rank_projects` の結果に Two-Tower 類似度を加え、
+    /// 案件起点と同じ総合スコア・内訳で案件をランキングする
+    pub fn rank_projects_for_talent(
+        &self,
+        talent: &Talent,
+        projects: &[Project],
+        two_tower: Option<&dyn TwoTowerEmbedder>,
+        two_tower_config: &TwoTowerConfig,
+    ) -> Vec<RankedTalentMatch> {
+        let talent_embedding = two_tower.map(|embedder| embedder.embed_talent(talent));
+        let embedder_meta = two_tower.map(|e| (e.name().to_string(), e.version().to_string()));
+        let total_weights = self.total_weights;
+
+        let mut ranked: Vec<RankedTalentMatch> = self
+            .rank_projects(talent, projects)
+            .into_iter()
+            .map(|m| {
+                let tt_score = two_tower
+                    .zip(talent_embedding.as_ref())
+                    .map(|(embedder, emb)| {
+                        let project_embedding = embedder.embed_project(&m.project);
+                        two_tower_config
+                            .normalize_score(embedder.similarity(&project_embedding, emb) as f64)
+                    });
+                let total_score = calculate_total_score_with_two_tower(
+                    m.detailed_score.total,
+                    0.0,
+                    0.0,
+                    tt_score,
+                    &total_weights,
+                    two_tower_config,
+                );
+
+                RankedTalentMatch {
+                    talent: talent.clone(),
+                    project: m.project,
+                    ko: m.ko,
+                    prefilter_score: m.prefilter_score,
+                    detailed_score: m.detailed_score,
+                    total_score,
+                    two_tower_score: tt_score,
+                    two_tower_embedder: embedder_meta.as_ref().map(|meta| meta.0.clone()),
+                    two_tower_version: embedder_meta.as_ref().map(|meta| meta.1.clone()),
+                }
+            })
+            .collect();
+
+        ranked.sort_by(|a, b| {
+            b.total_score
+                .partial_cmp(&a.total_score)
+                .unwrap_or(Ordering::Equal)
+        });
+        ranked
+    }
+
     /// KO 判定のみを先に確認したい場合のユーティリティ
     pub fn evaluate_ko(&self, project: &Project, talent: &Talent) -> KnockoutResultV2 {
         run_all_ko_checks(project, talent)
     }
 
+    /// 1 組の案件・人材について、ランキングと同じ KO 判定・詳細スコア・Two-Tower で説明を組み立てる
+    ///
+    /// prefilter で落ちる組み合わせ（HardKo・低スコア）も説明する。
+    pub fn explain(
+        &self,
+        project: &Project,
+        talent: &Talent,
+        two_tower: Option<&dyn TwoTowerEmbedder>,
+        two_tower_config: &TwoTowerConfig,
+    ) -> MatchExplanation {
+        let ko = run_all_ko_checks_with_skill_threshold(
+            project,
+            talent,
+            self.prefilter.config().skill_match_threshold,
+        );
+        let detailed_score = self.scorer.calculate_match_score(project, talent);
+        let tt_score = two_tower.map(|embedder| {
+            let similarity = embedder.similarity(
+                &embedder.embed_project(project),
+                &embedder.embed_talent(talent),
+            );
+            two_tower_config.normalize_score(similarity as f64)
+        });
+        let two_tower_input = two_tower
+            .zip(tt_score)
+            .map(|(embedder, score)| TwoTowerInput {
+                embedder: embedder.name(),
+                version: embedder.version(),
+                score,
+                config: two_tower_config,
+            });
+
+        explain_match(
+            project,
+            talent,
+            &ko,
+            &detailed_score,
+            self.scorer.config(),
+            two_tower_input,
+        )
+    }
+
     /// Prefilter + detailed score に加えて Two-Tower 類似度を組み合わせ、案件に対する人材をランキングする
     pub fn rank_talents_for_project(
         &self,
@@ -123,7 +244,7 @@ impl MatchingEngine {
             }
         }
 
-        let total_weights = MatchingConfig::detailed().total_score_weights;
+        let total_weights = self.total_weights;
         let mut ranked = Vec::new();
 
         for talent in talents {
@@ -131,7 +252,7 @@ impl MatchingEngine {
                 continue;
             };
 
-            let detailed_score = calculate_detailed_score(project, talent);
+            let detailed_score = self.scorer.calculate_match_score(project, talent);
             let business_score = detailed_score.total;
             let tt_score = talent.id.and_then(|id| two_tower_scores.get(&id).copied());
             let normalized_tt_score = tt_score.map(|s| two_tower_config.normalize_score(s));
@@ -203,26 +324,118 @@ impl RankedTalentMatch {
             created_at: None,
         })
     }
+
+    /// match_results へ UPSERT するためのレコードを組み立てる
+    pub fn to_match_result_insert(
+        &self,
+        match_run_id: impl Into<String>,
+        engine_version: Option<String>,
+        rule_version: Option<String>,
+    ) -> Option<MatchResultInsert> {
+        let talent_id = self.talent.id?;
+        let project_id = self.project.id?;
+
+        let ko_reasons = self.ko.prioritized_reasons();
+        let score_breakdown = build_score_breakdown_json(
+            &self.detailed_score,
+            self.total_score,
+            self.two_tower_score,
+        );
+
+        Some(MatchResultInsert {
+            talent_id,
+            project_id,
+            is_knockout: self.ko.is_hard_knockout,
+            ko_reasons: if ko_reasons.is_empty() {
+                None
+            } else {
+                Some(json!(ko_reasons))
+            },
+            needs_manual_review: self.ko.needs_manual_review,
+            score_total: Some(self.total_score),
+            score_breakdown: Some(score_breakdown),
+            engine_version,
+            rule_version,
+            match_run_id: Some(match_run_id.into()),
+            created_at: None,
+        })
+    }
+}
+
+/// 起動時に一度だけ読み込み、リクエストごとのランナーで共有する Two-Tower 設定と embedder
+#[derive(Clone)]
+pub struct SharedTwoTower {
+    pub config: TwoTowerConfig,
+    pub embedder: Option<Arc<dyn TwoTowerEmbedder>>,
 }
 
-/// 環境変数から Two-Tower を初期化するヘルパー。
-/// enabled=false の場合は embedder を生成せず None を返す。
-pub fn init_two_tower_from_env() -> (TwoTowerConfig, Option<Box<dyn TwoTowerEmbedder>>) {
-    let config = load_config_from_env();
-    if !config.enabled {
-        return (config, None);
+impl SharedTwoTower {
+    /// Two-Tower を使わない設定（embedder なし）
+    pub fn disabled() -> Self {
+        Self {
+            config: TwoTowerConfig::default(),
+            embedder: None,
+        }
+    }
+
+    /// 環境変数から Two-Tower を初期化する。モデルを読み込めなければエラーを返す。
+    /// enabled=false でも、実験アームのどれかが Two-Tower を使うなら embedder を生成する。
+    pub fn try_from_env(experiment: Option<&ExperimentConfig>) -> Result<Self, TwoTowerError> {
+        let config = load_config_from_env();
+        let needed = config.enabled
+            || experiment.is_some_and(|experiment| {
+                experiment
+                    .arms
+                    .iter()
+                    .any(|arm| arm.two_tower_config(&config).enabled)
+            });
+        if !needed {
+            return Ok(Self {
+                config,
+                embedder: None,
+            });
+        }
+
+        let embedder_name = std::env::var("TWO_TOWER_EMBEDDER").unwrap_or_else(|_| "hash".into());
+        let embedder_config = TwoTowerConfig {
+            enabled: true,
+            ..config.clone()
+        };
+        let embedder = try_create_embedder(&embedder_name, embedder_config)?;
+        Ok(Self {
+            config,
+            embedder: Some(Arc::from(embedder)),
+        })
     }
+}
+
+/// 実験アームごとのエンジンと Two-Tower 設定（embedder はランナーと共有）
+struct ArmRunner {
+    name: String,
+    engine: MatchingEngine,
+    two_tower_config: TwoTowerConfig,
+}
 
-    let embedder_name = std::env::var("TWO_TOWER_EMBEDDER").unwrap_or_else(|_| "hash".into());
-    let embedder = create_embedder(&embedder_name, config.clone());
-    (config, Some(embedder))
+struct ActiveExperiment {
+    config: ExperimentConfig,
+    arms: Vec<ArmRunner>,
+}
+
+/// 1 案件のランキングに使う設定（実験アーム or ランナー既定）
+struct RankingContext<'a> {
+    engine: &'a MatchingEngine,
+    two_tower_config: &'a TwoTowerConfig,
+    two_tower: Option<&'a dyn TwoTowerEmbedder>,
+    variant: Option<&'a str>,
 }
 
 /// マッチングを実行し、Two-Tower スコアと business_score を interaction_logs へ保存するランナー
 pub struct MatchRunner {
     engine: MatchingEngine,
     two_tower_config: TwoTowerConfig,
-    two_tower: Option<Box<dyn TwoTowerEmbedder>>,
+    two_tower: Option<Arc<dyn TwoTowerEmbedder>>,
+    talent_recall: Option<TalentRecall>,
+    experiment: Option<ActiveExperiment>,
     engine_version: Option<String>,
     config_version: Option<String>,
     variant: Option<String>,
@@ -233,15 +446,27 @@ impl MatchRunner {
     /// 環境変数を読み込んで初期化する。
     /// - match_run_id: sr_common::run_id の生成 ULID（runごとに一意）
     /// - Two-Tower: TWO_TOWER_ENABLED/TWO_TOWER_DIMENSION/TWO_TOWER_WEIGHT/TWO_TOWER_EMBEDDER で制御
+    /// - config_version: 現在のルールの rule_version（match_results.rule_version にも同じ値を記録）
+    ///
+    /// # Panics
+    /// Two-Tower のモデルを読み込めない場合。常駐プロセスは `SharedTwoTower::try_from_env` で起動時に検証し
+    /// `from_shared` を使う。
     pub fn from_env() -> Self {
-        let (two_tower_config, two_tower) = init_two_tower_from_env();
+        let shared = SharedTwoTower::try_from_env(None)
+            .unwrap_or_else(|err| panic!("failed to initialize two-tower embedder: {err}"));
+        Self::from_shared(shared)
+    }
 
+    /// 読み込み済みの Two-Tower を共有してランナーを作る（match_run_id はランナーごとに新規）
+    pub fn from_shared(two_tower: SharedTwoTower) -> Self {
         Self {
             engine: MatchingEngine::default(),
-            two_tower_config,
-            two_tower,
+            two_tower_config: two_tower.config,
+            two_tower: two_tower.embedder,
+            talent_recall: None,
+            experiment: None,
             engine_version: None,
-            config_version: None,
+            config_version: Some(active_rules().version.clone()),
             variant: None,
             match_run_id: run_id::generate(),
         }
@@ -262,21 +487,208 @@ impl MatchRunner {
         self
     }
 
+    /// 案件起点のランキングを A/B 実験にかける
+    ///
+    /// 案件 id ごとに割り当てたアームの重み・Two-Tower 設定・スキル閾値でランキングし、
+    /// アーム名を variant として記録する（`with_variant` より優先）。
+    /// 人材起点のランキングも案件ごとのアームで採点する。id の無い案件はランナー既定の設定のまま。
+    /// ここではモデルを読み込まない。Two-Tower を有効にするアームがあるのに embedder が無ければ
+    /// エラーを返すので、`SharedTwoTower::try_from_env(Some(&experiment))` で読み込んだものを渡す。
+    pub fn with_experiment(mut self, config: ExperimentConfig) -> Result<Self, TwoTowerError> {
+        if self.two_tower.is_none() {
+            if let Some(arm) = config
+                .arms
+                .iter()
+                .find(|arm| arm.two_tower_config(&self.two_tower_config).enabled)
+            {
+                return Err(TwoTowerError::MissingEmbedder(arm.name.clone()));
+            }
+        }
+
+        let arms = config
+            .arms
+            .iter()
+            .map(|arm| {
+                let defaults = MatchingEngineConfig::default();
+                let mut detailed = defaults.detailed;
+                if let Some(weights) = arm.weights.and_then(|w| w.normalized()) {
+                    detailed.weights = weights;
+                }
+                if let Some(total) = arm.total_score_weights {
+                    detailed.total_score_weights = total;
+                }
+                if let Some(threshold) = arm.skill_match_threshold {
+                    detailed.skill_match_minimum = threshold;
+                }
+                ArmRunner {
+                    name: arm.name.clone(),
+                    engine: MatchingEngine::new(MatchingEngineConfig {
+                        prefilter: PreFilterConfig {
+                            skill_match_threshold: arm.skill_match_threshold,
+                            ..defaults.prefilter
+                        },
+                        detailed,
+                    }),
+                    two_tower_config: arm.two_tower_config(&self.two_tower_config),
+                }
+            })
+            .collect();
+
+        self.experiment = Some(ActiveExperiment { config, arms });
+        Ok(self)
+    }
+
     pub fn with_match_run_id(mut self, value: impl Into<String>) -> Self {
         self.match_run_id = value.into();
         self
     }
 
+    pub fn match_run_id(&self) -> &str {
+        &self.match_run_id
+    }
+
+    pub fn two_tower(&self) -> Option<&dyn TwoTowerEmbedder> {
+        self.two_tower.as_deref()
+    }
+
+    pub fn experiment(&self) -> Option<&ExperimentConfig> {
+        self.experiment.as_ref().map(|e| &e.config)
+    }
+
+    /// 案件のランキングに使う prefilter（実験中ならアームのスキル閾値を反映したもの）
+    pub fn prefilter_for(&self, project: &Project) -> &EnhancedPreFilter {
+        self.context_for(project).engine.prefilter()
+    }
+
+    /// 案件に記録される variant（実験中ならアーム名）
+    pub fn variant_for(&self, project: &Project) -> Option<&str> {
+        self.context_for(project).variant
+    }
+
+    fn default_context(&self) -> RankingContext<'_> {
+        RankingContext {
+            engine: &self.engine,
+            two_tower_config: &self.two_tower_config,
+            two_tower: self
+                .two_tower
+                .as_deref()
+                .filter(|_| self.two_tower_config.enabled),
+            variant: self.variant.as_deref(),
+        }
+    }
+
+    fn context_for(&self, project: &Project) -> RankingContext<'_> {
+        let arm = self
+            .experiment
+            .as_ref()
+            .zip(project.id)
+            .map(|(experiment, id)| &experiment.arms[experiment.config.assign_index(id)]);
+        match arm {
+            Some(arm) => RankingContext {
+                engine: &arm.engine,
+                two_tower_config: &arm.two_tower_config,
+                two_tower: self
+                    .two_tower
+                    .as_deref()
+                    .filter(|_| arm.two_tower_config.enabled),
+                variant: Some(arm.name.as_str()),
+            },
+            None => self.default_context(),
+        }
+    }
+
+    /// Two-Tower の ANN recall ステージを差し替える（None で全人材を prefilter に渡す）
+    ///
+    /// 現在の embedder と異なるモデルで作ったインデックスは使わない。
+    pub fn set_talent_recall(&mut self, recall: Option<TalentRecall>) {
+        self.talent_recall = recall.filter(|recall| {
+            let usable = self
+                .two_tower
+                .as_deref()
+                .is_some_and(|embedder| recall.is_built_for(embedder));
+            if !usable {
+                tracing::warn!(
+                    "talent recall index does not match the active two-tower embedder; ignoring it"
+                );
+            }
+            usable
+        });
+    }
+
     /// ランキング結果を返す（永続化しない）
+    ///
+    /// recall ステージがあれば、案件埋め込みに近い上位 K 人材（+ インデックス外の人材）だけを評価する。
+    /// 実験中は案件に割り当てたアームの設定を使い、Two-Tower を使わないアームでは recall も掛けない。
     pub fn rank_talents(&self, project: &Project, talents: &[Talent]) -> Vec<RankedTalentMatch> {
-        self.engine.rank_talents_for_project(
+        let context = self.context_for(project);
+        let recalled;
+        let talents = match (&self.talent_recall, context.two_tower) {
+            (Some(recall), Some(embedder)) => {
+                recalled = recall.candidates(&embedder.embed_project(project), talents);
+                recalled.as_slice()
+            }
+            _ => talents,
+        };
+        context.engine.rank_talents_for_project(
             project,
             talents,
-            self.two_tower.as_deref(),
-            &self.two_tower_config,
+            context.two_tower,
+            context.two_tower_config,
         )
     }
 
+    /// 案件起点のランキングと同じ設定（実験アームを含む）で 1 組のマッチを説明する
+    pub fn explain(&self, project: &Project, talent: &Talent) -> MatchExplanation {
+        let context = self.context_for(project);
+        context
+            .engine
+            .explain(project, talent, context.two_tower, context.two_tower_config)
+    }
+
+    /// 人材に対する案件ランキングを返す（永続化しない）
+    ///
+    /// 実験中は案件ごとに割り当てたアームの設定で採点し、総合スコア順に並べ直す。
+    pub fn rank_projects(&self, talent: &Talent, projects: &[Project]) -> Vec<RankedTalentMatch> {
+        let Some(experiment) = &self.experiment else {
+            let context = self.default_context();
+            return context.engine.rank_projects_for_talent(
+                talent,
+                projects,
+                context.two_tower,
+                context.two_tower_config,
+            );
+        };
+
+        // アームごと（末尾は id の無い案件 = ランナー既定）に分けて採点する
+        let mut groups: Vec<Vec<Project>> = vec![Vec::new(); experiment.arms.len() + 1];
+        for project in projects {
+            let index = project
+                .id
+                .map(|id| experiment.config.assign_index(id))
+                .unwrap_or(experiment.arms.len());
+            groups[index].push(project.clone());
+        }
+
+        let mut ranked: Vec<RankedTalentMatch> = groups
+            .iter()
+            .filter_map(|group| Some((self.context_for(group.first()?), group)))
+            .flat_map(|(context, group)| {
+                context.engine.rank_projects_for_talent(
+                    talent,
+                    group,
+                    context.two_tower,
+                    context.two_tower_config,
+                )
+            })
+            .collect();
+        ranked.sort_by(|a, b| {
+            b.total_score
+                .partial_cmp(&a.total_score)
+                .unwrap_or(Ordering::Equal)
+        });
+        ranked
+    }
+
     /// interaction_logs へ INSERT するためのレコードを構築する
     pub fn build_interaction_logs(
         &self,
@@ -299,7 +711,7 @@ impl MatchRunner {
                     match_result_id,
                     self.engine_version.clone(),
                     self.config_version.clone(),
-                    self.variant.clone(),
+                    self.variant_for(project).map(str::to_string),
                 )
             })
             .collect()
@@ -311,49 +723,94 @@ impl MatchRunner {
         &self,
         project: &Project,
         talents: &[Talent],
-        match_result_ids: Option<&HashMap<i64, i64>>,
-    ) -> Vec<(i64, crate::db::match_results::MatchResultInsert)> {
+    ) -> Vec<(i64, MatchResultInsert)> {
         let ranked = self.rank_talents(project, talents);
 
         ranked
             .into_iter()
             .filter_map(|r| {
-                let talent_id = r.talent.id?;
-                let project_id = r.project.id?;
-                let _match_result_id = r
-                    .talent
-                    .id
-                    .and_then(|id| match_result_ids.and_then(|m| m.get(&id).copied()));
+                let insert = r.to_match_result_insert(
+                    self.match_run_id.clone(),
+                    self.engine_version.clone(),
+                    self.config_version.clone(),
+                )?;
+                Some((insert.talent_id, insert))
+            })
+            .collect()
+    }
 
-                let ko_reasons = r.ko.prioritized_reasons();
-
-                let score_breakdown =
-                    build_score_breakdown_json(&r.detailed_score, r.total_score, r.two_tower_score);
-
-                Some((
-                    talent_id,
-                    crate::db::match_results::MatchResultInsert {
-                        talent_id,
-                        project_id,
-                        is_knockout: r.ko.is_hard_knockout,
-                        ko_reasons: if ko_reasons.is_empty() {
-                            None
-                        } else {
-                            Some(json!(ko_reasons))
-                        },
-                        needs_manual_review: r.ko.needs_manual_review,
-                        score_total: Some(r.total_score),
-                        score_breakdown: Some(score_breakdown),
-                        engine_version: self.engine_version.clone(),
-                        rule_version: self.config_version.clone(),
-                        match_run_id: Some(self.match_run_id.clone()),
-                        created_at: None,
-                    },
-                ))
+    /// 1 回のランキングから match_results / interaction_logs の組を構築する（永続化は行わない）
+    pub fn build_run_records(&self, project: &Project, talents: &[Talent]) -> Vec<MatchRunRecord> {
+        self.records_from_ranked(&self.rank_talents(project, talents), true)
+    }
+
+    /// `project_arms=true` なら案件ごとの実験アームを variant に記録する（案件起点のランキング用）
+    fn records_from_ranked(
+        &self,
+        ranked: &[RankedTalentMatch],
+        project_arms: bool,
+    ) -> Vec<MatchRunRecord> {
+        ranked
+            .iter()
+            .filter_map(|r| {
+                let variant = if project_arms {
+                    self.variant_for(&r.project)
+                } else {
+                    self.variant.as_deref()
+                };
+                let match_result = r.to_match_result_insert(
+                    self.match_run_id.clone(),
+                    self.engine_version.clone(),
+                    self.config_version.clone(),
+                )?;
+                let interaction_log = r.to_interaction_log(
+                    self.match_run_id.clone(),
+                    None,
+                    self.engine_version.clone(),
+                    self.config_version.clone(),
+                    variant.map(str::to_string),
+                )?;
+
+                Some(MatchRunRecord {
+                    match_result,
+                    interaction_log,
+                })
             })
             .collect()
     }
 
+    /// ランキングを実行し、match_results と interaction_logs を同一トランザクションで保存する
+    pub async fn persist_project_run(
+        &self,
+        pool: &PgPool,
+        project: &Project,
+        talents: &[Talent],
+    ) -> Result<MatchRunWriteSummary, MatchRunStorageError> {
+        let records = self.build_run_records(project, talents);
+        insert_match_run(pool, &records).await
+    }
+
+    /// 人材起点でランキングし、案件起点と同じ形式で match_results / interaction_logs に保存する
+    pub async fn persist_talent_run(
+        &self,
+        pool: &PgPool,
+        talent: &Talent,
+        projects: &[Project],
+    ) -> Result<MatchRunWriteSummary, MatchRunStorageError> {
+        let records = self.records_from_ranked(&self.rank_projects(talent, projects), false);
+        insert_match_run(pool, &records).await
+    }
+
+    /// 実行済みの案件起点ランキング結果をこのランの match_run_id で保存し、採番された id を返す
+    pub async fn persist_ranked(
+        &self,
+        pool: &PgPool,
+        ranked: &[RankedTalentMatch],
+    ) -> Result<Vec<PersistedMatch>, MatchRunStorageError> {
+        let records = self.records_from_ranked(ranked, true);
+        insert_match_run_returning(pool, &records).await
+    }
+
     /// Two-Tower スコア・business_score を含む interaction_logs を保存する
     pub async fn insert_interaction_logs(
         &self,
@@ -394,6 +851,7 @@ fn build_score_breakdown_json(
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::two_tower::create_embedder;
     use serial_test::serial;
     use std::sync::Mutex;
 
@@ -533,6 +991,48 @@ mod tests {
             .all(|r| r.two_tower_embedder.as_deref() == Some("hash")));
     }
 
+    #[test]
+    fn reverse_ranking_scores_pairs_like_project_ranking() {
+        let engine = MatchingEngine::default();
+        let mut talent = base_talent();
+        talent.id = Some(1);
+
+        let mut project = base_project();
+        project.id = Some(10);
+        let mut hard_ko = base_project();
+        hard_ko.id = Some(11);
+        hard_ko.required_skills_keywords = vec!["Go".into()];
+
+        let two_tower_config = TwoTowerConfig {
+            enabled: true,
+            weight: 0.2,
+            ..Default::default()
+        };
+        let embedder = create_embedder("hash", two_tower_config.clone());
+
+        let reverse = engine.rank_projects_for_talent(
+            &talent,
+            &[project.clone(), hard_ko],
+            Some(embedder.as_ref()),
+            &two_tower_config,
+        );
+        let forward = engine.rank_talents_for_project(
+            &project,
+            std::slice::from_ref(&talent),
+            Some(embedder.as_ref()),
+            &two_tower_config,
+        );
+
+        assert_eq!(reverse.len(), 1);
+        assert_eq!(reverse[0].project.id, Some(10));
+        assert_eq!(reverse[0].total_score, forward[0].total_score);
+        assert_eq!(reverse[0].two_tower_score, forward[0].two_tower_score);
+        assert_eq!(
+            reverse[0].detailed_score.total,
+            forward[0].detailed_score.total
+        );
+    }
+
     #[test]
     fn converts_ranked_talent_to_interaction_log() {
         let engine = MatchingEngine::default();
@@ -616,6 +1116,171 @@ mod tests {
         );
     }
 
+    #[test]
+    #[serial]
+    fn shared_two_tower_reports_model_load_errors() {
+        with_env(
+            &[
+                ("TWO_TOWER_ENABLED", Some("1")),
+                ("TWO_TOWER_EMBEDDER", Some("onnx")),
+                ("TWO_TOWER_ONNX_PATH", Some("/nonexistent/two_tower.onnx")),
+            ],
+            || assert!(SharedTwoTower::try_from_env(None).is_err()),
+        );
+        with_env(
+            &[
+                ("TWO_TOWER_ENABLED", Some("1")),
+                ("TWO_TOWER_EMBEDDER", Some("onxx")),
+            ],
+            || {
+                assert!(matches!(
+                    SharedTwoTower::try_from_env(None),
+                    Err(TwoTowerError::UnknownEmbedder(name)) if name == "onxx"
+                ))
+            },
+        );
+        with_env(
+            &[
+                ("TWO_TOWER_ENABLED", Some("0")),
+                ("TWO_TOWER_EMBEDDER", Some("onnx")),
+            ],
+            || {
+                let shared = SharedTwoTower::try_from_env(None).unwrap();
+                assert!(shared.embedder.is_none());
+            },
+        );
+    }
+
+    #[test]
+    #[serial]
+    fn experiment_arms_rank_and_label_projects_by_assignment() {
+        with_env(
+            &[
+                ("TWO_TOWER_ENABLED", Some("0")),
+                ("TWO_TOWER_EMBEDDER", Some("hash")),
+            ],
+            || {
+                let experiment = ExperimentConfig::from_json(
+                    r#"{"name": "tt", "salt": "test", "arms": [
+                        {"name": "control"},
+                        {"name": "tt_50", "two_tower": {"weight": 0.5}}
+                    ]}"#,
+                )
+                .unwrap();
+                let control_id = (1..).find(|id| experiment.assign_index(*id) == 0).unwrap();
+                let treatment_id = (1..).find(|id| experiment.assign_index(*id) == 1).unwrap();
+
+                // embedder を読み込まずに Two-Tower のアームを渡すとエラー
+                assert!(matches!(
+                    MatchRunner::from_env().with_experiment(experiment.clone()),
+                    Err(TwoTowerError::MissingEmbedder(arm)) if arm == "tt_50"
+                ));
+
+                let shared = SharedTwoTower::try_from_env(Some(&experiment)).unwrap();
+                let runner = MatchRunner::from_shared(shared)
+                    .with_variant("manual")
+                    .with_experiment(experiment)
+                    .unwrap();
+                assert!(runner.two_tower().is_some());
+
+                let mut talent = base_talent();
+                talent.id = Some(7);
+                let project_for = |id| Project {
+                    id: Some(id),
+                    ..base_project()
+                };
+
+                let control = runner.build_run_records(&project_for(control_id), &[talent.clone()]);
+                assert_eq!(control.len(), 1);
+                assert_eq!(
+                    control[0].interaction_log.variant.as_deref(),
+                    Some("control")
+                );
+                assert_eq!(control[0].interaction_log.two_tower_score, None);
+
+                let treatment =
+                    runner.build_run_records(&project_for(treatment_id), &[talent.clone()]);
+                assert_eq!(
+                    treatment[0].interaction_log.variant.as_deref(),
+                    Some("tt_50")
+                );
+                assert!(treatment[0].interaction_log.two_tower_score.is_some());
+
+                // id の無い案件は実験対象外
+                assert_eq!(runner.variant_for(&base_project()), Some("manual"));
+
+                // 人材起点のランキングも案件ごとのアームで採点する
+                let reverse = runner.rank_projects(
+                    &talent,
+                    &[project_for(control_id), project_for(treatment_id)],
+                );
+                assert_eq!(reverse.len(), 2);
+                let scored = |id| {
+                    reverse
+                        .iter()
+                        .find(|r| r.project.id == Some(id))
+                        .unwrap()
+                        .two_tower_score
+                };
+                assert_eq!(scored(control_id), None);
+                assert!(scored(treatment_id).is_some());
+                assert!(reverse[0].total_score >= reverse[1].total_score);
+            },
+        );
+    }
+
+    #[test]
+    #[serial]
+    fn talent_recall_limits_ranked_candidates_to_nearest_and_unindexed() {
+        use crate::two_tower::{HnswConfig, HnswIndex};
+
+        with_env(
+            &[
+                ("TWO_TOWER_ENABLED", Some("1")),
+                ("TWO_TOWER_DIMENSION", Some("64")),
+                ("TWO_TOWER_EMBEDDER", Some("hash")),
+            ],
+            || {
+                let project = base_project();
+                let skills = ["graphql", "java", "cobol", "sap", "php"];
+                let talents: Vec<Talent> = skills
+                    .iter()
+                    .enumerate()
+                    .map(|(i, skill)| Talent {
+                        id: Some(i as i64 + 1),
+                        possessed_skills_keywords: vec!["rust".into(), (*skill).into()],
+                        ..base_talent()
+                    })
+                    .collect();
+
+                let mut runner = MatchRunner::from_env();
+                let embedder = runner.two_tower().unwrap();
+                let index = HnswIndex::build(
+                    embedder.dimension(),
+                    HnswConfig::default(),
+                    talents[..4]
+                        .iter()
+                        .zip(embedder.embed_talents(&talents[..4]))
+                        .map(|(t, e)| (t.id.unwrap(), e.vector)),
+                )
+                .unwrap();
+                let recall = TalentRecall::new(index, 2, embedder.name(), embedder.version());
+                runner.set_talent_recall(Some(recall));
+
+                let ranked = runner.rank_talents(&project, &talents);
+                let ids: Vec<i64> = ranked.iter().filter_map(|r| r.talent.id).collect();
+                // 上位 2 件 + インデックス外の talent 5
+                assert_eq!(ids.len(), 3);
+                assert!(ids.contains(&5));
+
+                let stale =
+                    TalentRecall::new(HnswIndex::new(64, HnswConfig::default()), 2, "hash", "v0");
+                runner.set_talent_recall(Some(stale));
+                assert_eq!(runner.rank_talents(&project, &talents).len(), 5);
+            },
+        );
+    }
+
     #[test]
     #[serial]
     fn build_interaction_logs_uses_match_result_ids_when_provided() {
@@ -651,7 +1316,7 @@ mod tests {
                 .with_engine_version("engine_v1")
                 .with_config_version("rule_v1");
 
-            let inserts = runner.build_match_result_inserts(&project, &[talent], None);
+            let inserts = runner.build_match_result_inserts(&project, &[talent]);
             assert_eq!(inserts.len(), 1);
 
             let (_, insert) = &inserts[0];
@@ -679,4 +1344,42 @@ mod tests {
             );
         });
     }
+
+    #[test]
+    #[serial]
+    fn build_run_records_pairs_match_results_with_interaction_logs() {
+        let mut project = base_project();
+        project.id = Some(5);
+
+        let mut talent_a = base_talent();
+        talent_a.id = Some(501);
+        let mut talent_b = base_talent();
+        talent_b.id = Some(502);
+        let anonymous = base_talent();
+
+        let runner = MatchRunner::from_env()
+            .with_engine_version("engine_v1")
+            .with_match_run_id("01HRUN0000000000000000000");
+
+        let records = runner.build_run_records(&project, &[talent_a, talent_b, anonymous]);
+
+        assert_eq!(records.len(), 2);
+        for record in &records {
+            assert_eq!(
+                record.match_result.talent_id,
+                record.interaction_log.talent_id
+            );
+            assert_eq!(record.match_result.project_id, 5);
+            assert_eq!(
+                record.match_result.match_run_id.as_deref(),
+                Some(runner.match_run_id())
+            );
+            assert_eq!(record.interaction_log.match_run_id, runner.match_run_id());
+            assert_eq!(record.interaction_log.match_result_id, None);
+            assert_eq!(
+                record.interaction_log.engine_version.as_deref(),
+                Some("engine_v1")
+            );
+        }
+    }
 }
@@ -1,6 +1,10 @@
 use std::cmp::Ordering;
 
-use super::{ko_checks::run_all_ko_checks, scoring::calculate_prefilter_score};
+use super::{
+    ko_checks::run_all_ko_checks_with_skill_threshold,
+    scoring::{BusinessRulesEngine, MatchingConfig},
+};
+use crate::rules::active_rules;
 use crate::{
     matching::ko_unified::KnockoutResultV2, matching::scoring::MatchScore, Project, Talent,
 };
@@ -11,13 +15,18 @@ pub struct PreFilterConfig {
     pub max_candidates: usize,
     /// 通過させる最小スコア（0.0〜1.0）。これ未満は除外。
     pub min_score: f64,
+    /// 必須スキル一致率の KO 閾値（None ならルールの skills.match_threshold）
+    pub skill_match_threshold: Option<f64>,
 }
 
 impl Default for PreFilterConfig {
+    /// ルールの prefilter 節から作る
     fn default() -> Self {
+        let rules = active_rules();
         Self {
-            max_candidates: 500,
-            min_score: 0.1,
+            max_candidates: rules.rules.prefilter.max_candidates,
+            min_score: rules.rules.prefilter.min_score,
+            skill_match_threshold: None,
         }
     }
 }
@@ -32,32 +41,41 @@ pub struct PrefilterCandidate {
 
 pub struct EnhancedPreFilter {
     config: PreFilterConfig,
+    scorer: BusinessRulesEngine,
 }
 
 impl EnhancedPreFilter {
     pub fn new(config: PreFilterConfig) -> Self {
-        Self { config }
+        let mut scoring = MatchingConfig::prefilter();
+        if let Some(threshold) = config.skill_match_threshold {
+            scoring.skill_match_minimum = threshold;
+        }
+        Self {
+            config,
+            scorer: BusinessRulesEngine::new(scoring),
+        }
     }
 
     pub fn default() -> Self {
         Self::new(PreFilterConfig::default())
     }
 
+    pub fn config(&self) -> &PreFilterConfig {
+        &self.config
+    }
+
     /// KO 判定と prefilter スコアをまとめて実行し、合格した場合のみ返す
     pub fn evaluate_candidate(
         &self,
         talent: &Talent,
         project: &Project,
     ) -> Option<PrefilterCandidate> {
-        let ko = run_all_ko_checks(project, talent);
-        if ko.is_hard_knockout {
-            return None;
-        }
-
-        let match_score = calculate_prefilter_score(project, talent);
-        if match_score.total <= self.config.min_score {
-            return None;
-        }
+        let ko = run_all_ko_checks_with_skill_threshold(
+            project,
+            talent,
+            self.config.skill_match_threshold,
+        );
+        let match_score = self.score_after_ko(talent, project, &ko)?;
 
         Some(PrefilterCandidate {
             project: project.clone(),
@@ -67,6 +85,21 @@ impl EnhancedPreFilter {
         })
     }
 
+    /// 判定済みの KO 結果を使って prefilter スコアを付ける（HardKo・最小スコア以下は None）
+    pub fn score_after_ko(
+        &self,
+        talent: &Talent,
+        project: &Project,
+        ko: &KnockoutResultV2,
+    ) -> Option<MatchScore> {
+        if ko.is_hard_knockout {
+            return None;
+        }
+
+        let match_score = self.scorer.calculate_match_score(project, talent);
+        (match_score.total > self.config.min_score).then_some(match_score)
+    }
+
     /// 事前フィルタリング（候補絞り込み）
     pub fn filter_candidates(
         &self,
@@ -140,6 +173,7 @@ mod tests {
         let filter = EnhancedPreFilter::new(PreFilterConfig {
             max_candidates: 1,
             min_score: 0.0,
+            ..PreFilterConfig::default()
         });
 
         let results =
@@ -150,6 +184,28 @@ mod tests {
         assert!(results[0].score >= 0.1);
     }
 
+    #[test]
+    fn skill_threshold_override_controls_required_skill_ko() {
+        let mut talent = base_talent();
+        talent.possessed_skills_keywords = vec!["rust".into()];
+
+        let strict = EnhancedPreFilter::new(PreFilterConfig {
+            skill_match_threshold: Some(0.8),
+            ..PreFilterConfig::default()
+        });
+        assert!(strict
+            .evaluate_candidate(&talent, &base_project())
+            .is_none());
+
+        let lenient = EnhancedPreFilter::new(PreFilterConfig {
+            skill_match_threshold: Some(0.5),
+            ..PreFilterConfig::default()
+        });
+        assert!(lenient
+            .evaluate_candidate(&talent, &base_project())
+            .is_some());
+    }
+
     #[test]
     fn keeps_softko_candidates_with_scores() {
         let filter = EnhancedPreFilter::default();
@@ -2,19 +2,21 @@ use super::{
     ko_checks::{check_availability_ko, check_flow_limit_ko},
     ko_unified::KoDecision,
     location::evaluate_location,
-    skills::{check_preferred_skills, check_required_skills},
-    weights::Weights,
+    skills::{check_preferred_skills, check_required_skills_with_threshold},
+    weights::{active_weights_file, Weights, WeightsFile},
 };
 use crate::{
     corrections::{nationality::is_japanese_nationality, normalize_contract_type_for_matching},
+    rules::{active_rules, MatchRules},
     two_tower::TwoTowerConfig,
     Project, Talent,
 };
 use chrono::{Datelike, Utc};
+use serde::{Deserialize, Serialize};
 
 use super::weights::{DETAILED_WEIGHTS, PREFILTER_WEIGHTS};
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
 pub struct TotalScoreWeights {
     pub business: f64,
     pub semantic: f64,
@@ -99,13 +101,30 @@ pub struct MatchingConfig {
 }
 
 impl Default for MatchingConfig {
+    /// MATCHING_WEIGHTS_PATH の学習済み重みがあればそれを、無ければコンパイル時定数を使う
     fn default() -> Self {
+        let config = Self::builtin();
+        match active_weights_file() {
+            Ok(Some(file)) => config.with_weights_file(file),
+            _ => config,
+        }
+    }
+}
+
+impl MatchingConfig {
+    /// 定数重みで組み立てた設定（学習済み重みファイルを無視する。閾値は現在のルール）
+    pub fn builtin() -> Self {
+        Self::from_rules(&active_rules().rules)
+    }
+
+    /// ルールファイルの閾値と定数重みで組み立てる
+    pub fn from_rules(rules: &MatchRules) -> Self {
         Self {
             weights: DETAILED_WEIGHTS,
-            tanka_profit_minimum: 5.0,
-            tanka_profit_optimal: 0.25,
-            skill_match_minimum: env_skill_threshold(),
-            experience_buffer_years: 0.5,
+            tanka_profit_minimum: rules.scoring.tanka_profit_minimum,
+            tanka_profit_optimal: rules.scoring.tanka_profit_optimal,
+            skill_match_minimum: rules.skills.match_threshold,
+            experience_buffer_years: rules.scoring.experience_buffer_years,
             total_score_weights: TotalScoreWeights {
                 business: 1.0,
                 semantic: 0.0,
@@ -116,16 +135,28 @@ impl Default for MatchingConfig {
             score_version: "v1-business-only".to_string(),
         }
     }
-}
 
-impl MatchingConfig {
+    /// 学習済み重みファイルの detailed / total_score_weights を適用する（score_version に version を残す）
+    pub fn with_weights_file(self, file: &WeightsFile) -> Self {
+        Self {
+            weights: file.detailed,
+            total_score_weights: file.total_score_weights.unwrap_or(self.total_score_weights),
+            score_version: format!("v1-weights-{}", file.version),
+            ..self
+        }
+    }
+
     pub fn detailed() -> Self {
         Self::default()
     }
 
     pub fn prefilter() -> Self {
+        let prefilter = active_weights_file()
+            .ok()
+            .flatten()
+            .and_then(|file| file.prefilter);
         Self {
-            weights: PREFILTER_WEIGHTS,
+            weights: prefilter.unwrap_or(PREFILTER_WEIGHTS),
             ..Self::default()
         }
     }
@@ -177,6 +208,10 @@ impl BusinessRulesEngine {
         Self { config }
     }
 
+    pub fn config(&self) -> &MatchingConfig {
+        &self.config
+    }
+
     /// 総合スコア計算（Phase2 詳細スコアリング）
     pub fn calculate_match_score(&self, project: &Project, talent: &Talent) -> MatchScore {
         let tanka = self.score_tanka(project, talent);
@@ -329,9 +364,10 @@ impl BusinessRulesEngine {
     }
 
     fn score_skills(&self, project: &Project, talent: &Talent) -> ScoringResult {
-        let required = check_required_skills(
+        let required = check_required_skills_with_threshold(
             &project.required_skills_keywords,
             &talent.possessed_skills_keywords,
+            Some(self.config.skill_match_minimum),
         );
 
         if required.requires_manual_review {
@@ -629,13 +665,6 @@ impl BusinessRulesEngine {
     }
 }
 
-fn env_skill_threshold() -> f64 {
-    std::env::var("SR_SKILL_MATCH_THRESHOLD")
-        .ok()
-        .and_then(|s| s.parse().ok())
-        .unwrap_or(0.3)
-}
-
 fn status_from_score(score: f64, unknown: bool) -> &'static str {
     if unknown {
         "UNKNOWN"
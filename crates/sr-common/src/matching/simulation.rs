@@ -0,0 +1,349 @@
+//! 案件条件の what-if シミュレーション
+//!
+//! 案件フィールドを書き換えた場合に、人材プールに対する KO + スコアリングの結果が
+//! どう変わるか（新たに通過・脱落する候補と、判定が変わった KO ルール）を比較する。永続化はしない。
+
+use std::cmp::Ordering;
+
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use thiserror::Error;
+
+use crate::{Project, Talent};
+
+use super::explain::RuleOutcome;
+use super::ko_checks::run_all_ko_checks_with_skill_threshold;
+use super::ko_unified::{KnockoutResultV2, KoDecision};
+use super::prefilter::EnhancedPreFilter;
+use super::scoring::calculate_detailed_score;
+
+/// 書き換えを許さないフィールド
+const IMMUTABLE_FIELDS: &[&str] = &["id"];
+
+#[derive(Debug, Error)]
+pub enum ProjectPatchError {
+    #[error("unknown project field: {0}")]
+    UnknownField(String),
+    #[error("project field cannot be changed: {0}")]
+    ImmutableField(String),
+    #[error("invalid project patch: {0}")]
+    Invalid(#[from] serde_json::Error),
+}
+
+/// `Project` の JSON 表現にトップレベルのフィールド単位で上書きする（null は未設定に戻す）
+pub fn apply_project_patch(
+    project: &Project,
+    patch: &Map<String, Value>,
+) -> Result<Project, ProjectPatchError> {
+    let Value::Object(mut fields) = serde_json::to_value(project)? else {
+        unreachable!("Project serializes to a JSON object");
+    };
+    for (key, value) in patch {
+        if IMMUTABLE_FIELDS.contains(&key.as_str()) {
+            return Err(ProjectPatchError::ImmutableField(key.clone()));
+        }
+        let Some(slot) = fields.get_mut(key) else {
+            return Err(ProjectPatchError::UnknownField(key.clone()));
+        };
+        *slot = value.clone();
+    }
+    Ok(serde_json::from_value(Value::Object(fields))?)
+}
+
+/// 1 人の人材に対する評価結果
+#[derive(Debug, Clone)]
+struct TalentEvaluation {
+    passed: bool,
+    needs_manual_review: bool,
+    score: Option<f64>,
+    ko: KnockoutResultV2,
+}
+
+/// prefilter（KO + 粗選別スコア）を通過した候補のみ詳細スコアを付ける。
+/// `include_softko=false` なら SoftKo の候補は通過扱いにしない（候補一覧の既定と同じ）
+fn evaluate(
+    prefilter: &EnhancedPreFilter,
+    project: &Project,
+    talent: &Talent,
+    include_softko: bool,
+) -> TalentEvaluation {
+    let ko = run_all_ko_checks_with_skill_threshold(
+        project,
+        talent,
+        prefilter.config().skill_match_threshold,
+    );
+    let score = prefilter
+        .score_after_ko(talent, project, &ko)
+        .map(|_| calculate_detailed_score(project, talent).total);
+
+    TalentEvaluation {
+        passed: score.is_some() && (include_softko || !ko.needs_manual_review),
+        needs_manual_review: ko.needs_manual_review,
+        score,
+        ko,
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct RuleChange {
+    pub rule: String,
+    pub before: RuleOutcome,
+    pub after: RuleOutcome,
+    /// 変更後の判定理由（Pass になった場合は変更前の理由）
+    pub reason: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct CandidateChange {
+    pub talent_id: i64,
+    /// 元の条件での詳細スコア（prefilter で落ちた場合は None）
+    pub baseline_score: Option<f64>,
+    /// 変更後の条件での詳細スコア
+    pub simulated_score: Option<f64>,
+    /// 変更後の条件で SoftKo が残るか
+    pub needs_manual_review: bool,
+    /// 判定が変わった KO ルール（空ならスコアの閾値または SoftKo の扱いで変化）
+    pub rule_changes: Vec<RuleChange>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ProjectSimulation {
+    pub talents_evaluated: usize,
+    pub baseline_passing: usize,
+    pub simulated_passing: usize,
+    pub newly_passing_count: usize,
+    pub newly_failing_count: usize,
+    /// 変更後の詳細スコア順（`limit` 件まで）
+    pub newly_passing: Vec<CandidateChange>,
+    /// 元の詳細スコア順（`limit` 件まで）
+    pub newly_failing: Vec<CandidateChange>,
+}
+
+/// ルール名で突き合わせる。片側にしか無いルール（クライアントプロファイルの `client_rule` など）は
+/// 無い側を Pass として扱う
+fn rule_changes(before: &KnockoutResultV2, after: &KnockoutResultV2) -> Vec<RuleChange> {
+    fn decision<'a>(result: &'a KnockoutResultV2, name: &str) -> Option<&'a KoDecision> {
+        result
+            .decisions
+            .iter()
+            .find(|(rule, _)| *rule == name)
+            .map(|(_, decision)| decision)
+    }
+    fn outcome(decision: Option<&KoDecision>) -> RuleOutcome {
+        decision.map(RuleOutcome::from).unwrap_or(RuleOutcome::Pass)
+    }
+
+    let mut names: Vec<&'static str> = before.decisions.iter().map(|(name, _)| *name).collect();
+    for (name, _) in &after.decisions {
+        if !names.contains(name) {
+            names.push(name);
+        }
+    }
+
+    names
+        .into_iter()
+        .filter_map(|name| {
+            let (old, new) = (decision(before, name), decision(after, name));
+            let (before, after) = (outcome(old), outcome(new));
+            (before != after).then(|| RuleChange {
+                rule: name.to_string(),
+                before,
+                after,
+                reason: new
+                    .and_then(KoDecision::reason)
+                    .or_else(|| old.and_then(KoDecision::reason))
+                    .map(str::to_string),
+            })
+        })
+        .collect()
+}
+
+/// 元の案件と書き換えた案件で人材プールを評価し、通過状況の差分を返す（id の無い人材は対象外）
+pub fn simulate_project_change(
+    prefilter: &EnhancedPreFilter,
+    baseline: &Project,
+    simulated: &Project,
+    talents: &[Talent],
+    include_softko: bool,
+    limit: usize,
+) -> ProjectSimulation {
+    let mut talents_evaluated = 0;
+    let mut baseline_passing = 0;
+    let mut simulated_passing = 0;
+    let mut newly_passing = Vec::new();
+    let mut newly_failing = Vec::new();
+
+    for talent in talents {
+        let Some(talent_id) = talent.id else {
+            continue;
+        };
+        talents_evaluated += 1;
+
+        let before = evaluate(prefilter, baseline, talent, include_softko);
+        let after = evaluate(prefilter, simulated, talent, include_softko);
+        baseline_passing += usize::from(before.passed);
+        simulated_passing += usize::from(after.passed);
+        if before.passed == after.passed {
+            continue;
+        }
+
+        let change = CandidateChange {
+            talent_id,
+            baseline_score: before.score,
+            simulated_score: after.score,
+            needs_manual_review: after.needs_manual_review,
+            rule_changes: rule_changes(&before.ko, &after.ko),
+        };
+        if after.passed {
+            newly_passing.push(change);
+        } else {
+            newly_failing.push(change);
+        }
+    }
+
+    fn by_score_desc(a: Option<f64>, b: Option<f64>) -> Ordering {
+        b.unwrap_or_default()
+            .partial_cmp(&a.unwrap_or_default())
+            .unwrap_or(Ordering::Equal)
+    }
+    newly_passing.sort_by(|a, b| {
+        by_score_desc(a.simulated_score, b.simulated_score).then(a.talent_id.cmp(&b.talent_id))
+    });
+    newly_failing.sort_by(|a, b| {
+        by_score_desc(a.baseline_score, b.baseline_score).then(a.talent_id.cmp(&b.talent_id))
+    });
+
+    let newly_passing_count = newly_passing.len();
+    let newly_failing_count = newly_failing.len();
+    newly_passing.truncate(limit);
+    newly_failing.truncate(limit);
+
+    ProjectSimulation {
+        talents_evaluated,
+        baseline_passing,
+        simulated_passing,
+        newly_passing_count,
+        newly_failing_count,
+        newly_passing,
+        newly_failing,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn talent(id: i64, desired: u32) -> Talent {
+        Talent {
+            id: Some(id),
+            desired_price_min: Some(desired),
+            possessed_skills_keywords: vec!["Rust".into()],
+            residential_todofuken: Some("東京都".into()),
+            ..Talent::default()
+        }
+    }
+
+    fn project() -> Project {
+        Project {
+            id: Some(1),
+            monthly_tanka_min: Some(60),
+            monthly_tanka_max: Some(70),
+            required_skills_keywords: vec!["Rust".into()],
+            work_todofuken: Some("東京都".into()),
+            ..Project::default()
+        }
+    }
+
+    #[test]
+    fn patch_overwrites_known_fields_only() {
+        let patch = json!({ "monthly_tanka_max": 85, "work_area": null });
+        let patched = apply_project_patch(&project(), patch.as_object().unwrap()).unwrap();
+        assert_eq!(patched.monthly_tanka_max, Some(85));
+        assert_eq!(patched.id, Some(1));
+
+        let unknown = json!({ "tanka_cap": 85 });
+        assert!(matches!(
+            apply_project_patch(&project(), unknown.as_object().unwrap()),
+            Err(ProjectPatchError::UnknownField(_))
+        ));
+        let id = json!({ "id": 2 });
+        assert!(matches!(
+            apply_project_patch(&project(), id.as_object().unwrap()),
+            Err(ProjectPatchError::ImmutableField(_))
+        ));
+        let invalid = json!({ "monthly_tanka_max": "85万" });
+        assert!(apply_project_patch(&project(), invalid.as_object().unwrap()).is_err());
+    }
+
+    #[test]
+    fn raising_tanka_cap_reports_newly_passing_candidates() {
+        let baseline = project();
+        let simulated = Project {
+            monthly_tanka_max: Some(85),
+            ..baseline.clone()
+        };
+        // 希望 75 万は上限 70 万では利益不足、85 万なら通過
+        let talents = vec![talent(1, 55), talent(2, 75), Talent::default()];
+
+        let result = simulate_project_change(
+            &EnhancedPreFilter::default(),
+            &baseline,
+            &simulated,
+            &talents,
+            true,
+            10,
+        );
+
+        assert_eq!(result.talents_evaluated, 2);
+        assert_eq!(result.newly_failing_count, 0);
+        assert_eq!(result.newly_passing_count, 1);
+        let change = &result.newly_passing[0];
+        assert_eq!(change.talent_id, 2);
+        assert_eq!(change.baseline_score, None);
+        assert!(change.simulated_score.is_some());
+        assert_eq!(change.rule_changes.len(), 1);
+        let tanka = &change.rule_changes[0];
+        assert_eq!(tanka.rule, "tanka");
+        assert_eq!(
+            (tanka.before, tanka.after),
+            (RuleOutcome::HardKo, RuleOutcome::Pass)
+        );
+        assert!(tanka
+            .reason
+            .as_deref()
+            .is_some_and(|r| r.contains("tanka_ko")));
+        assert_eq!(result.simulated_passing, result.baseline_passing + 1);
+    }
+
+    #[test]
+    fn rule_changes_match_rules_by_name() {
+        let hard = |reason: &str| KoDecision::HardKo {
+            reason: reason.into(),
+        };
+        let before =
+            KnockoutResultV2::new(vec![("tanka", hard("tanka_ko")), ("age", KoDecision::Pass)]);
+        // 変更後だけクライアントプロファイルが当たり、client_rule が増える
+        let after = KnockoutResultV2::new(vec![
+            ("tanka", KoDecision::Pass),
+            ("client_rule", hard("client_rule_violated: [x] y")),
+            ("age", KoDecision::Pass),
+        ]);
+
+        let changes = rule_changes(&before, &after);
+        let rules: Vec<&str> = changes.iter().map(|c| c.rule.as_str()).collect();
+        assert_eq!(rules, vec!["tanka", "client_rule"]);
+        assert_eq!(
+            (changes[1].before, changes[1].after),
+            (RuleOutcome::Pass, RuleOutcome::HardKo)
+        );
+
+        let reverse = rule_changes(&after, &before);
+        assert_eq!(reverse.len(), 2);
+        assert_eq!(reverse[1].after, RuleOutcome::Pass);
+        assert!(reverse[1]
+            .reason
+            .as_deref()
+            .is_some_and(|r| r.contains("client_rule_violated")));
+    }
+}
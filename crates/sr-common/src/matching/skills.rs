@@ -1,12 +1,10 @@
 use std::collections::HashSet;
 
+use crate::rules::active_rules;
 use crate::skill_normalizer::normalize_skill_set;
 
 fn get_skill_match_threshold() -> f64 {
-    std::env::var("SR_SKILL_MATCH_THRESHOLD")
-        .ok()
-        .and_then(|s| s.parse().ok())
-        .unwrap_or(0.3)
+    active_rules().rules.skills.match_threshold
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -65,7 +63,8 @@ pub fn check_preferred_skills(
     }
 }
 
-fn check_required_skills_with_threshold(
+/// 必須スキルのマッチング判定（閾値を明示。None ならルールの skills.match_threshold）
+pub fn check_required_skills_with_threshold(
     project_skills: &[String],
     talent_skills: &[String],
     threshold_override: Option<f64>,
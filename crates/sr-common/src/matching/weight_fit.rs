@@ -0,0 +1,377 @@
+//! フィードバックからの重み学習（非負制約付きロジスティック回帰）
+//!
+//! `match_results.score_breakdown` の要素スコア（tanka, location, skills, experience, contract, other）を
+//! 特徴量、`training_labels.label`（0.0〜1.0）をソフトターゲットとして
+//! `sigmoid(b + Σ β_i x_i)`（β_i ≥ 0）を射影勾配法で当てはめ、`w_i = β_i / Σβ` を重みとする。
+//! 非負制約により「スコアが高いほど良い」という各要素の向きを保つ。
+
+use chrono::NaiveDate;
+use serde::Serialize;
+use thiserror::Error;
+
+use super::scoring::{calculate_weighted_total_score, TotalScoreWeights};
+use super::weights::Weights;
+
+/// `Weights` と同じ並び
+pub const FACTOR_NAMES: [&str; 6] = [
+    "tanka",
+    "location",
+    "skills",
+    "experience",
+    "contract",
+    "other",
+];
+
+#[derive(Debug, Error, PartialEq)]
+pub enum WeightFitError {
+    #[error("no labeled pairs to fit")]
+    Empty,
+    #[error("labels have no variance (all {0}); nothing to learn")]
+    ConstantLabels(f64),
+    #[error("no factor is positively associated with the labels; keep the current weights")]
+    NoSignal,
+}
+
+/// 1 ペア分の要素スコアとラベル
+#[derive(Debug, Clone, PartialEq)]
+pub struct FactorExample {
+    pub interaction_id: i64,
+    pub run_date: NaiveDate,
+    pub label: f64,
+    /// `FACTOR_NAMES` 順の 0.0〜1.0 スコア
+    pub factors: [f64; 6],
+    pub semantic: Option<f64>,
+    pub historical: Option<f64>,
+}
+
+impl FactorExample {
+    /// score_breakdown JSON から要素スコアを取り出す（要素が 1 つでも欠けていれば None）
+    pub fn from_breakdown(
+        interaction_id: i64,
+        run_date: NaiveDate,
+        label: f64,
+        breakdown: &serde_json::Value,
+    ) -> Option<Self> {
+        let mut factors = [0.0; 6];
+        for (slot, name) in factors.iter_mut().zip(FACTOR_NAMES) {
+            *slot = breakdown.get(name)?.as_f64()?;
+        }
+        let optional = |key: &str| breakdown.get(key).and_then(serde_json::Value::as_f64);
+        Some(Self {
+            interaction_id,
+            run_date,
+            label,
+            factors,
+            semantic: optional("semantic_score"),
+            historical: optional("historical_score"),
+        })
+    }
+
+    pub fn business_score(&self, weights: &Weights) -> f64 {
+        self.factors
+            .iter()
+            .zip(weights.as_array())
+            .map(|(x, w)| x * w)
+            .sum()
+    }
+
+    /// マッチ実行時と同じ合成式での総合スコア（Two-Tower 無し、欠損成分は 0.0）
+    pub fn total_score(&self, weights: &Weights, total: &TotalScoreWeights) -> f64 {
+        calculate_weighted_total_score(
+            self.business_score(weights),
+            self.semantic.unwrap_or(0.0),
+            self.historical.unwrap_or(0.0),
+            total,
+        )
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LogisticConfig {
+    pub iterations: usize,
+    pub learning_rate: f64,
+    /// 係数（切片以外）への L2 正則化
+    pub l2: f64,
+}
+
+impl Default for LogisticConfig {
+    fn default() -> Self {
+        Self {
+            iterations: 3000,
+            learning_rate: 0.5,
+            l2: 1e-4,
+        }
+    }
+}
+
+/// 係数が非負のロジスティック回帰
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct NonNegativeLogistic {
+    pub coefficients: Vec<f64>,
+    pub intercept: f64,
+}
+
+fn sigmoid(z: f64) -> f64 {
+    1.0 / (1.0 + (-z).exp())
+}
+
+impl NonNegativeLogistic {
+    /// 全件勾配の射影勾配法（決定論的）。`features` は行ごとに同じ次元
+    pub fn fit(features: &[Vec<f64>], labels: &[f64], config: &LogisticConfig) -> Self {
+        let dim = features.first().map_or(0, Vec::len);
+        let n = features.len().max(1) as f64;
+        let mean_label = labels.iter().sum::<f64>() / n;
+        let mut model = Self {
+            coefficients: vec![0.0; dim],
+            // 係数 0 のときの最尤解から始める
+            intercept: (mean_label.clamp(1e-6, 1.0 - 1e-6)
+                / (1.0 - mean_label.clamp(1e-6, 1.0 - 1e-6)))
+            .ln(),
+        };
+
+        let mut grad = vec![0.0; dim];
+        for _ in 0..config.iterations {
+            grad.iter_mut().for_each(|g| *g = 0.0);
+            let mut grad_intercept = 0.0;
+            for (x, &y) in features.iter().zip(labels) {
+                let err = model.predict(x) - y;
+                grad_intercept += err;
+                for (g, xi) in grad.iter_mut().zip(x) {
+                    *g += err * xi;
+                }
+            }
+            model.intercept -= config.learning_rate * grad_intercept / n;
+            for (beta, g) in model.coefficients.iter_mut().zip(&grad) {
+                let step = g / n + config.l2 * *beta;
+                *beta = (*beta - config.learning_rate * step).max(0.0);
+            }
+        }
+        model
+    }
+
+    pub fn predict(&self, x: &[f64]) -> f64 {
+        let z = self.intercept
+            + self
+                .coefficients
+                .iter()
+                .zip(x)
+                .map(|(b, xi)| b * xi)
+                .sum::<f64>();
+        sigmoid(z)
+    }
+
+    /// 平均交差エントロピー（ラベルは 0.0〜1.0 のソフトターゲット）
+    pub fn log_loss(&self, features: &[Vec<f64>], labels: &[f64]) -> Option<f64> {
+        if features.is_empty() {
+            return None;
+        }
+        let total: f64 = features
+            .iter()
+            .zip(labels)
+            .map(|(x, &y)| {
+                let p = self.predict(x).clamp(1e-12, 1.0 - 1e-12);
+                -(y * p.ln() + (1.0 - y) * (1.0 - p).ln())
+            })
+            .sum();
+        Some(total / features.len() as f64)
+    }
+}
+
+/// 学習結果
+#[derive(Debug, Clone, PartialEq)]
+pub struct FittedWeights {
+    pub detailed: Weights,
+    pub total_score_weights: TotalScoreWeights,
+    pub detailed_model: NonNegativeLogistic,
+    /// semantic / historical が全ペアで観測できた場合のみ学習する
+    pub total_model: Option<NonNegativeLogistic>,
+}
+
+fn check_labels(examples: &[FactorExample]) -> Result<(), WeightFitError> {
+    let first = examples.first().ok_or(WeightFitError::Empty)?.label;
+    if examples
+        .iter()
+        .all(|e| (e.label - first).abs() < f64::EPSILON)
+    {
+        return Err(WeightFitError::ConstantLabels(first));
+    }
+    Ok(())
+}
+
+/// 係数を合計 1.0 の重みに変換する
+fn to_shares(coefficients: &[f64]) -> Option<Vec<f64>> {
+    let sum: f64 = coefficients.iter().sum();
+    (sum > 1e-9).then(|| coefficients.iter().map(|b| b / sum).collect())
+}
+
+/// detailed の要素重みと total_score_weights を学習する
+pub fn fit_weights(
+    examples: &[FactorExample],
+    config: &LogisticConfig,
+) -> Result<FittedWeights, WeightFitError> {
+    check_labels(examples)?;
+    let labels: Vec<f64> = examples.iter().map(|e| e.label).collect();
+
+    let features: Vec<Vec<f64>> = examples.iter().map(|e| e.factors.to_vec()).collect();
+    let detailed_model = NonNegativeLogistic::fit(&features, &labels, config);
+    let shares = to_shares(&detailed_model.coefficients).ok_or(WeightFitError::NoSignal)?;
+    let mut detailed = [0.0; 6];
+    detailed.copy_from_slice(&shares);
+    let detailed = Weights::from_array(detailed);
+
+    // business に加えて、全ペアで値のある semantic / historical だけを合成対象にする
+    let semantic = examples.iter().all(|e| e.semantic.is_some());
+    let historical = examples.iter().all(|e| e.historical.is_some());
+    let (total_score_weights, total_model) = if semantic || historical {
+        let features: Vec<Vec<f64>> = examples
+            .iter()
+            .map(|e| {
+                let mut row = vec![e.business_score(&detailed)];
+                if semantic {
+                    row.push(e.semantic.unwrap_or(0.0));
+                }
+                if historical {
+                    row.push(e.historical.unwrap_or(0.0));
+                }
+                row
+            })
+            .collect();
+        let model = NonNegativeLogistic::fit(&features, &labels, config);
+        let shares = to_shares(&model.coefficients).ok_or(WeightFitError::NoSignal)?;
+        let mut parts = shares.into_iter();
+        let business = parts.next().unwrap_or(1.0);
+        let weights = TotalScoreWeights {
+            business,
+            semantic: if semantic {
+                parts.next().unwrap_or(0.0)
+            } else {
+                0.0
+            },
+            historical: if historical {
+                parts.next().unwrap_or(0.0)
+            } else {
+                0.0
+            },
+        };
+        (weights, Some(model))
+    } else {
+        (
+            TotalScoreWeights {
+                business: 1.0,
+                semantic: 0.0,
+                historical: 0.0,
+            },
+            None,
+        )
+    };
+
+    Ok(FittedWeights {
+        detailed,
+        total_score_weights,
+        detailed_model,
+        total_model,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn example(id: i64, factors: [f64; 6], label: f64) -> FactorExample {
+        FactorExample {
+            interaction_id: id,
+            run_date: NaiveDate::from_ymd_opt(2026, 10, 1).unwrap(),
+            label,
+            factors,
+            semantic: None,
+            historical: None,
+        }
+    }
+
+    /// skills と tanka だけがラベルを決める合成データ（location は逆相関のノイズ）
+    fn synthetic() -> Vec<FactorExample> {
+        let mut state = 7u64;
+        let mut next = move || {
+            state = state
+                .wrapping_mul(6364136223846793005)
+                .wrapping_add(1442695040888963407);
+            (state >> 11) as f64 / (1u64 << 53) as f64
+        };
+        (0..600)
+            .map(|i| {
+                let factors = [next(), next(), next(), next(), next(), next()];
+                let z = -4.0 + 2.0 * factors[0] + 6.0 * factors[2] - 1.0 * factors[1];
+                let label = if next() < sigmoid(z) { 1.0 } else { 0.0 };
+                example(i, factors, label)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn recovers_dominant_factors_with_non_negative_weights() {
+        let fitted = fit_weights(&synthetic(), &LogisticConfig::default()).unwrap();
+        let w = fitted.detailed;
+
+        assert!((w.sum() - 1.0).abs() < 1e-9);
+        assert!(w.as_array().iter().all(|x| *x >= 0.0));
+        assert!(w.skills > w.tanka, "{w:?}");
+        assert!(w.tanka > w.experience, "{w:?}");
+        // 逆相関の要素は 0 に張り付く
+        assert_eq!(w.location, 0.0);
+        assert_eq!(fitted.total_score_weights.business, 1.0);
+        assert!(fitted.total_model.is_none());
+    }
+
+    #[test]
+    fn fitted_model_beats_constant_prediction() {
+        let examples = synthetic();
+        let labels: Vec<f64> = examples.iter().map(|e| e.label).collect();
+        let features: Vec<Vec<f64>> = examples.iter().map(|e| e.factors.to_vec()).collect();
+        let fitted = fit_weights(&examples, &LogisticConfig::default()).unwrap();
+
+        let constant = NonNegativeLogistic::fit(
+            &features,
+            &labels,
+            &LogisticConfig {
+                iterations: 0,
+                ..LogisticConfig::default()
+            },
+        );
+        assert!(
+            fitted.detailed_model.log_loss(&features, &labels).unwrap()
+                < constant.log_loss(&features, &labels).unwrap()
+        );
+    }
+
+    #[test]
+    fn rejects_degenerate_inputs_and_parses_breakdown() {
+        assert_eq!(
+            fit_weights(&[], &LogisticConfig::default()),
+            Err(WeightFitError::Empty)
+        );
+        let same = vec![example(1, [0.5; 6], 1.0), example(2, [0.2; 6], 1.0)];
+        assert_eq!(
+            fit_weights(&same, &LogisticConfig::default()),
+            Err(WeightFitError::ConstantLabels(1.0))
+        );
+        // スコアが高いほどラベルが低い → 非負制約の下では学習できない
+        let inverse = vec![example(1, [1.0; 6], 0.0), example(2, [0.0; 6], 1.0)];
+        assert_eq!(
+            fit_weights(&inverse, &LogisticConfig::default()),
+            Err(WeightFitError::NoSignal)
+        );
+
+        let date = NaiveDate::from_ymd_opt(2026, 10, 1).unwrap();
+        let breakdown = serde_json::json!({
+            "tanka": 0.9, "location": 1.0, "skills": 0.5, "experience": 0.7,
+            "contract": 1.0, "other": 0.2, "semantic_score": null, "total": 0.8
+        });
+        let parsed = FactorExample::from_breakdown(1, date, 1.0, &breakdown).unwrap();
+        assert_eq!(parsed.factors, [0.9, 1.0, 0.5, 0.7, 1.0, 0.2]);
+        assert_eq!(parsed.semantic, None);
+        assert!(
+            FactorExample::from_breakdown(1, date, 1.0, &serde_json::json!({"tanka": 1.0}))
+                .is_none()
+        );
+    }
+}
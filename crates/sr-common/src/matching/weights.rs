@@ -1,3 +1,16 @@
+use std::path::Path;
+
+use chrono::{DateTime, Utc};
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use thiserror::Error;
+
+use super::scoring::TotalScoreWeights;
+
+/// 学習済み重みファイル（sr-fit-weights の出力）のパス。未設定ならコンパイル時定数を使う
+pub const WEIGHTS_PATH_ENV: &str = "MATCHING_WEIGHTS_PATH";
+
 /// Prefilter重み（粗選別用）
 /// 目的: False Negative を減らす（取りこぼさない）
 /// → スキル重視（合わない人を残すより、合う人を逃さない）
@@ -22,7 +35,7 @@ pub const DETAILED_WEIGHTS: Weights = Weights {
     other: 0.0,
 };
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
 pub struct Weights {
     pub tanka: f64,
     pub location: f64,
@@ -36,6 +49,160 @@ impl Weights {
     pub fn sum(&self) -> f64 {
         self.tanka + self.location + self.skills + self.experience + self.contract + self.other
     }
+
+    pub fn as_array(&self) -> [f64; 6] {
+        [
+            self.tanka,
+            self.location,
+            self.skills,
+            self.experience,
+            self.contract,
+            self.other,
+        ]
+    }
+
+    pub fn from_array(values: [f64; 6]) -> Self {
+        let [tanka, location, skills, experience, contract, other] = values;
+        Self {
+            tanka,
+            location,
+            skills,
+            experience,
+            contract,
+            other,
+        }
+    }
+
+    /// 全要素が有限の非負値で、合計が正
+    pub fn is_valid(&self) -> bool {
+        self.as_array().iter().all(|w| w.is_finite() && *w >= 0.0) && self.sum() > 0.0
+    }
+
+    /// 合計 1.0 に正規化する（不正な重みは None）
+    pub fn normalized(&self) -> Option<Self> {
+        let sum = self.sum();
+        self.is_valid()
+            .then(|| Self::from_array(self.as_array().map(|w| w / sum)))
+    }
+}
+
+#[derive(Debug, Error)]
+pub enum WeightsFileError {
+    #[error("failed to read weights file {path}: {source}")]
+    Io {
+        path: String,
+        #[source]
+        source: std::io::Error,
+    },
+    #[error("failed to parse weights file: {0}")]
+    Parse(#[from] serde_json::Error),
+    #[error("invalid weights file: {0}")]
+    Invalid(String),
+}
+
+/// 学習済みの重み（バージョン付き JSON）
+///
+/// `prefilter` / `total_score_weights` を省略した場合はコンパイル時定数・既定値のまま。
+/// `fit` には学習時の指標などを自由形式で残す（読み込み時は参照しない）。
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct WeightsFile {
+    pub version: String,
+    pub created_at: DateTime<Utc>,
+    pub detailed: Weights,
+    #[serde(default)]
+    pub prefilter: Option<Weights>,
+    #[serde(default)]
+    pub total_score_weights: Option<TotalScoreWeights>,
+    #[serde(default)]
+    pub fit: Option<serde_json::Value>,
+}
+
+impl WeightsFile {
+    /// 重みの内容から決まるバージョン（SHA-256 先頭 16 桁）
+    pub fn compute_version(
+        detailed: &Weights,
+        prefilter: Option<&Weights>,
+        total_score_weights: Option<&TotalScoreWeights>,
+    ) -> String {
+        let payload = serde_json::json!({
+            "detailed": detailed,
+            "prefilter": prefilter,
+            "total_score_weights": total_score_weights,
+        });
+        Sha256::digest(payload.to_string().as_bytes())
+            .iter()
+            .take(8)
+            .map(|b| format!("{b:02x}"))
+            .collect()
+    }
+
+    /// 読み込んで検証し、重みを合計 1.0 に正規化する
+    pub fn from_json(json: &str) -> Result<Self, WeightsFileError> {
+        let mut file: Self = serde_json::from_str(json)?;
+        if file.version.trim().is_empty() || file.version.len() > 64 {
+            return Err(WeightsFileError::Invalid(
+                "version must be 1..=64 bytes".into(),
+            ));
+        }
+        file.detailed = file.detailed.normalized().ok_or_else(|| {
+            WeightsFileError::Invalid(
+                "detailed weights must be non-negative with a positive sum".into(),
+            )
+        })?;
+        if let Some(prefilter) = file.prefilter {
+            file.prefilter = Some(prefilter.normalized().ok_or_else(|| {
+                WeightsFileError::Invalid(
+                    "prefilter weights must be non-negative with a positive sum".into(),
+                )
+            })?);
+        }
+        if let Some(total) = file.total_score_weights {
+            let parts = [total.business, total.semantic, total.historical];
+            if parts.iter().any(|w| !w.is_finite() || *w < 0.0) {
+                return Err(WeightsFileError::Invalid(
+                    "total_score_weights must be non-negative".into(),
+                ));
+            }
+        }
+        Ok(file)
+    }
+
+    pub fn from_path(path: impl AsRef<Path>) -> Result<Self, WeightsFileError> {
+        let path = path.as_ref();
+        let json = std::fs::read_to_string(path).map_err(|source| WeightsFileError::Io {
+            path: path.display().to_string(),
+            source,
+        })?;
+        Self::from_json(&json)
+    }
+
+    /// `MATCHING_WEIGHTS_PATH` が設定されていれば読み込む（未設定なら None）
+    pub fn from_env() -> Result<Option<Self>, WeightsFileError> {
+        match std::env::var(WEIGHTS_PATH_ENV) {
+            Ok(path) if !path.trim().is_empty() => Self::from_path(path.trim()).map(Some),
+            _ => Ok(None),
+        }
+    }
+}
+
+static ACTIVE_WEIGHTS: Lazy<Result<Option<WeightsFile>, WeightsFileError>> = Lazy::new(|| {
+    let loaded = WeightsFile::from_env();
+    match &loaded {
+        Ok(Some(file)) => tracing::info!(version = %file.version, "loaded matching weights file"),
+        Ok(None) => {}
+        Err(err) => {
+            tracing::error!(error = %err, "ignoring matching weights file; using built-in weights")
+        }
+    }
+    loaded
+});
+
+/// プロセスで使う学習済み重み（初回呼び出し時に 1 度だけ読み込む）
+///
+/// 読み込みに失敗した場合、`MatchingConfig` はコンパイル時定数にフォールバックする。
+/// 常駐プロセスは起動時にこの関数を呼んでエラーなら終了すること。
+pub fn active_weights_file() -> Result<Option<&'static WeightsFile>, &'static WeightsFileError> {
+    ACTIVE_WEIGHTS.as_ref().map(Option::as_ref)
 }
 
 #[cfg(test)]
@@ -49,4 +216,34 @@ mod tests {
         assert!((pre_sum - 1.0).abs() < 1e-6);
         assert!((detailed_sum - 1.0).abs() < 1e-6);
     }
+
+    #[test]
+    fn weights_file_normalizes_and_validates() {
+        let json = serde_json::json!({
+            "version": "abc123",
+            "created_at": "2026-10-01T00:00:00Z",
+            "detailed": {"tanka": 2.0, "location": 1.0, "skills": 4.0,
+                         "experience": 1.0, "contract": 0.0, "other": 0.0},
+            "total_score_weights": {"business": 1.0, "semantic": 0.0, "historical": 0.0}
+        });
+        let file = WeightsFile::from_json(&json.to_string()).unwrap();
+        assert!((file.detailed.sum() - 1.0).abs() < 1e-12);
+        assert_eq!(file.detailed.skills, 0.5);
+        assert_eq!(file.prefilter, None);
+
+        let mut negative = json.clone();
+        negative["detailed"]["tanka"] = serde_json::json!(-0.1);
+        assert!(WeightsFile::from_json(&negative.to_string()).is_err());
+
+        let mut zero = json.clone();
+        zero["detailed"] = serde_json::to_value(Weights::from_array([0.0; 6])).unwrap();
+        assert!(WeightsFile::from_json(&zero.to_string()).is_err());
+
+        let version = WeightsFile::compute_version(&DETAILED_WEIGHTS, None, None);
+        assert_eq!(version.len(), 16);
+        assert_ne!(
+            version,
+            WeightsFile::compute_version(&PREFILTER_WEIGHTS, None, None)
+        );
+    }
 }
@@ -26,6 +26,26 @@ pub enum FinalMethod {
     ManualReview,
 }
 
+/// 抽出対象（ses.extraction_queue.entity_type）
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum EntityType {
+    /// 案件メール（ses.anken_emails → ses.projects）
+    #[default]
+    Project,
+    /// 人材メール（ses.jinzai_emails → ses.talents_enum）
+    Talent,
+}
+
+impl EntityType {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            EntityType::Project => "project",
+            EntityType::Talent => "talent",
+        }
+    }
+}
+
 impl QueueStatus {
     pub fn as_str(&self) -> &'static str {
         match self {
@@ -60,6 +80,8 @@ impl FinalMethod {
 pub struct ExtractionJob {
     pub id: u64,
     pub message_id: String,
+    #[serde(default)]
+    pub entity_type: EntityType,
     pub email_subject: String,
     pub email_received_at: DateTime<Utc>,
     pub subject_hash: String,
@@ -70,6 +92,9 @@ pub struct ExtractionJob {
     pub next_retry_at: Option<DateTime<Utc>>,
     pub last_error: Option<String>,
     pub partial_fields: Option<Value>,
+    /// Rust 抽出のフィールドごとの根拠（マッチ位置・ルール・確信度）。LLM 完了後も Rust 側の記録として残す
+    #[serde(default)]
+    pub field_evidence: Option<Value>,
     pub decision_reason: Option<String>,
     pub recommended_method: Option<RecommendedMethod>,
     pub final_method: Option<FinalMethod>,
@@ -84,6 +109,23 @@ pub struct ExtractionJob {
     pub manual_review_reason: Option<String>,
     pub reprocess_after: Option<DateTime<Utc>>,
     pub canary_target: bool,
+    /// 複数案件メールを分割したサブジョブの元メール message_id（分割していなければ None）
+    #[serde(default)]
+    pub source_message_id: Option<String>,
+    /// 元メール内のセクション番号（1 始まり）
+    #[serde(default)]
+    pub section_index: Option<u32>,
+    /// エンキュー時に分割したセクション本文。後段は元メールを分割し直さずこれを使う
+    #[serde(default)]
+    pub section_text: Option<String>,
+}
+
+/// サブジョブ ID の区切り（"<message_id>#<section_index>"）
+pub const SECTION_ID_SEPARATOR: char = '#';
+
+/// 元メールの message_id とセクション番号から決定的なサブジョブ ID を作る
+pub fn section_message_id(message_id: &str, section_index: u32) -> String {
+    format!("{message_id}{SECTION_ID_SEPARATOR}{section_index}")
 }
 
 impl ExtractionJob {
@@ -97,6 +139,7 @@ impl ExtractionJob {
         Self {
             id: 0,
             message_id: message_id.to_string(),
+            entity_type: EntityType::Project,
             email_subject: email_subject.to_string(),
             email_received_at,
             subject_hash: subject_hash.to_string(),
@@ -107,6 +150,7 @@ impl ExtractionJob {
             next_retry_at: None,
             last_error: None,
             partial_fields: None,
+            field_evidence: None,
             decision_reason: None,
             recommended_method: None,
             final_method: None,
@@ -121,8 +165,52 @@ impl ExtractionJob {
             manual_review_reason: None,
             reprocess_after: None,
             canary_target: false,
+            source_message_id: None,
+            section_index: None,
+            section_text: None,
+        }
+    }
+
+    /// 元メールの message_id（サブジョブなら分割元の ID）
+    pub fn source_message_id(&self) -> &str {
+        self.source_message_id
+            .as_deref()
+            .unwrap_or(&self.message_id)
+    }
+
+    /// 複数案件メールの `section_index` 番目（1 始まり）、本文 `section_text` を担当するサブジョブにする
+    pub fn into_section(mut self, section_index: u32, section_text: &str) -> Self {
+        let source = self.source_message_id().to_string();
+        self.message_id = section_message_id(&source, section_index);
+        self.source_message_id = Some(source);
+        self.section_index = Some(section_index);
+        self.section_text = Some(section_text.to_string());
+        self
+    }
+
+    /// このジョブが担当する本文。丸ごとジョブは元メール本文、サブジョブは保存済みのセクション本文。
+    /// セクション本文が保存されていないサブジョブは `None`（元メールを分割し直して推測しない）
+    pub fn source_body<'a>(&'a self, email_body: &'a str) -> Option<&'a str> {
+        match self.section_index {
+            Some(_) => self.section_text.as_deref(),
+            None => Some(email_body),
         }
     }
+
+    /// 既存ジョブと重複するか。
+    ///
+    /// - 同じ message_id は重複
+    /// - 同じ元メールの「丸ごとジョブ」と「セクションジョブ」は混在させない
+    /// - 件名ハッシュの一致（転送・再送）は別メール同士のみ重複扱い。同じメールのセクションは件名を共有する
+    fn duplicates(&self, other: &ExtractionJob) -> bool {
+        if self.message_id == other.message_id {
+            return true;
+        }
+        if self.source_message_id() == other.source_message_id() {
+            return self.section_index.is_none() != other.section_index.is_none();
+        }
+        self.subject_hash == other.subject_hash && self.status != QueueStatus::Completed
+    }
 }
 
 pub enum JobError {
@@ -155,11 +243,7 @@ pub struct ExtractionQueue {
 
 impl ExtractionQueue {
     pub fn enqueue(&mut self, mut job: ExtractionJob) {
-        if self.jobs.iter().any(|existing| {
-            existing.message_id == job.message_id
-                || (existing.subject_hash == job.subject_hash
-                    && existing.status != QueueStatus::Completed)
-        }) {
+        if self.jobs.iter().any(|existing| existing.duplicates(&job)) {
             return;
         }
         self.next_id += 1;
@@ -500,4 +584,42 @@ mod tests {
         queue.enqueue(second);
         assert_eq!(queue.jobs.len(), 2);
     }
+
+    #[test]
+    fn section_jobs_share_subject_but_not_whole_email_job() {
+        let mut queue = ExtractionQueue::default();
+        let base = ExtractionJob::new("msg-9", "複数案件", Utc::now(), "samehash");
+
+        queue.enqueue(base.clone().into_section(1, "案件1"));
+        queue.enqueue(base.clone().into_section(2, "案件2"));
+        assert_eq!(queue.jobs.len(), 2);
+        assert_eq!(queue.jobs[1].message_id, "msg-9#2");
+        assert_eq!(queue.jobs[1].source_message_id(), "msg-9");
+
+        // Re-splitting is idempotent and the whole-email job is rejected
+        queue.enqueue(base.clone().into_section(2, "案件2"));
+        queue.enqueue(base.clone());
+        assert_eq!(queue.jobs.len(), 2);
+
+        // Another email with the same subject is still held back while pending
+        queue.enqueue(
+            ExtractionJob::new("msg-10", "複数案件", Utc::now(), "samehash")
+                .into_section(1, "案件1"),
+        );
+        assert_eq!(queue.jobs.len(), 2);
+    }
+
+    #[test]
+    fn section_jobs_read_their_stored_section_not_the_email() {
+        let whole = ExtractionJob::new("msg-11", "複数案件", Utc::now(), "hash");
+        assert_eq!(whole.source_body("本文全体"), Some("本文全体"));
+
+        let section = whole.clone().into_section(2, "【案件2】AWS移行");
+        assert_eq!(section.source_body("本文全体"), Some("【案件2】AWS移行"));
+
+        // 保存前に積まれたサブジョブは元メールから推測しない
+        let mut legacy = section;
+        legacy.section_text = None;
+        assert_eq!(legacy.source_body("本文全体"), None);
+    }
 }
@@ -1,6 +1,6 @@
 pub mod extraction_queue;
 
 pub use extraction_queue::{
-    ExtractionJob, ExtractionQueue, FinalMethod, JobError, JobOutcome, QueueStatus,
-    RecommendedMethod,
+    section_message_id, EntityType, ExtractionJob, ExtractionQueue, FinalMethod, JobError,
+    JobOutcome, QueueStatus, RecommendedMethod, SECTION_ID_SEPARATOR,
 };
@@ -0,0 +1,504 @@
+//! マッチングルール設定ファイル（TOML）
+//!
+//! 散在していた閾値（SR_SKILL_MATCH_THRESHOLD / スコアリング定数 / PreFilterConfig / TankaParams /
+//! AUTO_MATCH_THRESHOLD・MANUAL_REVIEW_MARGIN）を 1 ファイルにまとめる。省略した項目は既定値。
+//! 解決後のルール全体の SHA-256 から `rule_version` を決め、match_results.rule_version と
+//! interaction_logs.config_version に記録する。
+//!
+//! `MATCH_RULES_PATH` が未設定なら、既定値に従来の環境変数を重ねたものを使う（後方互換）。
+
+use std::path::Path;
+use std::sync::{Arc, RwLock};
+
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use thiserror::Error;
+use tracing::warn;
+
+use crate::calculation::tanka_params::TankaParams;
+use crate::matching::client_profile::ClientProfile;
+
+/// ルールファイル（TOML）のパス
+pub const RULES_PATH_ENV: &str = "MATCH_RULES_PATH";
+/// ファイル指定時に無視される従来の環境変数
+const LEGACY_ENV_KEYS: [&str; 3] = [
+    "SR_SKILL_MATCH_THRESHOLD",
+    "AUTO_MATCH_THRESHOLD",
+    "MANUAL_REVIEW_MARGIN",
+];
+
+#[derive(Debug, Error)]
+pub enum RulesError {
+    #[error("failed to read rule file {path}: {source}")]
+    Io {
+        path: String,
+        #[source]
+        source: std::io::Error,
+    },
+    #[error("failed to parse rule file: {0}")]
+    Parse(#[from] toml::de::Error),
+    #[error("invalid match rules: {0}")]
+    Invalid(String),
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Default)]
+#[serde(deny_unknown_fields, default)]
+pub struct MatchRules {
+    pub skills: SkillRules,
+    pub scoring: ScoringRules,
+    pub prefilter: PrefilterRules,
+    pub tanka: TankaRules,
+    pub review: ReviewRules,
+    /// クライアント別の KO 上書き（`[[clients]]`、先に書いたものが優先）
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub clients: Vec<ClientProfile>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields, default)]
+pub struct SkillRules {
+    /// 必須スキル一致率の KO 閾値（KO 判定と skills スコアの両方）
+    pub match_threshold: f64,
+}
+
+impl Default for SkillRules {
+    fn default() -> Self {
+        Self {
+            match_threshold: 0.3,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields, default)]
+pub struct ScoringRules {
+    /// 最低粗利（万円）
+    pub tanka_profit_minimum: f64,
+    /// 理想の粗利率
+    pub tanka_profit_optimal: f64,
+    /// 経験年数の許容不足（年）
+    pub experience_buffer_years: f64,
+}
+
+impl Default for ScoringRules {
+    fn default() -> Self {
+        Self {
+            tanka_profit_minimum: 5.0,
+            tanka_profit_optimal: 0.25,
+            experience_buffer_years: 0.5,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields, default)]
+pub struct PrefilterRules {
+    pub max_candidates: usize,
+    pub min_score: f64,
+}
+
+impl Default for PrefilterRules {
+    fn default() -> Self {
+        Self {
+            max_candidates: 500,
+            min_score: 0.1,
+        }
+    }
+}
+
+/// 単価計算パラメータ（既定値は `TankaParams`）
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields, default)]
+pub struct TankaRules {
+    pub base_tanka: f64,
+    pub exp_rate_1_to_5: f64,
+    pub exp_rate_6_to_10: f64,
+    pub exp_rate_11_plus: f64,
+    pub exp_years_cap: i32,
+    pub premium_cloud: f64,
+    pub premium_pm_pmo: f64,
+    pub premium_ai_ml: f64,
+    pub premium_modern_dev: f64,
+    pub premium_cap: f64,
+    pub range_width: f64,
+    pub project_min_tanka: i32,
+    pub project_min_tanka_max: i32,
+}
+
+impl Default for TankaRules {
+    fn default() -> Self {
+        use TankaParams::*;
+        Self {
+            base_tanka: BASE_TANKA,
+            exp_rate_1_to_5: EXP_RATE_1_TO_5,
+            exp_rate_6_to_10: EXP_RATE_6_TO_10,
+            exp_rate_11_plus: EXP_RATE_11_PLUS,
+            exp_years_cap: EXP_YEARS_CAP,
+            premium_cloud: PREMIUM_CLOUD,
+            premium_pm_pmo: PREMIUM_PM_PMO,
+            premium_ai_ml: PREMIUM_AI_ML,
+            premium_modern_dev: PREMIUM_MODERN_DEV,
+            premium_cap: PREMIUM_CAP,
+            range_width: RANGE_WIDTH,
+            project_min_tanka: PROJECT_MIN_TANKA,
+            project_min_tanka_max: PROJECT_MIN_TANKA_MAX,
+        }
+    }
+}
+
+/// API レスポンスの自動マッチ / 手動レビュー判定
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields, default)]
+pub struct ReviewRules {
+    pub auto_match_threshold: f64,
+    pub manual_review_margin: f64,
+}
+
+impl Default for ReviewRules {
+    fn default() -> Self {
+        Self {
+            auto_match_threshold: 0.7,
+            manual_review_margin: 0.1,
+        }
+    }
+}
+
+/// 0.0〜1.0 の環境変数（エラーメッセージは環境変数名で出す）
+fn parse_env_unit(key: &str) -> Result<Option<f64>, RulesError> {
+    let value = match std::env::var(key) {
+        Ok(raw) => raw.parse::<f64>().map_err(|_| {
+            RulesError::Invalid(format!(
+                "{key} must be a number between 0.0 and 1.0 (got `{raw}`)"
+            ))
+        })?,
+        Err(std::env::VarError::NotPresent) => return Ok(None),
+        Err(err) => return Err(RulesError::Invalid(format!("failed to read {key}: {err}"))),
+    };
+    if !(0.0..=1.0).contains(&value) {
+        return Err(RulesError::Invalid(format!(
+            "{key} must be between 0.0 and 1.0 (got {value})"
+        )));
+    }
+    Ok(Some(value))
+}
+
+impl MatchRules {
+    pub fn from_toml(raw: &str) -> Result<Self, RulesError> {
+        let rules: Self = toml::from_str(raw)?;
+        rules.validate()?;
+        Ok(rules)
+    }
+
+    pub fn from_path(path: impl AsRef<Path>) -> Result<Self, RulesError> {
+        let path = path.as_ref();
+        let raw = std::fs::read_to_string(path).map_err(|source| RulesError::Io {
+            path: path.display().to_string(),
+            source,
+        })?;
+        Self::from_toml(&raw)
+    }
+
+    /// 既定値に従来の環境変数を重ねる（SR_SKILL_MATCH_THRESHOLD は従来どおり不正値を無視）
+    pub fn from_legacy_env() -> Result<Self, RulesError> {
+        let mut rules = Self::default();
+        if let Some(threshold) = std::env::var("SR_SKILL_MATCH_THRESHOLD")
+            .ok()
+            .and_then(|s| s.parse().ok())
+        {
+            rules.skills.match_threshold = threshold;
+        }
+        if let Some(value) = parse_env_unit("AUTO_MATCH_THRESHOLD")? {
+            rules.review.auto_match_threshold = value;
+        }
+        if let Some(value) = parse_env_unit("MANUAL_REVIEW_MARGIN")? {
+            rules.review.manual_review_margin = value;
+        }
+        rules.validate()?;
+        Ok(rules)
+    }
+
+    pub fn validate(&self) -> Result<(), RulesError> {
+        let unit = |key: &str, value: f64| {
+            if (0.0..=1.0).contains(&value) {
+                Ok(())
+            } else {
+                Err(RulesError::Invalid(format!(
+                    "{key} must be between 0.0 and 1.0 (got {value})"
+                )))
+            }
+        };
+        let non_negative = |key: &str, value: f64| {
+            if value.is_finite() && value >= 0.0 {
+                Ok(())
+            } else {
+                Err(RulesError::Invalid(format!(
+                    "{key} must be non-negative (got {value})"
+                )))
+            }
+        };
+
+        unit("skills.match_threshold", self.skills.match_threshold)?;
+        non_negative(
+            "scoring.tanka_profit_minimum",
+            self.scoring.tanka_profit_minimum,
+        )?;
+        unit(
+            "scoring.tanka_profit_optimal",
+            self.scoring.tanka_profit_optimal,
+        )?;
+        non_negative(
+            "scoring.experience_buffer_years",
+            self.scoring.experience_buffer_years,
+        )?;
+        if self.prefilter.max_candidates == 0 {
+            return Err(RulesError::Invalid(
+                "prefilter.max_candidates must be at least 1".into(),
+            ));
+        }
+        unit("prefilter.min_score", self.prefilter.min_score)?;
+
+        let tanka = &self.tanka;
+        for (key, value) in [
+            ("tanka.base_tanka", tanka.base_tanka),
+            ("tanka.exp_rate_1_to_5", tanka.exp_rate_1_to_5),
+            ("tanka.exp_rate_6_to_10", tanka.exp_rate_6_to_10),
+            ("tanka.exp_rate_11_plus", tanka.exp_rate_11_plus),
+            ("tanka.premium_cloud", tanka.premium_cloud),
+            ("tanka.premium_pm_pmo", tanka.premium_pm_pmo),
+            ("tanka.premium_ai_ml", tanka.premium_ai_ml),
+            ("tanka.premium_modern_dev", tanka.premium_modern_dev),
+            ("tanka.range_width", tanka.range_width),
+        ] {
+            non_negative(key, value)?;
+        }
+        if !(tanka.premium_cap.is_finite() && tanka.premium_cap >= 1.0) {
+            return Err(RulesError::Invalid(format!(
+                "tanka.premium_cap must be >= 1.0 (got {})",
+                tanka.premium_cap
+            )));
+        }
+        if tanka.exp_years_cap < 0 {
+            return Err(RulesError::Invalid(
+                "tanka.exp_years_cap must be non-negative".into(),
+            ));
+        }
+        if tanka.project_min_tanka > tanka.project_min_tanka_max {
+            return Err(RulesError::Invalid(
+                "tanka.project_min_tanka must not exceed tanka.project_min_tanka_max".into(),
+            ));
+        }
+
+        unit(
+            "review.auto_match_threshold",
+            self.review.auto_match_threshold,
+        )?;
+        unit(
+            "review.manual_review_margin",
+            self.review.manual_review_margin,
+        )?;
+
+        let mut names = std::collections::HashSet::new();
+        for client in &self.clients {
+            client.validate().map_err(RulesError::Invalid)?;
+            if !names.insert(client.name.as_str()) {
+                return Err(RulesError::Invalid(format!(
+                    "clients `{}` is defined more than once",
+                    client.name
+                )));
+            }
+        }
+        Ok(())
+    }
+
+    /// 解決後のルール全体から決まるバージョン（`rules-` + SHA-256 先頭 16 桁）
+    pub fn version(&self) -> String {
+        let canonical = serde_json::to_string(self).unwrap_or_default();
+        let digest: String = Sha256::digest(canonical.as_bytes())
+            .iter()
+            .take(8)
+            .map(|b| format!("{b:02x}"))
+            .collect();
+        format!("rules-{digest}")
+    }
+}
+
+/// 読み込み済みのルールとそのバージョン
+#[derive(Debug, Clone, PartialEq)]
+pub struct ActiveRules {
+    pub version: String,
+    /// 読み込んだファイル（None なら既定値 + 環境変数）
+    pub path: Option<String>,
+    pub rules: MatchRules,
+}
+
+impl ActiveRules {
+    pub fn new(rules: MatchRules, path: Option<String>) -> Self {
+        Self {
+            version: rules.version(),
+            path,
+            rules,
+        }
+    }
+
+    /// `MATCH_RULES_PATH` があればファイルを、無ければ既定値 + 従来の環境変数を読み込む
+    pub fn from_env() -> Result<Self, RulesError> {
+        match std::env::var(RULES_PATH_ENV) {
+            Ok(path) if !path.trim().is_empty() => {
+                let path = path.trim();
+                let ignored: Vec<&str> = LEGACY_ENV_KEYS
+                    .into_iter()
+                    .filter(|key| std::env::var_os(key).is_some())
+                    .collect();
+                if !ignored.is_empty() {
+                    warn!(
+                        ?ignored,
+                        path, "{RULES_PATH_ENV} is set; ignoring legacy environment overrides"
+                    );
+                }
+                Ok(Self::new(
+                    MatchRules::from_path(path)?,
+                    Some(path.to_string()),
+                ))
+            }
+            _ => Ok(Self::new(MatchRules::from_legacy_env()?, None)),
+        }
+    }
+}
+
+static ACTIVE_RULES: Lazy<RwLock<Option<Arc<ActiveRules>>>> = Lazy::new(|| RwLock::new(None));
+
+/// プロセス全体で使うルールを差し替える（sr-api の SIGHUP 再読込用）
+pub fn install_rules(rules: ActiveRules) -> Arc<ActiveRules> {
+    let rules = Arc::new(rules);
+    *ACTIVE_RULES.write().unwrap_or_else(|e| e.into_inner()) = Some(rules.clone());
+    rules
+}
+
+/// 起動時に呼ぶ: 環境から読み込んで検証し、プロセス全体に適用する
+pub fn init_active_rules() -> Result<Arc<ActiveRules>, RulesError> {
+    Ok(install_rules(ActiveRules::from_env()?))
+}
+
+/// 現在のルール。未初期化なら環境から読み込む
+///
+/// # Panics
+/// ルールファイル・環境変数が不正な場合。既定値で黙って動き続けないよう、
+/// 各バイナリは起動時に `init_active_rules()` で検証しておく。
+pub fn active_rules() -> Arc<ActiveRules> {
+    if let Some(rules) = ACTIVE_RULES
+        .read()
+        .unwrap_or_else(|e| e.into_inner())
+        .as_ref()
+    {
+        return rules.clone();
+    }
+    let rules =
+        ActiveRules::from_env().unwrap_or_else(|err| panic!("failed to load match rules: {err}"));
+    install_rules(rules)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::matching::client_profile::ClientRuleSeverity;
+
+    #[test]
+    fn partial_file_keeps_defaults_and_changes_version() {
+        let rules = MatchRules::from_toml(
+            r#"
+            [skills]
+            match_threshold = 0.5
+
+            [review]
+            auto_match_threshold = 0.8
+            "#,
+        )
+        .unwrap();
+        assert_eq!(rules.skills.match_threshold, 0.5);
+        assert_eq!(rules.review.auto_match_threshold, 0.8);
+        assert_eq!(rules.review.manual_review_margin, 0.1);
+        assert_eq!(rules.tanka, TankaRules::default());
+
+        let builtin = MatchRules::default();
+        assert_ne!(rules.version(), builtin.version());
+        assert_eq!(builtin.version(), MatchRules::default().version());
+        assert!(builtin.version().starts_with("rules-"));
+        assert_eq!(builtin.version().len(), "rules-".len() + 16);
+        // 既定値を明示しても同じバージョン
+        assert_eq!(
+            MatchRules::from_toml("[prefilter]\nmax_candidates = 500")
+                .unwrap()
+                .version(),
+            builtin.version()
+        );
+    }
+
+    #[test]
+    fn rejects_unknown_keys_and_out_of_range_values() {
+        for (raw, needle) in [
+            ("[skills]\nthreshold = 0.5", "unknown field"),
+            ("[skills]\nmatch_threshold = 1.5", "skills.match_threshold"),
+            (
+                "[prefilter]\nmax_candidates = 0",
+                "prefilter.max_candidates",
+            ),
+            ("[tanka]\npremium_cap = 0.5", "tanka.premium_cap"),
+            (
+                "[tanka]\nproject_min_tanka = 60\nproject_min_tanka_max = 55",
+                "tanka.project_min_tanka",
+            ),
+            (
+                "[review]\nmanual_review_margin = -0.1",
+                "review.manual_review_margin",
+            ),
+        ] {
+            let err = MatchRules::from_toml(raw).unwrap_err().to_string();
+            assert!(err.contains(needle), "{raw}: {err}");
+        }
+    }
+
+    #[test]
+    fn parses_client_profiles() {
+        let rules = MatchRules::from_toml(
+            r#"
+            [[clients]]
+            name = "acme"
+            sender_domains = ["acme.co.jp"]
+
+            [clients.overrides]
+            foreigner_allowed = false
+            jinzai_flow_limit = "SPONTO一社先まで"
+
+            [[clients.rules]]
+            name = "senior"
+            severity = "soft"
+            condition = { kind = "min_experience_years", years = 5 }
+            "#,
+        )
+        .unwrap();
+        let client = &rules.clients[0];
+        assert_eq!(client.overrides.foreigner_allowed, Some(false));
+        assert_eq!(client.rules[0].severity, ClientRuleSeverity::Soft);
+        assert_ne!(rules.version(), MatchRules::default().version());
+
+        for (raw, needle) in [
+            ("[[clients]]\nname = \"acme\"", "sender_domains"),
+            (
+                "[[clients]]\nname = \"acme\"\nclient_names = [\"アクメ\"]\n[clients.overrides]\njinzai_flow_limit = \"三社先\"",
+                "jinzai_flow_limit",
+            ),
+            (
+                "[[clients]]\nname = \"a\"\nclient_names = [\"x\"]\n[[clients]]\nname = \"a\"\nclient_names = [\"y\"]",
+                "more than once",
+            ),
+            (
+                "[[clients]]\nname = \"a\"\nclient_names = [\"x\"]\n[[clients.rules]]\nname = \"r\"\nseverity = \"hard\"\ncondition = { kind = \"max_height\" }",
+                "unknown variant",
+            ),
+        ] {
+            let err = MatchRules::from_toml(raw).unwrap_err().to_string();
+            assert!(err.contains(needle), "{raw}: {err}");
+        }
+    }
+}
@@ -11,6 +11,12 @@ pub const EXTRACTION_QUEUE_DDL: &str = r#"
 CREATE TABLE ses.extraction_queue (
     id SERIAL PRIMARY KEY,
     message_id VARCHAR(255) NOT NULL UNIQUE,
+    entity_type VARCHAR(10) NOT NULL DEFAULT 'project',
+    -- 複数案件メールを分割したサブジョブ（message_id = "<source_message_id>#<section_index>"）
+    source_message_id VARCHAR(255),
+    section_index INTEGER,
+    -- エンキュー時に分割したセクション本文（射影・LLM はこれを読む）
+    section_text TEXT,
     email_subject TEXT NOT NULL,
     email_received_at TIMESTAMPTZ NOT NULL,
     subject_hash VARCHAR(16) NOT NULL,
@@ -24,6 +30,8 @@ CREATE TABLE ses.extraction_queue (
     last_error TEXT,
 
     partial_fields JSONB,
+    -- Rust 抽出の根拠（フィールド名 → {start, end, rule, confidence}）
+    field_evidence JSONB,
     decision_reason TEXT,
 
     recommended_method VARCHAR(20),
@@ -49,6 +57,7 @@ CREATE TABLE ses.extraction_queue (
     CONSTRAINT chk_recommended_method CHECK (recommended_method IN ('rust_recommended', 'llm_recommended')),
     CONSTRAINT chk_final_method CHECK (final_method IS NULL OR final_method IN ('rust_completed', 'llm_completed', 'manual_review')),
     CONSTRAINT chk_priority CHECK (priority >= 0 AND priority <= 100),
+    CONSTRAINT chk_entity_type CHECK (entity_type IN ('project', 'talent')),
     CONSTRAINT chk_retry_count CHECK (retry_count >= 0 AND retry_count <= 100)
 );
 
@@ -56,6 +65,8 @@ CREATE INDEX idx_extraction_queue_status_priority ON ses.extraction_queue(status
 CREATE INDEX idx_extraction_queue_pending ON ses.extraction_queue(created_at, id) WHERE status = 'pending';
 CREATE INDEX idx_extraction_queue_status_created ON ses.extraction_queue(status, created_at, id);
 CREATE INDEX idx_extraction_queue_message_id ON ses.extraction_queue(message_id);
+CREATE INDEX idx_extraction_queue_entity_status ON ses.extraction_queue(entity_type, status);
+CREATE INDEX idx_extraction_queue_source_message ON ses.extraction_queue(source_message_id) WHERE source_message_id IS NOT NULL;
 CREATE INDEX idx_extraction_queue_subject_hash ON ses.extraction_queue(subject_hash, created_at);
 CREATE INDEX idx_extraction_queue_canary ON ses.extraction_queue(canary_target, created_at);
 CREATE INDEX idx_extraction_queue_reprocess ON ses.extraction_queue(reprocess_after) WHERE reprocess_after IS NOT NULL;
@@ -110,10 +121,21 @@ CREATE TABLE ses.talents_enum (
     desired_price_min INTEGER,
     available_date DATE,
     received_at TIMESTAMPTZ NOT NULL,
-    source_text TEXT
+    source_text TEXT,
+
+    -- 人材メール抽出ジョブからの射影（sr-extractor / sr-llm-worker）
+    extraction_job_id BIGINT,
+    final_method VARCHAR(20),
+    talent JSONB,
+    field_sources JSONB NOT NULL DEFAULT '{}'::jsonb,
+    source_updated_at TIMESTAMPTZ,
+    requires_manual_review BOOLEAN NOT NULL DEFAULT false,
+    manual_review_reason TEXT,
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT clock_timestamp()
 );
 
 CREATE INDEX idx_talents_enum_message_id ON ses.talents_enum(message_id);
+CREATE INDEX idx_talents_enum_extraction_job ON ses.talents_enum(extraction_job_id);
 "#;
 
 /// Master talent table for Lark-sourced talent data.
@@ -127,6 +149,7 @@ CREATE TABLE ses.talents (
     age INTEGER,
     birth_year INTEGER,
     nearest_station TEXT,
+    residential_todofuken TEXT,      -- 居住都道府県（正式名称）
 
     -- 単価・稼働
     desired_price INTEGER,           -- 参考原価（月額）
@@ -203,6 +226,74 @@ CREATE TABLE ses.projects_enum (
 CREATE INDEX idx_projects_enum_message_id ON ses.projects_enum(message_id);
 "#;
 
+/// 抽出完了ジョブを射影した typed 案件テーブル（message_id 単位で UPSERT）
+///
+/// `project` は matching 用 `Project` の全項目、`field_sources` は項目ごとの由来
+/// （job_id / rust・llm・derived）。検索用の主要項目は列としても持つ。
+pub const PROJECTS_DDL: &str = r#"
+CREATE TABLE IF NOT EXISTS ses.projects (
+    id BIGSERIAL PRIMARY KEY,
+    message_id VARCHAR(255) NOT NULL UNIQUE,
+    extraction_job_id BIGINT NOT NULL,
+    final_method VARCHAR(20) NOT NULL,
+    project_name TEXT NOT NULL,
+
+    work_todofuken VARCHAR(10),
+    work_area VARCHAR(10),
+    remote_onsite VARCHAR(20),
+    monthly_tanka_min INTEGER,
+    monthly_tanka_max INTEGER,
+    start_date DATE,
+    start_date_precision VARCHAR(20),
+
+    project JSONB NOT NULL,
+    field_sources JSONB NOT NULL DEFAULT '{}'::jsonb,
+
+    received_at TIMESTAMPTZ NOT NULL,
+    source_updated_at TIMESTAMPTZ NOT NULL,
+    requires_manual_review BOOLEAN NOT NULL DEFAULT false,
+    manual_review_reason TEXT,
+
+    created_at TIMESTAMPTZ NOT NULL DEFAULT clock_timestamp(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT clock_timestamp(),
+
+    CONSTRAINT chk_projects_final_method CHECK (final_method IN ('rust_completed', 'llm_completed'))
+);
+
+CREATE INDEX IF NOT EXISTS idx_projects_received_at ON ses.projects(received_at DESC, id);
+CREATE INDEX IF NOT EXISTS idx_projects_todofuken ON ses.projects(work_todofuken) WHERE work_todofuken IS NOT NULL;
+CREATE INDEX IF NOT EXISTS idx_projects_tanka ON ses.projects(monthly_tanka_min, monthly_tanka_max);
+CREATE INDEX IF NOT EXISTS idx_projects_start_date ON ses.projects(start_date) WHERE start_date IS NOT NULL;
+CREATE INDEX IF NOT EXISTS idx_projects_extraction_job ON ses.projects(extraction_job_id);
+
+COMMENT ON TABLE ses.projects IS '抽出結果から射影した案件テーブル（extraction_queue 完了ジョブ由来）';
+"#;
+
+/// Two-Tower の人材埋め込みストア（embedder / version / 次元ごとに 1 行）
+///
+/// `talent_updated_at` が `ses.talents.updated_at` より古い行は再計算対象。
+/// talents が未作成の環境でもマイグレーションが通るよう FK は張らず、孤立行は refresh 時に削除する。
+pub const TALENT_EMBEDDINGS_DDL: &str = r#"
+CREATE TABLE IF NOT EXISTS ses.talent_embeddings (
+    talent_id BIGINT NOT NULL,
+    embedder VARCHAR(50) NOT NULL,
+    embedder_version VARCHAR(64) NOT NULL,
+    dimension INTEGER NOT NULL,
+    embedding REAL[] NOT NULL,
+
+    -- 埋め込み元の ses.talents.updated_at
+    talent_updated_at TIMESTAMPTZ NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT clock_timestamp(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT clock_timestamp(),
+
+    PRIMARY KEY (talent_id, embedder, embedder_version, dimension),
+    CONSTRAINT chk_talent_embeddings_dimension CHECK (cardinality(embedding) = dimension)
+);
+
+CREATE INDEX IF NOT EXISTS idx_talent_embeddings_model
+    ON ses.talent_embeddings(embedder, embedder_version, dimension);
+"#;
+
 /// Proposed schema for daily match results snapshots.
 /// run_date is a generated column based on created_at in RUN_DATE_TIMEZONE.
 /// Same-day updates overwrite the previous record (UPSERT pattern).
@@ -304,10 +395,10 @@ CREATE TABLE ses.feedback_events (
     feedback_type TEXT NOT NULL,
     -- 許容値:
     --   GUI評価: thumbs_up, thumbs_down, review_ok, review_ng, review_pending
-    --   営業プロセス: accepted, rejected, interview_scheduled, no_response
+    --   営業プロセス: proposed, accepted, rejected, interview_scheduled, no_response
     CONSTRAINT chk_feedback_type CHECK (feedback_type IN (
         'thumbs_up', 'thumbs_down', 'review_ok', 'review_ng', 'review_pending',
-        'accepted', 'rejected', 'interview_scheduled', 'no_response'
+        'proposed', 'accepted', 'rejected', 'interview_scheduled', 'no_response'
     )),
 
     -- NG理由（review_ng / thumbs_down / rejected 時のみ）
@@ -493,106 +584,18 @@ CREATE INDEX idx_conversion_events_stage ON ses.conversion_events(stage, created
 COMMENT ON TABLE ses.conversion_events IS 'CV（面談化/成約）ログ（Two-Tower学習の強いシグナル）';
 "#;
 
-/// Interaction logging for recommendations and downstream training views.
-/// run_date is a generated column based on created_at in JST timezone.
-/// UNIQUE is per (match_run_id, talent_id, project_id) to allow multiple runs per day.
-pub static INTERACTION_LOGS_DDL: Lazy<String> = Lazy::new(|| {
-    format!(
+/// 統合学習ラベル VIEW の SQL（`INTERACTION_LOGS_DDL` とマイグレーションで同じ定義を使う）
+macro_rules! training_labels_view_sql {
+    () => {
         r#"
-CREATE TABLE ses.interaction_logs (
-    id BIGSERIAL PRIMARY KEY,
-
-    -- マッチング情報
-    match_result_id BIGINT REFERENCES ses.match_results(id) ON DELETE SET NULL,
-    talent_id BIGINT NOT NULL,
-    project_id BIGINT NOT NULL,
-    match_run_id VARCHAR(64) NOT NULL,  -- 実行インスタンスID（ULID/UUID、毎回生成）
-    engine_version VARCHAR(20),
-    config_version VARCHAR(20),
-
-    -- Two-Tower 予測
-    two_tower_score DOUBLE PRECISION,  -- 予測スコア
-    two_tower_embedder VARCHAR(50),    -- hash / onnx / candle
-    two_tower_version VARCHAR(20),     -- モデルバージョン
-
-    -- ビジネスルールスコア（比較用）
-    business_score DOUBLE PRECISION,
-
-    -- 結果（後から更新）
-    -- 許容値: accepted, rejected, interview_scheduled, review_ok, review_ng,
-    --         thumbs_up, thumbs_down, no_response, NULL（初期値）
-    -- ※ 'pending' 文字列は使わない（初期状態 = NULL）
-    outcome VARCHAR(20),
-    feedback_at TIMESTAMPTZ,
-
-    -- A/Bテスト
-    variant VARCHAR(50),  -- 'control', 'two_tower_10pct', ...
-
-    -- メタデータ
-    created_at TIMESTAMPTZ NOT NULL DEFAULT clock_timestamp(),
-
-    -- 基準タイムゾーンの日付（自動算出 - アプリは触れない、検索/集計用）
-    run_date DATE GENERATED ALWAYS AS (
-        {run_date}
-    ) STORED,
-
-    -- 同一 run 内の二重INSERT（リトライ/バグ）を抑止、別 run なら同日でも記録可
-    CONSTRAINT interaction_logs_unique_run_pair UNIQUE (match_run_id, talent_id, project_id)
-);
-
-CREATE INDEX idx_interaction_logs_match_run ON ses.interaction_logs(match_run_id, created_at DESC);
-CREATE INDEX idx_interaction_logs_match_result ON ses.interaction_logs(match_result_id);
-CREATE INDEX idx_interaction_logs_talent_run_date ON ses.interaction_logs(talent_id, run_date DESC, created_at DESC);
-CREATE INDEX idx_interaction_logs_project_run_date ON ses.interaction_logs(project_id, run_date DESC, created_at DESC);
-CREATE INDEX idx_interaction_logs_outcome ON ses.interaction_logs(outcome, created_at DESC)
-    WHERE outcome IS NOT NULL;
-
-CREATE OR REPLACE VIEW ses.training_pairs AS
-SELECT
-    il.talent_id,
-    il.project_id,
-    il.two_tower_score,
-    il.two_tower_embedder,
-    il.two_tower_version,
-    il.business_score,
-    il.outcome,
-    il.variant,
-    CASE
-        WHEN il.outcome = 'accepted' THEN 1.0
-        WHEN il.outcome = 'rejected' THEN 0.0
-        WHEN il.outcome = 'thumbs_up' THEN 1.0
-        WHEN il.outcome = 'thumbs_down' THEN 0.0
-        WHEN il.outcome = 'review_ok' THEN 1.0
-        WHEN il.outcome = 'review_ng' THEN 0.0
-        WHEN il.outcome = 'interview_scheduled' THEN 0.8
-        ELSE NULL
-    END AS label,
-    il.run_date,
-    il.created_at
-FROM ses.interaction_logs il
-WHERE il.outcome IS NOT NULL
-  AND il.outcome <> 'no_response';
--- ※ 'pending' は NULL で表現するため、outcome IS NOT NULL で除外済み
-
-CREATE OR REPLACE VIEW ses.training_stats AS
-SELECT
-    COUNT(*) FILTER (WHERE outcome = 'accepted') AS accepted_count,
-    COUNT(*) FILTER (WHERE outcome = 'rejected') AS rejected_count,
-    COUNT(*) FILTER (WHERE outcome IS NULL) AS pending_count,
-    -- Cold Start判定用: training_pairsで使えるラベル総数
-    -- ※ 'pending' は NULL で表現するため、outcome IS NOT NULL で除外済み
-    COUNT(*) FILTER (WHERE outcome IS NOT NULL AND outcome <> 'no_response') AS labeled_count,
-    MIN(created_at) AS first_log_at,
-    MAX(created_at) AS last_log_at,
-    COUNT(DISTINCT run_date) AS active_days  -- JST基準のrun_dateを使用
-FROM ses.interaction_logs;
-
 -- 統合学習ラベル: CV + FB + 行動ログを優先順位で統合
 -- ※ training_pairs は後方互換のため残す。新規学習はこちらを使用推奨
 --
 -- ラベルスケール設計:
 --   CV層（最強）: contract_signed=1.0, offer=0.9, interview=0.8, entry=0.7, contacted=0.4, lost=0.0
---   FB層（中間）: accepted/thumbs_up/review_ok=1.0, interview=0.8, rejected/thumbs_down/review_ng=0.0
+--   FB層（中間）: accepted/thumbs_up/review_ok=1.0, interview=0.8, proposed=0.4,
+--                 rejected/thumbs_down/review_ng=0.0
+--   ※ proposed（営業が提案しただけ）は承諾ではないので contacted と同じ重みに留める
 --   行動層（弱）: shortlisted/clicked_contact=0.3, copied_template=0.2, viewed_detail=0.1
 --
 -- best_stage 採用理由:
@@ -665,6 +668,7 @@ fb AS (
             WHEN outcome = 'review_ok'           THEN 1.0
             WHEN outcome = 'review_ng'           THEN 0.0
             WHEN outcome = 'interview_scheduled' THEN 0.8
+            WHEN outcome = 'proposed'            THEN 0.4
             ELSE NULL
         END AS fb_label
     FROM ses.interaction_logs
@@ -707,8 +711,110 @@ LEFT JOIN behavior_best bb ON bb.interaction_id = il.id
 WHERE cv.cv_label IS NOT NULL
    OR fb.fb_label IS NOT NULL
    OR bb.behavior_label IS NOT NULL;
-"#,
+"#
+    };
+}
+pub(crate) use training_labels_view_sql;
+
+pub const TRAINING_LABELS_VIEW_DDL: &str = training_labels_view_sql!();
+
+/// Interaction logging for recommendations and downstream training views.
+/// run_date is a generated column based on created_at in JST timezone.
+/// UNIQUE is per (match_run_id, talent_id, project_id) to allow multiple runs per day.
+pub static INTERACTION_LOGS_DDL: Lazy<String> = Lazy::new(|| {
+    format!(
+        r#"
+CREATE TABLE ses.interaction_logs (
+    id BIGSERIAL PRIMARY KEY,
+
+    -- マッチング情報
+    match_result_id BIGINT REFERENCES ses.match_results(id) ON DELETE SET NULL,
+    talent_id BIGINT NOT NULL,
+    project_id BIGINT NOT NULL,
+    match_run_id VARCHAR(64) NOT NULL,  -- 実行インスタンスID（ULID/UUID、毎回生成）
+    engine_version VARCHAR(20),
+    config_version VARCHAR(20),
+
+    -- Two-Tower 予測
+    two_tower_score DOUBLE PRECISION,  -- 予測スコア
+    two_tower_embedder VARCHAR(50),    -- hash / onnx / candle
+    two_tower_version VARCHAR(20),     -- モデルバージョン
+
+    -- ビジネスルールスコア（比較用）
+    business_score DOUBLE PRECISION,
+
+    -- 結果（後から更新）
+    -- 許容値: proposed, accepted, rejected, interview_scheduled, review_ok, review_ng,
+    --         thumbs_up, thumbs_down, no_response, NULL（初期値）
+    -- ※ 'pending' 文字列は使わない（初期状態 = NULL）
+    outcome VARCHAR(20),
+    feedback_at TIMESTAMPTZ,
+
+    -- A/Bテスト
+    variant VARCHAR(50),  -- 'control', 'two_tower_10pct', ...
+
+    -- メタデータ
+    created_at TIMESTAMPTZ NOT NULL DEFAULT clock_timestamp(),
+
+    -- 基準タイムゾーンの日付（自動算出 - アプリは触れない、検索/集計用）
+    run_date DATE GENERATED ALWAYS AS (
+        {run_date}
+    ) STORED,
+
+    -- 同一 run 内の二重INSERT（リトライ/バグ）を抑止、別 run なら同日でも記録可
+    CONSTRAINT interaction_logs_unique_run_pair UNIQUE (match_run_id, talent_id, project_id)
+);
+
+CREATE INDEX idx_interaction_logs_match_run ON ses.interaction_logs(match_run_id, created_at DESC);
+CREATE INDEX idx_interaction_logs_match_result ON ses.interaction_logs(match_result_id);
+CREATE INDEX idx_interaction_logs_talent_run_date ON ses.interaction_logs(talent_id, run_date DESC, created_at DESC);
+CREATE INDEX idx_interaction_logs_project_run_date ON ses.interaction_logs(project_id, run_date DESC, created_at DESC);
+CREATE INDEX idx_interaction_logs_outcome ON ses.interaction_logs(outcome, created_at DESC)
+    WHERE outcome IS NOT NULL;
+
+CREATE OR REPLACE VIEW ses.training_pairs AS
+SELECT
+    il.talent_id,
+    il.project_id,
+    il.two_tower_score,
+    il.two_tower_embedder,
+    il.two_tower_version,
+    il.business_score,
+    il.outcome,
+    il.variant,
+    CASE
+        WHEN il.outcome = 'accepted' THEN 1.0
+        WHEN il.outcome = 'rejected' THEN 0.0
+        WHEN il.outcome = 'thumbs_up' THEN 1.0
+        WHEN il.outcome = 'thumbs_down' THEN 0.0
+        WHEN il.outcome = 'review_ok' THEN 1.0
+        WHEN il.outcome = 'review_ng' THEN 0.0
+        WHEN il.outcome = 'interview_scheduled' THEN 0.8
+        ELSE NULL
+    END AS label,
+    il.run_date,
+    il.created_at
+FROM ses.interaction_logs il
+WHERE il.outcome IS NOT NULL
+  AND il.outcome <> 'no_response';
+-- ※ 'pending' は NULL で表現するため、outcome IS NOT NULL で除外済み
+
+CREATE OR REPLACE VIEW ses.training_stats AS
+SELECT
+    COUNT(*) FILTER (WHERE outcome = 'accepted') AS accepted_count,
+    COUNT(*) FILTER (WHERE outcome = 'rejected') AS rejected_count,
+    COUNT(*) FILTER (WHERE outcome IS NULL) AS pending_count,
+    -- Cold Start判定用: training_pairsで使えるラベル総数
+    -- ※ 'pending' は NULL で表現するため、outcome IS NOT NULL で除外済み
+    COUNT(*) FILTER (WHERE outcome IS NOT NULL AND outcome <> 'no_response') AS labeled_count,
+    MIN(created_at) AS first_log_at,
+    MAX(created_at) AS last_log_at,
+    COUNT(DISTINCT run_date) AS active_days  -- JST基準のrun_dateを使用
+FROM ses.interaction_logs;
+
+{training_labels}"#,
         run_date = RUN_DATE_EXPRESSION.as_str(),
+        training_labels = TRAINING_LABELS_VIEW_DDL,
     )
 });
 
@@ -761,6 +867,34 @@ mod tests {
         }
     }
 
+    #[test]
+    fn projects_schema_tracks_sources_and_filters() {
+        for required in [
+            "message_id VARCHAR(255) NOT NULL UNIQUE",
+            "extraction_job_id",
+            "project JSONB NOT NULL",
+            "field_sources JSONB",
+            "source_updated_at",
+            "idx_projects_received_at",
+            "idx_projects_todofuken",
+        ] {
+            assert!(PROJECTS_DDL.contains(required));
+        }
+    }
+
+    #[test]
+    fn talent_embeddings_schema_keys_by_model_and_tracks_freshness() {
+        for required in [
+            "PRIMARY KEY (talent_id, embedder, embedder_version, dimension)",
+            "embedding REAL[] NOT NULL",
+            "talent_updated_at TIMESTAMPTZ NOT NULL",
+            "chk_talent_embeddings_dimension",
+            "idx_talent_embeddings_model",
+        ] {
+            assert!(TALENT_EMBEDDINGS_DDL.contains(required));
+        }
+    }
+
     #[test]
     fn match_results_schema_contains_indexes_and_uniques() {
         for required in [
@@ -954,4 +1088,11 @@ mod tests {
             );
         }
     }
+
+    #[test]
+    fn training_labels_weights_proposed_below_accepted() {
+        assert!(TRAINING_LABELS_VIEW_DDL.contains("WHEN outcome = 'proposed'            THEN 0.4"));
+        assert!(TRAINING_LABELS_VIEW_DDL.contains("WHEN outcome = 'accepted'            THEN 1.0"));
+        assert!(FEEDBACK_EVENTS_DDL.contains("'proposed', 'accepted'"));
+    }
 }
@@ -1,5 +1,7 @@
 use std::collections::{HashMap, HashSet};
 use std::sync::LazyLock;
+
+use regex::Regex;
 use strsim::damerau_levenshtein;
 use unicode_normalization::UnicodeNormalization;
 
@@ -361,6 +363,65 @@ pub fn normalize_skills_vec(skills: &[String]) -> Vec<String> {
     result
 }
 
+/// 英数字スキル表記の連なり（"Spring Boot" / "C#" / ".NET" / "Node.js" など）
+static ASCII_RUN_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"\.?[a-z][a-z0-9.+#\-]*(?: [a-z0-9][a-z0-9.+#\-]*)*").unwrap());
+
+/// 非 ASCII を含むエイリアス（"機械学習" など）。本文への部分一致で探す
+static NON_ASCII_ALIASES: LazyLock<Vec<(&'static str, &'static str)>> = LazyLock::new(|| {
+    let mut aliases: Vec<_> = ALIAS_TO_CANONICAL
+        .iter()
+        .filter(|(alias, _)| !alias.is_ascii())
+        .map(|(alias, canonical)| (*alias, *canonical))
+        .collect();
+    aliases.sort();
+    aliases
+});
+
+/// 自由文の照合に使う英数字トークンの最小文字数。
+/// 「PG経験」（プログラマ）→ postgresql、「ES経験」→ elasticsearch のような誤検出を避けるため、
+/// 2 文字以下（pg / es / js / go / c# など）は行がそのトークンだけの場合にしか拾わない
+const MIN_FREE_TEXT_TOKEN_CHARS: usize = 3;
+
+/// 自由文から辞書にあるスキルを出現順に拾う（重複なし）。
+/// 英数字の連なりは全体 → 単語の順に照合し、非 ASCII エイリアスは部分一致で探す
+pub fn find_known_skills(text: &str) -> Vec<String> {
+    let normalized = nfkc_lower_trim(text);
+    let mut found: Vec<(usize, String)> = Vec::new();
+    let match_token = |token: &str| {
+        (token.chars().count() >= MIN_FREE_TEXT_TOKEN_CHARS || token == normalized)
+            .then(|| match_canonical_token(token))
+            .flatten()
+    };
+
+    for run in ASCII_RUN_RE.find_iter(&normalized) {
+        let phrase = run.as_str().trim_end_matches(['.', '-']);
+        if let Some(canonical) = match_token(phrase) {
+            found.push((run.start(), canonical));
+            continue;
+        }
+        let mut offset = run.start();
+        for word in phrase.split(' ') {
+            if let Some(canonical) = match_token(word.trim_end_matches(['.', '-'])) {
+                found.push((offset, canonical));
+            }
+            offset += word.len() + 1;
+        }
+    }
+    for (alias, canonical) in NON_ASCII_ALIASES.iter() {
+        if let Some(pos) = normalized.find(alias) {
+            found.push((pos, canonical.to_string()));
+        }
+    }
+
+    found.sort_by_key(|(pos, _)| *pos);
+    let mut seen = HashSet::new();
+    found
+        .into_iter()
+        .filter_map(|(_, skill)| seen.insert(skill.clone()).then_some(skill))
+        .collect()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -402,6 +463,30 @@ mod tests {
         assert_eq!(normalize_skill("rustt"), "rustt");
     }
 
+    #[test]
+    fn finds_known_skills_in_free_text() {
+        assert_eq!(
+            find_known_skills("Java（Spring Boot）での開発経験3年以上、ＡＷＳ/k8s 運用"),
+            vec!["java", "spring", "aws", "kubernetes"]
+        );
+        assert_eq!(
+            find_known_skills("機械学習モデルの構築経験、Pythonは必須"),
+            vec!["ml", "python"]
+        );
+        assert!(find_known_skills("コミュニケーション能力").is_empty());
+        assert_eq!(find_known_skills("Kuberntes の運用"), vec!["kubernetes"]);
+    }
+
+    #[test]
+    fn short_aliases_need_a_line_of_their_own() {
+        // SES メールの「PG」はプログラマ、「ES」は社員満足などで使われる
+        assert!(find_known_skills("PG経験3年以上").is_empty());
+        assert!(find_known_skills("ES経験").is_empty());
+        assert_eq!(find_known_skills("PG経験5年、Java"), vec!["java"]);
+        assert_eq!(find_known_skills("Go"), vec!["golang"]);
+        assert_eq!(find_known_skills(" JS "), vec!["javascript"]);
+    }
+
     #[test]
     fn test_unknown_skill_lowercases() {
         assert_eq!(normalize_skill("MyCustomFramework"), "mycustomframework");
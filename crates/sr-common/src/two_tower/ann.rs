@@ -0,0 +1,477 @@
+//! 人材埋め込みの近似最近傍インデックス（HNSW, プロセス内）
+//!
+//! 全人材への線形コサインスキャンの代わりに、案件埋め込みに近い上位 K 人材を返す recall ステージ。
+//! ベクトルは L2 正規化して保持するため、類似度は内積 = コサイン類似度。
+
+use std::cmp::{Ordering, Reverse};
+use std::collections::{BinaryHeap, HashMap, HashSet};
+
+use thiserror::Error;
+
+use super::{Embedding, TwoTowerEmbedder};
+use crate::Talent;
+
+/// レベル割り当ての上限（1e9 件でも十分な高さ）
+const MAX_LEVEL: usize = 16;
+
+#[derive(Debug, Error, PartialEq)]
+pub enum AnnIndexError {
+    #[error("talent {id} embedding has {actual} dims but the index expects {expected}")]
+    DimensionMismatch {
+        id: i64,
+        expected: usize,
+        actual: usize,
+    },
+    #[error("talent {0} is already indexed")]
+    DuplicateId(i64),
+}
+
+#[derive(Debug, Clone)]
+pub struct HnswConfig {
+    /// 上位レイヤーの最大近傍数（レイヤー 0 はこの 2 倍）
+    pub max_neighbors: usize,
+    pub ef_construction: usize,
+    /// 検索時の候補幅（k より小さい場合は k を使う）
+    pub ef_search: usize,
+    /// レベル割り当ての seed（同じ挿入順なら同じグラフになる）
+    pub seed: u64,
+}
+
+impl Default for HnswConfig {
+    fn default() -> Self {
+        Self {
+            max_neighbors: 16,
+            ef_construction: 100,
+            ef_search: 64,
+            seed: 42,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Scored {
+    similarity: f32,
+    node: u32,
+}
+
+impl Eq for Scored {}
+
+impl Ord for Scored {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.similarity
+            .total_cmp(&other.similarity)
+            .then_with(|| other.node.cmp(&self.node))
+    }
+}
+
+impl PartialOrd for Scored {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+fn normalized(mut vector: Vec<f32>) -> Vec<f32> {
+    let norm = vector.iter().map(|v| v * v).sum::<f32>().sqrt();
+    if norm > f32::EPSILON {
+        vector.iter_mut().for_each(|v| *v /= norm);
+    }
+    vector
+}
+
+fn dot(a: &[f32], b: &[f32]) -> f32 {
+    a.iter().zip(b).map(|(x, y)| x * y).sum()
+}
+
+/// splitmix64（レベル割り当て用の決定的な乱数）
+fn splitmix64(mut x: u64) -> u64 {
+    x = x.wrapping_add(0x9E37_79B9_7F4A_7C15);
+    x = (x ^ (x >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    x = (x ^ (x >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    x ^ (x >> 31)
+}
+
+/// Hierarchical Navigable Small World グラフ
+pub struct HnswIndex {
+    config: HnswConfig,
+    dimension: usize,
+    ids: Vec<i64>,
+    nodes: HashMap<i64, u32>,
+    vectors: Vec<Vec<f32>>,
+    /// node → layer → 近傍 node
+    links: Vec<Vec<Vec<u32>>>,
+    entry_point: Option<u32>,
+    top_layer: usize,
+}
+
+impl HnswIndex {
+    pub fn new(dimension: usize, config: HnswConfig) -> Self {
+        Self {
+            config,
+            dimension,
+            ids: Vec::new(),
+            nodes: HashMap::new(),
+            vectors: Vec::new(),
+            links: Vec::new(),
+            entry_point: None,
+            top_layer: 0,
+        }
+    }
+
+    /// `items` を順に挿入して構築する
+    pub fn build(
+        dimension: usize,
+        config: HnswConfig,
+        items: impl IntoIterator<Item = (i64, Vec<f32>)>,
+    ) -> Result<Self, AnnIndexError> {
+        let mut index = Self::new(dimension, config);
+        for (id, vector) in items {
+            index.insert(id, vector)?;
+        }
+        Ok(index)
+    }
+
+    pub fn len(&self) -> usize {
+        self.ids.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.ids.is_empty()
+    }
+
+    pub fn dimension(&self) -> usize {
+        self.dimension
+    }
+
+    pub fn contains(&self, id: i64) -> bool {
+        self.nodes.contains_key(&id)
+    }
+
+    fn max_links(&self, layer: usize) -> usize {
+        let m = self.config.max_neighbors.max(2);
+        if layer == 0 {
+            m * 2
+        } else {
+            m
+        }
+    }
+
+    fn random_level(&self, node: u32) -> usize {
+        let bits = splitmix64(self.config.seed ^ (node as u64).wrapping_mul(0xA24B_AED4_963E_E407));
+        // (0, 1] の一様乱数
+        let uniform = ((bits >> 11) as f64 + 1.0) / (1u64 << 53) as f64;
+        let scale = 1.0 / (self.config.max_neighbors.max(2) as f64).ln();
+        ((-uniform.ln() * scale) as usize).min(MAX_LEVEL)
+    }
+
+    fn similarity(&self, query: &[f32], node: u32) -> f32 {
+        dot(query, &self.vectors[node as usize])
+    }
+
+    /// 1 レイヤー内のビームサーチ。類似度の降順で最大 `ef` 件返す
+    fn search_layer(&self, query: &[f32], entries: &[u32], ef: usize, layer: usize) -> Vec<Scored> {
+        let mut visited: HashSet<u32> = entries.iter().copied().collect();
+        let mut candidates = BinaryHeap::new();
+        let mut results = BinaryHeap::new();
+        for &node in entries {
+            let scored = Scored {
+                similarity: self.similarity(query, node),
+                node,
+            };
+            candidates.push(scored);
+            results.push(Reverse(scored));
+        }
+        while results.len() > ef {
+            results.pop();
+        }
+
+        while let Some(current) = candidates.pop() {
+            let worst = results
+                .peek()
+                .map_or(f32::NEG_INFINITY, |r: &Reverse<Scored>| r.0.similarity);
+            if results.len() >= ef && current.similarity < worst {
+                break;
+            }
+            for &neighbor in &self.links[current.node as usize][layer] {
+                if !visited.insert(neighbor) {
+                    continue;
+                }
+                let scored = Scored {
+                    similarity: self.similarity(query, neighbor),
+                    node: neighbor,
+                };
+                let worst = results.peek().map_or(f32::NEG_INFINITY, |r| r.0.similarity);
+                if results.len() < ef || scored.similarity > worst {
+                    candidates.push(scored);
+                    results.push(Reverse(scored));
+                    if results.len() > ef {
+                        results.pop();
+                    }
+                }
+            }
+        }
+
+        results
+            .into_sorted_vec()
+            .into_iter()
+            .map(|Reverse(scored)| scored)
+            .collect()
+    }
+
+    /// 上位レイヤーを貪欲に降りて `target_layer` の入口を返す
+    fn descend(&self, query: &[f32], mut entry: u32, target_layer: usize) -> u32 {
+        for layer in (target_layer + 1..=self.top_layer).rev() {
+            if let Some(best) = self.search_layer(query, &[entry], 1, layer).first() {
+                entry = best.node;
+            }
+        }
+        entry
+    }
+
+    pub fn insert(&mut self, id: i64, vector: Vec<f32>) -> Result<(), AnnIndexError> {
+        if vector.len() != self.dimension {
+            return Err(AnnIndexError::DimensionMismatch {
+                id,
+                expected: self.dimension,
+                actual: vector.len(),
+            });
+        }
+        if self.nodes.contains_key(&id) {
+            return Err(AnnIndexError::DuplicateId(id));
+        }
+
+        let node = self.ids.len() as u32;
+        let level = self.random_level(node);
+        let vector = normalized(vector);
+        self.ids.push(id);
+        self.nodes.insert(id, node);
+        self.links.push(vec![Vec::new(); level + 1]);
+
+        let Some(entry) = self.entry_point else {
+            self.vectors.push(vector);
+            self.entry_point = Some(node);
+            self.top_layer = level;
+            return Ok(());
+        };
+
+        let mut entries = vec![self.descend(&vector, entry, level)];
+        for layer in (0..=level.min(self.top_layer)).rev() {
+            let found = self.search_layer(&vector, &entries, self.config.ef_construction, layer);
+            let max_links = self.max_links(layer);
+            let selected: Vec<u32> = found.iter().take(max_links).map(|s| s.node).collect();
+
+            for &neighbor in &selected {
+                let mut neighbor_links = self.links[neighbor as usize][layer].clone();
+                neighbor_links.push(node);
+                if neighbor_links.len() > max_links {
+                    let base = &self.vectors[neighbor as usize];
+                    let mut scored: Vec<Scored> = neighbor_links
+                        .iter()
+                        .map(|&n| Scored {
+                            similarity: if n == node {
+                                dot(base, &vector)
+                            } else {
+                                dot(base, &self.vectors[n as usize])
+                            },
+                            node: n,
+                        })
+                        .collect();
+                    scored.sort_by(|a, b| b.cmp(a));
+                    neighbor_links = scored.into_iter().take(max_links).map(|s| s.node).collect();
+                }
+                self.links[neighbor as usize][layer] = neighbor_links;
+            }
+            self.links[node as usize][layer] = selected;
+            entries = found.into_iter().map(|s| s.node).collect();
+        }
+
+        self.vectors.push(vector);
+        if level > self.top_layer {
+            self.entry_point = Some(node);
+            self.top_layer = level;
+        }
+        Ok(())
+    }
+
+    /// `query` に近い上位 k 件を (talent_id, コサイン類似度) の降順で返す。次元が違えば空
+    pub fn search(&self, query: &[f32], k: usize) -> Vec<(i64, f32)> {
+        let Some(entry) = self.entry_point else {
+            return Vec::new();
+        };
+        if k == 0 || query.len() != self.dimension {
+            return Vec::new();
+        }
+
+        let query = normalized(query.to_vec());
+        let entry = self.descend(&query, entry, 0);
+        self.search_layer(&query, &[entry], self.config.ef_search.max(k), 0)
+            .into_iter()
+            .take(k)
+            .map(|s| (self.ids[s.node as usize], s.similarity))
+            .collect()
+    }
+}
+
+/// Two-Tower の recall ステージ: 案件埋め込みに近い上位 K 人材だけを `EnhancedPreFilter` に渡す
+///
+/// インデックス構築後に追加・更新された人材（インデックスに無い id）は取りこぼさないよう常に残す。
+pub struct TalentRecall {
+    index: HnswIndex,
+    top_k: usize,
+    embedder: String,
+    version: String,
+}
+
+impl TalentRecall {
+    pub fn new(
+        index: HnswIndex,
+        top_k: usize,
+        embedder: impl Into<String>,
+        version: impl Into<String>,
+    ) -> Self {
+        Self {
+            index,
+            top_k,
+            embedder: embedder.into(),
+            version: version.into(),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.index.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.index.is_empty()
+    }
+
+    pub fn top_k(&self) -> usize {
+        self.top_k
+    }
+
+    /// インデックスを作った embedder と同じモデルか
+    pub fn is_built_for(&self, embedder: &dyn TwoTowerEmbedder) -> bool {
+        self.embedder == embedder.name()
+            && self.version == embedder.version()
+            && self.index.dimension() == embedder.dimension()
+    }
+
+    /// 上位 K 人材 + インデックス外の人材を元の順序のまま返す
+    pub fn candidates(&self, project_embedding: &Embedding, talents: &[Talent]) -> Vec<Talent> {
+        let hits: HashSet<i64> = self
+            .index
+            .search(&project_embedding.vector, self.top_k)
+            .into_iter()
+            .map(|(id, _)| id)
+            .collect();
+        talents
+            .iter()
+            .filter(|talent| {
+                talent
+                    .id
+                    .is_none_or(|id| hits.contains(&id) || !self.index.contains(id))
+            })
+            .cloned()
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::two_tower::EmbeddingSource;
+
+    fn random_vectors(count: usize, dimension: usize, seed: u64) -> Vec<(i64, Vec<f32>)> {
+        (0..count)
+            .map(|i| {
+                let vector = (0..dimension)
+                    .map(|j| {
+                        let bits = splitmix64(seed ^ ((i * dimension + j) as u64));
+                        (bits >> 40) as f32 / (1u64 << 24) as f32 - 0.5
+                    })
+                    .collect();
+                (i as i64 + 1, vector)
+            })
+            .collect()
+    }
+
+    fn brute_force(items: &[(i64, Vec<f32>)], query: &[f32], k: usize) -> Vec<i64> {
+        let query = normalized(query.to_vec());
+        let mut scored: Vec<(i64, f32)> = items
+            .iter()
+            .map(|(id, v)| (*id, dot(&normalized(v.clone()), &query)))
+            .collect();
+        scored.sort_by(|a, b| b.1.total_cmp(&a.1));
+        scored.into_iter().take(k).map(|(id, _)| id).collect()
+    }
+
+    #[test]
+    fn search_recalls_exact_neighbours() {
+        let items = random_vectors(600, 16, 7);
+        let index = HnswIndex::build(16, HnswConfig::default(), items.clone()).unwrap();
+        assert_eq!(index.len(), 600);
+
+        let queries = random_vectors(20, 16, 99);
+        let mut hits = 0;
+        for (_, query) in &queries {
+            let expected: HashSet<i64> = brute_force(&items, query, 10).into_iter().collect();
+            let found = index.search(query, 10);
+            assert_eq!(found.len(), 10);
+            assert!(found.windows(2).all(|w| w[0].1 >= w[1].1));
+            hits += found.iter().filter(|(id, _)| expected.contains(id)).count();
+        }
+        let recall = hits as f64 / (queries.len() * 10) as f64;
+        assert!(recall >= 0.95, "recall@10 = {recall}");
+    }
+
+    #[test]
+    fn rejects_bad_inserts_and_handles_empty_index() {
+        let mut index = HnswIndex::new(3, HnswConfig::default());
+        assert!(index.search(&[1.0, 0.0, 0.0], 5).is_empty());
+
+        index.insert(1, vec![1.0, 0.0, 0.0]).unwrap();
+        assert_eq!(
+            index.insert(2, vec![1.0, 0.0]),
+            Err(AnnIndexError::DimensionMismatch {
+                id: 2,
+                expected: 3,
+                actual: 2
+            })
+        );
+        assert_eq!(
+            index.insert(1, vec![0.0, 1.0, 0.0]),
+            Err(AnnIndexError::DuplicateId(1))
+        );
+        assert_eq!(index.search(&[2.0, 0.0, 0.0], 5), vec![(1, 1.0)]);
+        assert!(index.search(&[1.0, 0.0], 5).is_empty());
+    }
+
+    #[test]
+    fn recall_keeps_top_k_and_unindexed_talents() {
+        let index = HnswIndex::build(
+            2,
+            HnswConfig::default(),
+            vec![
+                (1, vec![1.0, 0.0]),
+                (2, vec![0.9, 0.1]),
+                (3, vec![0.0, 1.0]),
+            ],
+        )
+        .unwrap();
+        let recall = TalentRecall::new(index, 2, "hash", "v2");
+        let talents: Vec<Talent> = [Some(3), Some(1), Some(4), Some(2), None]
+            .into_iter()
+            .map(|id| Talent {
+                id,
+                ..Talent::default()
+            })
+            .collect();
+
+        let project = Embedding::new(vec![1.0, 0.0], 2, EmbeddingSource::Project);
+        let ids: Vec<Option<i64>> = recall
+            .candidates(&project, &talents)
+            .into_iter()
+            .map(|t| t.id)
+            .collect();
+        assert_eq!(ids, vec![Some(1), Some(4), Some(2), None]);
+    }
+}
@@ -1,47 +1,666 @@
-use super::{Embedding, EmbeddingSource, TwoTowerEmbedder};
-use crate::{Project, Talent};
-
-/// Candle (Rust-native) を使用した Two-Tower（スタブ）
-///
-/// Phase 4+ でPyTorchモデルをRustに移植
-pub struct CandleTwoTower {
-    dimension: usize,
+use thiserror::Error;
+
+/// Candle Two-Tower の学習・読み込みエラー
+#[derive(Debug, Error)]
+pub enum CandleTowerError {
+    #[error(
+        "two-tower candle support is not compiled in; rebuild sr-common with `--features candle`"
+    )]
+    FeatureDisabled,
+    #[error("failed to read weights {path}: {message}")]
+    Load { path: String, message: String },
+    #[error("weights {path} are missing tensor `{name}`")]
+    MissingTensor { path: String, name: String },
+    #[error("weights {path} output {actual}-dim embeddings but TWO_TOWER_DIMENSION is {expected}")]
+    DimensionMismatch {
+        path: String,
+        expected: usize,
+        actual: usize,
+    },
+    #[error("no usable training examples (need at least one labeled pair)")]
+    NoTrainingData,
+    #[error("invalid training config: {0}")]
+    InvalidConfig(String),
+    #[error("failed to load training labels: {0}")]
+    TrainingData(#[from] crate::db::TrainingDataError),
+    #[error("failed to write weights {path}: {message}")]
+    Save { path: String, message: String },
+    #[error("candle error: {0}")]
+    Candle(String),
 }
 
+#[cfg(feature = "candle")]
+pub use model::{train, train_from_training_labels, CandleTwoTower, TrainConfig, TrainReport};
+
+/// `candle` feature なしのビルドでは読み込みを常に失敗させる（値は作れない）
+#[cfg(not(feature = "candle"))]
+pub enum CandleTwoTower {}
+
+#[cfg(not(feature = "candle"))]
 impl CandleTwoTower {
-    pub fn new(dimension: usize) -> Self {
-        Self { dimension }
+    pub fn load(_weights_path: &str, _dimension: usize) -> Result<Self, CandleTowerError> {
+        Err(CandleTowerError::FeatureDisabled)
     }
 }
 
-impl TwoTowerEmbedder for CandleTwoTower {
+#[cfg(not(feature = "candle"))]
+impl super::TwoTowerEmbedder for CandleTwoTower {
     fn name(&self) -> &'static str {
-        "candle"
+        match *self {}
     }
 
     fn version(&self) -> &str {
-        "v1"
+        match *self {}
     }
 
     fn dimension(&self) -> usize {
-        self.dimension
+        match *self {}
+    }
+
+    fn embed_project(&self, _project: &crate::Project) -> super::Embedding {
+        match *self {}
+    }
+
+    fn embed_talent(&self, _talent: &crate::Talent) -> super::Embedding {
+        match *self {}
+    }
+}
+
+#[cfg(feature = "candle")]
+mod model {
+    use std::collections::HashMap;
+    use std::path::Path;
+
+    use candle_core::{DType, Device, Tensor, Var};
+    use candle_nn::{AdamW, Optimizer, ParamsAdamW};
+    use rand::rngs::StdRng;
+    use rand::seq::SliceRandom;
+    use rand::{Rng, SeedableRng};
+    use sha2::{Digest, Sha256};
+    use tracing::{error, info};
+
+    use super::CandleTowerError;
+    use crate::db::{fetch_training_examples, PgPool, TrainingExample};
+    use crate::two_tower::tokenizer::{self, WeightedToken, PADDING_TOKEN_ID};
+    use crate::two_tower::{Embedding, EmbeddingSource, TwoTowerEmbedder};
+    use crate::{Project, Talent};
+
+    const TOWERS: [&str; 2] = ["project", "talent"];
+    const L2_EPS: f64 = 1e-6;
+
+    impl From<candle_core::Error> for CandleTowerError {
+        fn from(value: candle_core::Error) -> Self {
+            CandleTowerError::Candle(value.to_string())
+        }
+    }
+
+    fn tensor_name(tower: &str, param: &str) -> String {
+        format!("{tower}.{param}")
+    }
+
+    /// 1 tower 分の順伝播: 重み付き平均プーリング → Linear → ReLU → Linear → L2 正規化
+    ///
+    /// `params` は `{tower}.embedding` `[V, H]`、`{tower}.fc1.{weight,bias}`、`{tower}.fc2.{weight,bias}`
+    fn tower_forward(
+        params: &HashMap<String, Tensor>,
+        tower: &str,
+        ids: &Tensor,
+        weights: &Tensor,
+    ) -> candle_core::Result<Tensor> {
+        let get = |param: &str| {
+            params
+                .get(&tensor_name(tower, param))
+                .ok_or_else(|| candle_core::Error::Msg(format!("missing {tower}.{param}")))
+        };
+        let (rows, seq_len) = ids.dims2()?;
+        let table = get("embedding")?;
+        let hidden = table.dim(1)?;
+
+        let gathered = table
+            .index_select(&ids.flatten_all()?, 0)?
+            .reshape((rows, seq_len, hidden))?;
+        let weight_sum = (weights.sum_keepdim(1)? + L2_EPS)?;
+        let pooled = gathered
+            .broadcast_mul(&weights.unsqueeze(2)?)?
+            .sum(1)?
+            .broadcast_div(&weight_sum)?;
+
+        let fc1 = pooled
+            .matmul(&get("fc1.weight")?.t()?)?
+            .broadcast_add(get("fc1.bias")?)?
+            .relu()?;
+        let out = fc1
+            .matmul(&get("fc2.weight")?.t()?)?
+            .broadcast_add(get("fc2.bias")?)?;
+        let norm = (out.sqr()?.sum_keepdim(1)? + L2_EPS)?.sqrt()?;
+        out.broadcast_div(&norm)
+    }
+
+    /// トークン列をパディング済みの `[N, L]` 語彙 ID / 重みテンソルに変換する
+    fn batch_tensors(
+        batch: &[Vec<WeightedToken>],
+        vocab_size: usize,
+        device: &Device,
+    ) -> candle_core::Result<(Tensor, Tensor)> {
+        let rows = batch.len().max(1);
+        let seq_len = batch.iter().map(Vec::len).max().unwrap_or(0).max(1);
+        let mut ids = vec![PADDING_TOKEN_ID as u32; rows * seq_len];
+        let mut weights = vec![0.0f32; rows * seq_len];
+        for (row, tokens) in batch.iter().enumerate() {
+            let (row_ids, row_weights) = tokenizer::token_ids(tokens, vocab_size);
+            for (col, (id, weight)) in row_ids.into_iter().zip(row_weights).enumerate() {
+                ids[row * seq_len + col] = id as u32;
+                weights[row * seq_len + col] = weight;
+            }
+        }
+        Ok((
+            Tensor::from_vec(ids, (rows, seq_len), device)?,
+            Tensor::from_vec(weights, (rows, seq_len), device)?,
+        ))
+    }
+
+    /// Candle (CPU) による学習可能な Two-Tower
+    ///
+    /// 案件・人材それぞれに `tokenizer` 語彙上の埋め込みテーブルと 2 層 MLP を持つ。
+    /// `version()` は safetensors ファイルの SHA-256 先頭 16 桁。
+    pub struct CandleTwoTower {
+        params: HashMap<String, Tensor>,
+        vocab_size: usize,
+        dimension: usize,
+        version: String,
+        device: Device,
+    }
+
+    impl CandleTwoTower {
+        /// safetensors の重みを読み込み、出力次元が `dimension` と一致するかを検証する
+        pub fn load(weights_path: &str, dimension: usize) -> Result<Self, CandleTowerError> {
+            let device = Device::Cpu;
+            let bytes = std::fs::read(weights_path).map_err(|err| CandleTowerError::Load {
+                path: weights_path.to_string(),
+                message: err.to_string(),
+            })?;
+            let params = candle_core::safetensors::load_buffer(&bytes, &device).map_err(|err| {
+                CandleTowerError::Load {
+                    path: weights_path.to_string(),
+                    message: err.to_string(),
+                }
+            })?;
+
+            for tower in TOWERS {
+                for param in [
+                    "embedding",
+                    "fc1.weight",
+                    "fc1.bias",
+                    "fc2.weight",
+                    "fc2.bias",
+                ] {
+                    let name = tensor_name(tower, param);
+                    if !params.contains_key(&name) {
+                        return Err(CandleTowerError::MissingTensor {
+                            path: weights_path.to_string(),
+                            name,
+                        });
+                    }
+                }
+                let actual = params[&tensor_name(tower, "fc2.weight")].dim(0)?;
+                if actual != dimension {
+                    return Err(CandleTowerError::DimensionMismatch {
+                        path: weights_path.to_string(),
+                        expected: dimension,
+                        actual,
+                    });
+                }
+            }
+            let vocab_size = params[&tensor_name("project", "embedding")].dim(0)?;
+
+            let tower = Self {
+                params,
+                vocab_size,
+                dimension,
+                version: weights_version(&bytes),
+                device,
+            };
+            info!(
+                weights_path,
+                version = %tower.version,
+                vocab_size,
+                dimension,
+                "loaded candle two-tower weights"
+            );
+            Ok(tower)
+        }
+
+        fn embed_batch(
+            &self,
+            tower: &str,
+            batch: Vec<Vec<WeightedToken>>,
+            source: EmbeddingSource,
+        ) -> Vec<Embedding> {
+            let result = batch_tensors(&batch, self.vocab_size, &self.device)
+                .and_then(|(ids, weights)| tower_forward(&self.params, tower, &ids, &weights))
+                .and_then(|out| out.to_vec2::<f32>());
+            match result {
+                Ok(rows) => rows
+                    .into_iter()
+                    .take(batch.len())
+                    .map(|vector| Embedding::new(vector, self.dimension, source))
+                    .collect(),
+                Err(err) => {
+                    error!(
+                        tower,
+                        batch = batch.len(),
+                        error = %err,
+                        "candle two-tower inference failed; using zero embeddings"
+                    );
+                    batch
+                        .iter()
+                        .map(|_| Embedding::new(vec![0.0; self.dimension], self.dimension, source))
+                        .collect()
+                }
+            }
+        }
+    }
+
+    impl TwoTowerEmbedder for CandleTwoTower {
+        fn name(&self) -> &'static str {
+            "candle"
+        }
+
+        fn version(&self) -> &str {
+            &self.version
+        }
+
+        fn dimension(&self) -> usize {
+            self.dimension
+        }
+
+        fn embed_project(&self, project: &Project) -> Embedding {
+            let tokens = tokenizer::tokenize_project(project);
+            self.embed_batch("project", vec![tokens], EmbeddingSource::Project)
+                .remove(0)
+        }
+
+        fn embed_talent(&self, talent: &Talent) -> Embedding {
+            let tokens = tokenizer::tokenize_talent(talent);
+            self.embed_batch("talent", vec![tokens], EmbeddingSource::Talent)
+                .remove(0)
+        }
+
+        fn embed_talents(&self, talents: &[Talent]) -> Vec<Embedding> {
+            if talents.is_empty() {
+                return Vec::new();
+            }
+            let batch = talents.iter().map(tokenizer::tokenize_talent).collect();
+            self.embed_batch("talent", batch, EmbeddingSource::Talent)
+        }
     }
 
-    fn embed_project(&self, _project: &Project) -> Embedding {
-        // Phase 4+: Candle 推論
-        Embedding::new(
-            vec![0.0; self.dimension],
-            self.dimension,
-            EmbeddingSource::Project,
-        )
+    fn weights_version(bytes: &[u8]) -> String {
+        let digest = Sha256::digest(bytes);
+        digest.iter().take(8).map(|b| format!("{b:02x}")).collect()
     }
 
-    fn embed_talent(&self, _talent: &Talent) -> Embedding {
-        // Phase 4+: Candle 推論
-        Embedding::new(
-            vec![0.0; self.dimension],
-            self.dimension,
-            EmbeddingSource::Talent,
-        )
+    /// 学習ハイパーパラメータ（既定値はノート PC の CPU で数分以内に終わる規模）
+    #[derive(Debug, Clone)]
+    pub struct TrainConfig {
+        /// `tokenizer::token_id` の語彙サイズ（0 はパディング）
+        pub vocab_size: usize,
+        pub hidden_dim: usize,
+        /// 出力次元（TWO_TOWER_DIMENSION と揃える）
+        pub dimension: usize,
+        pub epochs: usize,
+        pub batch_size: usize,
+        pub learning_rate: f64,
+        /// in-batch negatives の softmax 温度
+        pub temperature: f64,
+        /// BCE の logit = cos 類似度 × scale
+        pub bce_scale: f64,
+        /// label がこの値以上のペアを in-batch 対照学習の正例にする
+        pub positive_threshold: f64,
+        pub seed: u64,
+    }
+
+    impl Default for TrainConfig {
+        fn default() -> Self {
+            Self {
+                vocab_size: 4096,
+                hidden_dim: 64,
+                dimension: 256,
+                epochs: 20,
+                batch_size: 64,
+                learning_rate: 5e-3,
+                temperature: 0.1,
+                bce_scale: 5.0,
+                positive_threshold: 0.5,
+                seed: 42,
+            }
+        }
+    }
+
+    impl TrainConfig {
+        fn validate(&self) -> Result<(), CandleTowerError> {
+            let invalid = |msg: &str| Err(CandleTowerError::InvalidConfig(msg.into()));
+            if self.vocab_size < 2 {
+                return invalid("vocab_size must be at least 2");
+            }
+            if self.hidden_dim == 0 || self.dimension == 0 {
+                return invalid("hidden_dim and dimension must be positive");
+            }
+            if self.epochs == 0 || self.batch_size == 0 {
+                return invalid("epochs and batch_size must be positive");
+            }
+            if !(self.learning_rate > 0.0 && self.temperature > 0.0 && self.bce_scale > 0.0) {
+                return invalid("learning_rate, temperature and bce_scale must be positive");
+            }
+            Ok(())
+        }
+    }
+
+    #[derive(Debug, Clone)]
+    pub struct TrainReport {
+        pub examples: usize,
+        pub positives: usize,
+        /// エポックごとの平均 loss
+        pub epoch_losses: Vec<f32>,
+        /// 保存した重みの version（= `CandleTwoTower::version()`）
+        pub version: String,
+    }
+
+    /// 乱数 seed 固定の一様初期化（Xavier）
+    fn init_params(
+        config: &TrainConfig,
+        device: &Device,
+    ) -> candle_core::Result<HashMap<String, Var>> {
+        let mut rng = StdRng::seed_from_u64(config.seed);
+        let mut uniform = |rows: usize, cols: usize, fan_in: usize, fan_out: usize| {
+            let bound = (6.0 / (fan_in + fan_out) as f64).sqrt() as f32;
+            let values: Vec<f32> = (0..rows * cols)
+                .map(|_| rng.gen_range(-bound..bound))
+                .collect();
+            Tensor::from_vec(values, (rows, cols), device)
+        };
+
+        let (v, h, d) = (config.vocab_size, config.hidden_dim, config.dimension);
+        let mut params = HashMap::new();
+        for tower in TOWERS {
+            let mut embedding = uniform(v, h, v, h)?;
+            // パディング ID の行は 0 に固定する
+            embedding = Tensor::cat(
+                &[
+                    &Tensor::zeros((1, h), DType::F32, device)?,
+                    &embedding.narrow(0, 1, v - 1)?,
+                ],
+                0,
+            )?;
+            params.insert(
+                tensor_name(tower, "embedding"),
+                Var::from_tensor(&embedding)?,
+            );
+            params.insert(
+                tensor_name(tower, "fc1.weight"),
+                Var::from_tensor(&uniform(h, h, h, h)?)?,
+            );
+            params.insert(
+                tensor_name(tower, "fc1.bias"),
+                Var::zeros(h, DType::F32, device)?,
+            );
+            params.insert(
+                tensor_name(tower, "fc2.weight"),
+                Var::from_tensor(&uniform(d, h, h, d)?)?,
+            );
+            params.insert(
+                tensor_name(tower, "fc2.bias"),
+                Var::zeros(d, DType::F32, device)?,
+            );
+        }
+        Ok(params)
+    }
+
+    fn snapshot(params: &HashMap<String, Var>) -> HashMap<String, Tensor> {
+        params
+            .iter()
+            .map(|(name, var)| (name.clone(), var.as_tensor().clone()))
+            .collect()
+    }
+
+    /// BCE（全ラベル付きペア、soft label）+ in-batch negatives の対照損失（正例ペア同士）
+    fn batch_loss(
+        params: &HashMap<String, Tensor>,
+        batch: &[&TrainingExample],
+        config: &TrainConfig,
+        device: &Device,
+    ) -> candle_core::Result<Tensor> {
+        let projects: Vec<_> = batch
+            .iter()
+            .map(|e| tokenizer::tokenize_project(&e.project))
+            .collect();
+        let talents: Vec<_> = batch
+            .iter()
+            .map(|e| tokenizer::tokenize_talent(&e.talent))
+            .collect();
+        let (p_ids, p_weights) = batch_tensors(&projects, config.vocab_size, device)?;
+        let (t_ids, t_weights) = batch_tensors(&talents, config.vocab_size, device)?;
+        let p = tower_forward(params, "project", &p_ids, &p_weights)?;
+        let t = tower_forward(params, "talent", &t_ids, &t_weights)?;
+
+        // BCE with logits: softplus(x) - y * x（数値安定版: relu(x) + log(1 + exp(-|x|)) - y * x）
+        let labels: Vec<f32> = batch
+            .iter()
+            .map(|e| e.label.clamp(0.0, 1.0) as f32)
+            .collect();
+        let labels = Tensor::from_vec(labels, batch.len(), device)?;
+        let logits = ((&p * &t)?.sum(1)? * config.bce_scale)?;
+        let softplus = (logits.relu()? + (logits.abs()?.neg()?.exp()? + 1.0)?.log()?)?;
+        let mut loss = (softplus - (&labels * &logits)?)?.mean_all()?;
+
+        let positives: Vec<u32> = batch
+            .iter()
+            .enumerate()
+            .filter(|(_, e)| e.label >= config.positive_threshold)
+            .map(|(i, _)| i as u32)
+            .collect();
+        if positives.len() >= 2 {
+            let index = Tensor::new(positives.as_slice(), device)?;
+            let p_pos = p.index_select(&index, 0)?;
+            let t_pos = t.index_select(&index, 0)?;
+            let sim = (p_pos.matmul(&t_pos.t()?)? / config.temperature)?;
+            let targets = Tensor::arange(0u32, positives.len() as u32, device)?;
+            let contrastive = candle_nn::loss::cross_entropy(&sim, &targets)?;
+            loss = (loss + contrastive)?;
+        }
+        Ok(loss)
+    }
+
+    /// 学習データから重みを学習して `output` に safetensors で保存する
+    pub fn train(
+        examples: &[TrainingExample],
+        config: &TrainConfig,
+        output: &Path,
+    ) -> Result<TrainReport, CandleTowerError> {
+        config.validate()?;
+        if examples.is_empty() {
+            return Err(CandleTowerError::NoTrainingData);
+        }
+
+        let device = Device::Cpu;
+        let vars = init_params(config, &device)?;
+        let mut optimizer = AdamW::new(
+            vars.values().cloned().collect(),
+            ParamsAdamW {
+                lr: config.learning_rate,
+                weight_decay: 0.0,
+                ..ParamsAdamW::default()
+            },
+        )?;
+
+        let mut rng = StdRng::seed_from_u64(config.seed.wrapping_add(1));
+        let mut order: Vec<usize> = (0..examples.len()).collect();
+        let mut epoch_losses = Vec::with_capacity(config.epochs);
+        for epoch in 0..config.epochs {
+            order.shuffle(&mut rng);
+            let mut total = 0.0f32;
+            let mut batches = 0usize;
+            for chunk in order.chunks(config.batch_size) {
+                let batch: Vec<&TrainingExample> = chunk.iter().map(|&i| &examples[i]).collect();
+                let loss = batch_loss(&snapshot(&vars), &batch, config, &device)?;
+                optimizer.backward_step(&loss)?;
+                total += loss.to_scalar::<f32>()?;
+                batches += 1;
+            }
+            let mean = total / batches.max(1) as f32;
+            tracing::debug!(epoch, loss = mean, "two-tower training epoch finished");
+            epoch_losses.push(mean);
+        }
+
+        // HashMap の順序に依存しないよう名前順で保存する（同じ重み → 同じファイル → 同じ version）
+        let mut tensors: Vec<(String, Tensor)> = snapshot(&vars).into_iter().collect();
+        tensors.sort_by(|a, b| a.0.cmp(&b.0));
+        let refs: Vec<(&str, Tensor)> = tensors
+            .iter()
+            .map(|(n, t)| (n.as_str(), t.clone()))
+            .collect();
+        let save_error = |message: String| CandleTowerError::Save {
+            path: output.display().to_string(),
+            message,
+        };
+        let bytes =
+            safetensors::serialize(refs, None).map_err(|err| save_error(err.to_string()))?;
+        std::fs::write(output, &bytes).map_err(|err| save_error(err.to_string()))?;
+
+        let report = TrainReport {
+            examples: examples.len(),
+            positives: examples
+                .iter()
+                .filter(|e| e.label >= config.positive_threshold)
+                .count(),
+            epoch_losses,
+            version: weights_version(&bytes),
+        };
+        info!(
+            examples = report.examples,
+            positives = report.positives,
+            final_loss = report.epoch_losses.last().copied(),
+            version = %report.version,
+            output = %output.display(),
+            "trained candle two-tower"
+        );
+        Ok(report)
+    }
+
+    /// `ses.training_labels` を読み込んで学習する
+    pub async fn train_from_training_labels(
+        pool: &PgPool,
+        config: &TrainConfig,
+        output: &Path,
+    ) -> Result<TrainReport, CandleTowerError> {
+        let examples = fetch_training_examples(pool, None).await?;
+        train(&examples, config, output)
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        fn example(id: i64, skill: &str, talent_skill: &str, label: f64) -> TrainingExample {
+            TrainingExample {
+                interaction_id: id,
+                talent: Talent {
+                    id: Some(id),
+                    possessed_skills_keywords: vec![talent_skill.into()],
+                    ..Talent::default()
+                },
+                project: Project {
+                    id: Some(100 + id),
+                    required_skills_keywords: vec![skill.into()],
+                    ..Project::default()
+                },
+                label,
+                signal_source: Some("feedback".into()),
+                run_date: chrono::NaiveDate::from_ymd_opt(2026, 10, 1).unwrap(),
+                matched_at: chrono::Utc::now(),
+                features_updated_after_match: false,
+            }
+        }
+
+        fn small_dataset() -> Vec<TrainingExample> {
+            let skills = ["rust", "java", "python", "sap"];
+            let mut examples = Vec::new();
+            let mut id = 0;
+            for (i, project_skill) in skills.iter().enumerate() {
+                for (j, talent_skill) in skills.iter().enumerate() {
+                    id += 1;
+                    let label = if i == j { 1.0 } else { 0.0 };
+                    examples.push(example(id, project_skill, talent_skill, label));
+                }
+            }
+            examples
+        }
+
+        fn small_config() -> TrainConfig {
+            TrainConfig {
+                vocab_size: 64,
+                hidden_dim: 16,
+                dimension: 8,
+                epochs: 60,
+                batch_size: 8,
+                learning_rate: 2e-2,
+                ..TrainConfig::default()
+            }
+        }
+
+        #[test]
+        fn training_is_deterministic_and_separates_pairs() {
+            let dir = std::env::temp_dir().join(format!("sr-candle-{}", std::process::id()));
+            std::fs::create_dir_all(&dir).unwrap();
+            let first = dir.join("first.safetensors");
+            let second = dir.join("second.safetensors");
+
+            let examples = small_dataset();
+            let config = small_config();
+            let report = train(&examples, &config, &first).unwrap();
+            let again = train(&examples, &config, &second).unwrap();
+
+            assert_eq!(report.version, again.version);
+            assert_eq!(report.examples, 16);
+            assert_eq!(report.positives, 4);
+            assert!(report.epoch_losses.last().unwrap() < report.epoch_losses.first().unwrap());
+
+            let tower = CandleTwoTower::load(first.to_str().unwrap(), 8).unwrap();
+            assert_eq!(tower.version(), report.version);
+            assert_eq!(tower.name(), "candle");
+
+            let sim = |project_skill: &str, talent_skill: &str| {
+                let e = example(0, project_skill, talent_skill, 0.0);
+                tower.similarity(
+                    &tower.embed_project(&e.project),
+                    &tower.embed_talent(&e.talent),
+                )
+            };
+            assert!(sim("rust", "rust") > sim("rust", "java"));
+            assert!(sim("java", "java") > sim("java", "sap"));
+
+            let err = CandleTwoTower::load(first.to_str().unwrap(), 16)
+                .err()
+                .expect("dimension mismatch must not load");
+            assert!(matches!(
+                err,
+                CandleTowerError::DimensionMismatch {
+                    expected: 16,
+                    actual: 8,
+                    ..
+                }
+            ));
+
+            std::fs::remove_dir_all(&dir).ok();
+        }
+
+        #[test]
+        fn rejects_empty_training_data() {
+            let output = std::env::temp_dir().join("sr-candle-empty.safetensors");
+            assert!(matches!(
+                train(&[], &small_config(), &output),
+                Err(CandleTowerError::NoTrainingData)
+            ));
+        }
     }
 }
@@ -1,17 +1,20 @@
+pub mod ann;
 pub mod candle_tower;
 pub mod config;
 pub mod embedding;
 pub mod hash_tower;
 pub mod onnx_tower;
 pub mod similarity;
+pub mod store;
 pub mod tokenizer;
 
 use crate::{Project, Talent};
-pub use candle_tower::CandleTwoTower;
+pub use ann::{AnnIndexError, HnswConfig, HnswIndex, TalentRecall};
+pub use candle_tower::{CandleTowerError, CandleTwoTower};
 pub use config::TwoTowerConfig;
 pub use embedding::{Embedding, EmbeddingSource};
 pub use hash_tower::HashTwoTower;
-pub use onnx_tower::OnnxTwoTower;
+pub use onnx_tower::{OnnxTowerError, OnnxTwoTower};
 pub use similarity::cosine_similarity;
 use tracing::warn;
 
@@ -20,7 +23,7 @@ use tracing::warn;
 /// 実装例:
 /// - HashTwoTower: Feature Hashing（決定論的、学習不要）
 /// - OnnxTwoTower: ONNX Runtime（学習済みモデル読み込み）
-/// - CandleTwoTower: Candle（Rust-native推論）
+/// - CandleTwoTower: Candle（CPU で学習・推論、safetensors 重み）
 ///
 /// interaction_logs には name() と version() が記録される。
 pub trait TwoTowerEmbedder: Send + Sync {
@@ -91,21 +94,53 @@ pub trait TwoTowerEmbedder: Send + Sync {
     }
 }
 
+/// 学習済み Two-Tower 実装の初期化エラー
+#[derive(Debug, thiserror::Error)]
+pub enum TwoTowerError {
+    #[error(transparent)]
+    Onnx(#[from] OnnxTowerError),
+    #[error(transparent)]
+    Candle(#[from] CandleTowerError),
+    #[error("unknown two-tower embedder `{0}` (expected hash, onnx or candle)")]
+    UnknownEmbedder(String),
+    #[error("experiment arm `{0}` enables two-tower but no embedder is loaded")]
+    MissingEmbedder(String),
+}
+
 /// Two-Tower 実装のファクトリ
+///
+/// # Panics
+/// 未知の実装名、または `"onnx"` / `"candle"` でモデルを読み込めない場合（ファイル不在・次元不一致・feature なし）。
+/// 学習済みモデルを指定したのに hash で黙って動き続けることを防ぐため、起動時に落とす。
 pub fn create_embedder(name: &str, config: TwoTowerConfig) -> Box<dyn TwoTowerEmbedder> {
-    match name {
+    try_create_embedder(name, config)
+        .unwrap_or_else(|err| panic!("failed to initialize two-tower embedder `{name}`: {err}"))
+}
+
+/// Two-Tower 実装のファクトリ（読み込みエラーを返す版）
+///
+/// `"onnx"` は `TWO_TOWER_ONNX_PATH`（既定: models/two_tower.onnx）のモデルを、
+/// `"candle"` は `TWO_TOWER_CANDLE_PATH`（既定: models/two_tower.safetensors）の重みを読み込む。
+/// それ以外の名前（`onxx` などの typo）は hash に倒さずエラーにする。
+pub fn try_create_embedder(
+    name: &str,
+    config: TwoTowerConfig,
+) -> Result<Box<dyn TwoTowerEmbedder>, TwoTowerError> {
+    let embedder: Box<dyn TwoTowerEmbedder> = match name {
         "hash" => Box::new(HashTwoTower::new(config)),
         "onnx" => {
             let model_path = std::env::var("TWO_TOWER_ONNX_PATH")
                 .unwrap_or_else(|_| "models/two_tower.onnx".into());
-            match OnnxTwoTower::new(&model_path, config.dimension) {
-                Ok(tower) => Box::new(tower),
-                Err(_) => Box::new(HashTwoTower::new(config)),
-            }
+            Box::new(OnnxTwoTower::new(&model_path, config.dimension)?)
         }
-        "candle" => Box::new(CandleTwoTower::new(config.dimension)),
-        _ => Box::new(HashTwoTower::new(config)),
-    }
+        "candle" => {
+            let weights_path = std::env::var("TWO_TOWER_CANDLE_PATH")
+                .unwrap_or_else(|_| "models/two_tower.safetensors".into());
+            Box::new(CandleTwoTower::load(&weights_path, config.dimension)?)
+        }
+        other => return Err(TwoTowerError::UnknownEmbedder(other.to_string())),
+    };
+    Ok(embedder)
 }
 
 /// 環境変数から Two-Tower 設定を読み込み
@@ -1,53 +1,395 @@
-use super::{Embedding, EmbeddingSource, TwoTowerEmbedder};
-use crate::{Project, Talent};
+use thiserror::Error;
 
-/// ONNX Runtime を使用した Two-Tower（スタブ）
+/// 学習済み ONNX モデルの読み込み・推論エラー
 ///
-/// Phase 4 で学習済みモデルを読み込む
-pub struct OnnxTwoTower {
-    _model_path: String,
-    dimension: usize,
+/// 次元不一致やメタデータ欠落は hash へのフォールバックをせず、起動時に失敗させる。
+#[derive(Debug, Error)]
+pub enum OnnxTowerError {
+    #[error("two-tower ONNX support is not compiled in; rebuild sr-common with `--features onnx`")]
+    FeatureDisabled,
+    #[error("failed to load ONNX model {path}: {message}")]
+    Load { path: String, message: String },
+    #[error("ONNX model {path} is missing metadata `{key}`")]
+    MissingMetadata { path: String, key: &'static str },
+    #[error("ONNX model {path} has invalid metadata `{key}`: {value}")]
+    InvalidMetadata {
+        path: String,
+        key: &'static str,
+        value: String,
+    },
+    #[error(
+        "ONNX model {path} outputs {actual}-dim embeddings but TWO_TOWER_DIMENSION is {expected}"
+    )]
+    DimensionMismatch {
+        path: String,
+        expected: usize,
+        actual: usize,
+    },
+    #[error("ONNX inference failed: {0}")]
+    Inference(String),
 }
 
+#[cfg(feature = "onnx")]
+pub use runtime::OnnxTwoTower;
+
+/// `onnx` feature なしのビルドでは読み込みを常に失敗させる（値は作れない）
+#[cfg(not(feature = "onnx"))]
+pub enum OnnxTwoTower {}
+
+#[cfg(not(feature = "onnx"))]
 impl OnnxTwoTower {
-    pub fn new(model_path: &str, dimension: usize) -> Result<Self, String> {
-        Ok(Self {
-            _model_path: model_path.to_string(),
-            dimension,
-        })
+    pub fn new(_model_path: &str, _dimension: usize) -> Result<Self, OnnxTowerError> {
+        Err(OnnxTowerError::FeatureDisabled)
     }
 }
 
-impl TwoTowerEmbedder for OnnxTwoTower {
+#[cfg(not(feature = "onnx"))]
+impl super::TwoTowerEmbedder for OnnxTwoTower {
     fn name(&self) -> &'static str {
-        "onnx"
+        match *self {}
     }
 
     fn version(&self) -> &str {
-        // モデルファイル名やメタデータからバージョンを取得する設計
-        // 例: "20241215" (学習日) or "v3.2"
-        "v1"
+        match *self {}
     }
 
     fn dimension(&self) -> usize {
-        self.dimension
+        match *self {}
+    }
+
+    fn embed_project(&self, _project: &crate::Project) -> super::Embedding {
+        match *self {}
+    }
+
+    fn embed_talent(&self, _talent: &crate::Talent) -> super::Embedding {
+        match *self {}
+    }
+}
+
+#[cfg(feature = "onnx")]
+mod runtime {
+    use std::collections::HashMap;
+
+    use tracing::{error, info};
+    use tract_onnx::prelude::*;
+
+    use super::OnnxTowerError;
+    use crate::two_tower::tokenizer::{self, WeightedToken, PADDING_TOKEN_ID};
+    use crate::two_tower::{Embedding, EmbeddingSource, TwoTowerEmbedder};
+    use crate::{Project, Talent};
+
+    const TOKEN_IDS_INPUT: &str = "token_ids";
+    const TOKEN_WEIGHTS_INPUT: &str = "token_weights";
+    const VERSION_METADATA: &str = "version";
+    const VOCAB_SIZE_METADATA: &str = "vocab_size";
+
+    /// tract による ONNX Two-Tower 推論
+    ///
+    /// モデルの入出力契約（`scripts/gen_tiny_two_tower_onnx.py` 参照）:
+    /// - 入力 `token_ids` int64 `[N, L]`（`tokenizer::token_id`、0 はパディング）
+    /// - 入力 `token_weights` float32 `[N, L]`
+    /// - 出力 float32 `[N, D]`（案件・人材で共有の tower。出力は L2 正規化して使う）
+    /// - メタデータ `version`（interaction_logs.two_tower_version）と `vocab_size`
+    pub struct OnnxTwoTower {
+        model_path: String,
+        dimension: usize,
+        vocab_size: usize,
+        version: String,
+        /// モデル入力順での `token_ids` の位置（0 or 1）
+        token_ids_slot: usize,
+        plan: TypedRunnableModel<TypedModel>,
+    }
+
+    impl OnnxTwoTower {
+        /// モデルを読み込み、出力次元が `dimension` と一致するかを 1 件推論して検証する
+        pub fn new(model_path: &str, dimension: usize) -> Result<Self, OnnxTowerError> {
+            let load_error = |err: TractError| OnnxTowerError::Load {
+                path: model_path.to_string(),
+                message: format!("{err:#}"),
+            };
+
+            let onnx = tract_onnx::onnx();
+            let proto = onnx.proto_model_for_path(model_path).map_err(load_error)?;
+            let metadata: HashMap<&str, &str> = proto
+                .metadata_props
+                .iter()
+                .map(|entry| (entry.key.as_str(), entry.value.as_str()))
+                .collect();
+
+            let version = match metadata.get(VERSION_METADATA) {
+                Some(version) if !version.trim().is_empty() => version.trim().to_string(),
+                _ if proto.model_version > 0 => proto.model_version.to_string(),
+                _ => {
+                    return Err(OnnxTowerError::MissingMetadata {
+                        path: model_path.to_string(),
+                        key: VERSION_METADATA,
+                    })
+                }
+            };
+            let raw_vocab = metadata.get(VOCAB_SIZE_METADATA).ok_or_else(|| {
+                OnnxTowerError::MissingMetadata {
+                    path: model_path.to_string(),
+                    key: VOCAB_SIZE_METADATA,
+                }
+            })?;
+            let vocab_size = raw_vocab
+                .trim()
+                .parse::<usize>()
+                .ok()
+                .filter(|size| *size > 1)
+                .ok_or_else(|| OnnxTowerError::InvalidMetadata {
+                    path: model_path.to_string(),
+                    key: VOCAB_SIZE_METADATA,
+                    value: raw_vocab.to_string(),
+                })?;
+
+            let model = onnx.model_for_proto_model(&proto).map_err(load_error)?;
+            let input_names: Vec<String> = model
+                .input_outlets()
+                .map_err(load_error)?
+                .iter()
+                .map(|outlet| model.node(outlet.node).name.clone())
+                .collect();
+            let token_ids_slot = match input_names
+                .iter()
+                .map(String::as_str)
+                .collect::<Vec<_>>()
+                .as_slice()
+            {
+                [TOKEN_IDS_INPUT, TOKEN_WEIGHTS_INPUT] => 0,
+                [TOKEN_WEIGHTS_INPUT, TOKEN_IDS_INPUT] => 1,
+                _ => {
+                    return Err(OnnxTowerError::Load {
+                        path: model_path.to_string(),
+                        message: format!(
+                            "expected inputs [{TOKEN_IDS_INPUT}, {TOKEN_WEIGHTS_INPUT}], got {input_names:?}"
+                        ),
+                    })
+                }
+            };
+
+            let plan = model
+                .into_optimized()
+                .and_then(|model| model.into_runnable())
+                .map_err(load_error)?;
+
+            let tower = Self {
+                model_path: model_path.to_string(),
+                dimension,
+                vocab_size,
+                version,
+                token_ids_slot,
+                plan,
+            };
+
+            let probe = tower.infer(&[vec![]])?;
+            let actual = probe.first().map(Vec::len).unwrap_or_default();
+            if actual != dimension {
+                return Err(OnnxTowerError::DimensionMismatch {
+                    path: model_path.to_string(),
+                    expected: dimension,
+                    actual,
+                });
+            }
+
+            info!(
+                model_path,
+                version = %tower.version,
+                vocab_size,
+                dimension,
+                "loaded two-tower ONNX model"
+            );
+            Ok(tower)
+        }
+
+        pub fn model_path(&self) -> &str {
+            &self.model_path
+        }
+
+        /// トークン列をパディングして 1 回のバッチ推論にかけ、行ごとの生ベクトルを返す
+        fn infer(&self, batch: &[Vec<WeightedToken>]) -> Result<Vec<Vec<f32>>, OnnxTowerError> {
+            let rows = batch.len().max(1);
+            let seq_len = batch.iter().map(Vec::len).max().unwrap_or(0).max(1);
+
+            let mut ids = vec![PADDING_TOKEN_ID; rows * seq_len];
+            let mut weights = vec![0.0f32; rows * seq_len];
+            for (row, tokens) in batch.iter().enumerate() {
+                let (row_ids, row_weights) = tokenizer::token_ids(tokens, self.vocab_size);
+                let offset = row * seq_len;
+                ids[offset..offset + row_ids.len()].copy_from_slice(&row_ids);
+                weights[offset..offset + row_weights.len()].copy_from_slice(&row_weights);
+            }
+
+            let inference_error = |err: TractError| OnnxTowerError::Inference(format!("{err:#}"));
+            let ids = tract_ndarray::Array2::from_shape_vec((rows, seq_len), ids)
+                .map_err(|err| OnnxTowerError::Inference(err.to_string()))?;
+            let weights = tract_ndarray::Array2::from_shape_vec((rows, seq_len), weights)
+                .map_err(|err| OnnxTowerError::Inference(err.to_string()))?;
+            let ids: TValue = Tensor::from(ids).into();
+            let weights: TValue = Tensor::from(weights).into();
+            let inputs = if self.token_ids_slot == 0 {
+                tvec!(ids, weights)
+            } else {
+                tvec!(weights, ids)
+            };
+
+            let outputs = self.plan.run(inputs).map_err(inference_error)?;
+            let output = outputs
+                .first()
+                .ok_or_else(|| OnnxTowerError::Inference("model produced no outputs".into()))?;
+            let view = output.to_array_view::<f32>().map_err(inference_error)?;
+            let shape = view.shape();
+            if shape.len() != 2 || shape[0] != rows {
+                return Err(OnnxTowerError::Inference(format!(
+                    "unexpected output shape {shape:?} for batch of {rows}"
+                )));
+            }
+
+            Ok(view
+                .outer_iter()
+                .take(batch.len().max(1))
+                .map(|row| row.iter().copied().collect())
+                .collect())
+        }
+
+        fn to_embedding(&self, mut vector: Vec<f32>, source: EmbeddingSource) -> Embedding {
+            let norm: f32 = vector.iter().map(|x| x * x).sum::<f32>().sqrt();
+            if norm > 0.0 {
+                for v in &mut vector {
+                    *v /= norm;
+                }
+            }
+            Embedding::new(vector, self.dimension, source)
+        }
+
+        /// 推論失敗時はゼロベクトル（類似度 0）を返す。次元は読み込み時に検証済み
+        fn embed_batch(
+            &self,
+            batch: Vec<Vec<WeightedToken>>,
+            source: EmbeddingSource,
+        ) -> Vec<Embedding> {
+            match self.infer(&batch) {
+                Ok(vectors) => vectors
+                    .into_iter()
+                    .take(batch.len())
+                    .map(|v| self.to_embedding(v, source))
+                    .collect(),
+                Err(err) => {
+                    error!(
+                        model_path = %self.model_path,
+                        batch = batch.len(),
+                        error = %err,
+                        "two-tower ONNX inference failed; using zero embeddings"
+                    );
+                    batch
+                        .iter()
+                        .map(|_| Embedding::new(vec![0.0; self.dimension], self.dimension, source))
+                        .collect()
+                }
+            }
+        }
     }
 
-    fn embed_project(&self, _project: &Project) -> Embedding {
-        // Phase 4: ONNX 推論を実装
-        Embedding::new(
-            vec![0.0; self.dimension],
-            self.dimension,
-            EmbeddingSource::Project,
-        )
+    impl TwoTowerEmbedder for OnnxTwoTower {
+        fn name(&self) -> &'static str {
+            "onnx"
+        }
+
+        fn version(&self) -> &str {
+            &self.version
+        }
+
+        fn dimension(&self) -> usize {
+            self.dimension
+        }
+
+        fn embed_project(&self, project: &Project) -> Embedding {
+            let tokens = tokenizer::tokenize_project(project);
+            self.embed_batch(vec![tokens], EmbeddingSource::Project)
+                .remove(0)
+        }
+
+        fn embed_talent(&self, talent: &Talent) -> Embedding {
+            let tokens = tokenizer::tokenize_talent(talent);
+            self.embed_batch(vec![tokens], EmbeddingSource::Talent)
+                .remove(0)
+        }
+
+        fn embed_talents(&self, talents: &[Talent]) -> Vec<Embedding> {
+            if talents.is_empty() {
+                return Vec::new();
+            }
+            let batch = talents.iter().map(tokenizer::tokenize_talent).collect();
+            self.embed_batch(batch, EmbeddingSource::Talent)
+        }
     }
 
-    fn embed_talent(&self, _talent: &Talent) -> Embedding {
-        // Phase 4: ONNX 推論を実装
-        Embedding::new(
-            vec![0.0; self.dimension],
-            self.dimension,
-            EmbeddingSource::Talent,
-        )
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        fn fixture_path() -> String {
+            format!(
+                "{}/tests/fixtures/tiny_two_tower.onnx",
+                env!("CARGO_MANIFEST_DIR")
+            )
+        }
+
+        #[test]
+        fn loads_tiny_model_with_metadata_version() {
+            let tower = OnnxTwoTower::new(&fixture_path(), 4).expect("tiny model should load");
+            assert_eq!(tower.name(), "onnx");
+            assert_eq!(tower.version(), "tiny-20261017");
+            assert_eq!(tower.dimension(), 4);
+        }
+
+        #[test]
+        fn dimension_mismatch_fails_loudly() {
+            let err = OnnxTwoTower::new(&fixture_path(), 256)
+                .err()
+                .expect("dimension mismatch must not load");
+            assert!(matches!(
+                err,
+                OnnxTowerError::DimensionMismatch {
+                    expected: 256,
+                    actual: 4,
+                    ..
+                }
+            ));
+        }
+
+        #[test]
+        fn batched_talent_embeddings_match_single_inference() {
+            let tower = OnnxTwoTower::new(&fixture_path(), 4).unwrap();
+            let rust = Talent {
+                id: Some(1),
+                possessed_skills_keywords: vec!["rust".into(), "aws".into()],
+                residential_todofuken: Some("東京都".into()),
+                ..Talent::default()
+            };
+            let empty = Talent {
+                id: Some(2),
+                ..Talent::default()
+            };
+
+            let batched = tower.embed_talents(&[rust.clone(), empty]);
+            let single = tower.embed_talent(&rust);
+
+            assert_eq!(batched.len(), 2);
+            for (a, b) in batched[0].vector.iter().zip(&single.vector) {
+                assert!((a - b).abs() < 1e-5);
+            }
+            let norm: f32 = single.vector.iter().map(|x| x * x).sum::<f32>().sqrt();
+            assert!((norm - 1.0).abs() < 1e-5);
+            assert!(batched[1].vector.iter().all(|v| *v == 0.0));
+
+            let project = Project {
+                required_skills_keywords: vec!["Rust".into()],
+                ..Project::default()
+            };
+            let ranked = tower.rank_talents(&project, &[rust]);
+            assert_eq!(ranked.len(), 1);
+            assert!(ranked[0].1.is_finite());
+        }
     }
 }
@@ -0,0 +1,106 @@
+//! `ses.talent_embeddings` との同期と、recall 用インデックスの構築
+
+use tracing::{info, instrument, warn};
+
+use super::ann::{HnswConfig, HnswIndex, TalentRecall};
+use super::TwoTowerEmbedder;
+use crate::db::{
+    fetch_talent_embeddings, fetch_talents_needing_embedding, prune_talent_embeddings,
+    upsert_talent_embeddings, EmbeddingModelKey, PgPool, TalentEmbeddingStorageError,
+    TalentEmbeddingUpsert,
+};
+
+/// 1 回の SELECT / 埋め込み / UPSERT で扱う人材数
+const REFRESH_BATCH_SIZE: i64 = 500;
+
+pub fn model_key(embedder: &dyn TwoTowerEmbedder) -> EmbeddingModelKey {
+    EmbeddingModelKey {
+        embedder: embedder.name().to_string(),
+        version: embedder.version().to_string(),
+        dimension: embedder.dimension() as i32,
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct EmbeddingRefreshSummary {
+    pub embedded: u64,
+    pub pruned: u64,
+}
+
+impl EmbeddingRefreshSummary {
+    pub fn changed(&self) -> bool {
+        self.embedded > 0 || self.pruned > 0
+    }
+}
+
+/// 埋め込みが無い／`ses.talents.updated_at` より古い人材を再計算して保存し、旧モデルの行を消す
+#[instrument(skip(pool, embedder), fields(embedder = embedder.name(), version = embedder.version()))]
+pub async fn refresh_talent_embeddings(
+    pool: &PgPool,
+    embedder: &dyn TwoTowerEmbedder,
+) -> Result<EmbeddingRefreshSummary, TalentEmbeddingStorageError> {
+    let key = model_key(embedder);
+    let mut summary = EmbeddingRefreshSummary {
+        pruned: prune_talent_embeddings(pool, &key).await?,
+        ..Default::default()
+    };
+
+    loop {
+        let stale = fetch_talents_needing_embedding(pool, &key, REFRESH_BATCH_SIZE).await?;
+        if stale.is_empty() {
+            break;
+        }
+        let talents: Vec<_> = stale.iter().map(|s| s.talent.clone()).collect();
+        let rows: Vec<TalentEmbeddingUpsert> = embedder
+            .embed_talents(&talents)
+            .into_iter()
+            .zip(&stale)
+            .filter_map(|(embedding, s)| {
+                Some(TalentEmbeddingUpsert {
+                    talent_id: s.talent.id?,
+                    talent_updated_at: s.updated_at,
+                    embedding: embedding.vector,
+                })
+            })
+            .collect();
+        summary.embedded += upsert_talent_embeddings(pool, &key, &rows).await?;
+
+        if (stale.len() as i64) < REFRESH_BATCH_SIZE {
+            break;
+        }
+    }
+
+    if summary.changed() {
+        info!(
+            embedded = summary.embedded,
+            pruned = summary.pruned,
+            "refreshed talent embeddings"
+        );
+    }
+    Ok(summary)
+}
+
+/// 保存済みの最新埋め込みから recall ステージを構築する
+#[instrument(skip(pool, embedder, config), fields(embedder = embedder.name(), version = embedder.version()))]
+pub async fn load_talent_recall(
+    pool: &PgPool,
+    embedder: &dyn TwoTowerEmbedder,
+    top_k: usize,
+    config: HnswConfig,
+) -> Result<TalentRecall, TalentEmbeddingStorageError> {
+    let stored = fetch_talent_embeddings(pool, &model_key(embedder)).await?;
+    let mut index = HnswIndex::new(embedder.dimension(), config);
+    for row in stored {
+        // 次元は SELECT 条件と CHECK 制約で揃っているはずだが、壊れた行で全体を止めない
+        if let Err(err) = index.insert(row.talent_id, row.embedding) {
+            warn!(error = %err, "skipping talent embedding");
+        }
+    }
+    info!(talents = index.len(), top_k, "built talent ANN index");
+    Ok(TalentRecall::new(
+        index,
+        top_k,
+        embedder.name(),
+        embedder.version(),
+    ))
+}
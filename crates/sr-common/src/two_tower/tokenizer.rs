@@ -1,5 +1,16 @@
+use siphasher::sip::SipHasher13;
+use std::hash::{Hash, Hasher};
+
 use crate::{Project, Talent};
 
+/// 学習済みモデル（ONNX/Candle）の語彙 ID 用固定 seed
+/// ⚠️ 変更すると学習済みモデルと ID がずれる → モデルを再学習すること
+const TOKEN_ID_SEED_K0: u64 = 0x5352_5f74_6f6b_656e;
+const TOKEN_ID_SEED_K1: u64 = 0x7477_6f5f_746f_7772;
+
+/// 語彙 ID 0 はパディング用に予約
+pub const PADDING_TOKEN_ID: i64 = 0;
+
 /// 重み付きトークン
 #[derive(Debug, Clone)]
 pub struct WeightedToken {
@@ -137,6 +148,24 @@ pub fn tokenize_talent(talent: &Talent) -> Vec<WeightedToken> {
     tokens
 }
 
+/// トークンを 1..vocab_size の語彙 ID に写像する（0 はパディング）
+pub fn token_id(token: &str, vocab_size: usize) -> i64 {
+    if vocab_size <= 1 {
+        return PADDING_TOKEN_ID;
+    }
+    let mut hasher = SipHasher13::new_with_keys(TOKEN_ID_SEED_K0, TOKEN_ID_SEED_K1);
+    token.hash(&mut hasher);
+    1 + (hasher.finish() % (vocab_size as u64 - 1)) as i64
+}
+
+/// 重み付きトークン列をモデル入力（語彙 ID 列と重み列）に変換する
+pub fn token_ids(tokens: &[WeightedToken], vocab_size: usize) -> (Vec<i64>, Vec<f32>) {
+    tokens
+        .iter()
+        .map(|wt| (token_id(&wt.token, vocab_size), wt.weight))
+        .unzip()
+}
+
 /// 経験年数バケット: 0-2, 3-5, 6-10, 11+
 fn exp_years_bucket(years: i32) -> &'static str {
     match years {
@@ -166,6 +195,20 @@ fn normalize_skill_token(token: &str) -> String {
 mod tests {
     use super::*;
 
+    #[test]
+    fn token_ids_skip_padding_and_are_stable() {
+        let tokens = vec![
+            WeightedToken::new("skill:rust", 2.0),
+            WeightedToken::new("loc:東京都", 1.5),
+        ];
+        let (ids, weights) = token_ids(&tokens, 16);
+
+        assert_eq!(weights, vec![2.0, 1.5]);
+        assert!(ids.iter().all(|id| (1..16).contains(id)));
+        assert_eq!(ids, token_ids(&tokens, 16).0);
+        assert_eq!(token_id("skill:rust", 1), PADDING_TOKEN_ID);
+    }
+
     #[test]
     fn skill_tokens_are_lowercased_and_trimmed() {
         let project = Project {
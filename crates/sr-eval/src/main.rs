@@ -0,0 +1,542 @@
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::path::PathBuf;
+
+use clap::Parser;
+use dotenvy::dotenv;
+use serde::{Deserialize, Serialize};
+use sr_common::db::{
+    create_pool_from_url_checked, decode_training_examples, fetch_training_examples,
+    TrainingExample,
+};
+use sr_common::evaluation::{RankingSummary, ScoredLabel};
+use sr_common::logging::{init_tracing_subscriber, install_tracing_panic_hook};
+use sr_common::matching::pipeline::{MatchingEngine, MatchingEngineConfig};
+use sr_common::matching::scoring::{MatchingConfig, TotalScoreWeights};
+use sr_common::matching::weights::Weights;
+use sr_common::rules::init_active_rules;
+use sr_common::two_tower::{
+    load_config_from_env, try_create_embedder, CandleTwoTower, HashTwoTower, OnnxTwoTower,
+    TwoTowerConfig, TwoTowerEmbedder,
+};
+use sr_common::Project;
+use tracing::{error, info, warn};
+
+type CliResult<T> = Result<T, Box<dyn std::error::Error>>;
+
+/// ランキングから落ちた（HardKo / prefilter 不通過）ペアのスコア。全ての通過ペアより下に並べる
+const DROPPED_SCORE: f64 = -1.0;
+const CONTROL_VARIANT: &str = "control";
+
+#[derive(Debug, Parser)]
+#[command(
+    name = "sr-eval",
+    about = "Replay labeled project/talent pairs through MatchingEngine variants and compare ranking metrics"
+)]
+struct Cli {
+    /// PostgreSQL connection string (not needed with --dataset)
+    #[arg(long, env = "DATABASE_URL")]
+    db_url: Option<String>,
+
+    /// Evaluate a dataset snapshot written by sr-train instead of querying the database
+    #[arg(long)]
+    dataset: Option<PathBuf>,
+
+    /// Only use labels recorded on or after this date (YYYY-MM-DD)
+    #[arg(long)]
+    since: Option<chrono::NaiveDate>,
+
+    /// JSON array of variants (name, weights, total_score_weights, two_tower); `control` is always included
+    #[arg(long)]
+    variants: Option<PathBuf>,
+
+    /// Write the per-variant report as JSON to this path
+    #[arg(long)]
+    json_out: Option<PathBuf>,
+
+    /// Labels at or above this value count as positives
+    #[arg(long, default_value_t = 0.5)]
+    positive_threshold: f64,
+}
+
+/// バリアント定義（`--variants` の JSON 要素）。省略した項目は本番の既定値を使う
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct VariantSpec {
+    name: String,
+    #[serde(default)]
+    weights: Option<Weights>,
+    #[serde(default)]
+    total_score_weights: Option<TotalScoreWeights>,
+    #[serde(default)]
+    two_tower: Option<TwoTowerSpec>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct TwoTowerSpec {
+    /// hash / onnx / candle
+    #[serde(default = "default_embedder")]
+    embedder: String,
+    /// onnx / candle のモデルパス（省略時は TWO_TOWER_ONNX_PATH / TWO_TOWER_CANDLE_PATH）
+    #[serde(default)]
+    model_path: Option<String>,
+    weight: f32,
+    #[serde(default)]
+    dimension: Option<usize>,
+    #[serde(default)]
+    score_min: Option<f32>,
+    #[serde(default)]
+    score_max: Option<f32>,
+}
+
+fn default_embedder() -> String {
+    "hash".into()
+}
+
+fn control_spec() -> VariantSpec {
+    VariantSpec {
+        name: CONTROL_VARIANT.into(),
+        weights: None,
+        total_score_weights: None,
+        two_tower: None,
+    }
+}
+
+/// 先頭に control を置いたバリアント一覧（名前の重複はエラー）
+fn parse_variants(json: Option<&str>) -> CliResult<Vec<VariantSpec>> {
+    let mut specs: Vec<VariantSpec> = match json {
+        Some(json) => serde_json::from_str(json)?,
+        None => Vec::new(),
+    };
+    if !specs.iter().any(|spec| spec.name == CONTROL_VARIANT) {
+        specs.insert(0, control_spec());
+    }
+
+    let mut seen = std::collections::HashSet::new();
+    for spec in &specs {
+        if !seen.insert(spec.name.as_str()) {
+            return Err(format!("duplicate variant name `{}`", spec.name).into());
+        }
+        if let Some(weights) = spec.weights {
+            let parts = [
+                weights.tanka,
+                weights.location,
+                weights.skills,
+                weights.experience,
+                weights.contract,
+                weights.other,
+            ];
+            if parts.iter().any(|w| !w.is_finite() || *w < 0.0) {
+                return Err(format!(
+                    "variant `{}` has a negative or non-finite weight",
+                    spec.name
+                )
+                .into());
+            }
+            if (weights.sum() - 1.0).abs() > 1e-6 {
+                warn!(
+                    variant = %spec.name,
+                    sum = weights.sum(),
+                    "variant weights do not sum to 1.0; business scores are not rescaled"
+                );
+            }
+        }
+    }
+    Ok(specs)
+}
+
+/// MatchingEngine と Two-Tower を組み立て済みのバリアント
+struct Variant {
+    name: String,
+    engine: MatchingEngine,
+    two_tower_config: TwoTowerConfig,
+    embedder: Option<Box<dyn TwoTowerEmbedder>>,
+}
+
+impl Variant {
+    fn build(spec: &VariantSpec, base_two_tower: &TwoTowerConfig) -> CliResult<Self> {
+        let defaults = MatchingConfig::detailed();
+        let detailed = MatchingConfig {
+            weights: spec.weights.unwrap_or(defaults.weights),
+            total_score_weights: spec
+                .total_score_weights
+                .unwrap_or(defaults.total_score_weights),
+            ..defaults
+        };
+        let engine = MatchingEngine::new(MatchingEngineConfig {
+            detailed,
+            ..MatchingEngineConfig::default()
+        });
+
+        let Some(tt) = &spec.two_tower else {
+            return Ok(Self {
+                name: spec.name.clone(),
+                engine,
+                two_tower_config: TwoTowerConfig {
+                    enabled: false,
+                    ..base_two_tower.clone()
+                },
+                embedder: None,
+            });
+        };
+
+        let config = TwoTowerConfig {
+            enabled: true,
+            weight: tt.weight,
+            dimension: tt.dimension.unwrap_or(base_two_tower.dimension),
+            score_min: tt.score_min.unwrap_or(base_two_tower.score_min),
+            score_max: tt.score_max.unwrap_or(base_two_tower.score_max),
+        };
+        let embedder: Box<dyn TwoTowerEmbedder> =
+            match (tt.embedder.as_str(), tt.model_path.as_deref()) {
+                ("hash", _) => Box::new(HashTwoTower::new(config.clone())),
+                ("candle", Some(path)) => Box::new(CandleTwoTower::load(path, config.dimension)?),
+                ("onnx", Some(path)) => Box::new(OnnxTwoTower::new(path, config.dimension)?),
+                ("candle" | "onnx", None) => try_create_embedder(&tt.embedder, config.clone())?,
+                (other, _) => {
+                    return Err(
+                        format!("variant `{}` uses unknown embedder `{other}`", spec.name).into(),
+                    )
+                }
+            };
+        info!(
+            variant = %spec.name,
+            embedder = embedder.name(),
+            version = embedder.version(),
+            weight = config.weight,
+            "loaded two-tower embedder for variant"
+        );
+
+        Ok(Self {
+            name: spec.name.clone(),
+            engine,
+            two_tower_config: config,
+            embedder: Some(embedder),
+        })
+    }
+}
+
+/// 1 案件分のラベル付き候補（同じ人材が複数回ラベル付けされていれば最も強いラベルを使う）
+struct ReplayGroup<'a> {
+    project: &'a Project,
+    pairs: Vec<&'a TrainingExample>,
+}
+
+fn group_by_project(examples: &[TrainingExample]) -> Vec<ReplayGroup<'_>> {
+    let mut groups: BTreeMap<i64, BTreeMap<i64, &TrainingExample>> = BTreeMap::new();
+    for example in examples {
+        let (Some(project_id), Some(talent_id)) = (example.project.id, example.talent.id) else {
+            continue;
+        };
+        let slot = groups.entry(project_id).or_default().entry(talent_id);
+        slot.and_modify(|current| {
+            if example.label > current.label {
+                *current = example;
+            }
+        })
+        .or_insert(example);
+    }
+
+    groups
+        .into_values()
+        .map(|pairs| {
+            let pairs: Vec<&TrainingExample> = pairs.into_values().collect();
+            ReplayGroup {
+                project: &pairs[0].project,
+                pairs,
+            }
+        })
+        .collect()
+}
+
+#[derive(Debug, Serialize)]
+struct VariantReport {
+    variant: String,
+    #[serde(flatten)]
+    ranking: RankingSummary,
+    /// 正例なのに HardKo で落とされたペア数
+    ko_false_negatives: usize,
+    /// ko_false_negatives / 正例数
+    ko_false_negative_rate: Option<f64>,
+    /// 正例なのに prefilter（最低スコア）で落とされたペア数
+    prefilter_dropped_positives: usize,
+}
+
+fn evaluate_variant(
+    variant: &Variant,
+    groups: &[ReplayGroup<'_>],
+    positive_threshold: f64,
+) -> VariantReport {
+    let mut scored = Vec::with_capacity(groups.len());
+    let mut positives = 0usize;
+    let mut ko_false_negatives = 0usize;
+    let mut prefilter_dropped_positives = 0usize;
+
+    for group in groups {
+        let talents: Vec<_> = group.pairs.iter().map(|pair| pair.talent.clone()).collect();
+        let ranked = variant.engine.rank_talents_for_project(
+            group.project,
+            &talents,
+            variant.embedder.as_deref(),
+            &variant.two_tower_config,
+        );
+        let scores: HashMap<i64, f64> = ranked
+            .iter()
+            .filter_map(|m| m.talent.id.map(|id| (id, m.total_score)))
+            .collect();
+
+        let mut items = Vec::with_capacity(group.pairs.len());
+        for pair in &group.pairs {
+            let score = pair.talent.id.and_then(|id| scores.get(&id).copied());
+            if pair.label >= positive_threshold {
+                positives += 1;
+                if score.is_none() {
+                    if variant
+                        .engine
+                        .evaluate_ko(group.project, &pair.talent)
+                        .is_hard_knockout
+                    {
+                        ko_false_negatives += 1;
+                    } else {
+                        prefilter_dropped_positives += 1;
+                    }
+                }
+            }
+            items.push(ScoredLabel {
+                score: score.unwrap_or(DROPPED_SCORE),
+                label: pair.label,
+            });
+        }
+        scored.push(items);
+    }
+
+    VariantReport {
+        variant: variant.name.clone(),
+        ranking: RankingSummary::compute(&scored, positive_threshold),
+        ko_false_negatives,
+        ko_false_negative_rate: (positives > 0)
+            .then(|| ko_false_negatives as f64 / positives as f64),
+        prefilter_dropped_positives,
+    }
+}
+
+fn format_metric(value: Option<f64>) -> String {
+    value.map_or_else(|| "-".into(), |v| format!("{v:.4}"))
+}
+
+/// 人が読む用の比較表（JSON は --json-out）
+fn render_table(reports: &[VariantReport]) -> String {
+    let name_width = reports
+        .iter()
+        .map(|r| r.variant.chars().count())
+        .max()
+        .unwrap_or(0)
+        .max("variant".len());
+    let mut out = format!(
+        "{:<name_width$}  {:>6}  {:>7}  {:>7}  {:>7}  {:>7}  {:>7}  {:>7}  {:>7}\n",
+        "variant", "pairs", "NDCG@10", "MRR", "P@5", "P@10", "R@50", "AUC", "KO-FNR"
+    );
+    for report in reports {
+        let r = &report.ranking;
+        out.push_str(&format!(
+            "{:<name_width$}  {:>6}  {:>7}  {:>7}  {:>7}  {:>7}  {:>7}  {:>7}  {:>7}\n",
+            report.variant,
+            r.pairs,
+            format_metric(r.ndcg_at_10),
+            format_metric(r.mrr),
+            format_metric(r.precision_at_5),
+            format_metric(r.precision_at_10),
+            format_metric(r.recall_at_50),
+            format_metric(r.auc),
+            format_metric(report.ko_false_negative_rate),
+        ));
+    }
+    out
+}
+
+async fn load_examples(args: &Cli) -> CliResult<Vec<TrainingExample>> {
+    let examples = match &args.dataset {
+        Some(path) => decode_training_examples(&fs::read(path)?)?,
+        None => {
+            let db_url = args
+                .db_url
+                .as_deref()
+                .ok_or("--db-url (or DATABASE_URL) is required unless --dataset is given")?;
+            let pool = create_pool_from_url_checked(db_url).await?;
+            let since = args
+                .since
+                .and_then(|date| date.and_hms_opt(0, 0, 0))
+                .map(|at| at.and_utc());
+            return Ok(fetch_training_examples(&pool, since).await?);
+        }
+    };
+    Ok(match args.since {
+        Some(since) => examples
+            .into_iter()
+            .filter(|example| example.run_date >= since)
+            .collect(),
+        None => examples,
+    })
+}
+
+async fn run() -> CliResult<()> {
+    dotenv().ok();
+    init_tracing_subscriber(env!("CARGO_PKG_NAME"));
+    install_tracing_panic_hook(env!("CARGO_PKG_NAME"));
+
+    let args = Cli::parse();
+    let rules = init_active_rules()?;
+    info!(rule_version = %rules.version, "loaded match rules");
+    let variants_json = args.variants.as_ref().map(fs::read_to_string).transpose()?;
+    let specs = parse_variants(variants_json.as_deref())?;
+    let base_two_tower = load_config_from_env();
+    let variants = specs
+        .iter()
+        .map(|spec| Variant::build(spec, &base_two_tower))
+        .collect::<CliResult<Vec<_>>>()?;
+
+    let examples = load_examples(&args).await?;
+    let groups = group_by_project(&examples);
+    info!(
+        labels = examples.len(),
+        projects = groups.len(),
+        variants = variants.len(),
+        "replaying labeled pairs"
+    );
+
+    let reports: Vec<VariantReport> = variants
+        .iter()
+        .map(|variant| evaluate_variant(variant, &groups, args.positive_threshold))
+        .collect();
+
+    print!("{}", render_table(&reports));
+    if let Some(path) = &args.json_out {
+        fs::write(path, serde_json::to_vec_pretty(&reports)?)?;
+        info!(path = %path.display(), "wrote evaluation report");
+    }
+    Ok(())
+}
+
+#[tokio::main]
+async fn main() {
+    if let Err(err) = run().await {
+        error!(error = %err, "sr-eval failed");
+        std::process::exit(1);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{NaiveDate, Utc};
+    use sr_common::Talent;
+
+    fn example(id: i64, project_id: i64, talent: Talent, label: f64) -> TrainingExample {
+        TrainingExample {
+            interaction_id: id,
+            talent,
+            project: Project {
+                id: Some(project_id),
+                monthly_tanka_max: Some(80),
+                required_skills_keywords: vec!["Rust".into()],
+                ..Project::default()
+            },
+            label,
+            signal_source: Some("feedback".into()),
+            run_date: NaiveDate::from_ymd_opt(2026, 10, 1).unwrap(),
+            matched_at: Utc::now(),
+            features_updated_after_match: false,
+        }
+    }
+
+    fn talent(id: i64, skill: &str, price: u32) -> Talent {
+        Talent {
+            id: Some(id),
+            possessed_skills_keywords: vec![skill.into()],
+            desired_price_min: Some(price),
+            ..Talent::default()
+        }
+    }
+
+    #[test]
+    fn variants_always_include_control_and_reject_duplicates() {
+        let specs = parse_variants(Some(
+            r#"[{"name": "tt", "two_tower": {"weight": 0.3}},
+                {"name": "skills", "weights": {"tanka": 0.1, "location": 0.1, "skills": 0.6,
+                 "experience": 0.1, "contract": 0.1, "other": 0.0}}]"#,
+        ))
+        .unwrap();
+        let names: Vec<_> = specs.iter().map(|s| s.name.as_str()).collect();
+        assert_eq!(names, vec!["control", "tt", "skills"]);
+        assert_eq!(specs[1].two_tower.as_ref().unwrap().embedder, "hash");
+
+        assert!(parse_variants(Some(r#"[{"name": "a"}, {"name": "a"}]"#)).is_err());
+        assert!(parse_variants(Some(r#"[{"name": "a", "weigths": {}}]"#)).is_err());
+        assert_eq!(parse_variants(None).unwrap().len(), 1);
+    }
+
+    #[test]
+    fn grouping_keeps_strongest_label_per_pair() {
+        let examples = vec![
+            example(1, 10, talent(1, "Rust", 60), 0.1),
+            example(2, 10, talent(1, "Rust", 60), 1.0),
+            example(3, 10, talent(2, "Java", 60), 0.0),
+            example(4, 20, talent(1, "Rust", 60), 0.8),
+        ];
+        let groups = group_by_project(&examples);
+
+        assert_eq!(groups.len(), 2);
+        assert_eq!(groups[0].pairs.len(), 2);
+        assert_eq!(groups[0].pairs[0].interaction_id, 2);
+        assert_eq!(groups[1].project.id, Some(20));
+    }
+
+    #[test]
+    fn knocked_out_positives_count_as_false_negatives() {
+        let examples = vec![
+            example(1, 10, talent(1, "Rust", 60), 1.0),
+            example(2, 10, talent(2, "Java", 60), 0.0),
+            // 単価 KO（希望単価 200 > 上限 80）なのに成約した人材
+            example(3, 10, talent(3, "Rust", 200), 1.0),
+        ];
+        let groups = group_by_project(&examples);
+        let variant = Variant::build(&control_spec(), &TwoTowerConfig::default()).unwrap();
+        let report = evaluate_variant(&variant, &groups, 0.5);
+
+        assert_eq!(report.variant, "control");
+        assert_eq!(report.ranking.pairs, 3);
+        assert_eq!(report.ko_false_negatives, 1);
+        assert_eq!(report.ko_false_negative_rate, Some(0.5));
+
+        let table = render_table(&[report]);
+        assert!(table.starts_with("variant"));
+        assert!(table.contains("0.5000"));
+    }
+
+    #[test]
+    fn weight_variants_change_business_ranking() {
+        let skills_only = parse_variants(Some(
+            r#"[{"name": "skills_only", "weights": {"tanka": 0.0, "location": 0.0,
+                 "skills": 1.0, "experience": 0.0, "contract": 0.0, "other": 0.0}}]"#,
+        ))
+        .unwrap();
+        let examples = vec![
+            example(1, 10, talent(1, "Rust", 60), 1.0),
+            example(2, 10, talent(2, "Java", 60), 0.0),
+        ];
+        let groups = group_by_project(&examples);
+
+        let reports: Vec<_> = skills_only
+            .iter()
+            .map(|spec| {
+                let variant = Variant::build(spec, &TwoTowerConfig::default()).unwrap();
+                evaluate_variant(&variant, &groups, 0.5)
+            })
+            .collect();
+        assert_eq!(reports.len(), 2);
+        assert_eq!(reports[1].variant, "skills_only");
+        // スキル一致の人材が 1 位なら MRR = 1
+        assert_eq!(reports[1].ranking.mrr, Some(1.0));
+    }
+}
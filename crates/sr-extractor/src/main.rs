@@ -3,19 +3,21 @@ use clap::Parser;
 use dotenvy::dotenv;
 use serde_json::to_value;
 use sr_common::db::{
-    create_pool_from_url_checked, fetch_pending_emails, pending_copy, run_migrations,
-    upsert_extraction_job, PendingEmail,
+    create_pool_from_url_checked, fetch_pending_emails, fetch_pending_talent_emails,
+    materialize_completed_talents, pending_copy, run_migrations, upsert_extraction_job,
+    PendingEmail,
 };
+use sr_common::extraction::talent::{extract_all_talent_fields, TalentExtractorOutput};
 use sr_common::extraction::{
-    calculate_priority, evaluate_quality, extract_all_fields, extract_flow_dept,
-    extract_remote_onsite, extract_start_date_raw, extract_tanka, extract_work_todofuken,
-    ExtractorOutput, PartialFields,
+    calculate_priority, evaluate_quality, extract_partial_fields, extract_project_sections,
+    ExtractorOutput,
 };
 use sr_common::logging::{init_tracing_subscriber, install_tracing_panic_hook};
 use sr_common::normalize::{calculate_content_hash, calculate_subject_hash, normalize_subject};
 use sr_common::queue::{
-    ExtractionJob, ExtractionQueue, FinalMethod, JobOutcome, RecommendedMethod,
+    EntityType, ExtractionJob, ExtractionQueue, FinalMethod, JobOutcome, RecommendedMethod,
 };
+use sr_common::rules::init_active_rules;
 use std::collections::HashSet;
 use tokio::task::spawn_blocking;
 use tracing::{debug, error, info};
@@ -35,7 +37,7 @@ struct Cli {
     dry_run: bool,
 }
 
-const RULE_VERSION: &str = "2025-01-15-r1";
+const RULE_VERSION: &str = "2026-10-17-r2";
 const FETCH_LIMIT: i64 = 100;
 
 fn dedup_emails_by_body(emails: Vec<PendingEmail>) -> (Vec<PendingEmail>, usize) {
@@ -63,6 +65,50 @@ fn build_job_from_email(
 ) -> ExtractionJob {
     let mut job = ExtractionJob::new("", email_subject, email_received_at, subject_hash);
     job.partial_fields = to_value(&extraction.partial).ok();
+    job.field_evidence = to_value(&extraction.evidence).ok();
+    job.priority = calculate_priority(&extraction.quality);
+    job.recommended_method = Some(extraction.decision.recommended_method.clone());
+    job.decision_reason = Some(extraction.decision.reason.clone());
+    job.extractor_version = Some(env!("CARGO_PKG_VERSION").into());
+    job.rule_version = Some(RULE_VERSION.into());
+    job
+}
+
+/// 1 通分の抽出結果からジョブを作る。複数案件メールはセクションごとのサブジョブ
+/// （message_id = "<message_id>#<n>"）になる
+fn build_jobs_from_sections(
+    message_id: &str,
+    email_subject: &str,
+    email_received_at: chrono::DateTime<Utc>,
+    subject_hash: &str,
+    sections: &[(Option<(u32, String)>, ExtractorOutput)],
+) -> Vec<ExtractionJob> {
+    sections
+        .iter()
+        .map(|(section, extraction)| {
+            let mut job =
+                build_job_from_email(email_subject, email_received_at, subject_hash, extraction);
+            job.message_id = message_id.to_string();
+            job.requires_manual_review =
+                extraction.decision.recommended_method == RecommendedMethod::LlmRecommended;
+            job.manual_review_reason = job.decision_reason.clone();
+            match section {
+                Some((index, text)) => job.into_section(*index, text),
+                None => job,
+            }
+        })
+        .collect()
+}
+
+fn build_talent_job_from_email(
+    email_subject: &str,
+    email_received_at: chrono::DateTime<Utc>,
+    subject_hash: &str,
+    extraction: &TalentExtractorOutput,
+) -> ExtractionJob {
+    let mut job = ExtractionJob::new("", email_subject, email_received_at, subject_hash);
+    job.entity_type = EntityType::Talent;
+    job.partial_fields = to_value(&extraction.partial).ok();
     job.priority = calculate_priority(&extraction.quality);
     job.recommended_method = Some(extraction.decision.recommended_method.clone());
     job.decision_reason = Some(extraction.decision.reason.clone());
@@ -77,18 +123,11 @@ pub fn run_sample_flow() -> ExtractionQueue {
     let body_text = r#"
 【案件概要】
 月額70〜90万円、即日から参画できるフルリモート案件です（勤務地: 東京都）。
+【必須スキル】
+・Rust での開発経験
 "#;
 
-    let mut partial = PartialFields::default();
-    if let Some((min, max)) = extract_tanka(body_text) {
-        partial.monthly_tanka_min = Some(min);
-        partial.monthly_tanka_max = Some(max);
-    }
-    partial.start_date_raw = extract_start_date_raw(body_text);
-    partial.work_todofuken = extract_work_todofuken(body_text);
-    partial.remote_onsite = extract_remote_onsite(body_text);
-    partial.flow_dept = extract_flow_dept(body_text);
-    partial.outcome_tag = Some("unknown".to_string());
+    let partial = extract_partial_fields(body_text);
 
     let (quality, decision) = evaluate_quality(&partial);
     let priority = calculate_priority(&quality);
@@ -137,6 +176,8 @@ async fn run() -> Result<(), Box<dyn std::error::Error>> {
     install_tracing_panic_hook(env!("CARGO_PKG_NAME"));
 
     let args = Cli::parse();
+    let rules = init_active_rules()?;
+    info!(rule_version = %rules.version, path = ?rules.path, "loaded match rules");
     let pool = create_pool_from_url_checked(&args.db_url).await?;
 
     run_migrations(&pool).await?;
@@ -159,12 +200,7 @@ async fn run() -> Result<(), Box<dyn std::error::Error>> {
         let fetched = fetch_pending_emails(&pool, FETCH_LIMIT).await?;
         let (unique, dropped) = spawn_blocking(|| dedup_emails_by_body(fetched))
             .await
-            .map_err(|err| {
-                std::io::Error::new(
-                    std::io::ErrorKind::Other,
-                    format!("deduplication task failed: {err}"),
-                )
-            })?;
+            .map_err(|err| std::io::Error::other(format!("deduplication task failed: {err}")))?;
         (unique, dropped)
     };
     info!(
@@ -173,24 +209,65 @@ async fn run() -> Result<(), Box<dyn std::error::Error>> {
     );
 
     for email in emails {
+        let (normalized_subject, subject_hash, sections) = spawn_blocking({
+            let subject = email.subject.clone();
+            let body_text = email.body_text.clone();
+            move || {
+                let normalized_subject = normalize_subject(&subject);
+                let subject_hash = calculate_subject_hash(&subject);
+                let sections = extract_project_sections(&body_text, Some(&normalized_subject));
+                (normalized_subject, subject_hash, sections)
+            }
+        })
+        .await
+        .map_err(|err| std::io::Error::other(format!("extraction task failed: {err}")))?;
+        if sections.len() > 1 {
+            info!(
+                message_id = %email.message_id,
+                sections = sections.len(),
+                "split multi-project email into section jobs"
+            );
+        }
+
+        for job in build_jobs_from_sections(
+            &email.message_id,
+            &normalized_subject,
+            email.created_at,
+            &subject_hash,
+            &sections,
+        ) {
+            let pending = pending_copy(&job, email.created_at);
+            let rows = upsert_extraction_job(&pool, &pending).await?;
+            info!(rows, message_id = %pending.message_id, "enqueued job into postgres");
+        }
+    }
+
+    let (talent_emails, talent_deduped) = {
+        let fetched = fetch_pending_talent_emails(&pool, FETCH_LIMIT).await?;
+        spawn_blocking(|| dedup_emails_by_body(fetched))
+            .await
+            .map_err(|err| std::io::Error::other(format!("deduplication task failed: {err}")))?
+    };
+    info!(
+        count = talent_emails.len(),
+        deduped = talent_deduped,
+        "fetched pending talent emails to enqueue"
+    );
+
+    for email in talent_emails {
         let (normalized_subject, subject_hash, extraction) = spawn_blocking({
             let subject = email.subject.clone();
             let body_text = email.body_text.clone();
             move || {
                 let normalized_subject = normalize_subject(&subject);
                 let subject_hash = calculate_subject_hash(&subject);
-                let extraction = extract_all_fields(&body_text, Some(&normalized_subject));
+                let extraction = extract_all_talent_fields(&body_text, Some(&normalized_subject));
                 (normalized_subject, subject_hash, extraction)
             }
         })
         .await
-        .map_err(|err| {
-            std::io::Error::new(
-                std::io::ErrorKind::Other,
-                format!("extraction task failed: {err}"),
-            )
-        })?;
-        let mut job = build_job_from_email(
+        .map_err(|err| std::io::Error::other(format!("talent extraction task failed: {err}")))?;
+        let mut job = build_talent_job_from_email(
             &normalized_subject,
             email.created_at,
             &subject_hash,
@@ -203,7 +280,17 @@ async fn run() -> Result<(), Box<dyn std::error::Error>> {
 
         let pending = pending_copy(&job, email.created_at);
         let rows = upsert_extraction_job(&pool, &pending).await?;
-        info!(rows, message_id = %pending.message_id, "enqueued job into postgres");
+        info!(rows, message_id = %pending.message_id, "enqueued talent job into postgres");
+    }
+
+    // LLM 側で射影に失敗した人材ジョブをここで拾い直す
+    let projection = materialize_completed_talents(&pool, FETCH_LIMIT).await?;
+    if projection.projected > 0 || projection.skipped > 0 {
+        info!(
+            projected = projection.projected,
+            skipped = projection.skipped,
+            "materialized completed talent jobs into talents_enum"
+        );
     }
 
     Ok(())
@@ -237,6 +324,49 @@ mod tests {
         assert!(job.email_subject.contains("stub"));
     }
 
+    #[test]
+    fn multi_project_emails_become_section_jobs() {
+        let body = "【案件1】Java開発\n単価：60〜70万円\n\n【案件2】AWS移行\n単価：90万円";
+        let sections = extract_project_sections(body, Some("2件のご紹介"));
+        let jobs = build_jobs_from_sections("m-42", "2件のご紹介", Utc::now(), "hash", &sections);
+
+        assert_eq!(jobs.len(), 2);
+        assert_eq!(jobs[0].message_id, "m-42#1");
+        assert_eq!(jobs[1].message_id, "m-42#2");
+        assert!(jobs
+            .iter()
+            .all(|job| job.source_message_id.as_deref() == Some("m-42")));
+        assert_eq!(
+            jobs[1].section_text.as_deref(),
+            Some("【案件2】AWS移行\n単価：90万円")
+        );
+
+        let single = extract_project_sections("月額80万円", Some("件名"));
+        let jobs = build_jobs_from_sections("m-43", "件名", Utc::now(), "hash", &single);
+        assert_eq!(jobs.len(), 1);
+        assert_eq!(jobs[0].message_id, "m-43");
+        assert_eq!(jobs[0].section_index, None);
+    }
+
+    #[test]
+    fn talent_jobs_carry_entity_type_and_tier_decision() {
+        let body =
+            "【最寄駅】三鷹駅\n【稼働】即日\n【単価】65万円\n【スキル】Java\n【経験年数】5年";
+        let extraction = extract_all_talent_fields(body, Some("Java人材"));
+        let job = build_talent_job_from_email("Java人材", Utc::now(), "hash", &extraction);
+
+        assert_eq!(job.entity_type, EntityType::Talent);
+        assert_eq!(
+            job.recommended_method,
+            Some(RecommendedMethod::RustRecommended)
+        );
+        assert!(job
+            .partial_fields
+            .as_ref()
+            .and_then(|v| v.get("nearest_station_raw"))
+            .is_some());
+    }
+
     #[test]
     fn dedup_emails_by_body_removes_duplicate_payloads() {
         let now = Utc::now();
@@ -0,0 +1,386 @@
+use std::fs;
+use std::path::PathBuf;
+
+use chrono::{Duration, NaiveDate, Utc};
+use clap::Parser;
+use dotenvy::dotenv;
+use serde::Serialize;
+use sr_common::db::{create_pool_from_url_checked, fetch_score_breakdown_labels};
+use sr_common::evaluation::{roc_auc, ScoredLabel};
+use sr_common::logging::{init_tracing_subscriber, install_tracing_panic_hook};
+use sr_common::matching::scoring::{MatchingConfig, TotalScoreWeights};
+use sr_common::matching::weight_fit::{
+    fit_weights, FactorExample, LogisticConfig, NonNegativeLogistic,
+};
+use sr_common::matching::weights::{active_weights_file, Weights, WeightsFile};
+use sr_common::rules::init_active_rules;
+use tracing::{error, info, warn};
+
+type CliResult<T> = Result<T, Box<dyn std::error::Error>>;
+
+#[derive(Debug, Parser)]
+#[command(
+    name = "sr-fit-weights",
+    about = "Fit non-negative scoring weights from match_results.score_breakdown and training labels"
+)]
+struct Cli {
+    /// PostgreSQL connection string
+    #[arg(long, env = "DATABASE_URL")]
+    db_url: String,
+
+    /// Only use labels recorded on or after this date (YYYY-MM-DD)
+    #[arg(long)]
+    since: Option<NaiveDate>,
+
+    /// Directory for the versioned weights file
+    #[arg(long, default_value = "models/weights")]
+    output_dir: PathBuf,
+
+    /// First run_date of the validation split (defaults to the last --validation-days days)
+    #[arg(long)]
+    split_date: Option<NaiveDate>,
+
+    /// Number of most recent run dates held out for validation when --split-date is omitted
+    #[arg(long, default_value_t = 7, value_parser = clap::value_parser!(i64).range(1..=365))]
+    validation_days: i64,
+
+    /// Labels at or above this value count as positives for AUC
+    #[arg(long, default_value_t = 0.5)]
+    positive_threshold: f64,
+
+    /// Refuse to fit with fewer labeled pairs than this
+    #[arg(long, default_value_t = 200, value_parser = clap::value_parser!(u64).range(1..))]
+    min_pairs: u64,
+
+    #[arg(long, default_value_t = 3000)]
+    iterations: usize,
+
+    #[arg(long, default_value_t = 0.5)]
+    learning_rate: f64,
+
+    #[arg(long, default_value_t = 1e-4)]
+    l2: f64,
+}
+
+impl Cli {
+    fn logistic_config(&self) -> LogisticConfig {
+        LogisticConfig {
+            iterations: self.iterations,
+            learning_rate: self.learning_rate,
+            l2: self.l2,
+        }
+    }
+}
+
+/// `run_date` による train / validation 分割（sr-train と同じ境界の取り方）
+fn split_by_run_date(
+    examples: &[FactorExample],
+    split_date: Option<NaiveDate>,
+    validation_days: i64,
+) -> CliResult<(NaiveDate, Vec<FactorExample>, Vec<FactorExample>)> {
+    let latest = examples
+        .iter()
+        .map(|example| example.run_date)
+        .max()
+        .ok_or("no labeled pairs to split")?;
+    let split_date = split_date.unwrap_or(latest - Duration::days(validation_days - 1));
+
+    let (validation, train): (Vec<_>, Vec<_>) = examples
+        .iter()
+        .cloned()
+        .partition(|example| example.run_date >= split_date);
+    if train.is_empty() || validation.is_empty() {
+        return Err(format!(
+            "split at {split_date} leaves train={} validation={}; adjust --split-date or --validation-days",
+            train.len(),
+            validation.len()
+        )
+        .into());
+    }
+    Ok((split_date, train, validation))
+}
+
+/// 1 つの重み設定の評価値
+#[derive(Debug, Serialize)]
+struct WeightsEvaluation {
+    train_auc: Option<f64>,
+    validation_auc: Option<f64>,
+    /// 総合スコアを train でロジスティック較正したうえでの平均交差エントロピー
+    train_log_loss: Option<f64>,
+    validation_log_loss: Option<f64>,
+}
+
+/// 総合スコアを特徴量 1 つとして較正し、AUC と log loss を計算する。
+/// 総合スコアは確率ではないため、較正を挟んで重み設定どうしの log loss を比べられるようにする
+fn evaluate(
+    weights: &Weights,
+    total: &TotalScoreWeights,
+    train: &[FactorExample],
+    validation: &[FactorExample],
+    args: &Cli,
+) -> WeightsEvaluation {
+    let features = |examples: &[FactorExample]| -> (Vec<Vec<f64>>, Vec<f64>) {
+        examples
+            .iter()
+            .map(|e| (vec![e.total_score(weights, total)], e.label))
+            .unzip()
+    };
+    let auc = |(scores, labels): &(Vec<Vec<f64>>, Vec<f64>)| {
+        let items: Vec<ScoredLabel> = scores
+            .iter()
+            .zip(labels)
+            .map(|(score, &label)| ScoredLabel {
+                score: score[0],
+                label,
+            })
+            .collect();
+        roc_auc(&items, args.positive_threshold)
+    };
+
+    let train = features(train);
+    let validation = features(validation);
+    let calibration = NonNegativeLogistic::fit(&train.0, &train.1, &args.logistic_config());
+    WeightsEvaluation {
+        train_auc: auc(&train),
+        validation_auc: auc(&validation),
+        train_log_loss: calibration.log_loss(&train.0, &train.1),
+        validation_log_loss: calibration.log_loss(&validation.0, &validation.1),
+    }
+}
+
+/// weights-<version>.json の `fit` 節
+#[derive(Debug, Serialize)]
+struct FitReport {
+    split_date: NaiveDate,
+    train_pairs: usize,
+    validation_pairs: usize,
+    positive_threshold: f64,
+    iterations: usize,
+    learning_rate: f64,
+    l2: f64,
+    detailed_model: NonNegativeLogistic,
+    total_model: Option<NonNegativeLogistic>,
+    baseline_score_version: String,
+    baseline: WeightsEvaluation,
+    learned: WeightsEvaluation,
+}
+
+/// 分割 → 学習 → 現行重みとの比較 → 重みファイル保存（DB に触れない部分）
+fn fit_and_write(
+    args: &Cli,
+    baseline: &MatchingConfig,
+    examples: &[FactorExample],
+) -> CliResult<(WeightsFile, PathBuf)> {
+    if (examples.len() as u64) < args.min_pairs {
+        return Err(format!(
+            "only {} labeled pairs with a score breakdown (need --min-pairs {})",
+            examples.len(),
+            args.min_pairs
+        )
+        .into());
+    }
+
+    let (split_date, train, validation) =
+        split_by_run_date(examples, args.split_date, args.validation_days)?;
+    info!(
+        %split_date,
+        train = train.len(),
+        validation = validation.len(),
+        "split pairs by run_date"
+    );
+
+    let fitted = fit_weights(&train, &args.logistic_config())?;
+    let report = FitReport {
+        split_date,
+        train_pairs: train.len(),
+        validation_pairs: validation.len(),
+        positive_threshold: args.positive_threshold,
+        iterations: args.iterations,
+        learning_rate: args.learning_rate,
+        l2: args.l2,
+        baseline_score_version: baseline.score_version.clone(),
+        baseline: evaluate(
+            &baseline.weights,
+            &baseline.total_score_weights,
+            &train,
+            &validation,
+            args,
+        ),
+        learned: evaluate(
+            &fitted.detailed,
+            &fitted.total_score_weights,
+            &train,
+            &validation,
+            args,
+        ),
+        detailed_model: fitted.detailed_model,
+        total_model: fitted.total_model,
+    };
+
+    let file = WeightsFile {
+        version: WeightsFile::compute_version(
+            &fitted.detailed,
+            None,
+            Some(&fitted.total_score_weights),
+        ),
+        created_at: Utc::now(),
+        detailed: fitted.detailed,
+        prefilter: None,
+        total_score_weights: Some(fitted.total_score_weights),
+        fit: Some(serde_json::to_value(&report)?),
+    };
+    let path = args
+        .output_dir
+        .join(format!("weights-{}.json", file.version));
+    fs::write(&path, serde_json::to_vec_pretty(&file)?)?;
+
+    info!(
+        path = %path.display(),
+        version = %file.version,
+        baseline_auc = ?report.baseline.validation_auc,
+        learned_auc = ?report.learned.validation_auc,
+        baseline_log_loss = ?report.baseline.validation_log_loss,
+        learned_log_loss = ?report.learned.validation_log_loss,
+        "wrote weights file; set MATCHING_WEIGHTS_PATH to use it"
+    );
+    if report.learned.validation_auc < report.baseline.validation_auc {
+        warn!("learned weights rank the validation split worse than the current weights");
+    }
+    Ok((file, path))
+}
+
+async fn run() -> CliResult<()> {
+    dotenv().ok();
+    init_tracing_subscriber(env!("CARGO_PKG_NAME"));
+    install_tracing_panic_hook(env!("CARGO_PKG_NAME"));
+
+    let args = Cli::parse();
+    let rules = init_active_rules()?;
+    info!(rule_version = %rules.version, path = ?rules.path, "loaded match rules");
+    fs::create_dir_all(&args.output_dir)?;
+    // 比較基準は現在有効な重み（MATCHING_WEIGHTS_PATH が壊れていれば黙って定数に戻さない）
+    active_weights_file()?;
+    let baseline = MatchingConfig::default();
+
+    let pool = create_pool_from_url_checked(&args.db_url).await?;
+    let since = args
+        .since
+        .and_then(|date| date.and_hms_opt(0, 0, 0))
+        .map(|at| at.and_utc());
+    let rows = fetch_score_breakdown_labels(&pool, since).await?;
+    let total = rows.len();
+    let examples: Vec<FactorExample> = rows
+        .iter()
+        .filter_map(|row| {
+            FactorExample::from_breakdown(
+                row.interaction_id,
+                row.run_date,
+                row.label,
+                &row.score_breakdown,
+            )
+        })
+        .collect();
+    if examples.len() < total {
+        warn!(
+            skipped = total - examples.len(),
+            "some score breakdowns lack factor scores; skipped"
+        );
+    }
+
+    fit_and_write(&args, &baseline, &examples)?;
+    Ok(())
+}
+
+#[tokio::main]
+async fn main() {
+    if let Err(err) = run().await {
+        error!(error = %err, "sr-fit-weights failed");
+        std::process::exit(1);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// skills が高いほど良いラベルになる合成データ（run_date は 10/1〜10/20）
+    fn examples() -> Vec<FactorExample> {
+        (0..400)
+            .map(|i| {
+                let skills = (i % 10) as f64 / 10.0;
+                let location = ((i * 7) % 10) as f64 / 10.0;
+                FactorExample {
+                    interaction_id: i,
+                    run_date: NaiveDate::from_ymd_opt(2026, 10, 1 + (i % 20) as u32).unwrap(),
+                    label: if skills + 0.1 * location > 0.55 {
+                        1.0
+                    } else {
+                        0.0
+                    },
+                    factors: [0.5, location, skills, 0.5, 1.0, 0.5],
+                    semantic: None,
+                    historical: None,
+                }
+            })
+            .collect()
+    }
+
+    #[test]
+    fn cli_requires_db_url_and_validates_ranges() {
+        let cli = Cli::parse_from(["sr-fit-weights", "--db-url", "postgres://x"]);
+        assert_eq!(cli.output_dir, PathBuf::from("models/weights"));
+        assert_eq!(cli.validation_days, 7);
+        assert_eq!(cli.min_pairs, 200);
+        assert_eq!(cli.logistic_config(), LogisticConfig::default());
+
+        assert!(Cli::try_parse_from([
+            "sr-fit-weights",
+            "--db-url",
+            "postgres://x",
+            "--min-pairs",
+            "0"
+        ])
+        .is_err());
+    }
+
+    #[test]
+    fn writes_loadable_weights_file_that_beats_baseline() {
+        let dir = std::env::temp_dir().join(format!("sr-fit-weights-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let args = Cli::parse_from([
+            "sr-fit-weights",
+            "--db-url",
+            "postgres://x",
+            "--output-dir",
+            dir.to_str().unwrap(),
+            "--validation-days",
+            "5",
+        ]);
+
+        let (file, path) = fit_and_write(&args, &MatchingConfig::builtin(), &examples()).unwrap();
+        let loaded = WeightsFile::from_path(&path).unwrap();
+        assert_eq!(loaded.version, file.version);
+        assert!(loaded.detailed.skills > loaded.detailed.tanka);
+        assert_eq!(loaded.total_score_weights.unwrap().business, 1.0);
+
+        let fit = loaded.fit.unwrap();
+        assert_eq!(fit["train_pairs"], 300);
+        let learned = fit["learned"]["validation_auc"].as_f64().unwrap();
+        let baseline = fit["baseline"]["validation_auc"].as_f64().unwrap();
+        assert!(learned >= baseline, "learned={learned} baseline={baseline}");
+
+        fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn refuses_to_fit_below_min_pairs() {
+        let args = Cli::parse_from([
+            "sr-fit-weights",
+            "--db-url",
+            "postgres://x",
+            "--min-pairs",
+            "1000",
+        ]);
+        assert!(fit_and_write(&args, &MatchingConfig::builtin(), &examples()).is_err());
+    }
+}
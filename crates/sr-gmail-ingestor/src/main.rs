@@ -15,6 +15,7 @@ use sr_common::db::{
     create_pool_from_url_checked, run_migrations, DbPoolError, MigrationError, PgPool,
 };
 use sr_common::logging::{init_tracing_subscriber, install_tracing_panic_hook};
+use sr_common::rules::{init_active_rules, RulesError};
 use std::collections::HashSet;
 use tokio::task::spawn_blocking;
 use tokio::time::{interval, timeout, Duration};
@@ -112,6 +113,8 @@ enum IngestError {
     HtmlToText(#[from] html2text::Error),
     #[error("gmail api call timed out: {0}")]
     GmailTimeout(&'static str),
+    #[error("match rules error: {0}")]
+    Rules(#[from] RulesError),
 }
 
 struct GmailIngestor {
@@ -484,6 +487,8 @@ async fn run() -> Result<(), IngestError> {
     install_tracing_panic_hook(env!("CARGO_PKG_NAME"));
 
     let cli = Cli::parse();
+    let rules = init_active_rules()?;
+    info!(rule_version = %rules.version, path = ?rules.path, "loaded match rules");
     let pool = create_pool_from_url_checked(&cli.db_url).await?;
     run_migrations(&pool).await?;
 
@@ -0,0 +1,465 @@
+//! Lark「要員一覧」CSV エクスポートのパースと行単位の検証レポート
+//!
+//! 値の補正は sr-common の normalizer（都道府県・英語・スキル・駅名）に寄せ、
+//! 補正できずに落とした値は [`RowReport::normalized_away`] に残す。
+
+use std::collections::{HashMap, HashSet};
+use std::io::Read;
+
+use chrono::NaiveDate;
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+use sr_common::corrections::english_skill::correct_english_skill;
+use sr_common::corrections::station::normalize_station;
+use sr_common::corrections::todofuken::correct_todofuken;
+use sr_common::db::TalentMasterUpsert;
+use sr_common::skill_normalizer::normalize_skill;
+
+/// lark_record_id として扱う列名（先に見つかったものを採用）
+pub const RECORD_ID_COLUMNS: &[&str] = &["レコードID", "record_id", "Record ID", "lark_record_id"];
+
+/// 居住都道府県として扱う列名
+const RESIDENCE_COLUMNS: &[&str] = &["居住地", "都道府県", "居住都道府県"];
+
+/// 万円単位で入力されたとみなす参考原価の上限（これ未満は ×10,000 して円に揃える）
+const MAN_YEN_INPUT_THRESHOLD: i64 = 10_000;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RowStatus {
+    Accepted,
+    Rejected,
+}
+
+/// 補正できずに破棄した値
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct NormalizedAway {
+    pub field: &'static str,
+    pub raw: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct RowReport {
+    /// CSV 上の行番号（ヘッダー行 = 1）
+    pub line: u64,
+    pub lark_record_id: Option<String>,
+    pub name: Option<String>,
+    pub status: RowStatus,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub reasons: Vec<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub normalized_away: Vec<NormalizedAway>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub warnings: Vec<String>,
+}
+
+impl RowReport {
+    fn is_clean(&self) -> bool {
+        self.status == RowStatus::Accepted
+            && self.normalized_away.is_empty()
+            && self.warnings.is_empty()
+    }
+}
+
+/// インポート全体の検証レポート（問題のあった行のみ `rows` に残す）
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct ImportReport {
+    pub total_rows: u64,
+    pub accepted: u64,
+    pub rejected: u64,
+    pub normalized_away: u64,
+    pub rows: Vec<RowReport>,
+}
+
+#[derive(Debug)]
+pub struct ParsedImport {
+    pub talents: Vec<TalentMasterUpsert>,
+    pub report: ImportReport,
+}
+
+struct RowContext<'a> {
+    headers: &'a HashMap<String, usize>,
+    record: &'a csv::StringRecord,
+    report: RowReport,
+}
+
+impl RowContext<'_> {
+    fn raw(&self, column: &str) -> Option<&str> {
+        self.headers
+            .get(column)
+            .and_then(|&idx| self.record.get(idx))
+            .map(str::trim)
+            .filter(|v| !v.is_empty())
+    }
+
+    fn text(&self, column: &str) -> Option<String> {
+        self.raw(column).map(str::to_string)
+    }
+
+    fn drop_value(&mut self, field: &'static str, raw: &str) {
+        self.report.normalized_away.push(NormalizedAway {
+            field,
+            raw: raw.to_string(),
+        });
+    }
+
+    /// 値があればパースし、パースできなければ normalized_away に記録して None を返す
+    fn parse<T>(
+        &mut self,
+        column: &str,
+        field: &'static str,
+        parser: impl Fn(&str) -> Option<T>,
+    ) -> Option<T> {
+        let raw = self.raw(column)?.to_string();
+        let parsed = parser(&raw);
+        if parsed.is_none() {
+            self.drop_value(field, &raw);
+        }
+        parsed
+    }
+
+    fn list(&self, column: &str) -> Vec<String> {
+        self.raw(column).map(split_list).unwrap_or_default()
+    }
+}
+
+/// Lark CSV を読み込み、UPSERT 用の行と検証レポートを返す
+///
+/// `record_id_column` 未指定時は [`RECORD_ID_COLUMNS`] から自動検出する。
+/// レコード ID 列が無いエクスポートでは氏名・連絡先から決定的な ID を導出する。
+pub fn parse_lark_csv<R: Read>(
+    reader: R,
+    record_id_column: Option<&str>,
+) -> Result<ParsedImport, csv::Error> {
+    let mut csv_reader = csv::ReaderBuilder::new().flexible(true).from_reader(reader);
+    let headers: HashMap<String, usize> = csv_reader
+        .headers()?
+        .iter()
+        .enumerate()
+        .map(|(idx, h)| (h.trim_start_matches('\u{feff}').trim().to_string(), idx))
+        .collect();
+
+    let record_id_column = record_id_column.map(str::to_string).or_else(|| {
+        RECORD_ID_COLUMNS
+            .iter()
+            .find(|c| headers.contains_key(**c))
+            .map(|c| c.to_string())
+    });
+    let residence_column = RESIDENCE_COLUMNS
+        .iter()
+        .find(|c| headers.contains_key(**c))
+        .copied();
+
+    let mut talents = Vec::new();
+    let mut report = ImportReport::default();
+    let mut seen_ids = HashSet::new();
+
+    for result in csv_reader.records() {
+        let record = result?;
+        report.total_rows += 1;
+
+        let mut ctx = RowContext {
+            headers: &headers,
+            record: &record,
+            report: RowReport {
+                line: record.position().map(|p| p.line()).unwrap_or_default(),
+                lark_record_id: None,
+                name: None,
+                status: RowStatus::Accepted,
+                reasons: Vec::new(),
+                normalized_away: Vec::new(),
+                warnings: Vec::new(),
+            },
+        };
+
+        let talent = parse_row(&mut ctx, record_id_column.as_deref(), residence_column);
+        let mut row = ctx.report;
+
+        match talent {
+            Some(talent) if !seen_ids.insert(talent.lark_record_id.clone()) => {
+                row.status = RowStatus::Rejected;
+                row.reasons.push(format!(
+                    "duplicate lark_record_id in file: {}",
+                    talent.lark_record_id
+                ));
+            }
+            Some(talent) => talents.push(talent),
+            None => row.status = RowStatus::Rejected,
+        }
+
+        match row.status {
+            RowStatus::Accepted => report.accepted += 1,
+            RowStatus::Rejected => report.rejected += 1,
+        }
+        report.normalized_away += row.normalized_away.len() as u64;
+        if !row.is_clean() {
+            report.rows.push(row);
+        }
+    }
+
+    Ok(ParsedImport { talents, report })
+}
+
+fn parse_row(
+    ctx: &mut RowContext<'_>,
+    record_id_column: Option<&str>,
+    residence_column: Option<&str>,
+) -> Option<TalentMasterUpsert> {
+    let name = ctx.text("氏名");
+    ctx.report.name = name.clone();
+    let Some(name) = name else {
+        ctx.report.reasons.push("missing 氏名".into());
+        return None;
+    };
+
+    let email = ctx.text("メアド");
+    let phone = ctx.text("携帯番号");
+    let lark_record_id = match record_id_column.and_then(|c| ctx.text(c)) {
+        Some(id) => id,
+        None => {
+            let derived = derive_record_id(&name, email.as_deref(), phone.as_deref());
+            ctx.report
+                .warnings
+                .push(format!("lark_record_id missing; derived {derived}"));
+            derived
+        }
+    };
+    ctx.report.lark_record_id = Some(lark_record_id.clone());
+
+    let mut desired_price = ctx.parse("参考原価", "desired_price", parse_price);
+    if let Some(price) = desired_price.filter(|p| i64::from(*p) < MAN_YEN_INPUT_THRESHOLD) {
+        let yen = price.checked_mul(10_000);
+        ctx.report
+            .warnings
+            .push(format!("desired_price {price} treated as 万円"));
+        desired_price = yen;
+    }
+
+    let skill_tags = normalize_skill_tags(ctx);
+
+    Some(TalentMasterUpsert {
+        lark_record_id,
+        name,
+        age: ctx.parse("年齢", "age", parse_age),
+        birth_year: ctx.parse("生年", "birth_year", parse_birth_year),
+        nearest_station: ctx.parse("最寄り駅", "nearest_station", normalize_station),
+        residential_todofuken: residence_column
+            .and_then(|c| ctx.parse(c, "residential_todofuken", correct_todofuken)),
+        desired_price,
+        current_utilization: ctx.parse("現稼働率", "current_utilization", parse_utilization),
+        available_date: ctx.parse("稼働開始可能日", "available_date", parse_date),
+        sales_status: ctx.text("営業状況"),
+        sales_rep: ctx.text("担当営業"),
+        priority_rank: ctx.text("注力ランク"),
+        skill_tags,
+        project_preferences: ctx.list("案件希望"),
+        usage_types: ctx.list("用途"),
+        capability_pm: ctx.parse("PM/PMO", "capability_pm", parse_capability),
+        capability_se: ctx.parse("SE", "capability_se", parse_capability),
+        capability_bpo: ctx.parse("BPO", "capability_bpo", parse_capability),
+        capability_consul: ctx.parse("コンサル", "capability_consul", parse_capability),
+        english_level: ctx.parse("英語", "english_level", correct_english_skill),
+        business_relationship: ctx.text("SPONTOから見る商流"),
+        phone,
+        email,
+        linkedin_url: ctx.text("Linkedin"),
+        skill_sheet_url: ctx.text("スキルシート"),
+        skill_sheet_url_2: ctx.text("スキルシート２"),
+        memo: ctx.text("メモ"),
+        inflow_source: ctx.text("流入経路"),
+        inflow_date: ctx.parse("流入日", "inflow_date", parse_date),
+        registration_date: ctx.parse("登録日", "registration_date", parse_date),
+    })
+}
+
+/// スキルタグを正規形に揃える（重複除去・正規化で消えたタグは記録）
+fn normalize_skill_tags(ctx: &mut RowContext<'_>) -> Vec<String> {
+    let mut tags = Vec::new();
+    for raw in ctx.list("スキルタグ") {
+        let normalized = normalize_skill(&raw);
+        // 「C」「R」など 1 文字のスキルもあるので、正規化で空になったものだけ落とす
+        if normalized.is_empty() {
+            ctx.drop_value("skill_tags", &raw);
+            continue;
+        }
+        if !tags.contains(&normalized) {
+            tags.push(normalized);
+        }
+    }
+    tags
+}
+
+fn derive_record_id(name: &str, email: Option<&str>, phone: Option<&str>) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(name.as_bytes());
+    hasher.update(b"|");
+    hasher.update(email.unwrap_or_default().to_lowercase().as_bytes());
+    hasher.update(b"|");
+    hasher.update(phone.unwrap_or_default().as_bytes());
+    let bytes = hasher.finalize();
+    let mut hex: String = bytes.iter().map(|b| format!("{:02x}", b)).collect();
+    hex.truncate(16);
+    format!("derived-{hex}")
+}
+
+fn split_list(value: &str) -> Vec<String> {
+    value
+        .split([',', '、', '\n'])
+        .map(str::trim)
+        .filter(|v| !v.is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
+/// ◎〇△✕ → 3/2/1/0
+pub fn parse_capability(value: &str) -> Option<i32> {
+    match value.trim() {
+        "◎" | "〇〇" | "○○" => Some(3),
+        "〇" | "○" => Some(2),
+        "△" => Some(1),
+        "✕" | "×" | "✗" | "x" | "X" => Some(0),
+        _ => None,
+    }
+}
+
+/// 参考原価を円で返す（"800,000" / "1.05e+06" / "80万" / "80万円" に対応）
+pub fn parse_price(value: &str) -> Option<i32> {
+    let cleaned: String = value
+        .trim()
+        .chars()
+        .filter(|c| !matches!(c, ',' | '，' | '円' | '¥' | '￥' | ' '))
+        .collect();
+    let (number, multiplier) = match cleaned.strip_suffix('万') {
+        Some(number) => (number, 10_000.0),
+        None => (cleaned.as_str(), 1.0),
+    };
+    let yen = number.parse::<f64>().ok()? * multiplier;
+    (yen.is_finite() && yen > 0.0 && yen <= i32::MAX as f64).then(|| yen.round() as i32)
+}
+
+pub fn parse_age(value: &str) -> Option<i32> {
+    let age: i32 = value.trim().trim_end_matches('歳').parse().ok()?;
+    (15..=100).contains(&age).then_some(age)
+}
+
+pub fn parse_birth_year(value: &str) -> Option<i32> {
+    let year: i32 = value.trim().trim_end_matches('年').parse().ok()?;
+    (1900..=2010).contains(&year).then_some(year)
+}
+
+/// 稼働率を 0.0-1.0 に揃える（"80" / "80%" / "0.8"）
+pub fn parse_utilization(value: &str) -> Option<f32> {
+    let v: f32 = value.trim().trim_end_matches('%').parse().ok()?;
+    if !v.is_finite() || v < 0.0 {
+        return None;
+    }
+    let v = if v > 1.0 { v / 100.0 } else { v };
+    Some(v.min(1.0))
+}
+
+/// "2025/03/01" / "2025-03-01"（時刻付きは日付部分のみ）
+pub fn parse_date(value: &str) -> Option<NaiveDate> {
+    let date_part = value.split_whitespace().next()?;
+    NaiveDate::parse_from_str(date_part, "%Y/%m/%d")
+        .or_else(|_| NaiveDate::parse_from_str(date_part, "%Y-%m-%d"))
+        .ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CSV: &str = "\u{feff}レコードID,氏名,年齢,最寄り駅,居住地,参考原価,稼働開始可能日,スキルタグ,PM/PMO,SE,英語,SPONTOから見る商流\n\
+rec-1,山田 太郎,35,新宿,東京,800000,2025/03/01,\"Java, Spring Boot\",◎,△,ビジネスレベル,2社先\n\
+rec-2,,40,,,,,,,,,\n\
+rec-3,佐藤 花子,29,渋谷駅,火星,1.05e+06,来月,\"Python, C, R\",?,〇,宇宙語,SPONTO直\n\
+rec-1,山田 太郎(重複),35,,,,,,,,,\n\
+rec-4,鈴木 一郎,,,,75,2025-04-01,,,,,\n";
+
+    #[test]
+    fn parses_rows_and_reports_rejections() {
+        let parsed = parse_lark_csv(CSV.as_bytes(), None).unwrap();
+        let report = &parsed.report;
+
+        assert_eq!(report.total_rows, 5);
+        assert_eq!(report.accepted, 3);
+        assert_eq!(report.rejected, 2);
+        assert_eq!(parsed.talents.len(), 3);
+
+        let yamada = &parsed.talents[0];
+        assert_eq!(yamada.lark_record_id, "rec-1");
+        assert_eq!(yamada.nearest_station.as_deref(), Some("新宿駅"));
+        assert_eq!(yamada.residential_todofuken.as_deref(), Some("東京都"));
+        assert_eq!(yamada.desired_price, Some(800_000));
+        assert_eq!(yamada.available_date, NaiveDate::from_ymd_opt(2025, 3, 1));
+        assert_eq!(yamada.capability_pm, Some(3));
+        assert_eq!(yamada.capability_se, Some(1));
+        assert_eq!(yamada.english_level.as_deref(), Some("ビジネス"));
+        assert_eq!(yamada.skill_tags.len(), 2);
+
+        let missing_name = report.rows.iter().find(|r| r.line == 3).unwrap();
+        assert_eq!(missing_name.status, RowStatus::Rejected);
+        assert_eq!(missing_name.reasons, vec!["missing 氏名".to_string()]);
+
+        let duplicate = report.rows.iter().find(|r| r.line == 5).unwrap();
+        assert_eq!(duplicate.status, RowStatus::Rejected);
+        assert!(duplicate.reasons[0].contains("duplicate lark_record_id"));
+    }
+
+    #[test]
+    fn records_values_that_could_not_be_normalized() {
+        let parsed = parse_lark_csv(CSV.as_bytes(), None).unwrap();
+        let sato_row = parsed
+            .report
+            .rows
+            .iter()
+            .find(|r| r.lark_record_id.as_deref() == Some("rec-3"))
+            .unwrap();
+
+        let dropped: Vec<&str> = sato_row.normalized_away.iter().map(|n| n.field).collect();
+        assert_eq!(
+            dropped,
+            vec![
+                "residential_todofuken",
+                "available_date",
+                "capability_pm",
+                "english_level"
+            ]
+        );
+
+        let sato = &parsed.talents[1];
+        assert_eq!(sato.desired_price, Some(1_050_000));
+        assert_eq!(sato.residential_todofuken, None);
+        assert_eq!(sato.capability_se, Some(2));
+        assert_eq!(sato.skill_tags.len(), 3);
+        assert!(sato.skill_tags.contains(&normalize_skill("C")));
+        assert!(sato.skill_tags.contains(&normalize_skill("R")));
+
+        let suzuki = &parsed.talents[2];
+        assert_eq!(suzuki.desired_price, Some(750_000));
+        assert_eq!(suzuki.available_date, NaiveDate::from_ymd_opt(2025, 4, 1));
+    }
+
+    #[test]
+    fn derives_stable_record_id_when_column_is_missing() {
+        let csv = "氏名,メアド\n山田 太郎,taro@example.com\n";
+        let first = parse_lark_csv(csv.as_bytes(), None).unwrap();
+        let second = parse_lark_csv(csv.as_bytes(), None).unwrap();
+
+        let id = &first.talents[0].lark_record_id;
+        assert!(id.starts_with("derived-"));
+        assert_eq!(id, &second.talents[0].lark_record_id);
+        assert!(first.report.rows[0].warnings[0].contains("derived"));
+    }
+
+    #[test]
+    fn price_and_capability_parsers_match_lark_formats() {
+        assert_eq!(parse_price("800,000"), Some(800_000));
+        assert_eq!(parse_price("80万円"), Some(800_000));
+        assert_eq!(parse_price("応相談"), None);
+        assert_eq!(parse_capability("〇〇"), Some(3));
+        assert_eq!(parse_capability("×"), Some(0));
+        assert_eq!(parse_capability("-"), None);
+        assert_eq!(parse_utilization("80%"), Some(0.8));
+    }
+}
@@ -0,0 +1,146 @@
+use std::fs::File;
+use std::io::BufReader;
+use std::path::PathBuf;
+
+use clap::Parser;
+use dotenvy::dotenv;
+use sr_common::db::{create_pool_from_url_checked, run_migrations, upsert_talent_masters};
+use sr_common::logging::{init_tracing_subscriber, install_tracing_panic_hook};
+use sr_common::rules::init_active_rules;
+use tracing::{error, info, warn};
+
+mod lark_csv;
+
+use lark_csv::{parse_lark_csv, RowStatus};
+
+#[derive(Debug, Parser)]
+#[command(
+    name = "sr-import",
+    about = "Import the Lark talent export into ses.talents"
+)]
+struct Cli {
+    /// Path to the Lark CSV export (要員一覧)
+    #[arg(long)]
+    csv: PathBuf,
+
+    /// PostgreSQL connection string (not required with --dry-run)
+    #[arg(long, env = "DATABASE_URL")]
+    db_url: Option<String>,
+
+    /// Parse and validate only; skip DB writes
+    #[arg(long, default_value_t = false)]
+    dry_run: bool,
+
+    /// Write the per-row validation report as JSON to this path
+    #[arg(long)]
+    report: Option<PathBuf>,
+
+    /// Column holding the Lark record ID (auto-detected when omitted)
+    #[arg(long)]
+    record_id_column: Option<String>,
+
+    /// Exit with an error (and skip DB writes) when any row is rejected
+    #[arg(long, default_value_t = false)]
+    fail_on_rejected: bool,
+}
+
+async fn run() -> Result<(), Box<dyn std::error::Error>> {
+    dotenv().ok();
+    init_tracing_subscriber(env!("CARGO_PKG_NAME"));
+    install_tracing_panic_hook(env!("CARGO_PKG_NAME"));
+
+    let args = Cli::parse();
+    let rules = init_active_rules()?;
+    info!(rule_version = %rules.version, path = ?rules.path, "loaded match rules");
+
+    let file = File::open(&args.csv)?;
+    let parsed = parse_lark_csv(BufReader::new(file), args.record_id_column.as_deref())?;
+    let report = &parsed.report;
+
+    for row in report
+        .rows
+        .iter()
+        .filter(|r| r.status == RowStatus::Rejected)
+    {
+        warn!(
+            line = row.line,
+            lark_record_id = ?row.lark_record_id,
+            reasons = ?row.reasons,
+            "rejected row"
+        );
+    }
+    info!(
+        csv = %args.csv.display(),
+        total_rows = report.total_rows,
+        accepted = report.accepted,
+        rejected = report.rejected,
+        normalized_away = report.normalized_away,
+        "parsed lark export"
+    );
+
+    if let Some(path) = &args.report {
+        serde_json::to_writer_pretty(File::create(path)?, report)?;
+        info!(report = %path.display(), "wrote validation report");
+    }
+
+    if args.fail_on_rejected && report.rejected > 0 {
+        return Err(format!("{} rows rejected; aborting import", report.rejected).into());
+    }
+
+    if args.dry_run {
+        info!(
+            talents = parsed.talents.len(),
+            "dry-run mode: skipping DB writes"
+        );
+        return Ok(());
+    }
+
+    let db_url = args
+        .db_url
+        .as_deref()
+        .ok_or("--db-url or DATABASE_URL is required unless --dry-run is set")?;
+    let pool = create_pool_from_url_checked(db_url).await?;
+    run_migrations(&pool).await?;
+
+    let summary = upsert_talent_masters(&pool, &parsed.talents).await?;
+    info!(
+        inserted = summary.inserted,
+        updated = summary.updated,
+        "upserted talents into ses.talents"
+    );
+
+    Ok(())
+}
+
+#[tokio::main]
+async fn main() {
+    if let Err(err) = run().await {
+        error!(error = %err, "sr-import failed");
+        std::process::exit(1);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cli_parses_import_flags() {
+        let cli = Cli::parse_from([
+            "sr-import",
+            "--csv",
+            "talents.csv",
+            "--dry-run",
+            "--report",
+            "report.json",
+            "--record-id-column",
+            "レコードID",
+        ]);
+
+        assert_eq!(cli.csv, PathBuf::from("talents.csv"));
+        assert!(cli.dry_run);
+        assert_eq!(cli.report, Some(PathBuf::from("report.json")));
+        assert_eq!(cli.record_id_column.as_deref(), Some("レコードID"));
+        assert!(!cli.fail_on_rejected);
+    }
+}
@@ -6,14 +6,16 @@ use serde::{Deserialize, Serialize};
 use serde_json::{json, Value};
 use sr_common::db::util::TimedClientExt;
 use sr_common::db::{
-    create_pool_from_url_checked, fetch_email_body, lock_next_pending_job, run_migrations,
-    upsert_extraction_job, PgPool,
+    create_pool_from_url_checked, fetch_email_body, fetch_email_sender, fetch_talent_email_body,
+    lock_next_pending_job, materialize_extraction, materialize_talent_extraction, run_migrations,
+    upsert_extraction_job, CompletedExtraction, PgPool,
 };
 use sr_common::logging::{init_tracing_subscriber, install_tracing_panic_hook};
 use sr_common::queue::{
-    ExtractionJob, ExtractionQueue, FinalMethod, JobError, JobOutcome, QueueStatus,
+    EntityType, ExtractionJob, ExtractionQueue, FinalMethod, JobError, JobOutcome, QueueStatus,
     RecommendedMethod,
 };
+use sr_common::rules::init_active_rules;
 use sr_metrics::init_metrics;
 use std::collections::hash_map::DefaultHasher;
 use std::hash::{Hash, Hasher};
@@ -324,6 +326,8 @@ impl ShadowCompareRuntime {
 #[derive(Debug, Serialize)]
 struct LlmRequest {
     message_id: String,
+    /// 抽出スキーマの切り替え（project: 案件 / talent: 人材）
+    entity_type: EntityType,
     source_text: String,
     extractor_hints: serde_json::Value,
     model: String,
@@ -493,6 +497,22 @@ fn build_extractor_hints(partial_fields: &Option<serde_json::Value>) -> serde_js
     if let Some(years) = obj.get("min_experience_years").and_then(|v| v.as_f64()) {
         hints.insert("min_experience_years".to_string(), json!(years));
     }
+    // 人材メール（TalentPartialFields）のヒント
+    if let Some(price) = obj.get("desired_price_min").and_then(|v| v.as_i64()) {
+        hints.insert("desired_price_min".to_string(), json!(price));
+    }
+    if let Some(station) = obj.get("nearest_station_raw").and_then(|v| v.as_str()) {
+        hints.insert("nearest_station".to_string(), json!(station));
+    }
+    if let Some(availability) = obj.get("availability_raw").and_then(|v| v.as_str()) {
+        hints.insert("availability_raw".to_string(), json!(availability));
+    }
+    if let Some(skills) = obj.get("skills_keywords").and_then(|v| v.as_array()) {
+        hints.insert("skills_keywords".to_string(), json!(skills));
+    }
+    if let Some(years) = obj.get("experience_years").and_then(|v| v.as_f64()) {
+        hints.insert("experience_years".to_string(), json!(years));
+    }
     if let Some(reason) = obj.get("decision_reason").and_then(|v| v.as_str()) {
         hints.insert("_rust_decision_reason".to_string(), json!(reason));
     }
@@ -510,6 +530,7 @@ fn build_llm_request(
 ) -> LlmRequest {
     LlmRequest {
         message_id: job.message_id.clone(),
+        entity_type: job.entity_type,
         source_text: body_text.to_string(),
         extractor_hints: build_extractor_hints(&job.partial_fields),
         model: config.model.clone(),
@@ -1209,19 +1230,30 @@ async fn process_locked_job(
     );
 
     let shadow_selected = mark_shadow_canary(&mut locked, shadow_config.config());
-    let body_text = match fetch_email_body(pool, &locked.message_id).await {
+    let (body, source_table) = match locked.entity_type {
+        EntityType::Project => (
+            fetch_email_body(pool, locked.source_message_id()).await,
+            "anken_emails",
+        ),
+        EntityType::Talent => (
+            fetch_talent_email_body(pool, locked.source_message_id()).await,
+            "jinzai_emails",
+        ),
+    };
+    let email_body = match body {
         Ok(Some(body)) => body,
         Ok(None) => {
             warn!(
                 worker_id = %worker_id,
                 message_id = %locked.message_id,
                 job_id = locked.id,
-                "missing source_text in anken_emails; skipping job"
+                source_table,
+                "missing source_text in source emails; skipping job"
             );
             let (processed, _, result) = apply_outcome(
                 locked.clone(),
                 Err(JobError::Permanent {
-                    message: "missing source_text in anken_emails".into(),
+                    message: format!("missing source_text in {source_table}"),
                 }),
             );
             upsert_extraction_job(pool, &processed).await?;
@@ -1246,6 +1278,27 @@ async fn process_locked_job(
             return Ok(result);
         }
     };
+    // 複数案件メールのサブジョブはエンキュー時に保存した担当セクションだけを LLM に渡す
+    let body_text = match locked.source_body(&email_body) {
+        Some(body) => body.to_string(),
+        None => {
+            error!(
+                worker_id = %worker_id,
+                message_id = %locked.message_id,
+                job_id = locked.id,
+                section_index = ?locked.section_index,
+                "section sub-job has no stored section text; forcing manual review"
+            );
+            let (processed, _, result) = apply_outcome(
+                locked.clone(),
+                Err(JobError::Permanent {
+                    message: "missing section_text for section sub-job".into(),
+                }),
+            );
+            upsert_extraction_job(pool, &processed).await?;
+            return Ok(result);
+        }
+    };
     if body_text.trim().is_empty() {
         warn!(
             worker_id = %worker_id,
@@ -1303,6 +1356,75 @@ async fn process_locked_job(
         "persisted processed job",
     );
 
+    if processed.status == QueueStatus::Completed
+        && processed.final_method == Some(FinalMethod::LlmCompleted)
+        && processed.entity_type == EntityType::Talent
+    {
+        // 射影に失敗しても抽出結果自体は確定済み。sr-extractor の射影スイープで再試行される
+        let extraction = CompletedExtraction {
+            job: processed.clone(),
+            body_text: email_body.clone(),
+            received_at: processed.email_received_at,
+            sender_name: None,
+            sender_address: None,
+        };
+        match materialize_talent_extraction(pool, &extraction).await {
+            Ok(talent_enum_id) => info!(
+                worker_id = %worker_id,
+                message_id = %processed.message_id,
+                job_id = processed.id,
+                talent_enum_id = ?talent_enum_id,
+                "materialized talent from extraction job"
+            ),
+            Err(err) => warn!(
+                worker_id = %worker_id,
+                message_id = %processed.message_id,
+                job_id = processed.id,
+                error = %err,
+                "failed to materialize talent; will retry on next sweep"
+            ),
+        }
+    } else if processed.status == QueueStatus::Completed
+        && processed.final_method == Some(FinalMethod::LlmCompleted)
+    {
+        // 射影に失敗しても抽出結果自体は確定済み。sr-matcher の射影スイープで再試行される
+        let (sender_name, sender_address) = fetch_email_sender(pool, processed.source_message_id())
+            .await
+            .unwrap_or_else(|err| {
+                warn!(
+                    worker_id = %worker_id,
+                    message_id = %processed.message_id,
+                    error = %err,
+                    "failed to fetch email sender; projecting without client profile keys"
+                );
+                None
+            })
+            .unwrap_or_default();
+        let extraction = CompletedExtraction {
+            job: processed.clone(),
+            body_text: email_body.clone(),
+            received_at: processed.email_received_at,
+            sender_name,
+            sender_address,
+        };
+        match materialize_extraction(pool, &extraction).await {
+            Ok(project_id) => info!(
+                worker_id = %worker_id,
+                message_id = %processed.message_id,
+                job_id = processed.id,
+                project_id = ?project_id,
+                "materialized project from extraction job"
+            ),
+            Err(err) => warn!(
+                worker_id = %worker_id,
+                message_id = %processed.message_id,
+                job_id = processed.id,
+                error = %err,
+                "failed to materialize project; will retry on next sweep"
+            ),
+        }
+    }
+
     if shadow_selected {
         metrics::counter!(
             "shadow_comparisons_spawned_total",
@@ -1361,6 +1483,8 @@ async fn run() -> Result<(), Box<dyn std::error::Error>> {
     init_metrics("METRICS_PORT", 9898);
 
     let args = Cli::parse();
+    let rules = init_active_rules()?;
+    info!(rule_version = %rules.version, path = ?rules.path, "loaded match rules");
     let llm_config = LlmRuntimeConfig::from_env();
     let shadow_config = shadow_config_from_env(&llm_config);
     let shadow_runtime = ShadowCompareRuntime::new(shadow_config);
@@ -1574,6 +1698,31 @@ mod tests {
             .create()
     }
 
+    #[test]
+    fn talent_jobs_send_entity_type_and_talent_hints() {
+        let mut job = ExtractionJob::new("jinzai-1", "Java人材", Utc::now(), "hash");
+        job.entity_type = EntityType::Talent;
+        job.partial_fields = Some(json!({
+            "desired_price_min": 65,
+            "nearest_station_raw": "三鷹駅",
+            "skills_keywords": ["java"],
+        }));
+
+        let request = build_llm_request(&job, "body", &LlmRuntimeConfig::default());
+        let payload = serde_json::to_value(&request).unwrap();
+
+        assert_eq!(payload["entity_type"], json!("talent"));
+        assert_eq!(payload["extractor_hints"]["desired_price_min"], json!(65));
+        assert_eq!(
+            payload["extractor_hints"]["nearest_station"],
+            json!("三鷹駅")
+        );
+        assert_eq!(
+            payload["extractor_hints"]["skills_keywords"],
+            json!(["java"])
+        );
+    }
+
     #[test]
     #[serial]
     fn llm_job_is_marked_completed() {
@@ -0,0 +1,471 @@
+use std::collections::HashSet;
+use std::time::Duration as StdDuration;
+
+use chrono::{Duration, NaiveDate, Utc};
+use clap::Parser;
+use dotenvy::dotenv;
+use sr_common::db::{
+    create_pool_from_url_checked, fetch_active_projects, fetch_available_talents,
+    fetch_matchable_talents, materialize_completed_projects, run_migrations, PgPool,
+};
+use sr_common::logging::{init_tracing_subscriber, install_tracing_panic_hook};
+use sr_common::matching::experiment::ExperimentConfig;
+use sr_common::matching::pipeline::{MatchRunner, SharedTwoTower};
+use sr_common::matching::weights::active_weights_file;
+use sr_common::rules::init_active_rules;
+use sr_common::run_id;
+use sr_common::two_tower::store::{load_talent_recall, refresh_talent_embeddings};
+use sr_common::two_tower::HnswConfig;
+use tokio::time::sleep;
+use tracing::{error, info, info_span, warn};
+
+const ENGINE_VERSION: &str = concat!("sr-matcher-", env!("CARGO_PKG_VERSION"));
+const DEFAULT_LOOKBACK_DAYS: i64 = 30;
+/// 逆引きバッチで 1 人材あたりにランキングする案件数の上限（受信日の新しい順）
+const REVERSE_MATCH_PROJECT_LIMIT: i64 = 1000;
+
+#[derive(Debug, Parser)]
+#[command(
+    name = "sr-matcher",
+    about = "Rank talents for active projects and persist match results"
+)]
+struct Cli {
+    /// PostgreSQL connection string
+    #[arg(long, env = "DATABASE_URL")]
+    db_url: String,
+
+    /// Match a single project (ignores the lookback window and already-matched filter)
+    #[arg(long)]
+    project_id: Option<i64>,
+
+    /// Rank candidates without writing match_results / interaction_logs
+    #[arg(long, default_value_t = false)]
+    dry_run: bool,
+
+    /// Exit once no unmatched projects remain instead of polling for new ones
+    #[arg(long, default_value_t = false)]
+    exit_on_empty: bool,
+
+    /// Only consider projects received within this many days
+    #[arg(long, default_value_t = DEFAULT_LOOKBACK_DAYS)]
+    lookback_days: i64,
+
+    /// Maximum number of projects ranked per batch
+    #[arg(long, default_value_t = 50)]
+    batch_size: i64,
+
+    /// Idle poll interval in milliseconds when running as a long-lived service
+    #[arg(long, default_value_t = 60_000)]
+    idle_poll_interval_ms: u64,
+
+    /// Reverse mode: rank recent projects for talents available within this many days, then exit
+    #[arg(long, value_parser = clap::value_parser!(i32).range(0..=365))]
+    talents_available_within_days: Option<i32>,
+
+    /// Two-tower ANN recall: only the K nearest talents per project reach the prefilter
+    /// (requires TWO_TOWER_ENABLED; talents missing from the index are always kept)
+    #[arg(long, env = "TWO_TOWER_RECALL_TOP_K", value_parser = clap::value_parser!(u32).range(1..))]
+    recall_top_k: Option<u32>,
+
+    /// A/B experiment JSON: each project is hashed into an arm whose name is logged as the variant
+    #[arg(long, env = "MATCH_EXPERIMENT_CONFIG")]
+    experiment_config: Option<std::path::PathBuf>,
+}
+
+/// 同一プロセス内で処理済みの案件 ID（日付が変わったらリセット）
+///
+/// 候補 0 件の案件は match_results に行が残らないため、DB 側の「当日実行済み」判定だけでは
+/// 同じ案件を延々と再処理してしまう。プロセス内でも除外しておく。
+#[derive(Debug)]
+struct AttemptedProjects {
+    day: NaiveDate,
+    ids: HashSet<i64>,
+}
+
+impl AttemptedProjects {
+    fn new(today: NaiveDate) -> Self {
+        Self {
+            day: today,
+            ids: HashSet::new(),
+        }
+    }
+
+    fn roll_over(&mut self, today: NaiveDate) {
+        if today != self.day {
+            self.day = today;
+            self.ids.clear();
+        }
+    }
+
+    fn mark(&mut self, project_id: i64) {
+        self.ids.insert(project_id);
+    }
+
+    fn to_vec(&self) -> Vec<i64> {
+        let mut ids: Vec<i64> = self.ids.iter().copied().collect();
+        ids.sort_unstable();
+        ids
+    }
+}
+
+async fn shutdown_signal() {
+    let ctrl_c = async {
+        let _ = tokio::signal::ctrl_c().await;
+    };
+
+    #[cfg(unix)]
+    let terminate = async {
+        use tokio::signal::unix::{signal, SignalKind};
+        if let Ok(mut sigterm) = signal(SignalKind::terminate()) {
+            let _ = sigterm.recv().await;
+        }
+    };
+
+    #[cfg(not(unix))]
+    let terminate = std::future::pending::<()>();
+
+    tokio::select! {
+        _ = ctrl_c => {},
+        _ = terminate => {},
+    }
+}
+
+/// ses.talent_embeddings を最新化し、変化があれば（または未構築なら）recall インデックスを作り直す
+///
+/// dry-run では埋め込みを書き込まず、保存済みの埋め込みだけでインデックスを作る。
+async fn sync_talent_recall(
+    pool: &PgPool,
+    runner: &mut MatchRunner,
+    top_k: u32,
+    dry_run: bool,
+    loaded: &mut bool,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let Some(embedder) = runner.two_tower() else {
+        return Ok(());
+    };
+    let changed = if dry_run {
+        false
+    } else {
+        refresh_talent_embeddings(pool, embedder).await?.changed()
+    };
+    if changed || !*loaded {
+        let recall =
+            load_talent_recall(pool, embedder, top_k as usize, HnswConfig::default()).await?;
+        runner.set_talent_recall(Some(recall));
+        *loaded = true;
+    }
+    Ok(())
+}
+
+/// 稼働開始が近い人材ごとに案件をランキングし、案件起点と同じ形式で保存する（1 パスで終了）
+async fn run_talent_batch(
+    pool: &PgPool,
+    runner: &MatchRunner,
+    args: &Cli,
+    within_days: i32,
+) -> Result<(), Box<dyn std::error::Error>> {
+    if !args.dry_run {
+        materialize_completed_projects(pool, args.batch_size).await?;
+    }
+
+    let received_since = Utc::now() - Duration::days(args.lookback_days);
+    let projects = fetch_active_projects(
+        pool,
+        None,
+        received_since,
+        false,
+        &[],
+        REVERSE_MATCH_PROJECT_LIMIT,
+    )
+    .await?;
+    let talents = fetch_available_talents(pool, within_days).await?;
+    info!(
+        talents = talents.len(),
+        projects = projects.len(),
+        within_days,
+        "ranking projects for available talents"
+    );
+
+    for talent in &talents {
+        let Some(talent_id) = talent.id else {
+            continue;
+        };
+        let span = info_span!("match_talent", talent_id, match_run_id = %runner.match_run_id());
+        let _entered = span.enter();
+
+        if args.dry_run {
+            let ranked = runner.rank_projects(talent, &projects);
+            info!(
+                projects = ranked.len(),
+                top_score = ranked.first().map(|r| r.total_score),
+                "dry-run: ranked projects without persisting"
+            );
+            continue;
+        }
+
+        let summary = runner.persist_talent_run(pool, talent, &projects).await?;
+        info!(
+            match_results = summary.match_results,
+            interaction_logs = summary.interaction_logs,
+            projects = projects.len(),
+            "persisted match run for talent"
+        );
+    }
+
+    info!(
+        talents = talents.len(),
+        match_run_id = %runner.match_run_id(),
+        "reverse matcher finished"
+    );
+    Ok(())
+}
+
+async fn run() -> Result<(), Box<dyn std::error::Error>> {
+    dotenv().ok();
+    init_tracing_subscriber(env!("CARGO_PKG_NAME"));
+    install_tracing_panic_hook(env!("CARGO_PKG_NAME"));
+
+    let args = Cli::parse();
+    let experiment = args
+        .experiment_config
+        .as_deref()
+        .map(ExperimentConfig::from_path)
+        .transpose()?;
+    if let Some(weights) = active_weights_file()? {
+        info!(version = %weights.version, "using fitted matching weights");
+    }
+    let rules = init_active_rules()?;
+    info!(rule_version = %rules.version, path = ?rules.path, "loaded match rules");
+    let pool = create_pool_from_url_checked(&args.db_url).await?;
+    run_migrations(&pool).await?;
+
+    // モデルを読み込めない設定はバッチの途中で panic させず、起動時のエラーとして返す
+    let two_tower = SharedTwoTower::try_from_env(experiment.as_ref())?;
+    let mut runner = MatchRunner::from_shared(two_tower)
+        .with_engine_version(ENGINE_VERSION)
+        .with_match_run_id(run_id::get());
+    if let Some(experiment) = experiment {
+        info!(
+            experiment = %experiment.name,
+            arms = ?experiment.arm_names(),
+            "assigning projects to experiment arms"
+        );
+        runner = runner.with_experiment(experiment)?;
+    }
+
+    let status = pool.status();
+    info!(
+        size = status.size,
+        available = status.available,
+        match_run_id = %runner.match_run_id(),
+        project_id = ?args.project_id,
+        dry_run = args.dry_run,
+        lookback_days = args.lookback_days,
+        "created postgres connection pool for matcher",
+    );
+
+    if args.recall_top_k.is_some() && runner.two_tower().is_none() {
+        warn!("--recall-top-k is ignored because TWO_TOWER_ENABLED is off");
+    }
+    let mut recall_loaded = false;
+
+    if let Some(within_days) = args.talents_available_within_days {
+        return run_talent_batch(&pool, &runner, &args, within_days).await;
+    }
+
+    // --project-id / --dry-run は 1 パスで終了する（dry-run は書き込まないため未処理判定が進まない）
+    let single_pass = args.project_id.is_some() || args.dry_run;
+    let mut attempted = AttemptedProjects::new(Utc::now().date_naive());
+    let mut total_projects = 0usize;
+    let shutdown = shutdown_signal();
+    tokio::pin!(shutdown);
+
+    loop {
+        let now = Utc::now();
+        attempted.roll_over(now.date_naive());
+        let received_since = now - Duration::days(args.lookback_days);
+
+        // 抽出完了ジョブのうち ses.projects に未反映のものを先に射影しておく
+        if !args.dry_run {
+            let projection = materialize_completed_projects(&pool, args.batch_size).await?;
+            if projection.projected > 0 || projection.skipped > 0 {
+                info!(
+                    projected = projection.projected,
+                    skipped = projection.skipped,
+                    "materialized completed extraction jobs into projects"
+                );
+            }
+        }
+
+        let projects = fetch_active_projects(
+            &pool,
+            args.project_id,
+            received_since,
+            true,
+            &attempted.to_vec(),
+            args.batch_size,
+        )
+        .await?;
+
+        if projects.is_empty() {
+            if single_pass || args.exit_on_empty {
+                if total_projects == 0 {
+                    info!("no unmatched projects found; exiting");
+                }
+                break;
+            }
+
+            tokio::select! {
+                _ = &mut shutdown => {
+                    info!("shutdown signal received during idle wait; stopping matcher");
+                    break;
+                }
+                _ = sleep(StdDuration::from_millis(args.idle_poll_interval_ms)) => {}
+            }
+            continue;
+        }
+
+        let talents = fetch_matchable_talents(&pool).await?;
+        if let Some(top_k) = args.recall_top_k {
+            sync_talent_recall(&pool, &mut runner, top_k, args.dry_run, &mut recall_loaded).await?;
+        }
+        if talents.is_empty() {
+            warn!("no active talents found; projects will be recorded without candidates");
+        }
+
+        for project in &projects {
+            let Some(project_id) = project.id else {
+                continue;
+            };
+            attempted.mark(project_id);
+            total_projects += 1;
+
+            let span =
+                info_span!("match_project", project_id, match_run_id = %runner.match_run_id());
+            let _entered = span.enter();
+
+            if args.dry_run {
+                let ranked = runner.rank_talents(project, &talents);
+                info!(
+                    candidates = ranked.len(),
+                    top_score = ranked.first().map(|r| r.total_score),
+                    "dry-run: ranked candidates without persisting"
+                );
+                continue;
+            }
+
+            let summary = runner.persist_project_run(&pool, project, &talents).await?;
+            info!(
+                match_results = summary.match_results,
+                interaction_logs = summary.interaction_logs,
+                talents = talents.len(),
+                "persisted match run for project"
+            );
+        }
+
+        if single_pass {
+            break;
+        }
+    }
+
+    info!(
+        projects = total_projects,
+        match_run_id = %runner.match_run_id(),
+        "matcher finished"
+    );
+    Ok(())
+}
+
+#[tokio::main]
+async fn main() {
+    if let Err(err) = run().await {
+        error!(error = %err, "sr-matcher failed");
+        std::process::exit(1);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn attempted_projects_reset_on_new_day() {
+        let day1 = NaiveDate::from_ymd_opt(2025, 1, 10).unwrap();
+        let day2 = NaiveDate::from_ymd_opt(2025, 1, 11).unwrap();
+
+        let mut attempted = AttemptedProjects::new(day1);
+        attempted.mark(3);
+        attempted.mark(1);
+        attempted.mark(3);
+        assert_eq!(attempted.to_vec(), vec![1, 3]);
+
+        attempted.roll_over(day1);
+        assert_eq!(attempted.to_vec(), vec![1, 3]);
+
+        attempted.roll_over(day2);
+        assert!(attempted.to_vec().is_empty());
+    }
+
+    #[test]
+    fn cli_parses_worker_flags() {
+        let cli = Cli::parse_from([
+            "sr-matcher",
+            "--db-url",
+            "postgres://localhost/sr",
+            "--project-id",
+            "42",
+            "--dry-run",
+            "--exit-on-empty",
+        ]);
+
+        assert_eq!(cli.project_id, Some(42));
+        assert!(cli.dry_run);
+        assert!(cli.exit_on_empty);
+        assert_eq!(cli.lookback_days, DEFAULT_LOOKBACK_DAYS);
+        assert_eq!(cli.talents_available_within_days, None);
+        assert_eq!(cli.recall_top_k, None);
+        assert_eq!(cli.experiment_config, None);
+    }
+
+    #[test]
+    fn cli_parses_recall_top_k() {
+        let cli = Cli::parse_from([
+            "sr-matcher",
+            "--db-url",
+            "postgres://localhost/sr",
+            "--recall-top-k",
+            "300",
+        ]);
+        assert_eq!(cli.recall_top_k, Some(300));
+
+        assert!(Cli::try_parse_from([
+            "sr-matcher",
+            "--db-url",
+            "postgres://localhost/sr",
+            "--recall-top-k",
+            "0",
+        ])
+        .is_err());
+    }
+
+    #[test]
+    fn cli_parses_reverse_mode() {
+        let cli = Cli::parse_from([
+            "sr-matcher",
+            "--db-url",
+            "postgres://localhost/sr",
+            "--talents-available-within-days",
+            "14",
+        ]);
+        assert_eq!(cli.talents_available_within_days, Some(14));
+
+        assert!(Cli::try_parse_from([
+            "sr-matcher",
+            "--db-url",
+            "postgres://localhost/sr",
+            "--talents-available-within-days",
+            "-1",
+        ])
+        .is_err());
+    }
+}
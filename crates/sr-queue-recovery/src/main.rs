@@ -4,6 +4,7 @@ use dotenvy::dotenv;
 use sr_common::db::{create_pool_from_url_checked, recover_stuck_jobs, run_migrations};
 use sr_common::logging::{init_tracing_subscriber, install_tracing_panic_hook};
 use sr_common::queue::{ExtractionJob, ExtractionQueue, QueueStatus};
+use sr_common::rules::init_active_rules;
 use tracing::{debug, error, info};
 
 const DEFAULT_STUCK_THRESHOLD_MINUTES: i64 = 10;
@@ -47,6 +48,8 @@ async fn run() -> Result<(), Box<dyn std::error::Error>> {
     install_tracing_panic_hook(env!("CARGO_PKG_NAME"));
 
     let args = Cli::parse();
+    let rules = init_active_rules()?;
+    info!(rule_version = %rules.version, path = ?rules.path, "loaded match rules");
     let pool = create_pool_from_url_checked(&args.db_url).await?;
     run_migrations(&pool).await?;
     let status = pool.status();
@@ -0,0 +1,491 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use chrono::{Duration, NaiveDate, Utc};
+use clap::Parser;
+use dotenvy::dotenv;
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+use sr_common::db::{
+    create_pool_from_url_checked, decode_training_examples, encode_training_examples,
+    fetch_training_examples, TrainingExample,
+};
+use sr_common::evaluation::{RankingSummary, ScoredLabel};
+use sr_common::logging::{init_tracing_subscriber, install_tracing_panic_hook};
+use sr_common::rules::init_active_rules;
+use sr_common::two_tower::candle_tower::{self, TrainConfig};
+use sr_common::two_tower::{
+    load_config_from_env, CandleTwoTower, HashTwoTower, TwoTowerConfig, TwoTowerEmbedder,
+};
+use tracing::{error, info, warn};
+
+type CliResult<T> = Result<T, Box<dyn std::error::Error>>;
+
+#[derive(Debug, Parser)]
+#[command(
+    name = "sr-train",
+    about = "Snapshot ses.training_labels, train the two-tower embedder and report offline metrics"
+)]
+struct Cli {
+    /// PostgreSQL connection string (not needed with --dataset)
+    #[arg(long, env = "DATABASE_URL")]
+    db_url: Option<String>,
+
+    /// Re-train from an existing dataset snapshot (JSONL) instead of querying the database
+    #[arg(long)]
+    dataset: Option<PathBuf>,
+
+    /// Only snapshot labels recorded on or after this date (YYYY-MM-DD)
+    #[arg(long)]
+    since: Option<NaiveDate>,
+
+    /// Directory for the dataset snapshot, model artifact and metrics report
+    #[arg(long, default_value = "models/training")]
+    output_dir: PathBuf,
+
+    /// Embedder to train: `candle` trains weights, `hash` only reports baseline metrics
+    #[arg(long, env = "TWO_TOWER_EMBEDDER", default_value = "candle")]
+    embedder: String,
+
+    /// First run_date of the validation split (defaults to the last --validation-days days)
+    #[arg(long)]
+    split_date: Option<NaiveDate>,
+
+    /// Number of most recent run dates held out for validation when --split-date is omitted
+    #[arg(long, default_value_t = 7, value_parser = clap::value_parser!(i64).range(1..=365))]
+    validation_days: i64,
+
+    /// Drop pairs whose project/talent row was updated after the match run
+    #[arg(long, default_value_t = false)]
+    exclude_updated_features: bool,
+
+    /// Labels at or above this value count as positives for AUC / recall
+    #[arg(long, default_value_t = 0.5)]
+    positive_threshold: f64,
+
+    #[arg(long, default_value_t = 20)]
+    epochs: usize,
+
+    #[arg(long, default_value_t = 64)]
+    batch_size: usize,
+
+    #[arg(long, default_value_t = 5e-3)]
+    learning_rate: f64,
+
+    #[arg(long, default_value_t = 64)]
+    hidden_dim: usize,
+
+    #[arg(long, default_value_t = 4096)]
+    vocab_size: usize,
+
+    #[arg(long, default_value_t = 42)]
+    seed: u64,
+}
+
+impl Cli {
+    fn train_config(&self, dimension: usize) -> TrainConfig {
+        TrainConfig {
+            vocab_size: self.vocab_size,
+            hidden_dim: self.hidden_dim,
+            dimension,
+            epochs: self.epochs,
+            batch_size: self.batch_size,
+            learning_rate: self.learning_rate,
+            positive_threshold: self.positive_threshold,
+            seed: self.seed,
+            ..TrainConfig::default()
+        }
+    }
+}
+
+/// `run_date` による train / validation 分割（validation は split_date 以降のマッチ実行）
+#[derive(Debug)]
+struct DatasetSplit<'a> {
+    split_date: NaiveDate,
+    train: Vec<&'a TrainingExample>,
+    validation: Vec<&'a TrainingExample>,
+}
+
+/// 同じ run の中で train と validation が混ざらないよう、日付境界で分割する
+fn split_by_run_date(
+    examples: &[TrainingExample],
+    split_date: Option<NaiveDate>,
+    validation_days: i64,
+) -> CliResult<DatasetSplit<'_>> {
+    let latest = examples
+        .iter()
+        .map(|example| example.run_date)
+        .max()
+        .ok_or("no labeled pairs to split")?;
+    let split_date = split_date.unwrap_or(latest - Duration::days(validation_days - 1));
+
+    let (validation, train): (Vec<_>, Vec<_>) = examples
+        .iter()
+        .partition(|example| example.run_date >= split_date);
+    if train.is_empty() || validation.is_empty() {
+        return Err(format!(
+            "split at {split_date} leaves train={} validation={}; adjust --split-date or --validation-days",
+            train.len(),
+            validation.len()
+        )
+        .into());
+    }
+    Ok(DatasetSplit {
+        split_date,
+        train,
+        validation,
+    })
+}
+
+/// データセットのバージョン（JSONL の SHA-256 先頭 16 桁）
+fn dataset_version(bytes: &[u8]) -> String {
+    Sha256::digest(bytes)
+        .iter()
+        .take(8)
+        .map(|b| format!("{b:02x}"))
+        .collect()
+}
+
+/// 案件ごとに候補をまとめ、埋め込みの類似度でスコアリングしてランキング指標を計算する
+fn evaluate(
+    embedder: &dyn TwoTowerEmbedder,
+    examples: &[&TrainingExample],
+    positive_threshold: f64,
+) -> RankingSummary {
+    let mut by_project: BTreeMap<i64, Vec<&TrainingExample>> = BTreeMap::new();
+    for example in examples {
+        // id の無い案件は 1 ペアだけのクエリとして扱う
+        let key = example.project.id.unwrap_or(-example.interaction_id);
+        by_project.entry(key).or_default().push(example);
+    }
+
+    let groups: Vec<Vec<ScoredLabel>> = by_project
+        .values()
+        .map(|pairs| {
+            let project = embedder.embed_project(&pairs[0].project);
+            let talents: Vec<_> = pairs.iter().map(|pair| pair.talent.clone()).collect();
+            embedder
+                .embed_talents(&talents)
+                .iter()
+                .zip(pairs)
+                .map(|(talent, pair)| ScoredLabel {
+                    score: embedder.similarity(&project, talent) as f64,
+                    label: pair.label,
+                })
+                .collect()
+        })
+        .collect();
+    RankingSummary::compute(&groups, positive_threshold)
+}
+
+/// metrics-<dataset_version>.json の中身
+#[derive(Debug, Serialize)]
+struct TrainingRunReport {
+    dataset_version: String,
+    dataset_path: String,
+    embedder: String,
+    model_path: Option<String>,
+    model_version: String,
+    dimension: usize,
+    split_date: NaiveDate,
+    train_pairs: usize,
+    validation_pairs: usize,
+    excluded_updated_features: usize,
+    epoch_losses: Vec<f32>,
+    train_metrics: RankingSummary,
+    validation_metrics: RankingSummary,
+    created_at: chrono::DateTime<Utc>,
+}
+
+/// 分割 → 学習 → 評価 → レポート保存（DB に触れない部分）
+fn train_and_report(
+    args: &Cli,
+    two_tower: &TwoTowerConfig,
+    examples: Vec<TrainingExample>,
+    dataset_version: String,
+    dataset_path: &Path,
+) -> CliResult<TrainingRunReport> {
+    let total = examples.len();
+    let examples: Vec<TrainingExample> = examples
+        .into_iter()
+        .filter(|example| !(args.exclude_updated_features && example.features_updated_after_match))
+        .collect();
+    let excluded_updated_features = total - examples.len();
+
+    let split = split_by_run_date(&examples, args.split_date, args.validation_days)?;
+    info!(
+        split_date = %split.split_date,
+        train = split.train.len(),
+        validation = split.validation.len(),
+        excluded_updated_features,
+        "split dataset by run_date"
+    );
+
+    let (embedder, model_path, epoch_losses): (Box<dyn TwoTowerEmbedder>, _, _) =
+        match args.embedder.as_str() {
+            "candle" => {
+                let model_path = args
+                    .output_dir
+                    .join(format!("two_tower-{dataset_version}.safetensors"));
+                let train: Vec<TrainingExample> =
+                    split.train.iter().map(|&example| example.clone()).collect();
+                let report = candle_tower::train(
+                    &train,
+                    &args.train_config(two_tower.dimension),
+                    &model_path,
+                )?;
+                // 保存した重みを読み直して評価する（推論経路と同じ artifact を検証する）
+                let tower =
+                    CandleTwoTower::load(&model_path.to_string_lossy(), two_tower.dimension)?;
+                (
+                    Box::new(tower),
+                    Some(model_path.display().to_string()),
+                    report.epoch_losses,
+                )
+            }
+            "hash" => (Box::new(HashTwoTower::new(two_tower.clone())), None, Vec::new()),
+            other => {
+                return Err(format!(
+                    "embedder `{other}` cannot be trained by sr-train (use `candle`, or `hash` for a baseline)"
+                )
+                .into())
+            }
+        };
+
+    let report = TrainingRunReport {
+        dataset_version: dataset_version.clone(),
+        dataset_path: dataset_path.display().to_string(),
+        embedder: embedder.name().to_string(),
+        model_path,
+        model_version: embedder.version().to_string(),
+        dimension: embedder.dimension(),
+        split_date: split.split_date,
+        train_pairs: split.train.len(),
+        validation_pairs: split.validation.len(),
+        excluded_updated_features,
+        epoch_losses,
+        train_metrics: evaluate(embedder.as_ref(), &split.train, args.positive_threshold),
+        validation_metrics: evaluate(
+            embedder.as_ref(),
+            &split.validation,
+            args.positive_threshold,
+        ),
+        created_at: Utc::now(),
+    };
+
+    let report_path = args
+        .output_dir
+        .join(format!("metrics-{dataset_version}.json"));
+    fs::write(&report_path, serde_json::to_vec_pretty(&report)?)?;
+    info!(
+        report = %report_path.display(),
+        model_version = %report.model_version,
+        auc = ?report.validation_metrics.auc,
+        ndcg_at_10 = ?report.validation_metrics.ndcg_at_10,
+        recall_at_50 = ?report.validation_metrics.recall_at_50,
+        "wrote training metrics report"
+    );
+    Ok(report)
+}
+
+async fn run() -> CliResult<()> {
+    dotenv().ok();
+    init_tracing_subscriber(env!("CARGO_PKG_NAME"));
+    install_tracing_panic_hook(env!("CARGO_PKG_NAME"));
+
+    let args = Cli::parse();
+    let rules = init_active_rules()?;
+    info!(rule_version = %rules.version, path = ?rules.path, "loaded match rules");
+    fs::create_dir_all(&args.output_dir)?;
+
+    let (examples, version, dataset_path) = match &args.dataset {
+        Some(path) => {
+            let bytes = fs::read(path)?;
+            (
+                decode_training_examples(&bytes)?,
+                dataset_version(&bytes),
+                path.clone(),
+            )
+        }
+        None => {
+            let db_url = args
+                .db_url
+                .as_deref()
+                .ok_or("--db-url (or DATABASE_URL) is required unless --dataset is given")?;
+            let pool = create_pool_from_url_checked(db_url).await?;
+            let since = args
+                .since
+                .and_then(|date| date.and_hms_opt(0, 0, 0))
+                .map(|at| at.and_utc());
+            let examples = fetch_training_examples(&pool, since).await?;
+            let bytes = encode_training_examples(&examples)?;
+            let version = dataset_version(&bytes);
+            let path = args
+                .output_dir
+                .join(format!("training_labels-{version}.jsonl"));
+            fs::write(&path, &bytes)?;
+            info!(
+                pairs = examples.len(),
+                dataset = %path.display(),
+                "snapshotted training labels"
+            );
+            (examples, version, path)
+        }
+    };
+
+    let stale = examples
+        .iter()
+        .filter(|example| example.features_updated_after_match)
+        .count();
+    if stale > 0 && !args.exclude_updated_features {
+        warn!(
+            pairs = stale,
+            "some pairs use project/talent features updated after the match run; pass --exclude-updated-features to drop them"
+        );
+    }
+
+    train_and_report(
+        &args,
+        &load_config_from_env(),
+        examples,
+        version,
+        &dataset_path,
+    )?;
+    Ok(())
+}
+
+#[tokio::main]
+async fn main() {
+    if let Err(err) = run().await {
+        error!(error = %err, "sr-train failed");
+        std::process::exit(1);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sr_common::{Project, Talent};
+
+    fn example(id: i64, project_id: i64, skill: &str, label: f64, day: u32) -> TrainingExample {
+        TrainingExample {
+            interaction_id: id,
+            talent: Talent {
+                id: Some(id),
+                possessed_skills_keywords: vec![skill.into()],
+                ..Talent::default()
+            },
+            project: Project {
+                id: Some(project_id),
+                required_skills_keywords: vec!["rust".into()],
+                ..Project::default()
+            },
+            label,
+            signal_source: Some("feedback".into()),
+            run_date: NaiveDate::from_ymd_opt(2026, 10, day).unwrap(),
+            matched_at: Utc::now(),
+            features_updated_after_match: id % 5 == 0,
+        }
+    }
+
+    fn dataset() -> Vec<TrainingExample> {
+        (1..=12)
+            .map(|id| {
+                let (skill, label) = if id % 2 == 0 {
+                    ("rust", 1.0)
+                } else {
+                    ("cobol", 0.0)
+                };
+                example(id, 100 + id % 3, skill, label, 1 + id as u32)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn cli_defaults_to_candle_with_weekly_validation() {
+        let cli = Cli::parse_from(["sr-train", "--dataset", "labels.jsonl"]);
+        assert_eq!(cli.embedder, "candle");
+        assert_eq!(cli.validation_days, 7);
+        assert_eq!(cli.dataset, Some(PathBuf::from("labels.jsonl")));
+
+        assert!(Cli::try_parse_from(["sr-train", "--validation-days", "0"]).is_err());
+    }
+
+    #[test]
+    fn split_keeps_run_dates_disjoint() {
+        let examples = dataset();
+        let split = split_by_run_date(&examples, None, 3).unwrap();
+
+        assert_eq!(
+            split.split_date,
+            NaiveDate::from_ymd_opt(2026, 10, 11).unwrap()
+        );
+        assert_eq!(split.validation.len(), 3);
+        assert!(split
+            .train
+            .iter()
+            .all(|example| example.run_date < split.split_date));
+
+        let err = split_by_run_date(&examples, NaiveDate::from_ymd_opt(2026, 9, 1), 7);
+        assert!(err.is_err());
+    }
+
+    #[test]
+    fn dataset_round_trips_with_stable_version() {
+        let examples = dataset();
+        let bytes = encode_training_examples(&examples).unwrap();
+        assert_eq!(
+            dataset_version(&bytes),
+            dataset_version(&encode_training_examples(&examples).unwrap())
+        );
+
+        let decoded = decode_training_examples(&bytes).unwrap();
+        assert_eq!(decoded.len(), examples.len());
+        assert_eq!(decoded[3].talent, examples[3].talent);
+        assert_eq!(decoded[3].run_date, examples[3].run_date);
+    }
+
+    #[test]
+    fn trains_candle_and_writes_artifacts() {
+        let dir = std::env::temp_dir().join(format!("sr-train-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let args = Cli::parse_from([
+            "sr-train",
+            "--output-dir",
+            dir.to_str().unwrap(),
+            "--validation-days",
+            "4",
+            "--exclude-updated-features",
+            "--epochs",
+            "5",
+            "--hidden-dim",
+            "8",
+            "--vocab-size",
+            "64",
+        ]);
+        let two_tower = TwoTowerConfig {
+            dimension: 8,
+            ..TwoTowerConfig::default()
+        };
+
+        let report = train_and_report(
+            &args,
+            &two_tower,
+            dataset(),
+            "testdataset".into(),
+            &dir.join("labels.jsonl"),
+        )
+        .unwrap();
+
+        assert_eq!(report.embedder, "candle");
+        assert_eq!(report.excluded_updated_features, 2);
+        assert_eq!(report.train_pairs + report.validation_pairs, 10);
+        assert_eq!(report.epoch_losses.len(), 5);
+        assert!(dir.join("two_tower-testdataset.safetensors").exists());
+        assert!(dir.join("metrics-testdataset.json").exists());
+        assert!(report.validation_metrics.auc.is_some());
+
+        fs::remove_dir_all(&dir).ok();
+    }
+}
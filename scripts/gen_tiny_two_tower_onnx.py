@@ -0,0 +1,144 @@
+#!/usr/bin/env python3
+"""
+Generate the tiny Two-Tower ONNX model used by sr-common's `onnx` feature tests.
+
+The model follows the OnnxTwoTower input contract:
+  token_ids     int64   [N, L]  (0 = padding)
+  token_weights float32 [N, L]
+  -> embedding  float32 [N, D]  = sum_j weights[n, j] * table[token_ids[n, j]]
+
+No onnx/protobuf package is required; the protobuf wire format is written by hand.
+
+Usage:
+  python3 scripts/gen_tiny_two_tower_onnx.py crates/sr-common/tests/fixtures/tiny_two_tower.onnx
+"""
+import math
+import struct
+import sys
+
+VOCAB_SIZE = 16
+DIMENSION = 4
+MODEL_VERSION = "tiny-20261017"
+OPSET = 11
+
+FLOAT = 1
+INT64 = 7
+ATTR_INT = 2
+ATTR_INTS = 7
+
+
+def varint(value):
+    if value < 0:
+        value += 1 << 64
+    out = bytearray()
+    while True:
+        byte = value & 0x7F
+        value >>= 7
+        if value:
+            out.append(byte | 0x80)
+        else:
+            out.append(byte)
+            return bytes(out)
+
+
+def field_varint(number, value):
+    return varint(number << 3) + varint(value)
+
+
+def field_bytes(number, payload):
+    if isinstance(payload, str):
+        payload = payload.encode("utf-8")
+    return varint((number << 3) | 2) + varint(len(payload)) + payload
+
+
+def dimension(value):
+    if isinstance(value, str):
+        return field_bytes(2, value)
+    return field_varint(1, value)
+
+
+def value_info(name, elem_type, dims):
+    shape = b"".join(field_bytes(1, dimension(d)) for d in dims)
+    tensor_type = field_varint(1, elem_type) + field_bytes(2, shape)
+    type_proto = field_bytes(1, tensor_type)
+    return field_bytes(1, name) + field_bytes(2, type_proto)
+
+
+def attr_int(name, value):
+    return field_bytes(1, name) + field_varint(3, value) + field_varint(20, ATTR_INT)
+
+
+def attr_ints(name, values):
+    body = field_bytes(1, name)
+    for v in values:
+        body += field_varint(8, v)
+    return body + field_varint(20, ATTR_INTS)
+
+
+def node(op_type, inputs, outputs, name, attributes=()):
+    body = b"".join(field_bytes(1, i) for i in inputs)
+    body += b"".join(field_bytes(2, o) for o in outputs)
+    body += field_bytes(3, name) + field_bytes(4, op_type)
+    body += b"".join(field_bytes(5, a) for a in attributes)
+    return body
+
+
+def float_tensor(name, dims, values):
+    body = b"".join(field_varint(1, d) for d in dims)
+    body += field_varint(2, FLOAT)
+    body += field_bytes(8, name)
+    body += field_bytes(9, struct.pack("<%df" % len(values), *values))
+    return body
+
+
+def embedding_table():
+    # padding id 0 は 0 ベクトル、それ以外は決定論的な値
+    values = [0.0] * DIMENSION
+    for token_id in range(1, VOCAB_SIZE):
+        values += [
+            round(math.sin(token_id * DIMENSION + j + 1), 6) for j in range(DIMENSION)
+        ]
+    return values
+
+
+def model():
+    nodes = [
+        node("Gather", ["embedding_table", "token_ids"], ["gathered"], "gather", [attr_int("axis", 0)]),
+        node("Unsqueeze", ["token_weights"], ["weights_3d"], "unsqueeze", [attr_ints("axes", [2])]),
+        node("Mul", ["gathered", "weights_3d"], ["weighted"], "mul"),
+        node(
+            "ReduceSum",
+            ["weighted"],
+            ["embedding"],
+            "reduce_sum",
+            [attr_ints("axes", [1]), attr_int("keepdims", 0)],
+        ),
+    ]
+    graph = b"".join(field_bytes(1, n) for n in nodes)
+    graph += field_bytes(2, "tiny_two_tower")
+    graph += field_bytes(5, float_tensor("embedding_table", [VOCAB_SIZE, DIMENSION], embedding_table()))
+    graph += field_bytes(11, value_info("token_ids", INT64, ["N", "L"]))
+    graph += field_bytes(11, value_info("token_weights", FLOAT, ["N", "L"]))
+    graph += field_bytes(12, value_info("embedding", FLOAT, ["N", DIMENSION]))
+
+    metadata = [("version", MODEL_VERSION), ("vocab_size", str(VOCAB_SIZE))]
+
+    body = field_varint(1, 7)
+    body += field_bytes(2, "sr-common gen_tiny_two_tower_onnx.py")
+    body += field_bytes(7, graph)
+    body += field_bytes(8, field_bytes(1, "") + field_varint(2, OPSET))
+    for key, value in metadata:
+        body += field_bytes(14, field_bytes(1, key) + field_bytes(2, value))
+    return body
+
+
+def main():
+    if len(sys.argv) != 2:
+        print(__doc__)
+        sys.exit(1)
+    with open(sys.argv[1], "wb") as f:
+        f.write(model())
+
+
+if __name__ == "__main__":
+    main()